@@ -25,6 +25,8 @@ struct MemoryBucketLogProviderInner {
     names: HashMap<Uuid, String>,
     /// Track published status: bucket_id -> link -> published
     published: HashMap<Uuid, HashMap<Link, bool>>,
+    /// Parent links of each entry: bucket_id -> link -> parents
+    parents: HashMap<Uuid, HashMap<Link, Vec<Link>>>,
 }
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
@@ -83,7 +85,7 @@ impl BucketLogProvider for MemoryBucketLogProvider {
         id: Uuid,
         name: String,
         current: Link,
-        previous: Option<Link>,
+        parents: Vec<Link>,
         height: u64,
         published: bool,
     ) -> Result<(), BucketLogError<Self::Error>> {
@@ -100,37 +102,52 @@ impl BucketLogProvider for MemoryBucketLogProvider {
         // Get or create bucket entries
         let bucket_entries = inner.entries.entry(id).or_insert_with(HashMap::new);
 
-        // Check for conflict: same link at same height already exists
-        if let Some(existing_links) = bucket_entries.get(&height) {
-            if existing_links.contains(&current) {
-                return Err(BucketLogError::Conflict);
-            }
+        // Check for conflict: the link is already in the log
+        if bucket_entries
+            .values()
+            .any(|links| links.contains(&current))
+        {
+            return Err(BucketLogError::Conflict);
         }
 
-        // Validate the append based on previous link
-        if let Some(prev_link) = &previous {
-            // If there's a previous link, it should exist at height - 1
+        // Validate the append based on the parent links
+        if let Some((primary, others)) = parents.split_first() {
             if height == 0 {
                 return Err(BucketLogError::InvalidAppend(
                     current,
-                    prev_link.clone(),
+                    primary.clone(),
                     height,
                 ));
             }
 
+            // The primary parent should exist at height - 1
             let expected_prev_height = height - 1;
             let prev_exists = bucket_entries
                 .get(&expected_prev_height)
-                .map(|links| links.contains(prev_link))
+                .map(|links| links.contains(primary))
                 .unwrap_or(false);
 
             if !prev_exists {
                 return Err(BucketLogError::InvalidAppend(
                     current,
-                    prev_link.clone(),
+                    primary.clone(),
                     expected_prev_height,
                 ));
             }
+
+            // Any other parents should exist below this height
+            for parent in others {
+                let parent_exists = bucket_entries
+                    .iter()
+                    .any(|(h, links)| *h < height && links.contains(parent));
+                if !parent_exists {
+                    return Err(BucketLogError::InvalidAppend(
+                        current,
+                        parent.clone(),
+                        expected_prev_height,
+                    ));
+                }
+            }
         } else {
             // If no previous link, this must be genesis (height 0)
             if height != 0 {
@@ -169,7 +186,14 @@ impl BucketLogProvider for MemoryBucketLogProvider {
             .published
             .entry(id)
             .or_insert_with(HashMap::new)
-            .insert(current, published);
+            .insert(current.clone(), published);
+
+        // Store parent links
+        inner
+            .parents
+            .entry(id)
+            .or_insert_with(HashMap::new)
+            .insert(current, parents);
 
         Ok(())
     }
@@ -205,6 +229,26 @@ impl BucketLogProvider for MemoryBucketLogProvider {
             .unwrap_or_default())
     }
 
+    async fn parents(
+        &self,
+        id: Uuid,
+        link: Link,
+    ) -> Result<Vec<Link>, BucketLogError<Self::Error>> {
+        let inner = self.inner.read().map_err(|e| {
+            BucketLogError::Provider(MemoryBucketLogProviderError::Internal(format!(
+                "failed to acquire read lock: {}",
+                e
+            )))
+        })?;
+
+        Ok(inner
+            .parents
+            .get(&id)
+            .and_then(|links| links.get(&link))
+            .cloned()
+            .unwrap_or_default())
+    }
+
     async fn list_buckets(&self) -> Result<Vec<Uuid>, BucketLogError<Self::Error>> {
         let inner = self.inner.read().map_err(|e| {
             BucketLogError::Provider(MemoryBucketLogProviderError::Internal(format!(
@@ -266,7 +310,7 @@ mod tests {
 
         // Genesis append should succeed
         let result = provider
-            .append(id, "test".to_string(), link.clone(), None, 0, false)
+            .append(id, "test".to_string(), link.clone(), vec![], 0, false)
             .await;
         assert!(result.is_ok());
 
@@ -287,13 +331,13 @@ mod tests {
 
         // First append succeeds
         provider
-            .append(id, "test".to_string(), link.clone(), None, 0, false)
+            .append(id, "test".to_string(), link.clone(), vec![], 0, false)
             .await
             .unwrap();
 
         // Same link at same height should conflict
         let result = provider
-            .append(id, "test".to_string(), link, None, 0, false)
+            .append(id, "test".to_string(), link, vec![], 0, false)
             .await;
         assert!(matches!(result, Err(BucketLogError::Conflict)));
     }
@@ -307,13 +351,13 @@ mod tests {
 
         // Genesis
         provider
-            .append(id, "test".to_string(), link1, None, 0, false)
+            .append(id, "test".to_string(), link1, vec![], 0, false)
             .await
             .unwrap();
 
         // Append with non-existent previous should fail
         let result = provider
-            .append(id, "test".to_string(), link2.clone(), Some(link2), 1, false)
+            .append(id, "test".to_string(), link2.clone(), vec![link2], 1, false)
             .await;
         assert!(matches!(
             result,
@@ -330,13 +374,13 @@ mod tests {
 
         // Genesis
         provider
-            .append(id, "test".to_string(), link1.clone(), None, 0, false)
+            .append(id, "test".to_string(), link1.clone(), vec![], 0, false)
             .await
             .unwrap();
 
         // Valid append
         provider
-            .append(id, "test".to_string(), link2.clone(), Some(link1), 1, false)
+            .append(id, "test".to_string(), link2.clone(), vec![link1], 1, false)
             .await
             .unwrap();
 
@@ -359,7 +403,7 @@ mod tests {
 
         // Genesis (unpublished)
         provider
-            .append(id, "test".to_string(), link1.clone(), None, 0, false)
+            .append(id, "test".to_string(), link1.clone(), vec![], 0, false)
             .await
             .unwrap();
 
@@ -372,7 +416,7 @@ mod tests {
                 id,
                 "test".to_string(),
                 link2.clone(),
-                Some(link1.clone()),
+                vec![link1.clone()],
                 1,
                 true,
             )
@@ -390,7 +434,7 @@ mod tests {
                 id,
                 "test".to_string(),
                 link3.clone(),
-                Some(link2.clone()),
+                vec![link2.clone()],
                 2,
                 false,
             )
@@ -402,4 +446,84 @@ mod tests {
         assert_eq!(link, link2);
         assert_eq!(height, 1);
     }
+
+    #[tokio::test]
+    async fn test_fork_and_merge() {
+        let provider = MemoryBucketLogProvider::new();
+        let id = Uuid::new_v4();
+        let link = |n: u8| Link::new(0x55, Hash::from_bytes([n; 32]));
+        let name = || "test".to_string();
+
+        //        2 - 4
+        //      /      \
+        // 0 - 1        5
+        //      \      /
+        //        3 ---
+        provider
+            .append(id, name(), link(0), vec![], 0, false)
+            .await
+            .unwrap();
+        provider
+            .append(id, name(), link(1), vec![link(0)], 1, false)
+            .await
+            .unwrap();
+        provider
+            .append(id, name(), link(2), vec![link(1)], 2, false)
+            .await
+            .unwrap();
+        // A fork at the same height is fine
+        provider
+            .append(id, name(), link(3), vec![link(1)], 2, false)
+            .await
+            .unwrap();
+        provider
+            .append(id, name(), link(4), vec![link(2)], 3, false)
+            .await
+            .unwrap();
+
+        // The primary parent must sit right below the merge
+        let result = provider
+            .append(id, name(), link(5), vec![link(3), link(4)], 4, false)
+            .await;
+        assert!(matches!(
+            result,
+            Err(BucketLogError::InvalidAppend(_, _, 3))
+        ));
+        // Other parents must be in the log
+        let result = provider
+            .append(id, name(), link(5), vec![link(4), link(9)], 4, false)
+            .await;
+        assert!(matches!(
+            result,
+            Err(BucketLogError::InvalidAppend(_, _, _))
+        ));
+
+        provider
+            .append(id, name(), link(5), vec![link(4), link(3)], 4, true)
+            .await
+            .unwrap();
+
+        // The same version can't be appended twice, at any height
+        let result = provider
+            .append(id, name(), link(5), vec![link(3)], 3, false)
+            .await;
+        assert!(matches!(result, Err(BucketLogError::Conflict)));
+
+        assert_eq!(provider.heads(id, 2).await.unwrap(), vec![link(2), link(3)]);
+        assert_eq!(provider.head(id, None).await.unwrap(), (link(5), 4));
+        assert_eq!(
+            provider.parents(id, link(5)).await.unwrap(),
+            vec![link(4), link(3)]
+        );
+        assert!(provider.parents(id, link(0)).await.unwrap().is_empty());
+
+        // Both branches are in the merge's history, but not in each other's
+        assert!(provider.is_ancestor(id, link(3), link(5)).await.unwrap());
+        assert!(provider.is_ancestor(id, link(2), link(5)).await.unwrap());
+        assert!(provider.is_ancestor(id, link(0), link(4)).await.unwrap());
+        assert!(provider.is_ancestor(id, link(4), link(4)).await.unwrap());
+        assert!(!provider.is_ancestor(id, link(3), link(4)).await.unwrap());
+        assert!(!provider.is_ancestor(id, link(5), link(3)).await.unwrap());
+        assert!(!provider.is_ancestor(id, link(9), link(5)).await.unwrap());
+    }
 }
@@ -2,4 +2,4 @@ pub mod memory;
 mod provider;
 
 pub use memory::{MemoryBucketLogProvider, MemoryBucketLogProviderError};
-pub use provider::{BucketLogError, BucketLogProvider};
+pub use provider::{BucketLogError, BucketLogProvider, SyncMode};
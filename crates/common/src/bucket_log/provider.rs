@@ -1,6 +1,9 @@
 use std::fmt::{Debug, Display};
+use std::path::PathBuf;
+use std::str::FromStr;
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::linked_data::Link;
@@ -21,13 +24,52 @@ pub enum BucketLogError<T> {
     #[error("conflict with current log entry")]
     Conflict,
     /// An append does not implement a valid link structure
-    ///  st the previous link pointed at by the new log does
+    ///  st a parent link pointed at by the new log does
     ///  not exist in the log at the expected height --
-    ///  current, previous, height
+    ///  current, parent, height
     #[error("invalid append: {0}, {1}, {2}")]
     InvalidAppend(Link, Link, u64),
 }
 
+/// How much of a bucket a peer replicates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncMode {
+    /// Download every pinned blob of each synced version
+    #[default]
+    Full,
+    /// Download manifests, pins lists and directory nodes only; file
+    ///  contents are fetched from peers the first time they are read
+    Lazy,
+}
+
+impl SyncMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncMode::Full => "full",
+            SyncMode::Lazy => "lazy",
+        }
+    }
+}
+
+impl Display for SyncMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SyncMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(SyncMode::Full),
+            "lazy" => Ok(SyncMode::Lazy),
+            other => Err(format!("unknown sync mode: {}", other)),
+        }
+    }
+}
+
 #[async_trait]
 pub trait BucketLogProvider: Send + Sync + std::fmt::Debug + Clone + 'static {
     type Error: Display + Debug;
@@ -52,30 +94,83 @@ pub trait BucketLogProvider: Send + Sync + std::fmt::Debug + Clone + 'static {
     //  cache for.
     /// Append a version of the bucket to the log
     ///
+    /// Versions form a DAG: forks put several versions at one height,
+    ///  and a merge has more than one parent. The first parent is the
+    ///  primary one and must sit at `height - 1`; any others must already
+    ///  be in the log below `height`.
+    ///
     /// # Arguments
     /// * `id` - The UUID of the bucket
     /// * `name` - The friendly name for the bucket
     /// * `current` - The current link of the record
-    /// * `previous` - The previous link of the record
-    /// * `height` - The reported depth of the bucket version within the chain
+    /// * `parents` - The parent links of the record, primary parent first
+    /// * `height` - The reported depth of the bucket version within the log
     /// * `published` - Whether this version is published (mirrors can decrypt)
     ///
     /// Should fail with the following errors to be considered
     ///  correct:
-    /// * `Err(BucketLogError::Conflict)` - The append causes a conflict with the current log
-    /// * `Err(BucketLogError::InvalidHeight)` - The height is not greater than the previous height
+    /// * `Err(BucketLogError::Conflict)` - The link is already in the log
+    /// * `Err(BucketLogError::InvalidAppend)` - A parent is missing or at the wrong height
     async fn append(
         &self,
         id: Uuid,
         name: String,
         current: Link,
         // NOTE (amiller68): this should *only*
-        //  be null for the genesis of a bucket
-        previous: Option<Link>,
+        //  be empty for the genesis of a bucket
+        parents: Vec<Link>,
         height: u64,
         published: bool,
     ) -> Result<(), BucketLogError<Self::Error>>;
 
+    /// Get the parents recorded for a version, primary parent first
+    ///
+    /// # Arguments
+    /// * `id` - The UUID of the bucket
+    /// * `link` - The version to look up
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Link>)` - The parents (empty for genesis or unknown links)
+    async fn parents(&self, id: Uuid, link: Link)
+        -> Result<Vec<Link>, BucketLogError<Self::Error>>;
+
+    /// Check whether `ancestor` is `descendant` or part of its history
+    ///
+    /// Walks parents back from `descendant`, never below the ancestor's height.
+    async fn is_ancestor(
+        &self,
+        id: Uuid,
+        ancestor: Link,
+        descendant: Link,
+    ) -> Result<bool, BucketLogError<Self::Error>> {
+        let Some(floor) = self.has(id, ancestor.clone()).await?.into_iter().min() else {
+            return Ok(false);
+        };
+
+        let mut visited = std::collections::HashSet::new();
+        let mut queue = vec![descendant];
+        while let Some(link) = queue.pop() {
+            if link == ancestor {
+                return Ok(true);
+            }
+            if !visited.insert(link.clone()) {
+                continue;
+            }
+            let parents = self.parents(id, link).await?;
+            for parent in parents {
+                let above_floor = self
+                    .has(id, parent.clone())
+                    .await?
+                    .into_iter()
+                    .any(|height| height >= floor);
+                if above_floor {
+                    queue.push(parent);
+                }
+            }
+        }
+        Ok(false)
+    }
+
     /// Return the greatest height of the bucket version within the chain
     ///
     /// # Arguments
@@ -136,4 +231,19 @@ pub trait BucketLogProvider: Send + Sync + std::fmt::Debug + Clone + 'static {
         &self,
         id: Uuid,
     ) -> Result<Option<(Link, u64)>, BucketLogError<Self::Error>>;
+
+    /// How much of the bucket this peer replicates
+    ///
+    /// Providers that don't store a mode replicate everything.
+    async fn sync_mode(&self, _id: Uuid) -> Result<SyncMode, BucketLogError<Self::Error>> {
+        Ok(SyncMode::Full)
+    }
+
+    /// Paths kept available offline in a lazily synced bucket
+    ///
+    /// File contents under these paths are downloaded with every sync
+    ///  even when the bucket's [`SyncMode`] is lazy.
+    async fn offline_paths(&self, _id: Uuid) -> Result<Vec<PathBuf>, BucketLogError<Self::Error>> {
+        Ok(Vec::new())
+    }
 }
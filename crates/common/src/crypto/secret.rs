@@ -120,13 +120,40 @@ impl Secret {
     ///
     /// Returns an error if encryption fails (should be rare, only on system RNG failure).
     pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, SecretError> {
-        // Compute BLAKE3 hash of plaintext
         let plaintext_hash = blake3::hash(data);
+        self.seal(plaintext_hash.as_bytes(), data)
+    }
 
-        // Prepend hash to plaintext
-        let mut data_with_hash = Vec::with_capacity(BLAKE3_HASH_SIZE + data.len());
-        data_with_hash.extend_from_slice(plaintext_hash.as_bytes());
-        data_with_hash.extend_from_slice(data);
+    /// Compress data with zstd at the given level, then encrypt it
+    ///
+    /// The output format is: `nonce (12 bytes) || encrypted(hash(32) || zstd frame) || auth_tag (16 bytes)`.
+    /// The hash is still taken over the *uncompressed* plaintext, so
+    /// [`extract_plaintext_hash`](Self::extract_plaintext_hash) keeps working unchanged.
+    /// Nothing in the output marks it as compressed: callers record that
+    /// alongside the link and read it back with
+    /// [`decrypt_compressed`](Self::decrypt_compressed).
+    ///
+    /// Returns `None` if compression doesn't make the payload smaller; store
+    /// the data with [`encrypt`](Self::encrypt) instead.
+    pub fn encrypt_compressed(
+        &self,
+        data: &[u8],
+        level: i32,
+    ) -> Result<Option<Vec<u8>>, SecretError> {
+        let compressed = zstd::bulk::compress(data, level).map_err(SecretError::Io)?;
+        if compressed.len() >= data.len() {
+            return Ok(None);
+        }
+        let plaintext_hash = blake3::hash(data);
+        self.seal(plaintext_hash.as_bytes(), &compressed).map(Some)
+    }
+
+    /// Encrypt `hash || payload` under a fresh random nonce
+    fn seal(&self, hash: &[u8], payload: &[u8]) -> Result<Vec<u8>, SecretError> {
+        // Prepend hash to payload
+        let mut data_with_hash = Vec::with_capacity(BLAKE3_HASH_SIZE + payload.len());
+        data_with_hash.extend_from_slice(hash);
+        data_with_hash.extend_from_slice(payload);
 
         let key = Key::from_slice(self.bytes());
         let cipher = ChaCha20Poly1305::new(key);
@@ -161,6 +188,47 @@ impl Secret {
     /// - Decrypted data is too short to contain the hash header
     /// - Hash verification fails (data corruption)
     pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, SecretError> {
+        let (stored_hash, payload) = self.open(data)?;
+        verify_hash(&stored_hash, &payload)?;
+        Ok(payload)
+    }
+
+    /// Decrypt data written by [`encrypt_compressed`](Self::encrypt_compressed)
+    ///
+    /// `size` is the length of the uncompressed plaintext. Decompression stops
+    /// there, so a frame claiming to expand further is rejected rather than
+    /// exhausting memory.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`decrypt`](Self::decrypt), plus:
+    /// - The payload is not a valid zstd frame
+    /// - It decompresses to anything other than `size` bytes
+    pub fn decrypt_compressed(&self, data: &[u8], size: u64) -> Result<Vec<u8>, SecretError> {
+        let (stored_hash, payload) = self.open(data)?;
+
+        let decoder =
+            zstd::stream::read::Decoder::new(payload.as_slice()).map_err(SecretError::Io)?;
+        let mut plaintext = Vec::new();
+        decoder
+            .take(size.saturating_add(1))
+            .read_to_end(&mut plaintext)
+            .map_err(SecretError::Io)?;
+        if plaintext.len() as u64 != size {
+            return Err(anyhow::anyhow!(
+                "decompressed to {} bytes, expected {}",
+                plaintext.len(),
+                size
+            )
+            .into());
+        }
+
+        verify_hash(&stored_hash, &plaintext)?;
+        Ok(plaintext)
+    }
+
+    /// Authenticate and decrypt, splitting off the stored plaintext hash
+    fn open(&self, data: &[u8]) -> Result<([u8; BLAKE3_HASH_SIZE], Vec<u8>), SecretError> {
         if data.len() < NONCE_SIZE {
             return Err(anyhow::anyhow!("data too short for nonce").into());
         }
@@ -168,25 +236,19 @@ impl Secret {
         let key = Key::from_slice(self.bytes());
         let nonce = Nonce::from_slice(&data[..NONCE_SIZE]);
         let cipher = ChaCha20Poly1305::new(key);
-        let decrypted = cipher
+        let mut decrypted = cipher
             .decrypt(nonce, &data[NONCE_SIZE..])
             .map_err(|_| anyhow::anyhow!("decrypt error"))?;
 
-        // Extract hash and plaintext
+        // Extract hash and payload
         if decrypted.len() < BLAKE3_HASH_SIZE {
             return Err(anyhow::anyhow!("decrypted data too short for hash header").into());
         }
 
-        let stored_hash = &decrypted[..BLAKE3_HASH_SIZE];
-        let plaintext = &decrypted[BLAKE3_HASH_SIZE..];
-
-        // Verify hash integrity
-        let computed_hash = blake3::hash(plaintext);
-        if stored_hash != computed_hash.as_bytes() {
-            return Err(anyhow::anyhow!("hash verification failed - data corrupted").into());
-        }
-
-        Ok(plaintext.to_vec())
+        let payload = decrypted.split_off(BLAKE3_HASH_SIZE);
+        let mut hash = [0u8; BLAKE3_HASH_SIZE];
+        hash.copy_from_slice(&decrypted);
+        Ok((hash, payload))
     }
 
     /// Extract the BLAKE3 hash of the plaintext without decrypting the full content
@@ -261,6 +323,14 @@ impl Secret {
     }
 }
 
+/// Check a plaintext against the hash stored with it
+fn verify_hash(stored_hash: &[u8; BLAKE3_HASH_SIZE], plaintext: &[u8]) -> Result<(), SecretError> {
+    if blake3::hash(plaintext).as_bytes() != stored_hash {
+        return Err(anyhow::anyhow!("hash verification failed - data corrupted").into());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -367,4 +437,68 @@ mod test {
         let expected_hash = blake3::hash(data);
         assert_eq!(hash, *expected_hash.as_bytes());
     }
+
+    #[test]
+    fn test_compressed_encrypt_decrypt() {
+        let secret = Secret::generate();
+        let data = "timestamp=2026-10-18 level=info msg=\"request served\"\n".repeat(200);
+
+        let raw = secret.encrypt(data.as_bytes()).unwrap();
+        let compressed = secret
+            .encrypt_compressed(data.as_bytes(), 3)
+            .unwrap()
+            .unwrap();
+        assert!(compressed.len() < raw.len() / 4);
+
+        let decrypted = secret
+            .decrypt_compressed(&compressed, data.len() as u64)
+            .unwrap();
+        assert_eq!(decrypted, data.as_bytes());
+
+        // The stored hash covers the uncompressed plaintext
+        let hash = secret.extract_plaintext_hash(&compressed).unwrap();
+        assert_eq!(hash, *blake3::hash(data.as_bytes()).as_bytes());
+
+        // Without the flag the payload is never decompressed
+        assert!(secret.decrypt(&compressed).is_err());
+    }
+
+    #[test]
+    fn test_incompressible_data_not_compressed() {
+        let secret = Secret::generate();
+        let mut data = vec![0u8; 4096];
+        getrandom::getrandom(&mut data).unwrap();
+
+        assert!(secret.encrypt_compressed(&data, 3).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_decompressed_size_is_capped() {
+        let secret = Secret::generate();
+        let data = vec![0u8; 1024 * 1024];
+        let compressed = secret.encrypt_compressed(&data, 3).unwrap().unwrap();
+
+        // A frame expanding past the recorded size is rejected, not inflated
+        assert!(secret.decrypt_compressed(&compressed, 1024).is_err());
+        assert!(secret
+            .decrypt_compressed(&compressed, data.len() as u64 + 1)
+            .is_err());
+        assert_eq!(
+            secret
+                .decrypt_compressed(&compressed, data.len() as u64)
+                .unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn test_raw_plaintext_with_zstd_magic() {
+        // Uncompressed data that happens to look like a zstd frame (e.g. a
+        // stored .zst file) must round-trip untouched
+        let secret = Secret::generate();
+        let data = zstd::bulk::compress(b"already compressed elsewhere", 3).unwrap();
+
+        let encrypted = secret.encrypt(&data).unwrap();
+        assert_eq!(secret.decrypt(&encrypted).unwrap(), data);
+    }
 }
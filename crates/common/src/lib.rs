@@ -1,4 +1,9 @@
 pub mod bucket_log;
+/**
+ * Wall-clock helpers shared by the
+ *  expiry and timestamp checks.
+ */
+pub mod clock;
 /**
  * Cryptographic types and operations.
  *  - Public and Private key implementations
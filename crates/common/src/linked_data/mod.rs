@@ -1,7 +1,9 @@
 mod ipld;
 mod link;
 
-pub use ipld::{multibase, BlockEncoded, Cid, CidError, CodecError, LinkedData, LD_RAW_CODEC};
+pub use ipld::{
+    multibase, BlockEncoded, Cid, CidError, CodecError, LinkedData, LD_CBOR_CODEC, LD_RAW_CODEC,
+};
 pub use iroh_blobs::Hash;
 pub use link::Link;
 pub use serde_ipld_dagcbor::codec::DagCborCodec;
@@ -0,0 +1,308 @@
+//! # Membership history
+//!
+//! Every version of a bucket records its shares and is signed by the peer
+//! that wrote it, so the manifest chain already says who had access when,
+//! and who granted it. [`MembershipEvent::between`] reads that off one
+//! version by comparing its members with its parents'; walking the whole
+//! chain (see [`Mount::membership_history`](super::Mount::membership_history))
+//! gives an audit trail of the bucket's membership.
+//!
+//! A version only records a change that differs from all of its parents. A
+//! merge therefore doesn't repeat changes made on one of the merged
+//! branches: those are attributed to the version that made them. Secret
+//! shares are ignored, since they are re-issued on every save.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::PublicKey;
+use crate::linked_data::Link;
+
+use super::manifest::Manifest;
+use super::membership::{Member, Membership, Transition};
+use super::principal::PrincipalRole;
+
+/// How a version changed a principal's access
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MembershipEventKind {
+    /// The principal was given access
+    Added,
+    /// The principal's access was removed, or expired
+    Removed,
+    /// The principal's role changed
+    RoleChanged,
+    /// The principal's write scope or expiry changed
+    Updated,
+}
+
+impl std::fmt::Display for MembershipEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MembershipEventKind::Added => write!(f, "added"),
+            MembershipEventKind::Removed => write!(f, "removed"),
+            MembershipEventKind::RoleChanged => write!(f, "role_changed"),
+            MembershipEventKind::Updated => write!(f, "updated"),
+        }
+    }
+}
+
+/// A change to one principal's access, made by one version of a bucket
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MembershipEvent {
+    /// Whose access changed
+    pub principal: PublicKey,
+    pub kind: MembershipEventKind,
+    /// The principal's role after the change, or before it for removals
+    pub role: PrincipalRole,
+    /// The role before the change, if the principal had access
+    pub previous_role: Option<PrincipalRole>,
+    /// Write scope after the change, empty if unrestricted or removed
+    pub write_scope: Vec<PathBuf>,
+    /// Expiry after the change, in seconds since the Unix epoch
+    pub expires_at: Option<u64>,
+    /// The peer that signed the version
+    pub author: Option<PublicKey>,
+    /// Owners who approved the change, if the bucket has a quorum
+    ///
+    /// When verifying, only owners whose approval signature checks out.
+    pub approved_by: Vec<PublicKey>,
+    /// Height of the version
+    pub height: u64,
+    /// Link to the version
+    pub link: Link,
+    /// Whether the version's signature checked out, if it was checked
+    pub verified: Option<bool>,
+}
+
+impl MembershipEvent {
+    /// Get the membership changes a version makes over its parents
+    ///
+    /// `parents` are the version's parent manifests; none for the first
+    /// version, whose members are all added. Changes are compared against
+    /// the primary (first) parent. `verified` is set when verifying, and
+    /// then only approvals with a valid signature are reported.
+    pub fn between(
+        link: &Link,
+        manifest: &Manifest,
+        parents: &[Manifest],
+        verified: Option<bool>,
+    ) -> Vec<Self> {
+        let after = Membership::of(manifest);
+        let befores: Vec<Membership> = parents.iter().map(Membership::of).collect();
+        let approved_by = Self::approvers(manifest, parents, verified.is_some());
+
+        let mut keys: Vec<&String> = after.members().keys().collect();
+        for before in &befores {
+            keys.extend(before.members().keys());
+        }
+        keys.sort();
+        keys.dedup();
+
+        let mut events = Vec::new();
+        for key in keys {
+            let Ok(principal) = PublicKey::from_hex(key) else {
+                continue;
+            };
+            let now = after.members().get(key);
+            if befores
+                .iter()
+                .any(|before| before.members().get(key) == now)
+            {
+                continue;
+            }
+            // Every parent differs, so there is one unless this is the first version
+            let previous = befores.first().and_then(|before| before.members().get(key));
+            let Some((kind, member)) = Self::kind(previous, now) else {
+                continue;
+            };
+
+            events.push(Self {
+                principal,
+                kind,
+                role: member.role.clone(),
+                previous_role: previous.map(|member| member.role.clone()),
+                write_scope: now
+                    .map(|member| member.write_scope.clone())
+                    .unwrap_or_default(),
+                expires_at: now.and_then(|member| member.expires_at),
+                author: manifest.author().copied(),
+                approved_by: approved_by.clone(),
+                height: manifest.height(),
+                link: link.clone(),
+                verified,
+            });
+        }
+        events
+    }
+
+    /// Get the owners who approved a version's membership change
+    ///
+    /// Without `verify`, every key the version lists. With it, the change is
+    /// rebuilt from the parents and only approvals by owners of the primary
+    /// parent whose signature over that change is valid are kept.
+    fn approvers(manifest: &Manifest, parents: &[Manifest], verify: bool) -> Vec<PublicKey> {
+        if !verify {
+            return manifest
+                .approvals()
+                .keys()
+                .filter_map(|key| PublicKey::from_hex(key).ok())
+                .collect();
+        }
+
+        let parents: Vec<&Manifest> = parents.iter().collect();
+        let transition = Transition::between(
+            *manifest.id(),
+            &Membership::of(manifest),
+            &parents,
+            manifest.saved_at(),
+        );
+        let Transition::Change { mut change, .. } = transition else {
+            return Vec::new();
+        };
+        change.add_approvals(manifest.approvals());
+        change
+            .approvals()
+            .keys()
+            .filter_map(|key| PublicKey::from_hex(key).ok())
+            .collect()
+    }
+
+    /// Classify a change, returning the member it describes
+    fn kind<'a>(
+        before: Option<&'a Member>,
+        after: Option<&'a Member>,
+    ) -> Option<(MembershipEventKind, &'a Member)> {
+        match (before, after) {
+            (None, Some(after)) => Some((MembershipEventKind::Added, after)),
+            (Some(before), None) => Some((MembershipEventKind::Removed, before)),
+            (Some(before), Some(after)) if before.role != after.role => {
+                Some((MembershipEventKind::RoleChanged, after))
+            }
+            (Some(before), Some(after)) if before != after => {
+                Some((MembershipEventKind::Updated, after))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{Secret, SecretKey, SecretShare};
+    use crate::linked_data::{Hash, LD_RAW_CODEC};
+    use crate::mount::Share;
+    use uuid::Uuid;
+
+    fn link(byte: u8) -> Link {
+        Link::new(LD_RAW_CODEC, Hash::from_bytes([byte; 32]))
+    }
+
+    fn genesis(owner: &SecretKey) -> Manifest {
+        let share = SecretShare::new(&Secret::generate(), &owner.public()).unwrap();
+        let mut manifest = Manifest::new(
+            Uuid::new_v4(),
+            "audit".to_string(),
+            owner.public(),
+            share,
+            link(1),
+            link(2),
+            0,
+        );
+        manifest.sign(owner).unwrap();
+        manifest
+    }
+
+    #[test]
+    fn test_genesis_adds_creator() {
+        let owner = SecretKey::generate();
+        let manifest = genesis(&owner);
+
+        let events = MembershipEvent::between(&link(10), &manifest, &[], Some(true));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].principal, owner.public());
+        assert_eq!(events[0].kind, MembershipEventKind::Added);
+        assert_eq!(events[0].role, PrincipalRole::Owner);
+        assert_eq!(events[0].author, Some(owner.public()));
+    }
+
+    #[test]
+    fn test_changes_against_parent() {
+        let owner = SecretKey::generate();
+        let mirror = SecretKey::generate().public();
+        let reader = SecretKey::generate().public();
+
+        let mut parent = genesis(&owner);
+        parent.add_share(Share::new_mirror(mirror));
+        let mut child = parent.clone();
+        child.shares_mut().remove(&mirror.to_hex());
+        let share = SecretShare::new(&Secret::generate(), &reader).unwrap();
+        child.add_share(Share::new_reader(share, reader));
+
+        let mut events =
+            MembershipEvent::between(&link(11), &child, std::slice::from_ref(&parent), None);
+        events.sort_by_key(|event| event.kind as u8);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, MembershipEventKind::Added);
+        assert_eq!(events[0].principal, reader);
+        assert_eq!(events[1].kind, MembershipEventKind::Removed);
+        assert_eq!(events[1].principal, mirror);
+        assert_eq!(events[1].role, PrincipalRole::Mirror);
+    }
+
+    #[test]
+    fn test_verify_reports_only_valid_approvals() {
+        let owner = SecretKey::generate();
+        let other = SecretKey::generate();
+        let forger = SecretKey::generate();
+        let mirror = SecretKey::generate().public();
+
+        let mut parent = genesis(&owner);
+        let share = SecretShare::new(&Secret::generate(), &other.public()).unwrap();
+        parent.add_share(Share::new_owner(share, other.public()));
+        parent.set_quorum(2);
+        let mut child = parent.clone();
+        child.add_share(Share::new_mirror(mirror));
+
+        let parents = std::slice::from_ref(&parent);
+        let Transition::Change { mut change, .. } = Transition::between(
+            *child.id(),
+            &Membership::of(&child),
+            &[&parent],
+            child.saved_at(),
+        ) else {
+            panic!("adding a mirror changes the membership");
+        };
+        change.approve(&owner).unwrap();
+        let mut approvals = change.approvals().clone();
+        // A signature by someone else, listed under the other owner's key
+        let forged = forger.sign(b"not the change");
+        approvals.insert(other.public().to_hex(), forged);
+        child.set_approvals(approvals);
+
+        let events = MembershipEvent::between(&link(13), &child, parents, None);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].approved_by.len(), 2);
+
+        let events = MembershipEvent::between(&link(13), &child, parents, Some(true));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].approved_by, vec![owner.public()]);
+    }
+
+    #[test]
+    fn test_merge_keeps_branch_changes_quiet() {
+        let owner = SecretKey::generate();
+        let mirror = SecretKey::generate().public();
+
+        let base = genesis(&owner);
+        let mut branch = base.clone();
+        branch.add_share(Share::new_mirror(mirror));
+        let merge = branch.clone();
+
+        // The mirror was added on the branch, not by the merge
+        let events = MembershipEvent::between(&link(12), &merge, &[base, branch], None);
+        assert!(events.is_empty());
+    }
+}
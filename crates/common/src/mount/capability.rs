@@ -0,0 +1,256 @@
+//! # File capabilities
+//!
+//! Every file in a bucket is encrypted with its own [`Secret`], kept in the
+//! parent directory's [`NodeLink`]. Handing out the blob hash together with
+//! that secret lets anyone read the one file, and nothing else in the
+//! bucket, without publishing it.
+//!
+//! A [`FileCapability`] bundles the hash and secret with the file's name and
+//! MIME type, and is signed by the node that minted it so an optional expiry
+//! can't be stripped or extended. It encodes as a URL-safe string the
+//! gateway serves the file from. The signature doesn't make the secret any
+//! less bearer: whoever holds an unexpired capability can read the file.
+//!
+//! A valid signature only shows who minted a capability, not that they were
+//! allowed to: anyone holding a link could strip its expiry and sign it
+//! again with their own key. Nodes serving capabilities therefore check them
+//! with [`FileCapability::authorize`], which only trusts the node itself and
+//! the bucket's owners as issuers.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::crypto::{PublicKey, Secret, SecretError, SecretKey, Signature};
+use crate::linked_data::Hash;
+
+use super::manifest::Manifest;
+use super::node::NodeLink;
+use super::principal::PrincipalRole;
+
+/// Prefix of an encoded file capability
+pub const CAPABILITY_PREFIX: &str = "jaxcap";
+
+/// Errors that can occur while minting, decoding or opening a capability.
+#[derive(Debug, thiserror::Error)]
+pub enum CapabilityError {
+    #[error("capabilities can only be made for files")]
+    NotAFile,
+    #[error("not a file capability, expected the '{CAPABILITY_PREFIX}' prefix")]
+    Prefix,
+    #[error("invalid capability encoding: {0}")]
+    Encoding(#[from] data_encoding::DecodeError),
+    #[error("invalid capability contents: {0}")]
+    Codec(#[from] bincode::Error),
+    #[error("invalid signature on capability")]
+    InvalidSignature,
+    #[error("capability issuer is not trusted to link files from this bucket")]
+    UntrustedIssuer,
+    #[error("capability expired at {0}")]
+    Expired(u64),
+    #[error("secret error: {0}")]
+    Secret(#[from] SecretError),
+}
+
+/// A signed link that grants read access to a single file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileCapability {
+    /// The bucket the file was linked from
+    pub bucket_id: Uuid,
+    /// Hash of the encrypted file contents
+    pub hash: Hash,
+    /// The file's own encryption secret
+    pub secret: Secret,
+    /// File name, for downloads
+    pub name: String,
+    /// MIME type, if known
+    pub mime: Option<String>,
+    /// Uncompressed size, if the file was compressed before encryption
+    pub compressed: Option<u64>,
+    /// When the capability stops working, in seconds since the Unix epoch
+    pub expires_at: Option<u64>,
+    /// The node that minted the capability, and can serve the blob
+    pub issuer: PublicKey,
+    /// Ed25519 signature over every other field.
+    signature: Option<Signature>,
+}
+
+impl FileCapability {
+    /// Mint a capability for the file behind `link`, signed by `issuer`
+    pub fn new(
+        bucket_id: Uuid,
+        link: &NodeLink,
+        name: String,
+        expires_at: Option<u64>,
+        issuer: &SecretKey,
+    ) -> Result<Self, CapabilityError> {
+        let NodeLink::Data(link, secret, data) = link else {
+            return Err(CapabilityError::NotAFile);
+        };
+        let mut capability = Self {
+            bucket_id,
+            hash: link.hash(),
+            secret: secret.clone(),
+            name,
+            mime: data.mime().map(|mime| mime.to_string()),
+            compressed: data.compressed(),
+            expires_at,
+            issuer: issuer.public(),
+            signature: None,
+        };
+        capability.signature = Some(issuer.sign(&capability.signable_bytes()?));
+        Ok(capability)
+    }
+
+    /// Check the issuer's signature and that the capability hasn't expired
+    /// at `now`
+    pub fn verify(&self, now: u64) -> Result<(), CapabilityError> {
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or(CapabilityError::InvalidSignature)?;
+        self.issuer
+            .verify(&self.signable_bytes()?, signature)
+            .map_err(|_| CapabilityError::InvalidSignature)?;
+        match self.expires_at {
+            Some(expires_at) if expires_at <= now => Err(CapabilityError::Expired(expires_at)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the capability may be served by `node` at `now`
+    ///
+    /// Besides [`verify`](Self::verify), the issuer must be `node` itself or
+    /// an unexpired owner in `manifest`, the node's head of the bucket.
+    pub fn authorize(
+        &self,
+        node: &PublicKey,
+        manifest: Option<&Manifest>,
+        now: u64,
+    ) -> Result<(), CapabilityError> {
+        self.verify(now)?;
+        if self.issuer == *node {
+            return Ok(());
+        }
+        let is_owner = manifest
+            .filter(|manifest| *manifest.id() == self.bucket_id)
+            .and_then(|manifest| manifest.get_share(&self.issuer))
+            .is_some_and(|share| *share.role() == PrincipalRole::Owner && !share.is_expired(now));
+        if is_owner {
+            Ok(())
+        } else {
+            Err(CapabilityError::UntrustedIssuer)
+        }
+    }
+
+    /// Decrypt the file's contents
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CapabilityError> {
+        Ok(match self.compressed {
+            Some(size) => self.secret.decrypt_compressed(data, size)?,
+            None => self.secret.decrypt(data)?,
+        })
+    }
+
+    /// The bytes the issuer signs: the capability without its signature
+    fn signable_bytes(&self) -> Result<Vec<u8>, CapabilityError> {
+        let mut signable = self.clone();
+        signable.signature = None;
+        Ok(bincode::serialize(&signable)?)
+    }
+}
+
+impl fmt::Display for FileCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = bincode::serialize(self).map_err(|_| fmt::Error)?;
+        let encoded = data_encoding::BASE32_NOPAD.encode(&bytes);
+        write!(f, "{}{}", CAPABILITY_PREFIX, encoded.to_ascii_lowercase())
+    }
+}
+
+impl FromStr for FileCapability {
+    type Err = CapabilityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let encoded = s
+            .trim()
+            .strip_prefix(CAPABILITY_PREFIX)
+            .ok_or(CapabilityError::Prefix)?;
+        let bytes = data_encoding::BASE32_NOPAD.decode(encoded.to_ascii_uppercase().as_bytes())?;
+        Ok(bincode::deserialize(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::linked_data::{Link, LD_RAW_CODEC};
+
+    fn file_link() -> NodeLink {
+        let link = Link::new(LD_RAW_CODEC, Hash::from_bytes([3u8; 32]));
+        NodeLink::new_data_from_path(link, Secret::generate(), Path::new("/notes/report.pdf"))
+    }
+
+    #[test]
+    fn test_capability_roundtrip() {
+        let issuer = SecretKey::generate();
+        let capability = FileCapability::new(
+            Uuid::new_v4(),
+            &file_link(),
+            "report.pdf".to_string(),
+            None,
+            &issuer,
+        )
+        .unwrap();
+        assert_eq!(capability.mime.as_deref(), Some("application/pdf"));
+
+        let encoded = capability.to_string();
+        assert!(encoded.starts_with(CAPABILITY_PREFIX));
+        let decoded: FileCapability = encoded.parse().unwrap();
+        assert_eq!(decoded, capability);
+        assert!(decoded.verify(u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_extended_capability_is_rejected() {
+        let issuer = SecretKey::generate();
+        let mut capability = FileCapability::new(
+            Uuid::new_v4(),
+            &file_link(),
+            "report.pdf".to_string(),
+            Some(100),
+            &issuer,
+        )
+        .unwrap();
+        assert!(capability.verify(99).is_ok());
+        assert!(matches!(
+            capability.verify(100),
+            Err(CapabilityError::Expired(100))
+        ));
+
+        capability.expires_at = None;
+        assert!(matches!(
+            capability.verify(100),
+            Err(CapabilityError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_directories_have_no_capability() {
+        let link = NodeLink::new_dir(
+            Link::new(LD_RAW_CODEC, Hash::from_bytes([3u8; 32])),
+            Secret::generate(),
+        );
+        let result = FileCapability::new(
+            Uuid::new_v4(),
+            &link,
+            "notes".to_string(),
+            None,
+            &SecretKey::generate(),
+        );
+        assert!(matches!(result, Err(CapabilityError::NotAFile)));
+    }
+}
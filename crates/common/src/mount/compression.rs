@@ -0,0 +1,201 @@
+//! # Compression Policy
+//!
+//! Buckets can opt into transparent zstd compression of file contents. Data is
+//! compressed *before* it is encrypted (ciphertext doesn't compress), inside
+//! the [`Secret`](crate::crypto::Secret) envelope. The file's link records
+//! that it was compressed along with its uncompressed size, and readers go
+//! through [`NodeLink::decrypt`](super::NodeLink::decrypt), which only
+//! decompresses flagged blobs and never past that size.
+//!
+//! The policy lives on the [`Manifest`](super::Manifest) so every peer writing
+//! to the bucket applies the same rules. It decides per file, by MIME type:
+//!
+//! 1. An explicit per-MIME override (`"text/*"`, `"application/json"`) wins
+//! 2. Otherwise types that are already compressed (media, archives, PDFs,
+//!    fonts, office documents) are skipped
+//! 3. Everything else, including files of unknown type, is compressed
+
+use std::collections::BTreeMap;
+
+use mime::Mime;
+use serde::{Deserialize, Serialize};
+
+/// Default zstd level; favours speed, which is what matters on save
+pub const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
+/// MIME types whose contents are already compressed.
+///
+/// Entries ending in `/*` match the whole top-level type.
+const PRECOMPRESSED_MIMES: &[&str] = &[
+    "image/*",
+    "video/*",
+    "audio/*",
+    "font/woff",
+    "font/woff2",
+    "application/zip",
+    "application/gzip",
+    "application/x-gzip",
+    "application/x-bzip",
+    "application/x-bzip2",
+    "application/x-xz",
+    "application/x-7z-compressed",
+    "application/x-rar-compressed",
+    "application/vnd.rar",
+    "application/zstd",
+    "application/x-zstd",
+    "application/x-compress",
+    "application/x-lzip",
+    "application/java-archive",
+    "application/epub+zip",
+    "application/pdf",
+    "application/font-woff",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    "application/vnd.oasis.opendocument.text",
+    "application/vnd.oasis.opendocument.spreadsheet",
+    "application/vnd.oasis.opendocument.presentation",
+];
+
+/// Per-bucket compression settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressionPolicy {
+    /// zstd compression level (1-22)
+    level: i32,
+    /// Per-MIME overrides, keyed by exact type (`"application/json"`) or
+    /// top-level wildcard (`"text/*"`); `true` forces compression, `false`
+    /// disables it
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    mime: BTreeMap<String, bool>,
+}
+
+impl Default for CompressionPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMPRESSION_LEVEL)
+    }
+}
+
+impl CompressionPolicy {
+    /// Create a policy compressing everything compressible at `level`.
+    ///
+    /// The level is clamped to zstd's supported range.
+    pub fn new(level: i32) -> Self {
+        let mut policy = Self {
+            level: DEFAULT_COMPRESSION_LEVEL,
+            mime: BTreeMap::new(),
+        };
+        policy.set_level(level);
+        policy
+    }
+
+    /* Getters */
+
+    /// Get the zstd compression level.
+    pub fn level(&self) -> i32 {
+        self.level
+    }
+
+    /// Get the per-MIME overrides.
+    pub fn overrides(&self) -> &BTreeMap<String, bool> {
+        &self.mime
+    }
+
+    /// Decide whether a file of the given MIME type should be compressed.
+    ///
+    /// Returns the zstd level to use, or `None` to store the file raw.
+    pub fn level_for(&self, mime: Option<&Mime>) -> Option<i32> {
+        let compress = match mime {
+            Some(mime) => match self.override_for(mime) {
+                Some(compress) => compress,
+                None => !is_precompressed(mime),
+            },
+            None => true,
+        };
+        compress.then_some(self.level)
+    }
+
+    /// Look up an override, preferring an exact match over a wildcard.
+    fn override_for(&self, mime: &Mime) -> Option<bool> {
+        self.mime
+            .get(mime.essence_str())
+            .or_else(|| self.mime.get(&format!("{}/*", mime.type_())))
+            .copied()
+    }
+
+    /* Setters */
+
+    /// Set the zstd compression level, clamped to zstd's supported range.
+    pub fn set_level(&mut self, level: i32) {
+        self.level = level.clamp(1, 22);
+    }
+
+    /// Force compression on (`true`) or off (`false`) for a MIME pattern.
+    pub fn set_override(&mut self, pattern: impl Into<String>, compress: bool) {
+        self.mime.insert(pattern.into().to_lowercase(), compress);
+    }
+
+    /// Remove an override, falling back to the built-in rules.
+    pub fn remove_override(&mut self, pattern: &str) {
+        self.mime.remove(&pattern.to_lowercase());
+    }
+}
+
+/// Check whether a MIME type is on the built-in list of already-compressed types.
+pub fn is_precompressed(mime: &Mime) -> bool {
+    let essence = mime.essence_str();
+    let wildcard = format!("{}/*", mime.type_());
+    PRECOMPRESSED_MIMES
+        .iter()
+        .any(|m| *m == essence || *m == wildcard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mime(s: &str) -> Mime {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_default_skips_precompressed() {
+        let policy = CompressionPolicy::default();
+
+        assert_eq!(policy.level_for(Some(&mime("text/plain"))), Some(3));
+        assert_eq!(policy.level_for(Some(&mime("application/json"))), Some(3));
+        assert_eq!(policy.level_for(None), Some(3));
+
+        assert_eq!(policy.level_for(Some(&mime("image/png"))), None);
+        assert_eq!(policy.level_for(Some(&mime("video/mp4"))), None);
+        assert_eq!(policy.level_for(Some(&mime("application/zip"))), None);
+        assert_eq!(policy.level_for(Some(&mime("application/pdf"))), None);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut policy = CompressionPolicy::new(9);
+        // BMPs are uncompressed images
+        policy.set_override("image/bmp", true);
+        policy.set_override("text/*", false);
+        policy.set_override("text/csv", true);
+
+        assert_eq!(policy.level_for(Some(&mime("image/bmp"))), Some(9));
+        assert_eq!(policy.level_for(Some(&mime("image/jpeg"))), None);
+        assert_eq!(policy.level_for(Some(&mime("text/html"))), None);
+        assert_eq!(policy.level_for(Some(&mime("text/csv"))), Some(9));
+        // Parameters don't affect matching
+        assert_eq!(
+            policy.level_for(Some(&mime("text/csv; charset=utf-8"))),
+            Some(9)
+        );
+
+        policy.remove_override("TEXT/*");
+        assert_eq!(policy.level_for(Some(&mime("text/html"))), Some(9));
+    }
+
+    #[test]
+    fn test_level_clamped() {
+        assert_eq!(CompressionPolicy::new(0).level(), 1);
+        assert_eq!(CompressionPolicy::new(99).level(), 22);
+    }
+}
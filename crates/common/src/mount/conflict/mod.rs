@@ -14,6 +14,11 @@
 //! # Custom Resolvers
 //!
 //! Implement the [`ConflictResolver`] trait to create custom resolution strategies.
+//!
+//! # Per-Bucket Strategy
+//!
+//! A bucket records which built-in resolver sync should use when it merges
+//! diverged histories as a [`ConflictStrategy`] on its manifest.
 
 mod base_wins;
 mod conflict_file;
@@ -29,6 +34,8 @@ pub use types::{Conflict, MergeResult, Resolution, ResolvedConflict};
 
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
+
 use crate::crypto::PublicKey;
 
 use super::path_ops::{OpType, PathOperation};
@@ -51,6 +58,64 @@ pub trait ConflictResolver: std::fmt::Debug + Send + Sync {
     fn resolve(&self, conflict: &Conflict, local_peer: &PublicKey) -> Resolution;
 }
 
+/// Built-in conflict resolution strategy a bucket is configured with.
+///
+/// Stored on the manifest so every peer merging the bucket resolves
+/// conflicts the same way. [`ForkOnConflict`] is not offered here since it
+/// leaves conflicts for a human, which automatic sync can't do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictStrategy {
+    /// Keep both versions, renaming the incoming one (see [`ConflictFile`])
+    #[default]
+    ConflictFile,
+    /// Higher operation timestamp wins (see [`LastWriteWins`])
+    LastWriteWins,
+    /// The merging peer's version wins (see [`BaseWins`])
+    BaseWins,
+}
+
+impl ConflictStrategy {
+    /// Build the resolver for this strategy.
+    pub fn resolver(&self) -> Box<dyn ConflictResolver> {
+        match self {
+            ConflictStrategy::ConflictFile => Box::new(ConflictFile::new()),
+            ConflictStrategy::LastWriteWins => Box::new(LastWriteWins::new()),
+            ConflictStrategy::BaseWins => Box::new(BaseWins::new()),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictStrategy::ConflictFile => "conflict-file",
+            ConflictStrategy::LastWriteWins => "last-write-wins",
+            ConflictStrategy::BaseWins => "base-wins",
+        }
+    }
+}
+
+impl std::fmt::Display for ConflictStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ConflictStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "conflict-file" => Ok(ConflictStrategy::ConflictFile),
+            "last-write-wins" => Ok(ConflictStrategy::LastWriteWins),
+            "base-wins" => Ok(ConflictStrategy::BaseWins),
+            other => Err(format!(
+                "unknown conflict strategy '{}', expected conflict-file, last-write-wins or base-wins",
+                other
+            )),
+        }
+    }
+}
+
 /// Check if two operations conflict
 ///
 /// Two operations conflict if:
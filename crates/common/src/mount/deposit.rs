@@ -0,0 +1,313 @@
+//! # Deposits
+//!
+//! A deposit is a file submitted to a bucket by a
+//! [`Depositor`](super::PrincipalRole::Depositor), a principal that may add
+//! files but not read any.
+//!
+//! Depositors hold no share of the bucket secret, so they can't write a new
+//! version themselves. Instead the file is encrypted with a fresh secret that
+//! is sealed to each owner's public key, and the deposit object recording it
+//! is signed by the depositor. An owner that receives the deposit checks it
+//! against the bucket's current shares and links the already encrypted
+//! content into the bucket under [`INBOX_PATH`] (see
+//! [`Mount::accept_deposit`](super::Mount::accept_deposit)).
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::clock::unix_now;
+use crate::crypto::{
+    PublicKey, Secret, SecretError, SecretKey, SecretShare, SecretShareError, Signature,
+};
+use crate::linked_data::{BlockEncoded, CodecError, DagCborCodec, Link, LD_RAW_CODEC};
+use crate::peer::{BlobsStore, BlobsStoreError};
+
+use super::manifest::Manifest;
+use super::principal::PrincipalRole;
+
+/// Directory deposits are accepted into
+pub const INBOX_PATH: &str = "/inbox";
+
+/// Errors that can occur while creating, checking or opening a deposit.
+#[derive(Debug, thiserror::Error)]
+pub enum DepositError {
+    #[error("codec error: {0}")]
+    Codec(#[from] CodecError),
+    #[error("blobs store error: {0}")]
+    BlobsStore(#[from] BlobsStoreError),
+    #[error("secret error: {0}")]
+    Secret(#[from] SecretError),
+    #[error("share error: {0}")]
+    Share(#[from] SecretShareError),
+    #[error("invalid inbox path: {0}")]
+    InvalidPath(PathBuf),
+    #[error("deposit is for another bucket ({0})")]
+    WrongBucket(Uuid),
+    #[error("author is not a depositor of the bucket")]
+    NotDepositor,
+    #[error("bucket has no owners to deposit to")]
+    NoOwners,
+    #[error("deposit is not sealed to this key")]
+    NotSealedToUs,
+    #[error("invalid signature on deposit")]
+    InvalidSignature,
+}
+
+/// A signed, encrypted file submitted to a bucket's inbox.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Deposit {
+    /// The bucket the deposit is for.
+    bucket_id: Uuid,
+    /// Where the file goes, relative to [`INBOX_PATH`].
+    path: PathBuf,
+    /// Link to the encrypted file contents.
+    content: Link,
+    /// The content secret sealed to each owner, keyed by hex public key.
+    keys: BTreeMap<String, SecretShare>,
+    /// When the deposit was made, in seconds since the Unix epoch.
+    created_at: u64,
+    /// Public key of the depositor.
+    author: PublicKey,
+    /// Ed25519 signature over every other field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<Signature>,
+}
+
+impl BlockEncoded<DagCborCodec> for Deposit {}
+
+impl Deposit {
+    /// Encrypt `data` for the bucket's owners and store it as a deposit.
+    ///
+    /// `manifest` is the bucket's current version, which must list
+    /// `depositor` as a depositor. `path` is where the file should land
+    /// inside the inbox. Returns the deposit and the link it was stored at.
+    pub async fn create(
+        manifest: &Manifest,
+        path: &Path,
+        data: &[u8],
+        depositor: &SecretKey,
+        blobs: &BlobsStore,
+    ) -> Result<(Self, Link), DepositError> {
+        let is_depositor = manifest
+            .get_share(&depositor.public())
+            .is_some_and(|share| *share.role() == PrincipalRole::Depositor);
+        if !is_depositor {
+            return Err(DepositError::NotDepositor);
+        }
+        let path = inbox_relative(path)?;
+
+        let owners = manifest.get_shares_by_role(PrincipalRole::Owner);
+        if owners.is_empty() {
+            return Err(DepositError::NoOwners);
+        }
+
+        let secret = Secret::generate();
+        let mut keys = BTreeMap::new();
+        for owner in owners {
+            let identity = owner.principal().identity;
+            keys.insert(identity.to_hex(), SecretShare::new(&secret, &identity)?);
+        }
+
+        let hash = blobs.put(secret.encrypt(data)?).await?;
+        blobs.keep_hot(&hash).await?;
+        let created_at = unix_now();
+
+        let mut deposit = Self {
+            bucket_id: *manifest.id(),
+            path,
+            content: Link::new(LD_RAW_CODEC, hash),
+            keys,
+            created_at,
+            author: depositor.public(),
+            signature: None,
+        };
+        deposit.signature = Some(depositor.sign(&deposit.encode()?));
+
+        let hash = blobs.put(deposit.encode()?).await?;
+        blobs.keep_hot(&hash).await?;
+        let link = Link::new(deposit.codec(), hash);
+        Ok((deposit, link))
+    }
+
+    /* Getters */
+
+    /// Get the bucket the deposit is for.
+    pub fn bucket_id(&self) -> &Uuid {
+        &self.bucket_id
+    }
+
+    /// Get the path of the file relative to the inbox.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the absolute path the file is accepted at.
+    pub fn inbox_path(&self) -> PathBuf {
+        Path::new(INBOX_PATH).join(&self.path)
+    }
+
+    /// Get the link to the encrypted file contents.
+    pub fn content(&self) -> &Link {
+        &self.content
+    }
+
+    /// Get the public key of the depositor.
+    pub fn author(&self) -> &PublicKey {
+        &self.author
+    }
+
+    /// Get when the deposit was made, in seconds since the Unix epoch.
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    /* Verification */
+
+    /// Check the deposit against the bucket's current version.
+    ///
+    /// The deposit must be for this bucket, signed by a principal that is
+    /// still a depositor, and land inside the inbox.
+    #[allow(clippy::result_large_err)]
+    pub fn verify(&self, manifest: &Manifest) -> Result<(), DepositError> {
+        if self.bucket_id != *manifest.id() {
+            return Err(DepositError::WrongBucket(self.bucket_id));
+        }
+
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or(DepositError::InvalidSignature)?;
+        let mut signable = self.clone();
+        signable.signature = None;
+        self.author
+            .verify(&signable.encode()?, signature)
+            .map_err(|_| DepositError::InvalidSignature)?;
+
+        let is_depositor = manifest
+            .get_share(&self.author)
+            .is_some_and(|share| *share.role() == PrincipalRole::Depositor);
+        if !is_depositor {
+            return Err(DepositError::NotDepositor);
+        }
+
+        // Stored paths are already normalized; anything else could escape
+        // the inbox when joined onto it
+        if inbox_relative(&self.path)? != self.path {
+            return Err(DepositError::InvalidPath(self.path.clone()));
+        }
+        Ok(())
+    }
+
+    /// Recover the content secret with an owner's key.
+    #[allow(clippy::result_large_err)]
+    pub fn secret(&self, owner: &SecretKey) -> Result<Secret, DepositError> {
+        let share = self
+            .keys
+            .get(&owner.public().to_hex())
+            .ok_or(DepositError::NotSealedToUs)?;
+        Ok(share.recover(owner)?)
+    }
+}
+
+/// Normalize a path inside the inbox, rejecting anything that would escape it
+///
+/// Accepts `name`, `/name` or `/inbox/name` forms; the result is relative
+/// to the inbox.
+#[allow(clippy::result_large_err)]
+fn inbox_relative(path: &Path) -> Result<PathBuf, DepositError> {
+    let invalid = || DepositError::InvalidPath(path.to_path_buf());
+    let path = path
+        .strip_prefix(INBOX_PATH)
+        .or_else(|_| path.strip_prefix("/"))
+        .unwrap_or(path);
+
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            _ => return Err(invalid()),
+        }
+    }
+    if relative.as_os_str().is_empty() {
+        return Err(invalid());
+    }
+    Ok(relative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mount::Share;
+
+    #[test]
+    fn test_inbox_relative_paths() {
+        assert_eq!(
+            inbox_relative(Path::new("report.pdf")).unwrap(),
+            PathBuf::from("report.pdf")
+        );
+        assert_eq!(
+            inbox_relative(Path::new("/inbox/alice/report.pdf")).unwrap(),
+            PathBuf::from("alice/report.pdf")
+        );
+        assert!(inbox_relative(Path::new("../etc/passwd")).is_err());
+        assert!(inbox_relative(Path::new("alice/../../x")).is_err());
+        assert!(inbox_relative(Path::new("/inbox")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deposit_round_trip() {
+        let blobs = BlobsStore::memory().await.unwrap();
+        let owner = SecretKey::generate();
+        let depositor = SecretKey::generate();
+        let mut manifest = Manifest::new(
+            Uuid::new_v4(),
+            "test".to_string(),
+            owner.public(),
+            SecretShare::default(),
+            Link::default(),
+            Link::default(),
+            0,
+        );
+
+        let result =
+            Deposit::create(&manifest, Path::new("a.txt"), b"hi", &depositor, &blobs).await;
+        assert!(matches!(result, Err(DepositError::NotDepositor)));
+
+        manifest.add_share(Share::new_depositor(depositor.public()));
+        let (deposit, link) =
+            Deposit::create(&manifest, Path::new("a.txt"), b"hi", &depositor, &blobs)
+                .await
+                .unwrap();
+        let stored: Deposit = blobs.get_cbor(&link.hash()).await.unwrap();
+        assert_eq!(stored, deposit);
+        stored.verify(&manifest).unwrap();
+        assert_eq!(stored.inbox_path(), PathBuf::from("/inbox/a.txt"));
+
+        // Only owners can open it
+        let secret = stored.secret(&owner).unwrap();
+        let data = blobs.get(&stored.content().hash()).await.unwrap();
+        assert_eq!(secret.decrypt(&data).unwrap(), b"hi");
+        assert!(matches!(
+            stored.secret(&depositor),
+            Err(DepositError::NotSealedToUs)
+        ));
+
+        // Tampering breaks the signature
+        let mut tampered = stored.clone();
+        tampered.path = PathBuf::from("b.txt");
+        assert!(matches!(
+            tampered.verify(&manifest),
+            Err(DepositError::InvalidSignature)
+        ));
+
+        // A depositor that was removed can't deposit anymore
+        manifest.shares_mut().remove(&depositor.public().to_hex());
+        assert!(matches!(
+            stored.verify(&manifest),
+            Err(DepositError::NotDepositor)
+        ));
+    }
+}
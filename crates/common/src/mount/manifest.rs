@@ -5,24 +5,45 @@
 //! - **Identity**: UUID and friendly name
 //! - **Access control**: Map of principals to their shares
 //! - **Content**: Links to the entry node and pin set
-//! - **History**: Link to previous manifest version and height in the chain
-//! - **Publication state**: Optional plaintext secret for public read access
+//! - **History**: Links to parent manifest versions and height in the version graph
+//! - **Publication state**: Optional plaintext secret for public read access, or links to published subtrees
+//! - **Compression**: Optional [`CompressionPolicy`] applied to file contents on write
+//! - **Membership quorum**: Optional number of owners that must approve changes to the shares
 //!
 //! ## Encryption Model
 //!
 //! - **Owners** have an encrypted [`SecretShare`] that they can decrypt with their private key
 //! - **Mirrors** have no individual share; they use [`Manifest::public`] when available
+//! - **Depositors** have no share and can't decrypt; they submit [`Deposit`](super::Deposit)s
 //! - **Publishing** stores the bucket's secret in plaintext, making it readable by anyone with the manifest
+//! - **Publishing subtrees** stores the [`NodeLink`]s of chosen directories instead, so only those are readable
 //!
 //! ## Versioning
 //!
 //! Each modification creates a new manifest with:
-//! - `previous` pointing to the prior manifest's CID
-//! - `height` incremented by 1
+//! - `parents` listing the CIDs of the versions it builds on
+//! - `previous` pointing to the first parent
+//! - `height` one more than the highest parent
 //!
-//! This forms an immutable version chain for history traversal.
+//! Ordinary edits have a single parent. A merge of diverged versions lists
+//! every merged head, with the highest one first. Versions therefore form a
+//! DAG rather than a chain; following only `previous` walks its first-parent
+//! spine, which is all that manifests written before `parents` existed have
+//! (see [`Manifest::parents`]).
+//!
+//! ## Skip Pointers
+//!
+//! Walking the first-parent spine one version at a time is linear in the
+//! history length. Each manifest therefore also records `skips`: the
+//! first-parent ancestors 2, 4, 8, ... versions below it, up to genesis.
+//! Jumping along the largest skip that doesn't overshoot reaches any ancestor
+//! height in a logarithmic number of manifest loads (see
+//! [`Manifest::skip_toward`]). Skips are derived from the parent's own skips at
+//! save time, so a list may stop early where it would pass through manifests
+//! written before skips existed.
 
 use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -31,6 +52,9 @@ use crate::crypto::{PublicKey, Secret, SecretKey, SecretShare, Signature};
 use crate::linked_data::{BlockEncoded, CodecError, DagCborCodec, Link};
 use crate::version::Version;
 
+use super::compression::CompressionPolicy;
+use super::conflict::ConflictStrategy;
+use super::node::NodeLink;
 use super::principal::{Principal, PrincipalRole};
 
 /// Errors that can occur during manifest operations.
@@ -47,14 +71,31 @@ pub enum ManifestError {
 /// Combines a [`Principal`] (identity + role) with an optional encrypted secret share.
 /// The share structure differs by role:
 ///
-/// - **Owners**: Always have `Some(SecretShare)` encrypted to their public key
+/// - **Owners** and **Readers**: Always have `Some(SecretShare)` encrypted to their public key
 /// - **Mirrors**: Always have `None`; use the manifest's `public` secret instead
+/// - **Depositors**: Always have `None`; they can't read the bucket at all
+///
+/// An owner may also carry a write scope: path prefixes outside of which it
+/// can't change the bucket (see [`Share::can_write`]). Any share may carry
+/// an expiry, after which owners remove it (see [`Share::is_expired`]).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Share {
     principal: Principal,
     /// The encrypted share of the bucket's secret key.
-    /// Only owners have this; mirrors use the manifest's public secret instead.
+    /// Owners and readers have this; mirrors use the manifest's public secret instead.
     share: Option<SecretShare>,
+    /// Absolute path prefixes this principal may write under.
+    ///
+    /// Empty means unrestricted. A scoped owner can only change files under
+    /// these prefixes and can't change the bucket's shares.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    write_scope: Vec<PathBuf>,
+    /// When access ends, in seconds since the Unix epoch.
+    ///
+    /// Owners remove expired shares the next time they save, which also
+    /// re-keys the bucket. `None` never expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<u64>,
 }
 
 impl Share {
@@ -66,6 +107,36 @@ impl Share {
                 identity: public_key,
             },
             share: Some(share),
+            write_scope: Vec::new(),
+            expires_at: None,
+        }
+    }
+
+    /// Create a new reader share with an encrypted secret.
+    pub fn new_reader(share: SecretShare, public_key: PublicKey) -> Self {
+        Self {
+            principal: Principal {
+                role: PrincipalRole::Reader,
+                identity: public_key,
+            },
+            share: Some(share),
+            write_scope: Vec::new(),
+            expires_at: None,
+        }
+    }
+
+    /// Create a new depositor share.
+    ///
+    /// Depositors never get a secret; they seal their deposits to the owners.
+    pub fn new_depositor(public_key: PublicKey) -> Self {
+        Self {
+            principal: Principal {
+                role: PrincipalRole::Depositor,
+                identity: public_key,
+            },
+            share: None,
+            write_scope: Vec::new(),
+            expires_at: None,
         }
     }
 
@@ -80,6 +151,8 @@ impl Share {
                 identity: public_key,
             },
             share: None,
+            write_scope: Vec::new(),
+            expires_at: None,
         }
     }
 
@@ -92,7 +165,7 @@ impl Share {
 
     /// Get the encrypted secret share.
     ///
-    /// Returns `Some` for owners, `None` for mirrors.
+    /// Returns `Some` for owners and readers, `None` for mirrors.
     pub fn share(&self) -> Option<&SecretShare> {
         self.share.as_ref()
     }
@@ -102,12 +175,58 @@ impl Share {
         &self.principal.role
     }
 
+    /// Get the path prefixes this principal may write under.
+    ///
+    /// Empty means the principal isn't restricted to any paths.
+    pub fn write_scope(&self) -> &[PathBuf] {
+        &self.write_scope
+    }
+
+    /// Whether the principal is restricted to a write scope
+    pub fn is_scoped(&self) -> bool {
+        !self.write_scope.is_empty()
+    }
+
+    /// Whether the write scope allows changing `path`
+    ///
+    /// The path may be absolute or relative to the bucket root. Prefixes
+    /// match whole components, so `/artifacts` covers `/artifacts/build.log`
+    /// but not `/artifacts-old`.
+    pub fn can_write(&self, path: &Path) -> bool {
+        let path = path.strip_prefix("/").unwrap_or(path);
+        self.write_scope.is_empty()
+            || self
+                .write_scope
+                .iter()
+                .any(|prefix| path.starts_with(prefix.strip_prefix("/").unwrap_or(prefix)))
+    }
+
+    /// Get when access ends, in seconds since the Unix epoch.
+    pub fn expires_at(&self) -> Option<u64> {
+        self.expires_at
+    }
+
+    /// Whether the share has expired at `now` (seconds since the Unix epoch)
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
     /* Setters */
 
     /// Set the encrypted secret share.
     pub fn set_share(&mut self, share: SecretShare) {
         self.share = Some(share);
     }
+
+    /// Set the path prefixes this principal may write under.
+    pub fn set_write_scope(&mut self, write_scope: Vec<PathBuf>) {
+        self.write_scope = write_scope;
+    }
+
+    /// Set when access ends; `None` never expires.
+    pub fn set_expires_at(&mut self, expires_at: Option<u64>) {
+        self.expires_at = expires_at;
+    }
 }
 
 /// Map of hex-encoded public keys to their shares.
@@ -159,8 +278,23 @@ pub struct Manifest {
     entry: Link,
     /// Link to the [`Pins`](super::Pins) blob hash set.
     pins: Link,
-    /// Link to the previous manifest version (forms history chain).
+    /// Link to the first parent manifest version.
+    ///
+    /// Always equal to `parents[0]` when `parents` is set; kept so readers
+    /// that predate `parents` can still follow history.
     previous: Option<Link>,
+    /// Links to every parent manifest version, primary parent first.
+    ///
+    /// Empty for genesis and for manifests written before this field existed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    parents: Vec<Link>,
+    /// First-parent ancestors at power-of-two distances.
+    ///
+    /// `skips[i]` is the version `2^(i + 1)` heights below this one along the
+    /// first-parent spine. Empty for the first two versions and for manifests
+    /// written before this field existed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    skips: Vec<Link>,
     /// Optional link to the encrypted path operations log (CRDT).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     ops_log: Option<Link>,
@@ -170,6 +304,52 @@ pub struct Manifest {
     /// Publishing is opt-in per version via `save(publish: true)`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     public: Option<Secret>,
+    /// Published directories, keyed by absolute path.
+    ///
+    /// Each link carries the directory's secret in plaintext, so anyone with
+    /// the manifest can read that subtree but nothing outside it. Unlike
+    /// `public`, the paths stay published across saves, which refresh the
+    /// links to the directories' current versions.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    published: BTreeMap<String, NodeLink>,
+    /// Compression applied to file contents before encryption.
+    ///
+    /// Unset means files are stored raw. Readers don't need this to decrypt;
+    /// it only tells writers how to store new data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    compression: Option<CompressionPolicy>,
+    /// How sync resolves conflicts when merging diverged histories.
+    ///
+    /// Unset means [`ConflictStrategy::ConflictFile`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    conflict_strategy: Option<ConflictStrategy>,
+    /// Owner approvals needed to change the bucket's shares.
+    ///
+    /// Unset means any single owner can (see [`Manifest::quorum`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    quorum: Option<u32>,
+    /// Number of approved membership changes made under a quorum.
+    ///
+    /// Orders concurrent changes when forked histories merge. Zero until the
+    /// first change that needed approvals.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    membership_epoch: u64,
+    /// Owner signatures approving the membership change this version makes.
+    ///
+    /// Keyed by hex public key; each signs the
+    /// [`MembershipChange`](super::MembershipChange) from the primary parent's
+    /// membership to this one's.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    approvals: BTreeMap<String, Signature>,
+    /// When this version was saved, in seconds since the Unix epoch.
+    ///
+    /// Never earlier than any parent's. Being signed, it is the time peers
+    /// check share expiry against, so they agree on which shares had expired
+    /// whatever their own clocks say. Peers refuse versions dated more than a
+    /// few minutes ahead of their clock. Zero for manifests written before
+    /// this field existed.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    saved_at: u64,
     /// Public key of the peer who signed this manifest.
     ///
     /// Set when the manifest is signed via [`Manifest::sign`].
@@ -208,23 +388,23 @@ impl Manifest {
         Manifest {
             id,
             name,
-            shares: BTreeMap::from([(
-                owner.to_hex(),
-                Share {
-                    principal: Principal {
-                        role: PrincipalRole::Owner,
-                        identity: owner,
-                    },
-                    share: Some(share),
-                },
-            )]),
+            shares: BTreeMap::from([(owner.to_hex(), Share::new_owner(share, owner))]),
             entry,
             pins,
             previous: None,
+            parents: Vec::new(),
+            skips: Vec::new(),
             height,
             version: Version::default(),
             ops_log: None,
             public: None,
+            published: BTreeMap::new(),
+            compression: None,
+            conflict_strategy: None,
+            quorum: None,
+            membership_epoch: 0,
+            approvals: BTreeMap::new(),
+            saved_at: 0,
             author: None,
             signature: None,
         }
@@ -262,6 +442,42 @@ impl Manifest {
         &self.previous
     }
 
+    /// Get the links of every parent version, primary parent first.
+    ///
+    /// Falls back to `previous` for manifests written before `parents` existed.
+    pub fn parents(&self) -> &[Link] {
+        if self.parents.is_empty() {
+            self.previous.as_slice()
+        } else {
+            &self.parents
+        }
+    }
+
+    /// Get the first-parent skip pointers, nearest first.
+    pub fn skips(&self) -> &[Link] {
+        &self.skips
+    }
+
+    /// Get the farthest first-parent ancestor we can jump to without going
+    /// below `height`.
+    ///
+    /// Returns `None` if `height` is not below this version or there is no
+    /// previous version.
+    pub fn skip_toward(&self, height: u64) -> Option<&Link> {
+        let distance = self.height.checked_sub(height).filter(|d| *d > 0)?;
+        // skips[i] sits 2^(i + 1) below us
+        let reachable = (u64::BITS - 1 - distance.leading_zeros()) as usize;
+        match reachable.min(self.skips.len()) {
+            0 => self.previous.as_ref(),
+            n => Some(&self.skips[n - 1]),
+        }
+    }
+
+    /// Check if this manifest merges diverged versions.
+    pub fn is_merge(&self) -> bool {
+        self.parents().len() > 1
+    }
+
     /// Get the version chain height.
     pub fn height(&self) -> u64 {
         self.height
@@ -300,6 +516,15 @@ impl Manifest {
         self.shares.values().filter(|s| *s.role() == role).collect()
     }
 
+    /// Get the public keys of shares that have expired at `now`.
+    pub fn expired_shares(&self, now: u64) -> Vec<PublicKey> {
+        self.shares
+            .values()
+            .filter(|share| share.is_expired(now))
+            .map(|share| share.principal().identity)
+            .collect()
+    }
+
     /// Check if the bucket is published.
     ///
     /// Published buckets have their secret stored in plaintext, allowing
@@ -313,6 +538,51 @@ impl Manifest {
         self.public.as_ref()
     }
 
+    /// Get the published subtrees, keyed by absolute path.
+    pub fn published(&self) -> &BTreeMap<String, NodeLink> {
+        &self.published
+    }
+
+    /// Check if mirrors can read anything in this version, either the whole
+    /// bucket or some published subtrees.
+    ///
+    /// This is what the bucket log tracks as the latest published version.
+    pub fn has_published_content(&self) -> bool {
+        self.is_published() || !self.published.is_empty()
+    }
+
+    /// Get the compression policy, if compression is enabled.
+    pub fn compression(&self) -> Option<&CompressionPolicy> {
+        self.compression.as_ref()
+    }
+
+    /// Get the configured conflict strategy (defaults to conflict files).
+    pub fn conflict_strategy(&self) -> ConflictStrategy {
+        self.conflict_strategy.unwrap_or_default()
+    }
+
+    /// Get the number of owners that must approve a change to the shares.
+    ///
+    /// Defaults to 1: any unscoped owner can change them alone.
+    pub fn quorum(&self) -> u32 {
+        self.quorum.unwrap_or(1)
+    }
+
+    /// Get the number of approved membership changes made under a quorum.
+    pub fn membership_epoch(&self) -> u64 {
+        self.membership_epoch
+    }
+
+    /// Get the owner approvals of this version's membership change.
+    pub fn approvals(&self) -> &BTreeMap<String, Signature> {
+        &self.approvals
+    }
+
+    /// Get when this version was saved, in seconds since the Unix epoch.
+    pub fn saved_at(&self) -> u64 {
+        self.saved_at
+    }
+
     /// Get the author (signer's public key) if the manifest is signed.
     pub fn author(&self) -> Option<&PublicKey> {
         self.author.as_ref()
@@ -340,9 +610,22 @@ impl Manifest {
         self.pins = pins_link;
     }
 
-    /// Set the previous manifest link.
+    /// Set a single previous manifest link.
     pub fn set_previous(&mut self, previous: Link) {
-        self.previous = Some(previous);
+        self.set_parents(vec![previous]);
+    }
+
+    /// Set the parent manifest links, primary parent first.
+    ///
+    /// `previous` is kept in step with the first parent.
+    pub fn set_parents(&mut self, parents: Vec<Link>) {
+        self.previous = parents.first().cloned();
+        self.parents = parents;
+    }
+
+    /// Set the first-parent skip pointers, nearest first.
+    pub fn set_skips(&mut self, skips: Vec<Link>) {
+        self.skips = skips;
     }
 
     /// Set the version chain height.
@@ -387,6 +670,55 @@ impl Manifest {
         self.public = None;
     }
 
+    /// Publish the directory at an absolute path through its link.
+    pub fn publish_subtree(&mut self, path: String, link: NodeLink) {
+        self.published.insert(path, link);
+    }
+
+    /// Stop publishing the directory at an absolute path.
+    ///
+    /// Returns false if it wasn't published.
+    pub fn unpublish_subtree(&mut self, path: &str) -> bool {
+        self.published.remove(path).is_some()
+    }
+
+    /// Replace the published subtrees.
+    pub fn set_published(&mut self, published: BTreeMap<String, NodeLink>) {
+        self.published = published;
+    }
+
+    /// Set or clear (`None`) the compression policy.
+    ///
+    /// Only affects data written afterwards; existing blobs keep their encoding.
+    pub fn set_compression(&mut self, policy: Option<CompressionPolicy>) {
+        self.compression = policy;
+    }
+
+    /// Set the conflict strategy used when sync merges diverged histories.
+    pub fn set_conflict_strategy(&mut self, strategy: ConflictStrategy) {
+        self.conflict_strategy = Some(strategy);
+    }
+
+    /// Set the number of owners that must approve a change to the shares.
+    pub fn set_quorum(&mut self, quorum: u32) {
+        self.quorum = (quorum > 1).then_some(quorum);
+    }
+
+    /// Set the number of approved membership changes made under a quorum.
+    pub fn set_membership_epoch(&mut self, epoch: u64) {
+        self.membership_epoch = epoch;
+    }
+
+    /// Set the owner approvals of this version's membership change.
+    pub fn set_approvals(&mut self, approvals: BTreeMap<String, Signature>) {
+        self.approvals = approvals;
+    }
+
+    /// Set when this version was saved, in seconds since the Unix epoch.
+    pub fn set_saved_at(&mut self, saved_at: u64) {
+        self.saved_at = saved_at;
+    }
+
     /* Signing */
 
     /// Sign this manifest with the given secret key.
@@ -440,6 +772,10 @@ impl Manifest {
     }
 }
 
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -497,6 +833,50 @@ mod tests {
         assert_eq!(principal, decoded);
     }
 
+    #[test]
+    fn test_share_write_scope() {
+        use ipld_core::codec::Codec;
+        use serde_ipld_dagcbor::codec::DagCborCodec;
+
+        let mut share = Share::new_owner(SecretShare::default(), SecretKey::generate().public());
+        assert!(share.can_write(Path::new("/anything")));
+
+        share.set_write_scope(vec![PathBuf::from("/artifacts")]);
+        assert!(share.can_write(Path::new("/artifacts/build.log")));
+        assert!(share.can_write(Path::new("artifacts/a/b")));
+        assert!(!share.can_write(Path::new("/artifacts-old/build.log")));
+        assert!(!share.can_write(Path::new("/src/main.rs")));
+
+        // Scopes survive encoding
+        let encoded = DagCborCodec::encode_to_vec(&share).unwrap();
+        let decoded: Share = DagCborCodec::decode_from_slice(&encoded).unwrap();
+        assert_eq!(decoded.write_scope(), share.write_scope());
+    }
+
+    #[test]
+    fn test_share_expiry() {
+        use ipld_core::codec::Codec;
+        use serde_ipld_dagcbor::codec::DagCborCodec;
+
+        let mut manifest = create_test_manifest();
+        let reader = SecretKey::generate().public();
+        let mut share = Share::new_reader(SecretShare::default(), reader);
+        assert!(!share.is_expired(u64::MAX));
+
+        share.set_expires_at(Some(1_000));
+        assert!(!share.is_expired(999));
+        assert!(share.is_expired(1_000));
+
+        // Expiry survives encoding
+        let encoded = DagCborCodec::encode_to_vec(&share).unwrap();
+        let decoded: Share = DagCborCodec::decode_from_slice(&encoded).unwrap();
+        assert_eq!(decoded.expires_at(), Some(1_000));
+
+        manifest.add_share(share);
+        assert!(manifest.expired_shares(999).is_empty());
+        assert_eq!(manifest.expired_shares(1_000), vec![reader]);
+    }
+
     #[test]
     fn test_manifest_signing() {
         let secret_key = SecretKey::generate();
@@ -577,4 +957,49 @@ mod tests {
         assert_eq!(manifest.author(), Some(&secret_key1.public()));
         assert_ne!(manifest.author(), Some(&secret_key2.public()));
     }
+
+    #[test]
+    fn test_parents_fall_back_to_previous() {
+        use ipld_core::codec::Codec;
+        use serde_ipld_dagcbor::codec::DagCborCodec;
+
+        let first = Link::new(0x71, iroh_blobs::Hash::from_bytes([1; 32]));
+        let second = Link::new(0x71, iroh_blobs::Hash::from_bytes([2; 32]));
+
+        // Manifests written before `parents` existed only carry `previous`
+        let mut manifest = create_test_manifest();
+        manifest.previous = Some(first.clone());
+        let encoded = DagCborCodec::encode_to_vec(&manifest).unwrap();
+        let legacy: Manifest = DagCborCodec::decode_from_slice(&encoded).unwrap();
+        assert_eq!(legacy.parents(), std::slice::from_ref(&first));
+        assert!(!legacy.is_merge());
+
+        manifest.set_parents(vec![second.clone(), first.clone()]);
+        let encoded = DagCborCodec::encode_to_vec(&manifest).unwrap();
+        let merge: Manifest = DagCborCodec::decode_from_slice(&encoded).unwrap();
+        assert_eq!(merge.previous(), &Some(second.clone()));
+        assert_eq!(merge.parents(), &[second, first]);
+        assert!(merge.is_merge());
+    }
+
+    #[test]
+    fn test_skip_toward_picks_farthest_jump() {
+        let link = |n: u8| Link::new(0x71, iroh_blobs::Hash::from_bytes([n; 32]));
+
+        // At height 10: previous is 9, skips reach 8, 6 and 2
+        let mut manifest = create_test_manifest();
+        manifest.set_height(10);
+        manifest.set_previous(link(9));
+        manifest.set_skips(vec![link(8), link(6), link(2)]);
+
+        assert_eq!(manifest.skip_toward(10), None);
+        assert_eq!(manifest.skip_toward(11), None);
+        assert_eq!(manifest.skip_toward(9), Some(&link(9)));
+        assert_eq!(manifest.skip_toward(8), Some(&link(8)));
+        assert_eq!(manifest.skip_toward(7), Some(&link(8)));
+        assert_eq!(manifest.skip_toward(5), Some(&link(6)));
+        assert_eq!(manifest.skip_toward(2), Some(&link(2)));
+        // Nothing reaches further than the last skip
+        assert_eq!(manifest.skip_toward(0), Some(&link(2)));
+    }
 }
@@ -0,0 +1,478 @@
+//! # Membership quorum
+//!
+//! By default any unscoped owner can add or remove principals on its own. A
+//! bucket can instead require several owners to agree: with a
+//! [`quorum`](Manifest::quorum) of M, a version that changes the shares (or
+//! the quorum itself) is only valid if it carries approvals from M unscoped
+//! owners of the version it builds on.
+//!
+//! An approval is an owner's signature over a [`MembershipChange`]: the
+//! bucket, the principals and quorum before and after, and the change's
+//! epoch. Secret shares are left out, since they are re-issued on every save.
+//! Owners pass a proposed change between them to collect approvals, and
+//! whoever holds enough of them saves the version that applies it.
+//!
+//! ## Epochs
+//!
+//! Every membership change bumps the bucket's membership epoch. When forked
+//! histories merge, the merge keeps the membership of the branch with the
+//! highest epoch (the merging owner's own on a tie), so a branch forked from
+//! before an approved change can't undo it. A quorum only protects history
+//! written after it was set, though: an owner can still fork from a version
+//! that had no quorum.
+//!
+//! Removing shares that have expired needs no approvals; their expiry was
+//! approved along with them.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::crypto::{PublicKey, SecretKey, Signature};
+use crate::linked_data::{BlockEncoded, CodecError, DagCborCodec, Hash};
+
+use super::manifest::{Manifest, Share};
+use super::principal::PrincipalRole;
+
+/// Errors that can occur while proposing or approving membership changes.
+#[derive(Debug, thiserror::Error)]
+pub enum MembershipError {
+    #[error("codec error: {0}")]
+    Codec(#[from] CodecError),
+    #[error("{0} is not an owner that can approve membership changes")]
+    NotApprover(String),
+    #[error("invalid approval signature from {0}")]
+    InvalidApproval(String),
+    #[error("a quorum of {quorum} needs as many unscoped owners, found {owners}")]
+    InvalidQuorum { quorum: u32, owners: usize },
+}
+
+/// A principal's access to a bucket, without its secret share.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Member {
+    pub role: PrincipalRole,
+    pub write_scope: Vec<PathBuf>,
+    pub expires_at: Option<u64>,
+}
+
+impl From<&Share> for Member {
+    fn from(share: &Share) -> Self {
+        Self {
+            role: share.role().clone(),
+            write_scope: share.write_scope().to_vec(),
+            expires_at: share.expires_at(),
+        }
+    }
+}
+
+impl Member {
+    /// Whether this member's approval counts towards a quorum
+    pub fn can_approve(&self) -> bool {
+        self.role == PrincipalRole::Owner && self.write_scope.is_empty()
+    }
+}
+
+/// Who has access to a bucket, and how many owners must approve changing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Membership {
+    /// Members by hex-encoded public key.
+    members: BTreeMap<String, Member>,
+    /// Owner approvals needed to change the membership.
+    quorum: u32,
+}
+
+impl Membership {
+    /// Get the membership recorded in a manifest.
+    pub fn of(manifest: &Manifest) -> Self {
+        Self {
+            members: manifest
+                .shares()
+                .iter()
+                .map(|(key, share)| (key.clone(), Member::from(share)))
+                .collect(),
+            quorum: manifest.quorum(),
+        }
+    }
+
+    /* Getters */
+
+    /// Get the members by hex-encoded public key.
+    pub fn members(&self) -> &BTreeMap<String, Member> {
+        &self.members
+    }
+
+    /// Get the number of owner approvals needed to change the membership.
+    pub fn quorum(&self) -> u32 {
+        self.quorum
+    }
+
+    /// Whether `key` is an unscoped owner, whose approval counts
+    pub fn can_approve(&self, key: &PublicKey) -> bool {
+        self.members
+            .get(&key.to_hex())
+            .is_some_and(Member::can_approve)
+    }
+
+    /// Get the public keys of the owners whose approval counts.
+    pub fn approvers(&self) -> Vec<PublicKey> {
+        self.members
+            .iter()
+            .filter(|(_, member)| member.can_approve())
+            .filter_map(|(key, _)| PublicKey::from_hex(key).ok())
+            .collect()
+    }
+
+    /* Checks */
+
+    /// Check that enough owners remain to ever meet the quorum.
+    #[allow(clippy::result_large_err)]
+    pub fn validate(&self) -> Result<(), MembershipError> {
+        let owners = self.approvers().len();
+        if self.quorum == 0 || self.quorum as usize > owners {
+            return Err(MembershipError::InvalidQuorum {
+                quorum: self.quorum,
+                owners,
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether this is `earlier`, possibly less shares that expired by `now`
+    pub fn follows(&self, earlier: &Membership, now: u64) -> bool {
+        self.quorum == earlier.quorum
+            && self
+                .members
+                .iter()
+                .all(|(key, member)| earlier.members.get(key) == Some(member))
+            && earlier.members.iter().all(|(key, member)| {
+                self.members.contains_key(key)
+                    || member
+                        .expires_at
+                        .is_some_and(|expires_at| expires_at <= now)
+            })
+    }
+}
+
+/// A change to a bucket's membership, with the owner approvals collected so far.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MembershipChange {
+    /// The bucket being changed.
+    bucket_id: Uuid,
+    /// Membership epoch of the version that applies the change.
+    epoch: u64,
+    /// Owner approvals the change needs.
+    required: u32,
+    /// Membership of the version the change builds on.
+    from: Membership,
+    /// Membership after the change.
+    to: Membership,
+    /// Owner signatures over every other field, keyed by hex public key.
+    approvals: BTreeMap<String, Signature>,
+}
+
+impl BlockEncoded<DagCborCodec> for MembershipChange {}
+
+impl MembershipChange {
+    /// Create a change with no approvals yet.
+    pub fn new(
+        bucket_id: Uuid,
+        epoch: u64,
+        required: u32,
+        from: Membership,
+        to: Membership,
+    ) -> Self {
+        Self {
+            bucket_id,
+            epoch,
+            required,
+            from,
+            to,
+            approvals: BTreeMap::new(),
+        }
+    }
+
+    /* Getters */
+
+    /// Get the bucket being changed.
+    pub fn bucket_id(&self) -> &Uuid {
+        &self.bucket_id
+    }
+
+    /// Get the membership epoch of the version that applies the change.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Get the number of owner approvals the change needs.
+    pub fn required(&self) -> u32 {
+        self.required
+    }
+
+    /// Get the membership the change builds on.
+    pub fn from(&self) -> &Membership {
+        &self.from
+    }
+
+    /// Get the membership after the change.
+    pub fn to(&self) -> &Membership {
+        &self.to
+    }
+
+    /// Get the approvals collected so far, keyed by hex public key.
+    pub fn approvals(&self) -> &BTreeMap<String, Signature> {
+        &self.approvals
+    }
+
+    /// Get the content hash identifying the change, regardless of approvals.
+    #[allow(clippy::result_large_err)]
+    pub fn id(&self) -> Result<Hash, MembershipError> {
+        Ok(Hash::new(self.signable_bytes()?))
+    }
+
+    /* Approvals */
+
+    /// Approve the change with an owner's key.
+    #[allow(clippy::result_large_err)]
+    pub fn approve(&mut self, owner: &SecretKey) -> Result<(), MembershipError> {
+        let signature = owner.sign(&self.signable_bytes()?);
+        self.add_approval(&owner.public(), signature)
+    }
+
+    /// Add another owner's approval, checking its signature.
+    #[allow(clippy::result_large_err)]
+    pub fn add_approval(
+        &mut self,
+        owner: &PublicKey,
+        signature: Signature,
+    ) -> Result<(), MembershipError> {
+        if !self.from.can_approve(owner) {
+            return Err(MembershipError::NotApprover(owner.to_hex()));
+        }
+        owner
+            .verify(&self.signable_bytes()?, &signature)
+            .map_err(|_| MembershipError::InvalidApproval(owner.to_hex()))?;
+        self.approvals.insert(owner.to_hex(), signature);
+        Ok(())
+    }
+
+    /// Add every valid approval from a list keyed by hex public key.
+    ///
+    /// Returns the number of approvals that were valid.
+    pub fn add_approvals(&mut self, approvals: &BTreeMap<String, Signature>) -> usize {
+        approvals
+            .iter()
+            .filter(|(key, signature)| {
+                PublicKey::from_hex(key)
+                    .ok()
+                    .is_some_and(|owner| self.add_approval(&owner, **signature).is_ok())
+            })
+            .count()
+    }
+
+    /// Whether enough owners have approved the change.
+    pub fn is_approved(&self) -> bool {
+        self.approvals.len() >= self.required as usize
+    }
+
+    /// Get the bytes owners sign: the change without its approvals.
+    #[allow(clippy::result_large_err)]
+    fn signable_bytes(&self) -> Result<Vec<u8>, MembershipError> {
+        let mut signable = self.clone();
+        signable.approvals.clear();
+        Ok(signable.encode()?)
+    }
+}
+
+/// How a version's membership follows from its parents'.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transition {
+    /// The membership of a parent at the highest epoch, possibly less
+    /// expired shares; the version keeps that epoch.
+    Unchanged { epoch: u64 },
+    /// A change from the primary parent's membership.
+    Change {
+        change: MembershipChange,
+        /// Whether a parent has a quorum above one, so the change needs
+        /// approvals.
+        needs_approval: bool,
+    },
+}
+
+impl Transition {
+    /// Work out how `membership` follows from a version's parents.
+    ///
+    /// `parents` lists the parent manifests primary first; genesis has none.
+    /// A change needs as many approvals as the highest quorum among the
+    /// parents, from owners of the primary parent.
+    pub fn between(
+        bucket_id: Uuid,
+        membership: &Membership,
+        parents: &[&Manifest],
+        now: u64,
+    ) -> Self {
+        let Some(primary) = parents.first() else {
+            return Transition::Unchanged { epoch: 0 };
+        };
+
+        let epoch = parents
+            .iter()
+            .map(|parent| parent.membership_epoch())
+            .max()
+            .unwrap_or_default();
+        let unchanged = parents
+            .iter()
+            .filter(|parent| parent.membership_epoch() == epoch)
+            .any(|parent| membership.follows(&Membership::of(parent), now));
+        if unchanged {
+            return Transition::Unchanged { epoch };
+        }
+
+        let required = parents
+            .iter()
+            .map(|parent| parent.quorum())
+            .max()
+            .unwrap_or(1);
+        Transition::Change {
+            change: MembershipChange::new(
+                bucket_id,
+                epoch + 1,
+                required,
+                Membership::of(primary),
+                membership.clone(),
+            ),
+            needs_approval: required > 1,
+        }
+    }
+
+    /// Get the membership epoch of a version with this transition.
+    pub fn epoch(&self) -> u64 {
+        match self {
+            Transition::Unchanged { epoch } => *epoch,
+            Transition::Change { change, .. } => change.epoch(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::SecretShare;
+    use crate::linked_data::Link;
+
+    fn manifest(owners: &[&SecretKey], quorum: u32) -> Manifest {
+        let mut manifest = Manifest::new(
+            Uuid::new_v4(),
+            "test".to_string(),
+            owners[0].public(),
+            SecretShare::default(),
+            Link::default(),
+            Link::default(),
+            0,
+        );
+        for owner in &owners[1..] {
+            manifest.add_share(Share::new_owner(SecretShare::default(), owner.public()));
+        }
+        manifest.set_quorum(quorum);
+        manifest
+    }
+
+    #[test]
+    fn test_change_needs_quorum_of_owners() {
+        let (a, b, c) = (
+            SecretKey::generate(),
+            SecretKey::generate(),
+            SecretKey::generate(),
+        );
+        let previous = manifest(&[&a, &b, &c], 2);
+        let mut next = previous.clone();
+        next.shares_mut().remove(&c.public().to_hex());
+
+        let Transition::Change {
+            mut change,
+            needs_approval,
+        } = Transition::between(*previous.id(), &Membership::of(&next), &[&previous], 0)
+        else {
+            panic!("removing an owner is a change");
+        };
+        assert!(needs_approval);
+        assert_eq!(change.epoch(), 1);
+
+        change.approve(&a).unwrap();
+        assert!(!change.is_approved());
+        // Outsiders can't approve
+        assert!(matches!(
+            change.approve(&SecretKey::generate()),
+            Err(MembershipError::NotApprover(_))
+        ));
+        change.approve(&b).unwrap();
+        assert!(change.is_approved());
+
+        // Approvals only count for the change they signed
+        let mut other = next.clone();
+        other.shares_mut().remove(&b.public().to_hex());
+        let Transition::Change {
+            change: mut other, ..
+        } = Transition::between(*previous.id(), &Membership::of(&other), &[&previous], 0)
+        else {
+            panic!("removing owners is a change");
+        };
+        assert_ne!(other.id().unwrap(), change.id().unwrap());
+        assert_eq!(other.add_approvals(change.approvals()), 0);
+    }
+
+    #[test]
+    fn test_expired_shares_drop_without_approval() {
+        let (a, b) = (SecretKey::generate(), SecretKey::generate());
+        let mut previous = manifest(&[&a, &b], 2);
+        let mut reader = Share::new_reader(SecretShare::default(), SecretKey::generate().public());
+        reader.set_expires_at(Some(100));
+        previous.add_share(reader.clone());
+
+        let mut next = previous.clone();
+        next.shares_mut()
+            .remove(&reader.principal().identity.to_hex());
+        let transition =
+            |now| Transition::between(*previous.id(), &Membership::of(&next), &[&previous], now);
+        assert_eq!(transition(100), Transition::Unchanged { epoch: 0 });
+        assert!(matches!(transition(99), Transition::Change { .. }));
+    }
+
+    #[test]
+    fn test_merge_keeps_highest_epoch() {
+        let (a, b) = (SecretKey::generate(), SecretKey::generate());
+        let stale = manifest(&[&a, &b], 2);
+        let mut current = stale.clone();
+        current.shares_mut().remove(&b.public().to_hex());
+        current.set_quorum(1);
+        current.set_membership_epoch(1);
+
+        // Taking the newer membership is no change; the stale one is
+        let parents = [&stale, &current];
+        assert_eq!(
+            Transition::between(*stale.id(), &Membership::of(&current), &parents, 0),
+            Transition::Unchanged { epoch: 1 }
+        );
+        assert!(matches!(
+            Transition::between(*stale.id(), &Membership::of(&stale), &parents, 0),
+            Transition::Change {
+                needs_approval: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_validate_quorum() {
+        let (a, b) = (SecretKey::generate(), SecretKey::generate());
+        assert!(Membership::of(&manifest(&[&a, &b], 2)).validate().is_ok());
+        assert!(matches!(
+            Membership::of(&manifest(&[&a, &b], 3)).validate(),
+            Err(MembershipError::InvalidQuorum {
+                quorum: 3,
+                owners: 2
+            })
+        ));
+    }
+}
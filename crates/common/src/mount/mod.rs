@@ -7,6 +7,11 @@
 //! - **[`Mount`]**: In-memory representation of a bucket with CRUD operations
 //! - **[`Pins`]**: Set of content hashes that should be kept available
 //! - **[`Principal`]**: Access control entries (peer identity + role)
+//! - **[`Deposit`]**: A file submitted to the inbox by a principal that can't read the bucket
+//! - **[`MembershipChange`]**: A change to a bucket's principals that a quorum of owners approves
+//! - **[`CompressionPolicy`]**: Per-bucket rules for compressing file contents before encryption
+//! - **[`FileCapability`]**: A signed link that lets anyone holding it read one file
+//! - **[`MembershipEvent`]**: A change to who can access a bucket, read off the manifest chain
 //!
 //! # Architecture
 //!
@@ -42,20 +47,31 @@
 //! - The root node's secret is shared with authorized peers via [`Share`](crate::crypto::Share)
 //! - This provides fine-grained access control and efficient key rotation
 
+mod audit;
+mod capability;
+mod compression;
 mod conflict;
+mod deposit;
 mod manifest;
 mod maybe_mime;
+mod membership;
 mod mount_inner;
 mod node;
 mod path_ops;
 mod pins;
 mod principal;
 
+pub use audit::{MembershipEvent, MembershipEventKind};
+pub use capability::{CapabilityError, FileCapability, CAPABILITY_PREFIX};
+pub use compression::{is_precompressed, CompressionPolicy, DEFAULT_COMPRESSION_LEVEL};
 pub use conflict::{
     conflicts_with_mv_source, operations_conflict, BaseWins, Conflict, ConflictFile,
-    ConflictResolver, ForkOnConflict, LastWriteWins, MergeResult, Resolution, ResolvedConflict,
+    ConflictResolver, ConflictStrategy, ForkOnConflict, LastWriteWins, MergeResult, Resolution,
+    ResolvedConflict,
 };
+pub use deposit::{Deposit, DepositError, INBOX_PATH};
 pub use manifest::{Manifest, ManifestError, Share, Shares};
+pub use membership::{Member, Membership, MembershipChange, MembershipError, Transition};
 pub use mount_inner::{Mount, MountError};
 pub use node::{Node, NodeError, NodeLink};
 pub use path_ops::{merge_logs, OpId, OpType, PathOpLog, PathOperation};
@@ -1,4 +1,5 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -6,17 +7,33 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use crate::crypto::{PublicKey, Secret, SecretError, SecretKey, SecretShare};
-use crate::linked_data::{BlockEncoded, CodecError, Link};
+use crate::clock::unix_now;
+use crate::crypto::{PublicKey, Secret, SecretError, SecretKey, SecretShare, Signature};
+
+use crate::linked_data::{BlockEncoded, CodecError, Hash, Link};
 use crate::peer::{BlobsStore, BlobsStoreError};
 
-use super::conflict::MergeResult;
+use super::audit::MembershipEvent;
+use super::capability::{CapabilityError, FileCapability};
+use super::compression::CompressionPolicy;
+use super::conflict::{ConflictStrategy, MergeResult};
+use super::deposit::{Deposit, DepositError};
 use super::manifest::{Manifest, ManifestError, Share};
-use super::node::{Node, NodeError, NodeLink};
+use super::maybe_mime::MaybeMime;
+use super::membership::{Membership, MembershipChange, MembershipError, Transition};
+use super::node::{Data, Node, NodeError, NodeLink};
 use super::path_ops::{OpType, PathOpLog};
 use super::pins::Pins;
 use super::principal::PrincipalRole;
 
+/// Key of a published subtree in the manifest: its absolute, cleaned path
+fn published_key(path: &Path) -> String {
+    Path::new("/")
+        .join(clean_path(path))
+        .to_string_lossy()
+        .to_string()
+}
+
 pub fn clean_path(path: &Path) -> PathBuf {
     if !path.is_absolute() {
         panic!("path is not absolute");
@@ -45,6 +62,12 @@ pub struct MountInner {
     pub peer_id: PublicKey,
     // the secret key for signing manifests
     pub secret_key: SecretKey,
+    // heads (link, height) of branches merged into this mount, consumed by the next save
+    pub merge_parents: Vec<(Link, u64)>,
+    // owner approvals of a membership change, consumed by the next save
+    pub membership_approvals: BTreeMap<String, Signature>,
+    // published subtrees by cleaned path, set when only those can be decrypted
+    pub published_view: Option<BTreeMap<PathBuf, NodeLink>>,
 }
 
 impl MountInner {
@@ -104,8 +127,26 @@ pub enum MountError {
     ShareNotFound,
     #[error("mirror cannot mount: bucket is not published")]
     MirrorCannotMount,
+    #[error("our share in this bucket has expired")]
+    ShareExpired,
     #[error("unauthorized: only owners can perform this operation")]
     Unauthorized,
+    #[error("path is outside the write scope of this key: {0}")]
+    OutOfScope(PathBuf),
+    #[error("depositors cannot mount buckets")]
+    DepositorCannotMount,
+    #[error("deposit error: {0}")]
+    Deposit(#[from] DepositError),
+    #[error("membership error: {0}")]
+    Membership(#[from] MembershipError),
+    #[error("membership change needs {required} owner approvals, has {approvals}")]
+    QuorumNotMet { required: u32, approvals: usize },
+    #[error("membership change no longer applies to the bucket")]
+    StaleMembershipChange,
+    #[error("cannot publish the root as a subtree, publish the bucket instead")]
+    PublishRoot,
+    #[error("capability error: {0}")]
+    Capability(#[from] CapabilityError),
 }
 
 impl Mount {
@@ -126,6 +167,20 @@ impl Mount {
     ///
     /// If `publish` is true, the secret will be stored in plaintext, allowing
     /// mirrors to decrypt the bucket contents.
+    ///
+    /// Every save encrypts the new version with a fresh secret. When an owner
+    /// saves, shares that have expired are dropped first, so the new secret
+    /// is not shared with them.
+    ///
+    /// After a [`merge`](Self::merge) the new manifest lists every merged head
+    /// as a parent. The highest one comes first and becomes `previous` (the
+    /// returned previous link), and the new height is one above it, so the
+    /// version is ahead of all the branches.
+    ///
+    /// If the bucket has a membership quorum, a change to its shares must
+    /// have been applied with enough approvals (see
+    /// [`apply_membership_change`](Self::apply_membership_change)), or the
+    /// save fails with [`MountError::QuorumNotMet`].
     pub async fn save(
         &self,
         blobs: &BlobsStore,
@@ -140,6 +195,8 @@ impl Mount {
             manifest_template,
             ops_log,
             secret_key,
+            merge_parents,
+            membership_approvals,
         ) = {
             let inner = self.0.lock().await;
             (
@@ -150,12 +207,54 @@ impl Mount {
                 inner.manifest.clone(),
                 inner.ops_log.clone(),
                 inner.secret_key.clone(),
+                inner.merge_parents.clone(),
+                inner.membership_approvals.clone(),
             )
         };
 
+        let parents = Self::ordered_parents((previous_link, previous_height), merge_parents);
+        let (previous_link, previous_height) = parents[0].clone();
+        let parent_links: Vec<Link> = parents.into_iter().map(|(link, _)| link).collect();
+
         // Increment the height of the mount
         let height = previous_height + 1;
 
+        // Drop shares expired by the time of saving, then check the
+        // membership against the parents
+        let parent_manifests = Self::parent_manifests(&parent_links, blobs).await?;
+        let mut manifest = manifest_template;
+        manifest.set_saved_at(Self::saved_at(&parent_manifests));
+        let saved_at = manifest.saved_at();
+        if manifest
+            .get_share(&secret_key.public())
+            .is_some_and(|share| share.is_expired(saved_at))
+        {
+            return Err(MountError::ShareExpired);
+        }
+        Self::remove_expired_shares(&mut manifest, &secret_key.public(), saved_at);
+        let transition = Self::membership_transition(&manifest, &parent_manifests);
+        let mut approvals = BTreeMap::new();
+        if let Transition::Change {
+            mut change,
+            needs_approval: true,
+        } = transition.clone()
+        {
+            change.to().validate()?;
+            change.add_approvals(&membership_approvals);
+            if !change.is_approved() {
+                return Err(MountError::QuorumNotMet {
+                    required: change.required(),
+                    approvals: change.approvals().len(),
+                });
+            }
+            approvals = change.approvals().clone();
+        }
+        manifest.set_membership_epoch(transition.epoch());
+        manifest.set_approvals(approvals);
+
+        // Skip pointers follow the primary parent
+        let skips = Self::_skips_for_child_of(&previous_link, blobs).await?;
+
         // Create a new secret for the updated root
         let secret = Secret::generate();
 
@@ -165,7 +264,9 @@ impl Mount {
         // Serialize current pins to blobs
         // put the new root link into the pins, as well as the previous link
         pins.insert(entry.clone().hash());
-        pins.insert(previous_link.hash());
+        for parent in &parent_links {
+            pins.insert(parent.hash());
+        }
 
         // Encrypt and store the ops log if it has any operations
         let ops_log_link = if !ops_log.is_empty() {
@@ -178,15 +279,26 @@ impl Mount {
 
         let pins_link = Self::_put_pins_in_blobs(&pins, blobs).await?;
 
-        // Re-encrypt owner shares with the new secret (mirrors stay unchanged)
-        let mut manifest = manifest_template;
+        // Re-encrypt owner and reader shares with the new secret (mirrors stay unchanged)
         for share in manifest.shares_mut().values_mut() {
-            if *share.role() == PrincipalRole::Owner {
+            if share.role().has_secret_share() {
                 let secret_share = SecretShare::new(&secret, &share.principal().identity)?;
                 share.set_share(secret_share);
             }
         }
 
+        // Point published subtrees at their directories' current versions
+        let mut published = BTreeMap::new();
+        for path in manifest.published().keys() {
+            // A subtree that was removed or replaced by a file stops being published
+            if let Some(link) =
+                Self::_get_dir_link_at_path(&entry_node, Path::new(path), blobs).await?
+            {
+                published.insert(path.clone(), link);
+            }
+        }
+        manifest.set_published(published);
+
         // Update publish state: publish with new secret, or clear stale public secret
         if publish {
             manifest.publish(&secret);
@@ -196,7 +308,8 @@ impl Mount {
             manifest.unpublish();
         }
         manifest.set_pins(pins_link.clone());
-        manifest.set_previous(previous_link.clone());
+        manifest.set_parents(parent_links);
+        manifest.set_skips(skips);
         manifest.set_entry(entry.clone());
         manifest.set_height(height);
 
@@ -219,6 +332,8 @@ impl Mount {
             inner.manifest = manifest;
             inner.height = height;
             inner.link = link.clone();
+            inner.merge_parents.clear();
+            inner.membership_approvals.clear();
             // Clear the ops_log - it's now persisted in the manifest
             // Future operations start a fresh log for the next version
             // IMPORTANT: Preserve the clock value so future ops have unique timestamps
@@ -257,6 +372,7 @@ impl Mount {
             pins_link.clone(),
             0, // initial height is 0
         );
+        manifest.set_saved_at(unix_now());
         // Sign the manifest with the owner's key
         manifest.sign(owner)?;
         let link = Self::_put_manifest_in_blobs(&manifest, blobs).await?;
@@ -272,6 +388,9 @@ impl Mount {
                 ops_log: PathOpLog::new(),
                 peer_id: owner.public(),
                 secret_key: owner.clone(),
+                merge_parents: Vec::new(),
+                membership_approvals: BTreeMap::new(),
+                published_view: None,
             })),
             blobs.clone(),
         ))
@@ -281,49 +400,96 @@ impl Mount {
         link: &Link,
         secret_key: &SecretKey,
         blobs: &BlobsStore,
+    ) -> Result<Self, MountError> {
+        Self::_load(link, secret_key, blobs, false).await
+    }
+
+    /// Load only what a version publishes, whatever our role in the bucket.
+    ///
+    /// This is how a mirror sees the version: the whole bucket if it is
+    /// published, otherwise only the published subtrees. Use it to serve a
+    /// bucket to the public, so an owner's own access doesn't leak what
+    /// wasn't published.
+    pub async fn load_published(
+        link: &Link,
+        secret_key: &SecretKey,
+        blobs: &BlobsStore,
+    ) -> Result<Self, MountError> {
+        Self::_load(link, secret_key, blobs, true).await
+    }
+
+    async fn _load(
+        link: &Link,
+        secret_key: &SecretKey,
+        blobs: &BlobsStore,
+        published_only: bool,
     ) -> Result<Self, MountError> {
         let public_key = &secret_key.public();
         let manifest = Self::_get_manifest_from_blobs(link, blobs).await?;
 
-        let bucket_share = match manifest.get_share(public_key) {
-            Some(share) => share,
-            None => return Err(MountError::ShareNotFound),
+        let role = if published_only {
+            PrincipalRole::Mirror
+        } else {
+            match manifest.get_share(public_key) {
+                Some(share) => share.role().clone(),
+                None => return Err(MountError::ShareNotFound),
+            }
         };
 
         // Get the secret based on role
-        let secret = match bucket_share.role() {
-            PrincipalRole::Owner => {
-                // Owners decrypt their individual share
-                let share = bucket_share.share().ok_or(MountError::ShareNotFound)?;
-                share.recover(secret_key)?
+        let secret = match role {
+            PrincipalRole::Owner | PrincipalRole::Reader => {
+                // Owners and readers decrypt their individual share
+                let share = manifest
+                    .get_share(public_key)
+                    .and_then(|share| share.share())
+                    .ok_or(MountError::ShareNotFound)?;
+                Some(share.recover(secret_key)?)
             }
-            PrincipalRole::Mirror => {
+            PrincipalRole::Mirror => match manifest.public() {
                 // Mirrors use the public secret (if bucket is published)
-                manifest
-                    .public()
-                    .cloned()
-                    .ok_or(MountError::MirrorCannotMount)?
-            }
+                Some(secret) => Some(secret.clone()),
+                // or read only the published subtrees
+                None if !manifest.published().is_empty() => None,
+                None => return Err(MountError::MirrorCannotMount),
+            },
+            PrincipalRole::Depositor => return Err(MountError::DepositorCannotMount),
         };
 
         let pins = Self::_get_pins_from_blobs(manifest.pins(), blobs).await?;
-        let entry = Self::_get_node_from_blobs(
-            &NodeLink::Dir(manifest.entry().clone(), secret.clone()),
-            blobs,
-        )
-        .await?;
 
         // Read height from the manifest
         let height = manifest.height();
 
-        // Load the ops log if it exists, otherwise create a new one
-        let ops_log = if let Some(ops_link) = manifest.ops_log() {
-            let mut log = Self::_get_ops_log_from_blobs(ops_link, &secret, blobs).await?;
-            // Rebuild local clock from operations after deserialization
-            log.rebuild_clock();
-            log
-        } else {
-            PathOpLog::new()
+        let (entry, ops_log, published_view) = match secret {
+            Some(secret) => {
+                let entry = Self::_get_node_from_blobs(
+                    &NodeLink::Dir(manifest.entry().clone(), secret.clone()),
+                    blobs,
+                )
+                .await?;
+
+                // Load the ops log if it exists, otherwise create a new one
+                let ops_log = if let Some(ops_link) = manifest.ops_log() {
+                    let mut log = Self::_get_ops_log_from_blobs(ops_link, &secret, blobs).await?;
+                    // Rebuild local clock from operations after deserialization
+                    log.rebuild_clock();
+                    log
+                } else {
+                    PathOpLog::new()
+                };
+                (entry, ops_log, None)
+            }
+            None => {
+                // The root and ops log stay sealed; reads resolve within the subtrees
+                let view = manifest
+                    .published()
+                    .iter()
+                    .filter(|(path, _)| Path::new(path).is_absolute())
+                    .map(|(path, link)| (clean_path(Path::new(path)), link.clone()))
+                    .collect();
+                (Node::default(), PathOpLog::new(), Some(view))
+            }
         };
 
         Ok(Mount(
@@ -336,6 +502,9 @@ impl Mount {
                 ops_log,
                 peer_id: secret_key.public(),
                 secret_key: secret_key.clone(),
+                merge_parents: Vec::new(),
+                membership_approvals: BTreeMap::new(),
+                published_view,
             })),
             blobs.clone(),
         ))
@@ -349,6 +518,43 @@ impl Mount {
         Self::_get_manifest_from_blobs(link, blobs).await
     }
 
+    /// Walk the manifest chain back from `head` and collect every change to
+    /// the bucket's membership, oldest first.
+    ///
+    /// Only manifests are loaded, so this works without a share in the
+    /// bucket. With `verify`, each version's signature is checked and the
+    /// result recorded on its events; unsigned versions count as unverified.
+    /// Approvals are checked too, and only valid approvers are reported.
+    pub async fn membership_history(
+        head: &Link,
+        blobs: &BlobsStore,
+        verify: bool,
+    ) -> Result<Vec<MembershipEvent>, MountError> {
+        let mut manifests = BTreeMap::new();
+        let mut queue = vec![head.clone()];
+        while let Some(link) = queue.pop() {
+            if manifests.contains_key(&link) {
+                continue;
+            }
+            let manifest = Self::_get_manifest_from_blobs(&link, blobs).await?;
+            queue.extend(manifest.parents().iter().cloned());
+            manifests.insert(link, manifest);
+        }
+
+        let mut events = Vec::new();
+        for (link, manifest) in &manifests {
+            let parents: Vec<Manifest> = manifest
+                .parents()
+                .iter()
+                .filter_map(|parent| manifests.get(parent).cloned())
+                .collect();
+            let verified = verify.then(|| manifest.verify_signature().unwrap_or(false));
+            events.extend(MembershipEvent::between(link, manifest, &parents, verified));
+        }
+        events.sort_by(|a, b| a.height.cmp(&b.height).then_with(|| a.link.cmp(&b.link)));
+        Ok(events)
+    }
+
     /// Add an owner to this bucket.
     /// Owners get an encrypted share immediately.
     pub async fn add_owner(&mut self, peer: PublicKey) -> Result<(), MountError> {
@@ -360,6 +566,17 @@ impl Mount {
         Ok(())
     }
 
+    /// Add a reader to this bucket.
+    /// Readers get an encrypted share like owners but cannot write.
+    pub async fn add_reader(&mut self, peer: PublicKey) -> Result<(), MountError> {
+        let mut inner = self.0.lock().await;
+        let secret_share = SecretShare::new(&Secret::default(), &peer)?;
+        inner
+            .manifest
+            .add_share(Share::new_reader(secret_share, peer));
+        Ok(())
+    }
+
     /// Add a mirror to this bucket.
     /// Mirrors can sync bucket data but cannot decrypt until published.
     pub async fn add_mirror(&mut self, peer: PublicKey) {
@@ -367,6 +584,13 @@ impl Mount {
         inner.manifest.add_share(Share::new_mirror(peer));
     }
 
+    /// Add a depositor to this bucket.
+    /// Depositors can submit files to the inbox but cannot read the bucket.
+    pub async fn add_depositor(&mut self, peer: PublicKey) {
+        let mut inner = self.0.lock().await;
+        inner.manifest.add_share(Share::new_depositor(peer));
+    }
+
     /// Remove a share from the bucket.
     ///
     /// Only owners can remove shares. Returns an error if the caller is not an owner
@@ -380,7 +604,7 @@ impl Mount {
             .manifest
             .get_share(&our_key)
             .ok_or(MountError::ShareNotFound)?;
-        if *our_share.role() != PrincipalRole::Owner {
+        if *our_share.role() != PrincipalRole::Owner || our_share.is_scoped() {
             return Err(MountError::Unauthorized);
         }
 
@@ -393,12 +617,344 @@ impl Mount {
         Ok(())
     }
 
+    /// Restrict an owner to writing under the given path prefixes.
+    ///
+    /// An empty scope lifts the restriction. Peers reject versions a scoped
+    /// owner makes outside its scope, or that change the bucket's shares.
+    /// Takes effect on the next save.
+    pub async fn set_write_scope(
+        &self,
+        peer: PublicKey,
+        scope: Vec<PathBuf>,
+    ) -> Result<(), MountError> {
+        if let Some(path) = scope.iter().find(|path| !path.is_absolute()) {
+            return Err(MountError::Default(anyhow::anyhow!(
+                "write scope paths must be absolute: {}",
+                path.display()
+            )));
+        }
+
+        let mut inner = self.0.lock().await;
+        let share = inner
+            .manifest
+            .shares_mut()
+            .get_mut(&peer.to_hex())
+            .ok_or(MountError::ShareNotFound)?;
+        if *share.role() != PrincipalRole::Owner {
+            return Err(MountError::Default(anyhow::anyhow!(
+                "only owners can have a write scope"
+            )));
+        }
+        share.set_write_scope(scope);
+        Ok(())
+    }
+
+    /// Set when a principal's access ends, in seconds since the Unix epoch.
+    ///
+    /// `None` clears the expiry. Once it passes, the next save by an owner
+    /// removes the share and re-keys the bucket. Takes effect on the next
+    /// save.
+    ///
+    /// We can only change our own expiry in a bucket with a quorum, where
+    /// other owners have to approve it; otherwise an owner could keep
+    /// itself in the bucket past its expiry.
+    pub async fn set_share_expiry(
+        &self,
+        peer: PublicKey,
+        expires_at: Option<u64>,
+    ) -> Result<(), MountError> {
+        let mut inner = self.0.lock().await;
+        if peer == inner.secret_key.public() && inner.manifest.quorum() <= 1 {
+            return Err(MountError::Unauthorized);
+        }
+        let share = inner
+            .manifest
+            .shares_mut()
+            .get_mut(&peer.to_hex())
+            .ok_or(MountError::ShareNotFound)?;
+        share.set_expires_at(expires_at);
+        Ok(())
+    }
+
+    /// Drop shares that have expired at `now` from a manifest about to be saved
+    ///
+    /// Only unscoped owners whose own share is still live may remove
+    /// shares, so anyone else leaves them for such an owner.
+    fn remove_expired_shares(manifest: &mut Manifest, our_key: &PublicKey, now: u64) {
+        let is_owner = manifest.get_share(our_key).is_some_and(|share| {
+            *share.role() == PrincipalRole::Owner && !share.is_scoped() && !share.is_expired(now)
+        });
+        if !is_owner {
+            return;
+        }
+        for peer in manifest.expired_shares(now) {
+            tracing::info!(
+                "Removing expired share of {} from bucket {}",
+                peer.to_hex(),
+                manifest.id()
+            );
+            manifest.shares_mut().remove(&peer.to_hex());
+        }
+    }
+
+    /// Get the number of owners that must approve a change to the shares.
+    pub async fn quorum(&self) -> u32 {
+        let inner = self.0.lock().await;
+        inner.manifest.quorum()
+    }
+
+    /// Require `quorum` unscoped owners to approve changes to the shares.
+    ///
+    /// Only unscoped owners can set the quorum, and there must be at least
+    /// as many of them. Changing the quorum is itself a membership change,
+    /// approved under the old quorum. Takes effect on the next save.
+    pub async fn set_quorum(&self, quorum: u32) -> Result<(), MountError> {
+        let mut inner = self.0.lock().await;
+        let our_key = inner.secret_key.public();
+        if !Membership::of(&inner.manifest).can_approve(&our_key) {
+            return Err(MountError::Unauthorized);
+        }
+
+        let mut manifest = inner.manifest.clone();
+        manifest.set_quorum(quorum);
+        Membership::of(&manifest).validate()?;
+        inner.manifest.set_quorum(quorum);
+        Ok(())
+    }
+
+    /// Get the membership change the next save would make, if it needs approvals.
+    ///
+    /// Returns `None` when the shares are unchanged, or the bucket has no
+    /// quorum so any owner may change them. The change has no approvals
+    /// yet; see [`MembershipChange::approve`].
+    pub async fn membership_change(&self) -> Result<Option<MembershipChange>, MountError> {
+        let (mut manifest, parent_links, our_key) = {
+            let inner = self.0.lock().await;
+            let parents = Self::ordered_parents(
+                (inner.link.clone(), inner.height),
+                inner.merge_parents.clone(),
+            );
+            (
+                inner.manifest.clone(),
+                parents
+                    .into_iter()
+                    .map(|(link, _)| link)
+                    .collect::<Vec<_>>(),
+                inner.secret_key.public(),
+            )
+        };
+
+        let parent_manifests = Self::parent_manifests(&parent_links, &self.1).await?;
+        manifest.set_saved_at(Self::saved_at(&parent_manifests));
+        let saved_at = manifest.saved_at();
+        Self::remove_expired_shares(&mut manifest, &our_key, saved_at);
+        match Self::membership_transition(&manifest, &parent_manifests) {
+            Transition::Change {
+                change,
+                needs_approval: true,
+            } => {
+                change.to().validate()?;
+                Ok(Some(change))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Apply an approved membership change to the shares.
+    ///
+    /// The change must build on the membership of the version this mount
+    /// was loaded from; shares edited since are replaced. Principals the
+    /// change adds get a secret share on save if their role has one. The
+    /// approvals are recorded by the next save, which fails if they don't
+    /// meet the quorum.
+    pub async fn apply_membership_change(
+        &mut self,
+        change: &MembershipChange,
+    ) -> Result<(), MountError> {
+        let primary = {
+            let inner = self.0.lock().await;
+            Self::ordered_parents(
+                (inner.link.clone(), inner.height),
+                inner.merge_parents.clone(),
+            )
+            .remove(0)
+            .0
+        };
+        let parent = Self::_get_manifest_from_blobs(&primary, &self.1).await?;
+        if change.bucket_id() != parent.id()
+            || change.epoch() != parent.membership_epoch() + 1
+            || Membership::of(&parent) != *change.from()
+        {
+            return Err(MountError::StaleMembershipChange);
+        }
+
+        let mut inner = self.0.lock().await;
+
+        let mut shares = BTreeMap::new();
+        for (key, member) in change.to().members() {
+            let mut share = match inner.manifest.shares().get(key) {
+                Some(share) if *share.role() == member.role => share.clone(),
+                _ => {
+                    let peer = PublicKey::from_hex(key).map_err(|e| {
+                        MountError::Default(anyhow::anyhow!("invalid member key {}: {}", key, e))
+                    })?;
+                    match member.role {
+                        PrincipalRole::Owner => {
+                            Share::new_owner(SecretShare::new(&Secret::default(), &peer)?, peer)
+                        }
+                        PrincipalRole::Reader => {
+                            Share::new_reader(SecretShare::new(&Secret::default(), &peer)?, peer)
+                        }
+                        PrincipalRole::Mirror => Share::new_mirror(peer),
+                        PrincipalRole::Depositor => Share::new_depositor(peer),
+                    }
+                }
+            };
+            share.set_write_scope(member.write_scope.clone());
+            share.set_expires_at(member.expires_at);
+            shares.insert(key.clone(), share);
+        }
+
+        *inner.manifest.shares_mut() = shares;
+        inner.manifest.set_quorum(change.to().quorum());
+        inner.membership_approvals = change.approvals().clone();
+        Ok(())
+    }
+
+    /// Order a new version's parents: the highest head first, ties keeping ours
+    fn ordered_parents(head: (Link, u64), merge_parents: Vec<(Link, u64)>) -> Vec<(Link, u64)> {
+        let mut parents = vec![head];
+        parents.extend(merge_parents);
+        let primary =
+            parents.iter().enumerate().fold(
+                0,
+                |best, (i, (_, h))| if *h > parents[best].1 { i } else { best },
+            );
+        let head = parents.remove(primary);
+        parents.insert(0, head);
+        parents
+    }
+
+    /// Load the manifests of a version's parents, primary first
+    async fn parent_manifests(
+        parent_links: &[Link],
+        blobs: &BlobsStore,
+    ) -> Result<Vec<Manifest>, MountError> {
+        let mut parents = Vec::with_capacity(parent_links.len());
+        for link in parent_links {
+            parents.push(Self::_get_manifest_from_blobs(link, blobs).await?);
+        }
+        Ok(parents)
+    }
+
+    /// Get the time to record on a version saved now
+    ///
+    /// A version is never recorded as saved before its parents, even if our
+    /// clock is behind the peers that wrote them.
+    fn saved_at(parents: &[Manifest]) -> u64 {
+        parents
+            .iter()
+            .map(Manifest::saved_at)
+            .fold(unix_now(), u64::max)
+    }
+
+    /// Work out how a manifest's membership follows from its parents
+    ///
+    /// Shares count as expired if they had by the manifest's `saved_at`.
+    fn membership_transition(manifest: &Manifest, parents: &[Manifest]) -> Transition {
+        let parents: Vec<&Manifest> = parents.iter().collect();
+        Transition::between(
+            *manifest.id(),
+            &Membership::of(manifest),
+            &parents,
+            manifest.saved_at(),
+        )
+    }
+
+    /// Fail if our own write scope doesn't cover `path`
+    async fn check_write_scope(&self, path: &Path) -> Result<(), MountError> {
+        let inner = self.0.lock().await;
+        match inner.manifest.get_share(&inner.peer_id) {
+            Some(share) if !share.can_write(path) => {
+                Err(MountError::OutOfScope(path.to_path_buf()))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Check if this bucket is published (mirrors can decrypt).
     pub async fn is_published(&self) -> bool {
         let inner = self.0.lock().await;
         inner.manifest.is_published()
     }
 
+    /// Get the bucket's compression policy, if compression is enabled.
+    pub async fn compression(&self) -> Option<CompressionPolicy> {
+        let inner = self.0.lock().await;
+        inner.manifest.compression().cloned()
+    }
+
+    /// Set or clear the bucket's compression policy.
+    ///
+    /// Applies to files added after this call and is persisted on the next save.
+    pub async fn set_compression(&self, policy: Option<CompressionPolicy>) {
+        let mut inner = self.0.lock().await;
+        inner.manifest.set_compression(policy);
+    }
+
+    /// Get the conflict strategy sync uses when merging into this bucket.
+    pub async fn conflict_strategy(&self) -> ConflictStrategy {
+        let inner = self.0.lock().await;
+        inner.manifest.conflict_strategy()
+    }
+
+    /// Set the conflict strategy, persisted on the next save.
+    pub async fn set_conflict_strategy(&self, strategy: ConflictStrategy) {
+        let mut inner = self.0.lock().await;
+        inner.manifest.set_conflict_strategy(strategy);
+    }
+
+    /// Publish the directory at `path`, persisted on the next save.
+    ///
+    /// Mirrors and the gateway can then read everything under it, but
+    /// nothing outside the published subtrees unless the whole bucket is
+    /// published. The subtree stays published across saves until
+    /// [`unpublish_path`](Self::unpublish_path) is called.
+    pub async fn publish_path(&self, path: &Path) -> Result<(), MountError> {
+        if clean_path(path) == Path::new("") {
+            return Err(MountError::PublishRoot);
+        }
+        self.check_write_scope(path).await?;
+        let link = self.get(path).await?;
+        if !link.is_dir() {
+            return Err(MountError::PathNotNode(path.to_path_buf()));
+        }
+
+        let mut inner = self.0.lock().await;
+        inner.manifest.publish_subtree(published_key(path), link);
+        Ok(())
+    }
+
+    /// Stop publishing the directory at `path`, persisted on the next save.
+    ///
+    /// Returns false if it wasn't published.
+    pub async fn unpublish_path(&self, path: &Path) -> Result<bool, MountError> {
+        self.check_write_scope(path).await?;
+        let mut inner = self.0.lock().await;
+        Ok(inner.manifest.unpublish_subtree(&published_key(path)))
+    }
+
+    /// Get the published subtrees' absolute paths.
+    pub async fn published_paths(&self) -> Vec<PathBuf> {
+        let inner = self.0.lock().await;
+        inner
+            .manifest
+            .published()
+            .keys()
+            .map(PathBuf::from)
+            .collect()
+    }
+
     /// Save and publish this bucket, granting decryption access to all mirrors.
     ///
     /// This is a convenience method equivalent to `save(blobs, true)`.
@@ -406,24 +962,37 @@ impl Mount {
         self.save(&self.1, true).await
     }
 
-    pub async fn add<R>(&mut self, path: &Path, data: R) -> Result<(), MountError>
+    pub async fn add<R>(&mut self, path: &Path, mut data: R) -> Result<(), MountError>
     where
         R: Read + Send + Sync + 'static + Unpin,
     {
+        self.check_write_scope(path).await?;
         let secret = Secret::generate();
 
-        let encrypted_reader = secret.encrypt_reader(data)?;
+        // Compress before encrypting when the bucket's policy allows it for this type
+        let level = {
+            let inner = self.0.lock().await;
+            inner
+                .manifest
+                .compression()
+                .and_then(|policy| policy.level_for(MaybeMime::from_path(path).0.as_ref()))
+        };
+        let mut plaintext = Vec::new();
+        data.read_to_end(&mut plaintext).map_err(SecretError::Io)?;
+        let compressed = match level {
+            Some(level) => secret.encrypt_compressed(&plaintext, level)?,
+            None => None,
+        };
+        // Readers only decompress blobs the link marks as compressed
+        let compressed_size = compressed.is_some().then_some(plaintext.len() as u64);
+        let encrypted_bytes = match compressed {
+            Some(encrypted) => encrypted,
+            None => secret.encrypt(&plaintext)?,
+        };
 
         // TODO (amiller68): this is incredibly dumb
         use bytes::Bytes;
         use futures::stream;
-        let encrypted_bytes = {
-            let mut buf = Vec::new();
-            let mut reader = encrypted_reader;
-            reader.read_to_end(&mut buf).map_err(SecretError::Io)?;
-            buf
-        };
-
         let stream = Box::pin(stream::once(async move {
             Ok::<_, std::io::Error>(Bytes::from(encrypted_bytes))
         }));
@@ -432,7 +1001,77 @@ impl Mount {
 
         let link = Link::new(crate::linked_data::LD_RAW_CODEC, hash);
 
-        let node_link = NodeLink::new_data_from_path(link.clone(), secret, path);
+        let mut data = Data::from_path(path);
+        data.set_compressed(compressed_size);
+        let node_link = NodeLink::Data(link.clone(), secret, data);
+
+        self.insert_node_link(path, node_link).await?;
+
+        // Record the add operation in the ops log
+        {
+            let mut inner = self.0.lock().await;
+            let peer_id = inner.peer_id;
+            inner
+                .ops_log
+                .record(peer_id, OpType::Add, clean_path(path), Some(link), false);
+        }
+
+        Ok(())
+    }
+
+    /// Link a depositor's file into the bucket's inbox.
+    ///
+    /// The deposit is checked against the current shares and its content
+    /// secret recovered with our key; the encrypted content must already be
+    /// in the blobs store. Returns the path the file was added at, or `None`
+    /// if the same content is already there.
+    pub async fn accept_deposit(
+        &mut self,
+        deposit: &Deposit,
+    ) -> Result<Option<PathBuf>, MountError> {
+        let secret = {
+            let inner = self.0.lock().await;
+            deposit.verify(&inner.manifest)?;
+            deposit.secret(&inner.secret_key)?
+        };
+        let path = deposit.inbox_path();
+        self.check_write_scope(&path).await?;
+
+        let content = deposit.content().clone();
+        if !self.1.stat(&content.hash()).await? {
+            return Err(MountError::LinkNotFound(content));
+        }
+
+        match self.get(&path).await {
+            Ok(NodeLink::Data(link, _, _)) if link == content => return Ok(None),
+            Ok(_) => return Err(MountError::PathAlreadyExists(path)),
+            Err(MountError::PathNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        let node_link = NodeLink::new_data_from_path(content.clone(), secret, &path);
+        self.insert_node_link(&path, node_link).await?;
+
+        let mut inner = self.0.lock().await;
+        let peer_id = inner.peer_id;
+        inner.ops_log.record(
+            peer_id,
+            OpType::Add,
+            clean_path(&path),
+            Some(content),
+            false,
+        );
+        Ok(Some(path))
+    }
+
+    /// Link an existing (already stored and encrypted) node into the tree at
+    /// `path`, updating the entry and pins. Does not record an operation.
+    async fn insert_node_link(
+        &mut self,
+        path: &Path,
+        node_link: NodeLink,
+    ) -> Result<(), MountError> {
+        let hash = node_link.link().hash();
 
         let root_node = {
             let inner = self.0.lock().await;
@@ -465,18 +1104,13 @@ impl Mount {
             if let Some(entry) = new_entry {
                 inner.entry = entry;
             }
-
-            // Record the add operation in the ops log
-            let peer_id = inner.peer_id;
-            inner
-                .ops_log
-                .record(peer_id, OpType::Add, clean_path(path), Some(link), false);
         }
 
         Ok(())
     }
 
     pub async fn rm(&mut self, path: &Path) -> Result<(), MountError> {
+        self.check_write_scope(path).await?;
         let path = clean_path(path);
         let parent_path = path
             .parent()
@@ -559,6 +1193,7 @@ impl Mount {
     }
 
     pub async fn mkdir(&mut self, path: &Path) -> Result<(), MountError> {
+        self.check_write_scope(path).await?;
         let path = clean_path(path);
 
         // Check if the path already exists
@@ -687,6 +1322,8 @@ impl Mount {
                 to: to.to_path_buf(),
             });
         }
+        self.check_write_scope(from).await?;
+        self.check_write_scope(to).await?;
 
         // ============================================================
         // STEP 1: Retrieve the NodeLink at the source path
@@ -835,20 +1472,12 @@ impl Mount {
         let mut items = BTreeMap::new();
         let path = clean_path(path);
 
-        let inner = self.0.lock().await;
-        let root_node = inner.entry.clone();
-        drop(inner);
-
-        let node = if path == Path::new("") {
-            root_node
-        } else {
-            match Self::_get_node_at_path(&root_node, &path, &self.1).await {
-                Ok(node) => node,
-                Err(MountError::LinkNotFound(_)) => {
-                    return Err(MountError::PathNotNode(path.to_path_buf()))
-                }
-                Err(err) => return Err(err),
+        let node = match self._get_dir_node(&path).await {
+            Ok(node) => node,
+            Err(MountError::LinkNotFound(_)) => {
+                return Err(MountError::PathNotNode(path.to_path_buf()))
             }
+            Err(err) => return Err(err),
         };
 
         for (name, link) in node.get_links() {
@@ -902,14 +1531,135 @@ impl Mount {
         Ok(all_items)
     }
 
+    /// List every file under a path, reading directory nodes level by level
+    ///
+    /// Before each level is read, `prefetch` is handed the hashes of its
+    /// directory nodes so a lazily synced bucket can download them in one
+    /// batch rather than one request per directory. A file path yields just
+    /// that file. Paths are relative to the root, like [`Self::ls_deep`].
+    pub async fn walk_files<F, Fut>(
+        &self,
+        path: &Path,
+        mut prefetch: F,
+    ) -> Result<BTreeMap<PathBuf, NodeLink>, MountError>
+    where
+        F: FnMut(Vec<Hash>) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let path = clean_path(path);
+        let mut files = BTreeMap::new();
+
+        let mut level = if path == Path::new("") {
+            let root_node = self.0.lock().await.entry.clone();
+            vec![(path, root_node)]
+        } else {
+            match self.get(&Path::new("/").join(&path)).await? {
+                link if link.is_dir() => {
+                    prefetch(vec![link.link().hash()]).await;
+                    let node = Self::_get_node_from_blobs(&link, &self.1).await?;
+                    vec![(path, node)]
+                }
+                data => {
+                    files.insert(path, data);
+                    return Ok(files);
+                }
+            }
+        };
+
+        while !level.is_empty() {
+            let mut dirs = Vec::new();
+            for (dir_path, node) in level {
+                for (name, link) in node.get_links() {
+                    let child_path = dir_path.join(name);
+                    if link.is_dir() {
+                        dirs.push((child_path, link.clone()));
+                    } else {
+                        files.insert(child_path, link.clone());
+                    }
+                }
+            }
+
+            if !dirs.is_empty() {
+                prefetch(dirs.iter().map(|(_, link)| link.link().hash()).collect()).await;
+            }
+
+            level = Vec::with_capacity(dirs.len());
+            for (dir_path, link) in dirs {
+                let node = Self::_get_node_from_blobs(&link, &self.1).await?;
+                level.push((dir_path, node));
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Paths whose content differs between `base` and this mount
+    ///
+    /// Subtrees with the same link on both sides are skipped without being
+    /// read. Returns the changed files, plus empty directories that were
+    /// added or removed; a directory added or removed with its contents shows
+    /// up as the files inside it. Paths are relative to the root, like
+    /// [`Self::ls_deep`].
+    pub async fn diff_paths(&self, base: &Mount) -> Result<BTreeSet<PathBuf>, MountError> {
+        let ours = self.0.lock().await.entry.clone();
+        let theirs = base.0.lock().await.entry.clone();
+
+        let mut changed = BTreeSet::new();
+        let mut queue = vec![(PathBuf::new(), Some(theirs), Some(ours))];
+        while let Some((dir_path, old, new)) = queue.pop() {
+            let old_links = old.as_ref().map(Node::get_links);
+            let new_links = new.as_ref().map(Node::get_links);
+            let names: BTreeSet<&String> = old_links
+                .iter()
+                .chain(new_links.iter())
+                .flat_map(|links| links.keys())
+                .collect();
+
+            // An empty directory that only exists on one side
+            if names.is_empty() && old.is_some() != new.is_some() {
+                changed.insert(dir_path.clone());
+            }
+
+            for name in names {
+                let old_link = old_links.and_then(|links| links.get(name));
+                let new_link = new_links.and_then(|links| links.get(name));
+                if old_link == new_link {
+                    continue;
+                }
+
+                let path = dir_path.join(name);
+                let old_dir = match old_link {
+                    Some(link) if link.is_dir() => {
+                        Some(Self::_get_node_from_blobs(link, &base.1).await?)
+                    }
+                    _ => None,
+                };
+                let new_dir = match new_link {
+                    Some(link) if link.is_dir() => {
+                        Some(Self::_get_node_from_blobs(link, &self.1).await?)
+                    }
+                    _ => None,
+                };
+
+                // A file on either side changed; directories are compared below
+                if old_link.is_some_and(NodeLink::is_data)
+                    || new_link.is_some_and(NodeLink::is_data)
+                {
+                    changed.insert(path.clone());
+                }
+                if old_dir.is_some() || new_dir.is_some() {
+                    queue.push((path, old_dir, new_dir));
+                }
+            }
+        }
+
+        Ok(changed)
+    }
+
     #[allow(clippy::await_holding_lock)]
     pub async fn cat(&self, path: &Path) -> Result<Vec<u8>, MountError> {
         let path = clean_path(path);
 
-        let inner = self.0.lock().await;
-        let root_node = inner.entry.clone();
-        drop(inner);
-
         let (parent_path, file_name) = if let Some(parent) = path.parent() {
             (
                 parent,
@@ -919,35 +1669,55 @@ impl Mount {
             return Err(MountError::PathNotFound(path.to_path_buf()));
         };
 
-        let parent_node = if parent_path == Path::new("") {
-            root_node
-        } else {
-            Self::_get_node_at_path(&root_node, parent_path, &self.1).await?
-        };
+        let parent_node = self._get_dir_node(parent_path).await?;
 
         let link = parent_node
             .get_link(&file_name)
             .ok_or_else(|| MountError::PathNotFound(path.to_path_buf()))?;
 
         match link {
-            NodeLink::Data(link, secret, _) => {
-                let encrypted_data = self.1.get(&link.hash()).await?;
-                let data = secret.decrypt(&encrypted_data)?;
+            NodeLink::Data(data_link, _, _) => {
+                let encrypted_data = self.1.get(&data_link.hash()).await?;
+                let data = link.decrypt(&encrypted_data)?;
                 Ok(data)
             }
             NodeLink::Dir(_, _) => Err(MountError::PathNotNode(path.to_path_buf())),
         }
     }
 
+    /// Mint a capability that lets anyone holding it read the file at `path`
+    ///
+    /// The capability is signed with this mount's key and stops working at
+    /// `expires_at`, if given. Later versions of the file aren't covered: it
+    /// opens the contents the path has now.
+    pub async fn file_capability(
+        &self,
+        path: &Path,
+        expires_at: Option<u64>,
+    ) -> Result<FileCapability, MountError> {
+        let link = self.get(path).await?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let (bucket_id, secret_key) = {
+            let inner = self.0.lock().await;
+            (*inner.manifest.id(), inner.secret_key.clone())
+        };
+        Ok(FileCapability::new(
+            bucket_id,
+            &link,
+            name,
+            expires_at,
+            &secret_key,
+        )?)
+    }
+
     /// Get the NodeLink for a file at a given path
     #[allow(clippy::await_holding_lock)]
     pub async fn get(&self, path: &Path) -> Result<NodeLink, MountError> {
         let path = clean_path(path);
 
-        let inner = self.0.lock().await;
-        let root_node = inner.entry.clone();
-        drop(inner);
-
         let (parent_path, file_name) = if let Some(parent) = path.parent() {
             (
                 parent,
@@ -957,11 +1727,7 @@ impl Mount {
             return Err(MountError::PathNotFound(path.to_path_buf()));
         };
 
-        let parent_node = if parent_path == Path::new("") {
-            root_node
-        } else {
-            Self::_get_node_at_path(&root_node, parent_path, &self.1).await?
-        };
+        let parent_node = self._get_dir_node(parent_path).await?;
 
         parent_node
             .get_link(&file_name)
@@ -969,6 +1735,74 @@ impl Mount {
             .ok_or_else(|| MountError::PathNotFound(path.to_path_buf()))
     }
 
+    /// Get the directory node at a cleaned path.
+    ///
+    /// A mount that can only read published subtrees resolves paths within
+    /// them. Directories above them are listed as holding just the
+    /// subtrees, keyed by their path below the directory.
+    async fn _get_dir_node(&self, path: &Path) -> Result<Node, MountError> {
+        let (root_node, view) = {
+            let inner = self.0.lock().await;
+            (inner.entry.clone(), inner.published_view.clone())
+        };
+
+        let Some(view) = view else {
+            if path == Path::new("") {
+                return Ok(root_node);
+            }
+            return Self::_get_node_at_path(&root_node, path, &self.1).await;
+        };
+
+        for (subtree, link) in &view {
+            if let Ok(rest) = path.strip_prefix(subtree) {
+                let node = Self::_get_node_from_blobs(link, &self.1).await?;
+                if rest == Path::new("") {
+                    return Ok(node);
+                }
+                return Self::_get_node_at_path(&node, rest, &self.1).await;
+            }
+        }
+
+        let mut node = Node::default();
+        for (subtree, link) in &view {
+            if let Ok(rest) = subtree.strip_prefix(path) {
+                node.insert(rest.to_string_lossy().to_string(), link.clone());
+            }
+        }
+        if node.size() == 0 {
+            return Err(MountError::PathNotFound(Path::new("/").join(path)));
+        }
+        Ok(node)
+    }
+
+    /// Get the link to the directory at `path`, or `None` if there is no
+    /// directory there.
+    async fn _get_dir_link_at_path(
+        root: &Node,
+        path: &Path,
+        blobs: &BlobsStore,
+    ) -> Result<Option<NodeLink>, MountError> {
+        if !path.is_absolute() {
+            return Ok(None);
+        }
+        let path = clean_path(path);
+        let mut node = root.clone();
+        let mut parts = path.iter().peekable();
+        while let Some(part) = parts.next() {
+            let Some(link) = node.get_link(&part.to_string_lossy()) else {
+                return Ok(None);
+            };
+            if !link.is_dir() {
+                return Ok(None);
+            }
+            if parts.peek().is_none() {
+                return Ok(Some(link.clone()));
+            }
+            node = Self::_get_node_from_blobs(link, blobs).await?;
+        }
+        Ok(None)
+    }
+
     async fn _get_node_at_path(
         node: &Node,
         path: &Path,
@@ -1054,6 +1888,14 @@ impl Mount {
         let hash = link.hash();
         tracing::debug!("_get_bucket_from_blobs: Bucket hash: {}", hash);
 
+        if let Err(e) = blobs.fetch_missing(&hash).await {
+            tracing::warn!(
+                "_get_bucket_from_blobs: Failed to fetch hash {} from peers: {}",
+                hash,
+                e
+            );
+        }
+
         match blobs.stat(&hash).await {
             Ok(true) => {
                 tracing::debug!(
@@ -1096,6 +1938,14 @@ impl Mount {
         let hash = link.hash();
         tracing::debug!("_get_pins_from_blobs: Pins hash: {}", hash);
 
+        if let Err(e) = blobs.fetch_missing(&hash).await {
+            tracing::warn!(
+                "_get_pins_from_blobs: Failed to fetch hash {} from peers: {}",
+                hash,
+                e
+            );
+        }
+
         match blobs.stat(&hash).await {
             Ok(true) => {
                 tracing::debug!("_get_pins_from_blobs: Pins hash {} exists in blobs", hash);
@@ -1138,6 +1988,14 @@ impl Mount {
 
         tracing::debug!("_get_node_from_blobs: Checking for node at hash {}", hash);
 
+        if let Err(e) = blobs.fetch_missing(&hash).await {
+            tracing::warn!(
+                "_get_node_from_blobs: Failed to fetch hash {} from peers: {}",
+                hash,
+                e
+            );
+        }
+
         match blobs.stat(&hash).await {
             Ok(true) => {
                 tracing::debug!("_get_node_from_blobs: Node hash {} exists in blobs", hash);
@@ -1187,6 +2045,7 @@ impl Mount {
         let _data = node.encode()?;
         let data = secret.encrypt(&_data)?;
         let hash = blobs.put(data).await?;
+        blobs.keep_hot(&hash).await?;
         // NOTE (amiller68): nodes are always stored as raw
         //  since they are encrypted blobs
         let link = Link::new(crate::linked_data::LD_RAW_CODEC, hash);
@@ -1199,6 +2058,7 @@ impl Mount {
     ) -> Result<Link, MountError> {
         let data = bucket_data.encode()?;
         let hash = blobs.put(data).await?;
+        blobs.keep_hot(&hash).await?;
         // NOTE (amiller68): buckets are unencrypted, so they can inherit
         //  the codec of the bucket itself (which is currently always cbor)
         let link = Link::new(bucket_data.codec(), hash);
@@ -1208,6 +2068,7 @@ impl Mount {
     pub async fn _put_pins_in_blobs(pins: &Pins, blobs: &BlobsStore) -> Result<Link, MountError> {
         // Create a hash list blob from the pins (raw bytes: 32 bytes per hash, concatenated)
         let hash = blobs.create_hash_list(pins.iter().copied()).await?;
+        blobs.keep_hot(&hash).await?;
         // Pins are stored as raw blobs containing concatenated hashes
         // Note: The underlying blob is HashSeq format, but Link doesn't track this
         let link = Link::new(crate::linked_data::LD_RAW_CODEC, hash);
@@ -1225,6 +2086,14 @@ impl Mount {
             hash
         );
 
+        if let Err(e) = blobs.fetch_missing(&hash).await {
+            tracing::warn!(
+                "_get_ops_log_from_blobs: Failed to fetch hash {} from peers: {}",
+                hash,
+                e
+            );
+        }
+
         match blobs.stat(&hash).await {
             Ok(true) => {
                 tracing::debug!(
@@ -1287,16 +2156,17 @@ impl Mount {
         Ok(link)
     }
 
-    /// Collect all ops from manifest chain back to (but not including) ancestor_link.
+    /// Collect all ops from the version DAG since (but not including) ancestor_link.
     ///
-    /// Traverses the manifest chain starting from the current version, collecting
-    /// ops_logs from each manifest until we reach the ancestor (or genesis).
-    /// The collected ops are merged in chronological order.
+    /// Traverses every version reachable from the current one through its
+    /// parents, skipping the ancestor and everything in its history, and
+    /// collects the ops_log of each. The collected ops are merged in
+    /// chronological order.
     ///
     /// # Arguments
     ///
-    /// * `ancestor_link` - Stop when reaching this link (not included). None means collect all
-    ///   accessible ops (stops when we can't decrypt a manifest).
+    /// * `ancestor_link` - Exclude this version and its history. None means collect all
+    ///   accessible ops (stops at versions we can't decrypt).
     /// * `blobs` - The blob store to read manifests from
     ///
     /// # Returns
@@ -1313,22 +2183,18 @@ impl Mount {
         let current_ops = inner.ops_log.clone();
         drop(inner);
 
-        let mut all_logs: Vec<PathOpLog> = Vec::new();
-
-        // Start with any unsaved operations in the current mount
-        if !current_ops.is_empty() {
-            all_logs.push(current_ops);
-        }
+        // Versions already covered by the ancestor
+        let mut visited = match ancestor_link {
+            Some(ancestor) => Self::_ancestors(ancestor, blobs).await?,
+            None => std::collections::HashSet::new(),
+        };
 
-        // Walk the chain from current manifest backwards
-        let mut link = current_link;
+        let mut logs: Vec<(u64, PathOpLog)> = Vec::new();
+        let mut queue = std::collections::VecDeque::from([current_link]);
 
-        loop {
-            // Check if we've reached the ancestor
-            if let Some(ancestor) = ancestor_link {
-                if &link == ancestor {
-                    break;
-                }
+        while let Some(link) = queue.pop_front() {
+            if !visited.insert(link.clone()) {
+                continue;
             }
 
             // Load the manifest at this link
@@ -1336,7 +2202,7 @@ impl Mount {
 
             // Get the secret for this manifest version
             // If we can't get the secret (e.g., we weren't a member at this point),
-            // stop traversing - we can't read older ops anyway
+            // don't traverse past it - we can't read older ops anyway
             let secret = match self.get_secret_for_manifest(&manifest, &secret_key) {
                 Ok(s) => s,
                 Err(MountError::ShareNotFound) => {
@@ -1344,7 +2210,7 @@ impl Mount {
                         "collect_ops_since: stopping at link {} - no share for current user",
                         link.hash()
                     );
-                    break;
+                    continue;
                 }
                 Err(e) => return Err(e),
             };
@@ -1353,26 +2219,66 @@ impl Mount {
             if let Some(ops_link) = manifest.ops_log() {
                 let mut ops_log = Self::_get_ops_log_from_blobs(ops_link, &secret, blobs).await?;
                 ops_log.rebuild_clock();
-                all_logs.push(ops_log);
+                logs.push((manifest.height(), ops_log));
             }
 
-            // Move to previous manifest
-            match manifest.previous() {
-                Some(prev) => link = prev.clone(),
-                None => break, // Reached genesis
-            }
+            queue.extend(manifest.parents().iter().cloned());
         }
 
-        // Merge all logs in chronological order (oldest first, so reverse)
-        all_logs.reverse();
+        // Merge all logs in chronological order (oldest first), then any
+        // unsaved operations in the current mount
+        logs.sort_by_key(|(height, _)| *height);
         let mut merged = PathOpLog::new();
-        for log in all_logs {
+        for (_, log) in logs {
             merged.merge(&log);
         }
+        if !current_ops.is_empty() {
+            merged.merge(&current_ops);
+        }
 
         Ok(merged)
     }
 
+    /// Collect a version and everything in its history.
+    async fn _ancestors(
+        link: &Link,
+        blobs: &BlobsStore,
+    ) -> Result<std::collections::HashSet<Link>, MountError> {
+        let mut ancestors = std::collections::HashSet::new();
+        let mut queue = vec![link.clone()];
+        while let Some(link) = queue.pop() {
+            if ancestors.insert(link.clone()) {
+                let manifest = Self::_get_manifest_from_blobs(&link, blobs).await?;
+                queue.extend(manifest.parents().iter().cloned());
+            }
+        }
+        Ok(ancestors)
+    }
+
+    /// Compute the skip pointers for a new version whose primary parent is `parent`.
+    ///
+    /// The nearest skip is the parent's own previous version; each further one
+    /// is the previous skip's skip at the same level, so this loads one
+    /// manifest per level. The list stops early at genesis, at manifests
+    /// without skips, and at manifests we don't have locally.
+    async fn _skips_for_child_of(
+        parent: &Link,
+        blobs: &BlobsStore,
+    ) -> Result<Vec<Link>, MountError> {
+        let parent = Self::_get_manifest_from_blobs(parent, blobs).await?;
+        let mut skips: Vec<Link> = Vec::new();
+        let mut next = parent.previous().clone();
+        while let Some(link) = next {
+            next = match Self::_get_manifest_from_blobs(&link, blobs).await {
+                Ok(manifest) => manifest.skips().get(skips.len()).cloned(),
+                Err(MountError::LinkNotFound(_)) => None,
+                Err(e) => return Err(e),
+            };
+            skips.push(link);
+        }
+        Ok(skips)
+    }
+
     /// Get the decryption secret for a manifest.
     ///
     /// Decrypts the secret share using the provided secret key.
@@ -1388,7 +2294,7 @@ impl Mount {
             .ok_or(MountError::ShareNotFound)?;
 
         match share.role() {
-            PrincipalRole::Owner => {
+            PrincipalRole::Owner | PrincipalRole::Reader => {
                 let secret_share = share.share().ok_or(MountError::ShareNotFound)?;
                 Ok(secret_share.recover(secret_key)?)
             }
@@ -1396,13 +2302,16 @@ impl Mount {
                 .public()
                 .cloned()
                 .ok_or(MountError::MirrorCannotMount),
+            PrincipalRole::Depositor => Err(MountError::DepositorCannotMount),
         }
     }
 
-    /// Find the common ancestor between this mount's chain and another's.
+    /// Find the common ancestor between this mount's history and another's.
     ///
-    /// Walks both chains backwards via the `previous` links and returns
-    /// the first link where both chains converge.
+    /// Collects every version in this mount's history, then walks the other
+    /// mount's history highest version first and returns the first version
+    /// both share. With merges in the history there can be several common
+    /// ancestors; this picks the highest, i.e. the most recent shared state.
     ///
     /// # Arguments
     ///
@@ -1411,39 +2320,35 @@ impl Mount {
     ///
     /// # Returns
     ///
-    /// The common ancestor link, or None if chains never converge (different buckets).
+    /// The common ancestor link, or None if histories never converge (different buckets).
     pub async fn find_common_ancestor(
         &self,
         other: &Mount,
         blobs: &BlobsStore,
     ) -> Result<Option<Link>, MountError> {
-        // Build set of all links in self's chain
-        let mut self_chain: std::collections::HashSet<Link> = std::collections::HashSet::new();
-
-        let self_link = self.link().await;
-        let mut link = self_link;
-
-        loop {
-            self_chain.insert(link.clone());
-            let manifest = Self::_get_manifest_from_blobs(&link, blobs).await?;
-            match manifest.previous() {
-                Some(prev) => link = prev.clone(),
-                None => break,
-            }
-        }
+        let self_history = Self::_ancestors(&self.link().await, blobs).await?;
 
-        // Walk other's chain and find first link in self's set
-        let other_link = other.link().await;
-        let mut link = other_link;
+        // Walk other's history by descending height
+        let (other_link, other_height) = {
+            let inner = other.0.lock().await;
+            (inner.link.clone(), inner.height)
+        };
+        let mut visited = std::collections::HashSet::new();
+        let mut queue = std::collections::BinaryHeap::from([(other_height, other_link)]);
 
-        loop {
-            if self_chain.contains(&link) {
+        while let Some((_, link)) = queue.pop() {
+            if self_history.contains(&link) {
                 return Ok(Some(link));
             }
+            if !visited.insert(link.clone()) {
+                continue;
+            }
             let manifest = Self::_get_manifest_from_blobs(&link, blobs).await?;
-            match manifest.previous() {
-                Some(prev) => link = prev.clone(),
-                None => break,
+            for parent in manifest.parents() {
+                let height = Self::_get_manifest_from_blobs(parent, blobs)
+                    .await?
+                    .height();
+                queue.push((height, parent.clone()));
             }
         }
 
@@ -1453,21 +2358,91 @@ impl Mount {
 
     /// Apply resolved operations to the entry tree.
     ///
-    /// For each operation in the resolved state:
-    /// - Files with content links are checked for existence (cannot recreate without secret)
+    /// - Removals and moves that won on the incoming branch are replayed
+    /// - Files whose winning content isn't in our tree are linked in from the
+    ///   incoming tree (which holds their secrets), including renamed conflict
+    ///   copies; files missing from both are only recorded in the ops log
     /// - Directories are created if they don't exist
-    async fn apply_resolved_state(&mut self, merged_ops: &PathOpLog) -> Result<(), MountError> {
+    async fn apply_resolved_state(
+        &mut self,
+        merged_ops: &PathOpLog,
+        incoming_ops: &PathOpLog,
+        incoming: &Mount,
+    ) -> Result<(), MountError> {
+        // Index the incoming tree's files by content link
+        let incoming_files: std::collections::HashMap<Link, NodeLink> = incoming
+            .ls_deep(Path::new("/"))
+            .await?
+            .into_values()
+            .filter(|node_link| node_link.is_data())
+            .map(|node_link| (node_link.link().clone(), node_link))
+            .collect();
+
+        // Replay removals and moves from the incoming branch that won
+        for op in incoming_ops.ops_in_order() {
+            let won = merged_ops
+                .resolve_path(&op.path)
+                .is_some_and(|winner| winner.id == op.id);
+            if !won {
+                continue;
+            }
+            let abs_path = Path::new("/").join(&op.path);
+            match &op.op_type {
+                OpType::Remove => match self.rm(&abs_path).await {
+                    Ok(()) | Err(MountError::PathNotFound(_)) => {}
+                    Err(e) => return Err(e),
+                },
+                OpType::Mv { from } => {
+                    let from_path = Path::new("/").join(from);
+                    let to_missing =
+                        matches!(self.get(&abs_path).await, Err(MountError::PathNotFound(_)));
+                    if to_missing && self.get(&from_path).await.is_ok() {
+                        self.mv(&from_path, &abs_path).await?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
         let resolved_state = merged_ops.resolve_all();
 
+        // A move doesn't record a removal at its source, so a source whose
+        // latest op predates a winning move out of it is gone
+        let moved_away: std::collections::HashSet<&PathBuf> = resolved_state
+            .values()
+            .filter_map(|op| match &op.op_type {
+                OpType::Mv { from } => resolved_state
+                    .get(from)
+                    .filter(|source| source.id < op.id)
+                    .map(|_| from),
+                _ => None,
+            })
+            .collect();
+
         for (path, op) in &resolved_state {
-            if op.content_link.is_some() {
-                // File operation - check if it exists in our tree
-                let abs_path = Path::new("/").join(path);
-                match self.get(&abs_path).await {
-                    Ok(_) => {
-                        // File exists, skip
+            if moved_away.contains(path) {
+                continue;
+            }
+            let abs_path = Path::new("/").join(path);
+            if let Some(content_link) = &op.content_link {
+                // File operation - check what our tree holds at this path
+                let current = match self.get(&abs_path).await {
+                    Ok(node_link) => Some(node_link),
+                    Err(MountError::PathNotFound(_)) => None,
+                    Err(e) => return Err(e),
+                };
+                if current
+                    .as_ref()
+                    .is_some_and(|node_link| node_link.link() == content_link)
+                {
+                    continue;
+                }
+
+                match incoming_files.get(content_link) {
+                    Some(node_link) => {
+                        self.insert_node_link(&abs_path, node_link.clone()).await?;
                     }
-                    Err(MountError::PathNotFound(_)) => {
+                    None if current.is_none() => {
                         // Can't recreate - ops_log stores Link but not decryption secret
                         tracing::warn!(
                             "apply_resolved_state: cannot recreate file {} - no secret",
@@ -1477,11 +2452,12 @@ impl Mount {
                         let mut inner = self.0.lock().await;
                         inner.ops_log.merge(&PathOpLog::from_operation(op));
                     }
-                    Err(e) => return Err(e),
+                    None => {
+                        // Our version is what we have; nothing to link in
+                    }
                 }
-            } else if op.is_dir && matches!(op.op_type, super::path_ops::OpType::Mkdir) {
+            } else if op.is_dir && matches!(op.op_type, OpType::Mkdir) {
                 // Directory operation - create if missing
-                let abs_path = Path::new("/").join(path);
                 match self.mkdir(&abs_path).await {
                     Ok(()) => {}
                     Err(MountError::PathAlreadyExists(_)) => {}
@@ -1493,32 +2469,41 @@ impl Mount {
         Ok(())
     }
 
-    /// Merge another mount's changes into this one using the given resolver.
+    /// Merge another mount's changes into this one without saving.
     ///
     /// This method:
     /// 1. Finds the common ancestor between the two chains
     /// 2. Collects ops from both chains since that ancestor
     /// 3. Merges using the resolver
-    /// 4. Applies the merged state
-    /// 5. Saves the result as a new version
+    /// 4. Applies the merged state, linking in files from `incoming`
+    /// 5. Merges share changes made on the incoming branch
+    /// 6. Records `incoming`'s head as the merge parent for the next save
+    ///
+    /// If `incoming` is already part of our history this is a no-op. If our
+    /// saved history is part of `incoming`'s (only unsaved local changes
+    /// differ), the next save simply builds on `incoming`'s head instead.
     ///
     /// # Arguments
     ///
     /// * `incoming` - The mount to merge changes from
     /// * `resolver` - The conflict resolution strategy to use
     /// * `blobs` - The blob store
-    ///
-    /// # Returns
-    ///
-    /// A MergeResult containing conflict information and the new version link.
-    pub async fn merge_from<R: super::ConflictResolver>(
+    pub async fn merge(
         &mut self,
         incoming: &Mount,
-        resolver: &R,
+        resolver: &dyn super::ConflictResolver,
         blobs: &BlobsStore,
-    ) -> Result<(MergeResult, Link), MountError> {
+    ) -> Result<MergeResult, MountError> {
+        let (incoming_link, incoming_height) = {
+            let inner = incoming.0.lock().await;
+            (inner.link.clone(), inner.height)
+        };
+
         // Find the common ancestor
         let ancestor = self.find_common_ancestor(incoming, blobs).await?;
+        if ancestor.as_ref() == Some(&incoming_link) {
+            return Ok(MergeResult::new());
+        }
 
         // Collect ops from both chains since the ancestor
         let local_ops = self.collect_ops_since(ancestor.as_ref(), blobs).await?;
@@ -1535,14 +2520,92 @@ impl Mount {
         let merge_result = merged_ops.merge_with_resolver(&incoming_ops, resolver, &peer_id);
 
         // Apply merged state to the entry tree
-        self.apply_resolved_state(&merged_ops).await?;
+        self.apply_resolved_state(&merged_ops, &incoming_ops, incoming)
+            .await?;
+
+        let incoming_manifest = incoming.0.lock().await.manifest.clone();
+        let base_manifest = match &ancestor {
+            Some(link) => Some(Self::_get_manifest_from_blobs(link, blobs).await?),
+            None => None,
+        };
+
+        let mut inner = self.0.lock().await;
 
         // Merge the ops_log to include all merged operations
+        inner.ops_log.merge(&merged_ops);
+
+        if ancestor.as_ref() == Some(&inner.link) {
+            // Our saved history is behind incoming: build straight on top of it
+            inner.link = incoming_link;
+            inner.height = incoming_height;
+            inner.manifest = incoming_manifest;
+            return Ok(merge_result);
+        }
+
+        // Under a quorum, keep the membership with the highest epoch (ours on
+        // a tie) so a stale branch can't undo an approved change. Otherwise
+        // three-way merge the shares: keep members the incoming branch
+        // added, drop members it removed
+        let has_quorum = [
+            Some(&inner.manifest),
+            Some(&incoming_manifest),
+            base_manifest.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|manifest| manifest.quorum() > 1);
+        if has_quorum {
+            if incoming_manifest.membership_epoch() > inner.manifest.membership_epoch() {
+                *inner.manifest.shares_mut() = incoming_manifest.shares().clone();
+                inner.manifest.set_quorum(incoming_manifest.quorum());
+            }
+        } else if let Some(base) = &base_manifest {
+            for (key, share) in incoming_manifest.shares() {
+                if !base.shares().contains_key(key) && !inner.manifest.shares().contains_key(key) {
+                    inner.manifest.add_share(share.clone());
+                }
+            }
+            for key in base.shares().keys() {
+                if !incoming_manifest.shares().contains_key(key) {
+                    inner.manifest.shares_mut().remove(key);
+                }
+            }
+        }
+
+        // Reference the incoming head from the next version
+        if !inner
+            .merge_parents
+            .iter()
+            .any(|(link, _)| *link == incoming_link)
         {
-            let mut inner = self.0.lock().await;
-            inner.ops_log.merge(&merged_ops);
+            inner.merge_parents.push((incoming_link, incoming_height));
         }
 
+        Ok(merge_result)
+    }
+
+    /// Merge another mount's changes into this one using the given resolver.
+    ///
+    /// Runs [`merge`](Self::merge) and saves the result as a new version whose
+    /// manifest references both heads.
+    ///
+    /// # Arguments
+    ///
+    /// * `incoming` - The mount to merge changes from
+    /// * `resolver` - The conflict resolution strategy to use
+    /// * `blobs` - The blob store
+    ///
+    /// # Returns
+    ///
+    /// A MergeResult containing conflict information and the new version link.
+    pub async fn merge_from<R: super::ConflictResolver>(
+        &mut self,
+        incoming: &Mount,
+        resolver: &R,
+        blobs: &BlobsStore,
+    ) -> Result<(MergeResult, Link), MountError> {
+        let merge_result = self.merge(incoming, resolver, blobs).await?;
+
         // Save the merged state
         let (link, _, _) = self.save(blobs, false).await?;
 
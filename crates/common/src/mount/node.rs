@@ -6,7 +6,7 @@ use std::path::Path;
 use mime::Mime;
 use serde::{Deserialize, Serialize};
 
-use crate::crypto::Secret;
+use crate::crypto::{Secret, SecretError};
 use crate::linked_data::{BlockEncoded, DagCborCodec, Link, LinkedData};
 
 use super::maybe_mime::MaybeMime;
@@ -36,6 +36,10 @@ pub struct Data {
     // Data Links may have metadata built for them, which are parsed
     //  from the links data at inclusion time
     metadata: Option<BTreeMap<String, LinkedData>>,
+    // Set when the blob holds a zstd frame, to the size of the
+    //  uncompressed contents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    compressed: Option<u64>,
 }
 
 impl Default for Data {
@@ -50,6 +54,7 @@ impl Data {
         Self {
             mime: MaybeMime(None),
             metadata: None,
+            compressed: None,
         }
     }
 
@@ -65,6 +70,7 @@ impl Data {
             } else {
                 Some(metadata)
             },
+            compressed: None,
         }
     }
 
@@ -88,6 +94,16 @@ impl Data {
     pub fn metadata(&self) -> Option<&BTreeMap<String, LinkedData>> {
         self.metadata.as_ref()
     }
+
+    /// Uncompressed size, if the blob was compressed before encryption
+    pub fn compressed(&self) -> Option<u64> {
+        self.compressed
+    }
+
+    /// Record that the blob is compressed, and how large it was before
+    pub fn set_compressed(&mut self, size: Option<u64>) {
+        self.compressed = size;
+    }
 }
 
 // Lastly, we have a node, which is either a data link,
@@ -129,6 +145,14 @@ impl NodeLink {
         }
     }
 
+    /// Decrypt the blob this link points at, decompressing it if needed
+    pub fn decrypt(&self, blob: &[u8]) -> Result<Vec<u8>, SecretError> {
+        match self.data().and_then(Data::compressed) {
+            Some(size) => self.secret().decrypt_compressed(blob, size),
+            None => self.secret().decrypt(blob),
+        }
+    }
+
     /// Get data info if this is a Data link
     pub fn data(&self) -> Option<&Data> {
         match self {
@@ -208,6 +232,7 @@ mod test {
                 Data {
                     metadata: None,
                     mime: MaybeMime(None),
+                    compressed: None,
                 },
             ),
         );
@@ -46,6 +46,35 @@ pub enum PrincipalRole {
     /// - Cannot modify bucket contents
     /// - Useful for CDN/gateway nodes that serve published content
     Mirror,
+
+    /// Private read-only access.
+    ///
+    /// Readers:
+    /// - Always have an encrypted [`SecretShare`](crate::crypto::SecretShare),
+    ///   re-issued on every save like an owner's
+    /// - Can decrypt every version without the bucket being published
+    /// - Cannot modify bucket contents; versions they author are rejected
+    /// - Useful for auditors, family members and downstream consumers
+    Reader,
+
+    /// Write-only access through the bucket's inbox.
+    ///
+    /// Depositors:
+    /// - Never have a [`SecretShare`](crate::crypto::SecretShare) and cannot
+    ///   mount the bucket
+    /// - Submit files as signed [`Deposit`](super::Deposit)s, encrypted to
+    ///   the owners' public keys
+    /// - Only ever add files under [`INBOX_PATH`](super::INBOX_PATH); an owner
+    ///   accepts each deposit into the bucket
+    /// - Useful for collecting submissions from external collaborators
+    Depositor,
+}
+
+impl PrincipalRole {
+    /// Whether principals with this role hold their own encrypted secret share
+    pub fn has_secret_share(&self) -> bool {
+        matches!(self, PrincipalRole::Owner | PrincipalRole::Reader)
+    }
 }
 
 impl std::fmt::Display for PrincipalRole {
@@ -53,6 +82,8 @@ impl std::fmt::Display for PrincipalRole {
         match self {
             PrincipalRole::Owner => write!(f, "Owner"),
             PrincipalRole::Mirror => write!(f, "Mirror"),
+            PrincipalRole::Reader => write!(f, "Reader"),
+            PrincipalRole::Depositor => write!(f, "Depositor"),
         }
     }
 }
@@ -0,0 +1,193 @@
+//! Requests for access to a bucket
+//!
+//! A peer that isn't in a bucket's shares can ask an owner to add it with
+//! the [`RequestAccess`] protocol message. The request is signed by the
+//! requester, so the owner's daemon can keep it in an
+//! [`AccessRequestStore`] and show who asked for what until an owner
+//! approves or denies it.
+//!
+//! [`RequestAccess`]: crate::peer::protocol::RequestAccess
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::crypto::{PublicKey, SecretKey, Signature};
+use crate::mount::PrincipalRole;
+
+/// Longest note a requester can attach, in bytes
+pub const MAX_NOTE_LEN: usize = 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AccessRequestError {
+    #[error("invalid signature on access request")]
+    InvalidSignature,
+    #[error("note is longer than {MAX_NOTE_LEN} bytes")]
+    NoteTooLong,
+    #[error("codec error: {0}")]
+    Codec(#[from] bincode::Error),
+}
+
+/// A signed request to be added to a bucket's shares
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessRequest {
+    /// The bucket the requester wants to join
+    pub bucket_id: Uuid,
+    /// Who is asking
+    pub requester: PublicKey,
+    /// Role the requester asks for
+    pub role: PrincipalRole,
+    /// Free-form message to the owners
+    pub note: Option<String>,
+    /// When the request was made, in seconds since the Unix epoch
+    pub requested_at: u64,
+    /// Ed25519 signature over every other field.
+    signature: Option<Signature>,
+}
+
+impl AccessRequest {
+    /// Create a request signed by the requester
+    pub fn new(
+        bucket_id: Uuid,
+        role: PrincipalRole,
+        note: Option<String>,
+        requester: &SecretKey,
+        requested_at: u64,
+    ) -> Result<Self, AccessRequestError> {
+        if note.as_ref().is_some_and(|note| note.len() > MAX_NOTE_LEN) {
+            return Err(AccessRequestError::NoteTooLong);
+        }
+        let mut request = Self {
+            bucket_id,
+            requester: requester.public(),
+            role,
+            note,
+            requested_at,
+            signature: None,
+        };
+        request.signature = Some(requester.sign(&request.signable_bytes()?));
+        Ok(request)
+    }
+
+    /// Rebuild a stored request from its parts and signature
+    pub fn from_parts(
+        bucket_id: Uuid,
+        requester: PublicKey,
+        role: PrincipalRole,
+        note: Option<String>,
+        requested_at: u64,
+        signature: Signature,
+    ) -> Self {
+        Self {
+            bucket_id,
+            requester,
+            role,
+            note,
+            requested_at,
+            signature: Some(signature),
+        }
+    }
+
+    pub fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    /// Check the note's length and that the requester signed the request
+    pub fn verify(&self) -> Result<(), AccessRequestError> {
+        if self
+            .note
+            .as_ref()
+            .is_some_and(|note| note.len() > MAX_NOTE_LEN)
+        {
+            return Err(AccessRequestError::NoteTooLong);
+        }
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or(AccessRequestError::InvalidSignature)?;
+        self.requester
+            .verify(&self.signable_bytes()?, signature)
+            .map_err(|_| AccessRequestError::InvalidSignature)
+    }
+
+    /// The bytes the requester signs: the request without its signature
+    fn signable_bytes(&self) -> Result<Vec<u8>, AccessRequestError> {
+        let mut signable = self.clone();
+        signable.signature = None;
+        Ok(bincode::serialize(&signable)?)
+    }
+}
+
+/// Queue of access requests waiting for an owner to review them
+///
+/// Peers built without a store refuse access requests, since nobody could
+/// review them.
+#[async_trait]
+pub trait AccessRequestStore: Send + Sync + std::fmt::Debug {
+    /// Queue a verified request for the bucket's owners to review
+    ///
+    /// A newer request from the same requester replaces a queued one,
+    /// unless that one was denied. Returns whether the request was queued.
+    async fn queue(&self, request: &AccessRequest) -> anyhow::Result<bool>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_request_verifies() {
+        let requester = SecretKey::generate();
+        let request = AccessRequest::new(
+            Uuid::new_v4(),
+            PrincipalRole::Reader,
+            Some("from the design team".to_string()),
+            &requester,
+            1_700_000_000,
+        )
+        .unwrap();
+        assert!(request.verify().is_ok());
+
+        // A stored copy still verifies
+        let stored = AccessRequest::from_parts(
+            request.bucket_id,
+            request.requester,
+            request.role.clone(),
+            request.note.clone(),
+            request.requested_at,
+            *request.signature().unwrap(),
+        );
+        assert!(stored.verify().is_ok());
+    }
+
+    #[test]
+    fn test_tampered_request_is_rejected() {
+        let requester = SecretKey::generate();
+        let mut request = AccessRequest::new(
+            Uuid::new_v4(),
+            PrincipalRole::Reader,
+            None,
+            &requester,
+            1_700_000_000,
+        )
+        .unwrap();
+
+        request.role = PrincipalRole::Owner;
+        assert!(matches!(
+            request.verify(),
+            Err(AccessRequestError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_long_note_is_rejected() {
+        let result = AccessRequest::new(
+            Uuid::new_v4(),
+            PrincipalRole::Mirror,
+            Some("x".repeat(MAX_NOTE_LEN + 1)),
+            &SecretKey::generate(),
+            0,
+        );
+        assert!(matches!(result, Err(AccessRequestError::NoteTooLong)));
+    }
+}
@@ -1,29 +1,122 @@
+use std::collections::HashSet;
 use std::future::IntoFuture;
 use std::ops::Deref;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use bytes::Bytes;
-use futures::Stream;
+use futures::{Stream, StreamExt};
+use iroh::endpoint::Connection;
 use iroh::{Endpoint, NodeId};
 use iroh_blobs::{
     api::{
         blobs::{BlobReader as Reader, BlobStatus, Blobs},
-        downloader::{Downloader, Shuffled},
+        downloader::{DownloadProgressItem, Downloader, Shuffled},
         ExportBaoError, RequestError,
     },
     store::{fs::FsStore, mem::MemStore},
     BlobsProtocol, Hash,
 };
 
-use object_store::ObjectStore as ObjStore;
+use object_store::{ObjectStore as ObjStore, ScrubReport, TierStats};
+use tokio::sync::{OwnedRwLockWriteGuard, RwLock, RwLockReadGuard};
 
+use super::peer_health::PeerHealth;
 use crate::{
     crypto::PublicKey,
     linked_data::{BlockEncoded, CodecError, DagCborCodec},
 };
 
+/// Maximum number of hashes requested from a peer at once
+const DOWNLOAD_BATCH_SIZE: usize = 1024;
+
+/// Smallest batch a download is split into for parallelism
+const MIN_DOWNLOAD_BATCH_SIZE: usize = 16;
+
+/// Number of batches downloaded at once unless configured otherwise
+pub const DEFAULT_DOWNLOAD_PARALLELISM: usize = 4;
+
+/// Minimum time between byte progress reports during a download
+pub const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long a provider gets to accept a connection before a download
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Progress reported while downloading blobs from peers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadEvent {
+    /// The number of blobs missing locally, which is what will be fetched
+    Started { total: usize },
+    /// A provider is being asked for the current batch
+    Provider(PublicKey),
+    /// A provider failed to serve the current batch
+    ProviderFailed(PublicKey),
+    /// Blobs and bytes fetched so far
+    Progress {
+        done: usize,
+        total: usize,
+        bytes: u64,
+    },
+}
+
+/// Running totals shared by the concurrent batches of a download
+struct BatchProgress<'a, F> {
+    on_event: &'a F,
+    total: usize,
+    done: AtomicUsize,
+    bytes: AtomicU64,
+    last_report: Mutex<Instant>,
+}
+
+impl<'a, F: Fn(DownloadEvent)> BatchProgress<'a, F> {
+    fn new(on_event: &'a F, total: usize) -> Self {
+        Self {
+            on_event,
+            total,
+            done: AtomicUsize::new(0),
+            bytes: AtomicU64::new(0),
+            last_report: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn emit(&self, event: DownloadEvent) {
+        (self.on_event)(event);
+    }
+
+    /// Count downloaded bytes, reporting at most once per [`PROGRESS_INTERVAL`]
+    fn add_bytes(&self, bytes: u64) {
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+        let due = {
+            let mut last_report = self.last_report.lock().unwrap();
+            let due = last_report.elapsed() >= PROGRESS_INTERVAL;
+            if due {
+                *last_report = Instant::now();
+            }
+            due
+        };
+        if due {
+            self.report();
+        }
+    }
+
+    /// Count a finished batch; always reported
+    fn complete_batch(&self, blobs: usize) {
+        self.done.fetch_add(blobs, Ordering::Relaxed);
+        self.report();
+    }
+
+    fn report(&self) {
+        self.emit(DownloadEvent::Progress {
+            done: self.done.load(Ordering::Relaxed),
+            total: self.total,
+            bytes: self.bytes.load(Ordering::Relaxed),
+        });
+    }
+}
+
 // TODO (amiller68): maybe at some point it would make sense
 //  to implement some sort of `BlockStore` trait over BlobStore
 /// Client over a local iroh-blob store.
@@ -34,6 +127,37 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct BlobsStore {
     pub inner: Arc<BlobsProtocol>,
+    /// Handle to the ObjectStore backend, when not using a legacy iroh store
+    object_store: Option<ObjStore>,
+    /// Number of batches downloaded from peers at once
+    download_parallelism: usize,
+    /// Download health of the peers we fetch from
+    health: PeerHealth,
+    /// Where to fetch blobs missing locally on first read, if anywhere
+    remote: Option<Arc<RemoteSource>>,
+    /// Whether writes are accepted; held shared by every write so that
+    /// [`Self::pause_writes`] can hold them off
+    write_gate: Arc<RwLock<bool>>,
+}
+
+/// Writes to a [`BlobsStore`] held off by [`BlobsStore::pause_writes`]
+///
+/// Writes resume when this is dropped, unless it was [closed](Self::close).
+#[derive(Debug)]
+pub struct WritePause(OwnedRwLockWriteGuard<bool>);
+
+impl WritePause {
+    /// Refuse every further write, e.g. once the blobs moved to another store
+    pub fn close(mut self) {
+        *self.0 = false;
+    }
+}
+
+/// Peers a [`BlobsStore`] fetches missing blobs from on demand
+#[derive(Debug)]
+struct RemoteSource {
+    endpoint: Endpoint,
+    peer_ids: Vec<PublicKey>,
 }
 
 impl Deref for BlobsStore {
@@ -57,6 +181,8 @@ pub enum BlobsStoreError {
     Decode(#[from] CodecError),
     #[error("object store error: {0}")]
     ObjectStore(#[from] object_store::BlobStoreError),
+    #[error("blob store no longer accepts writes; restart the daemon")]
+    WritesClosed,
 }
 
 impl BlobsStore {
@@ -68,6 +194,11 @@ impl BlobsStore {
         let blobs = BlobsProtocol::new(&store, None);
         Ok(Self {
             inner: Arc::new(blobs),
+            object_store: None,
+            download_parallelism: DEFAULT_DOWNLOAD_PARALLELISM,
+            health: PeerHealth::new(),
+            remote: None,
+            write_gate: Arc::new(RwLock::new(true)),
         })
     }
 
@@ -77,6 +208,11 @@ impl BlobsStore {
         let blobs = BlobsProtocol::new(&store, None);
         Ok(Self {
             inner: Arc::new(blobs),
+            object_store: None,
+            download_parallelism: DEFAULT_DOWNLOAD_PARALLELISM,
+            health: PeerHealth::new(),
+            remote: None,
+            write_gate: Arc::new(RwLock::new(true)),
         })
     }
 
@@ -92,13 +228,13 @@ impl BlobsStore {
         max_import_size: Option<u64>,
     ) -> Result<Self, BlobsStoreError> {
         let store = ObjStore::new_local(db_path, objects_path, max_import_size).await?;
-        Ok(Self::from_store(store.into()))
+        Ok(Self::from_object_store(store))
     }
 
     /// In-memory via ObjectStore.
     pub async fn memory() -> Result<Self, BlobsStoreError> {
         let store = ObjStore::new_ephemeral().await?;
-        Ok(Self::from_store(store.into()))
+        Ok(Self::from_object_store(store))
     }
 
     /// S3-backed via ObjectStore.
@@ -124,7 +260,14 @@ impl BlobsStore {
             max_import_size,
         )
         .await?;
-        Ok(Self::from_store(store.into()))
+        Ok(Self::from_object_store(store))
+    }
+
+    /// Wrap an already constructed ObjectStore (e.g. one with a disk cache).
+    pub fn from_object_store(store: ObjStore) -> Self {
+        let mut blobs = Self::from_store(store.clone().into());
+        blobs.object_store = Some(store);
+        blobs
     }
 
     /// Wrap an existing iroh-blobs Store (kept for flexibility).
@@ -132,9 +275,107 @@ impl BlobsStore {
         let blobs = BlobsProtocol::new(&store, None);
         Self {
             inner: Arc::new(blobs),
+            object_store: None,
+            download_parallelism: DEFAULT_DOWNLOAD_PARALLELISM,
+            health: PeerHealth::new(),
+            remote: None,
+            write_gate: Arc::new(RwLock::new(true)),
         }
     }
 
+    /// Set how many batches are downloaded from peers at once
+    pub fn with_download_parallelism(mut self, parallelism: usize) -> Self {
+        self.download_parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Download health of the peers we fetch from
+    pub fn peer_health(&self) -> &PeerHealth {
+        &self.health
+    }
+
+    /// A handle on the same store that fetches missing blobs from peers
+    ///
+    /// Reads through the returned store ([`Self::get`], [`Self::get_cbor`],
+    /// [`Self::get_reader`] and [`Self::fetch_missing`]) download a blob
+    /// from `peer_ids` the first time it is needed, instead of failing. This
+    /// is how lazily synced buckets are read.
+    pub fn with_remote(&self, endpoint: Endpoint, peer_ids: Vec<PublicKey>) -> Self {
+        let mut blobs = self.clone();
+        blobs.remote = Some(Arc::new(RemoteSource { endpoint, peer_ids }));
+        blobs
+    }
+
+    /// Fetch a blob from peers if it is missing and a remote is set
+    ///
+    /// A no-op for stores without a remote (see [`Self::with_remote`]).
+    pub async fn fetch_missing(&self, hash: &Hash) -> Result<(), BlobsStoreError> {
+        let Some(remote) = &self.remote else {
+            return Ok(());
+        };
+        if remote.peer_ids.is_empty() {
+            return Ok(());
+        }
+
+        self.download_hashes(vec![*hash], remote.peer_ids.clone(), &remote.endpoint)
+            .await
+    }
+
+    /// Statistics for the ObjectStore disk cache, if one is configured.
+    pub fn cache_stats(&self) -> Option<object_store::CacheStats> {
+        self.object_store.as_ref().and_then(ObjStore::cache_stats)
+    }
+
+    /// Per-tier blob usage, if the ObjectStore is tiered.
+    pub async fn tier_stats(&self) -> Result<Option<TierStats>, BlobsStoreError> {
+        match &self.object_store {
+            Some(store) => Ok(store.tier_stats().await?),
+            None => Ok(None),
+        }
+    }
+
+    /// Keep a metadata blob (manifest, pins, node) out of the cold tier.
+    ///
+    /// No-op for legacy iroh stores and untiered object stores.
+    pub async fn keep_hot(&self, hash: &Hash) -> Result<(), BlobsStoreError> {
+        if let Some(store) = &self.object_store {
+            store.set_keep_hot(hash, true).await?;
+        }
+        Ok(())
+    }
+
+    /// Verify every stored blob against its hash.
+    ///
+    /// Object-store backends also regenerate outboards and reconcile SQLite
+    /// metadata with storage. Corrupt or missing blobs are evicted so that a
+    /// subsequent [`Self::download_hash`] fetches them again.
+    pub async fn scrub(&self) -> Result<ScrubReport, BlobsStoreError> {
+        let report = match &self.object_store {
+            Some(store) => store.scrub().await?,
+            None => object_store::scrub_iroh_store(self.inner.store()).await?,
+        };
+        Ok(report)
+    }
+
+    /// Hold off writes until the returned pause is dropped
+    ///
+    /// Waits for writes in flight to finish. Puts and downloads through this
+    /// store (and its clones) then wait for the pause to end, so the store
+    /// can be read in full without new blobs turning up, e.g. to finish a
+    /// migration.
+    pub async fn pause_writes(&self) -> WritePause {
+        WritePause(self.write_gate.clone().write_owned().await)
+    }
+
+    /// Wait until writes are allowed, keeping them allowed while held
+    async fn write_permit(&self) -> Result<RwLockReadGuard<'_, bool>, BlobsStoreError> {
+        let permit = self.write_gate.read().await;
+        if !*permit {
+            return Err(BlobsStoreError::WritesClosed);
+        }
+        Ok(permit)
+    }
+
     /// Get a handle to the underlying blobs client against
     ///  the store
     pub fn blobs(&self) -> &Blobs {
@@ -143,6 +384,7 @@ impl BlobsStore {
 
     /// Get a blob as bytes
     pub async fn get(&self, hash: &Hash) -> Result<Bytes, BlobsStoreError> {
+        self.fetch_missing(hash).await?;
         let bytes = self.blobs().get_bytes(*hash).await?;
         Ok(bytes)
     }
@@ -152,12 +394,14 @@ impl BlobsStore {
         &self,
         hash: &Hash,
     ) -> Result<T, BlobsStoreError> {
+        self.fetch_missing(hash).await?;
         let bytes = self.blobs().get_bytes(*hash).await?;
         Ok(T::decode(&bytes)?)
     }
 
     /// Get a blob from the store as a reader
     pub async fn get_reader(&self, hash: Hash) -> Result<Reader, BlobsStoreError> {
+        self.fetch_missing(&hash).await?;
         let reader = self.blobs().reader(hash);
         Ok(reader)
     }
@@ -167,6 +411,7 @@ impl BlobsStore {
         &self,
         stream: impl Stream<Item = std::io::Result<Bytes>> + Send + Unpin + 'static + std::marker::Sync,
     ) -> Result<Hash, BlobsStoreError> {
+        let _permit = self.write_permit().await?;
         let outcome = self
             .blobs()
             .add_stream(stream)
@@ -180,6 +425,7 @@ impl BlobsStore {
 
     /// Store a vec of bytes as a blob
     pub async fn put(&self, data: Vec<u8>) -> Result<Hash, BlobsStoreError> {
+        let _permit = self.write_permit().await?;
         let hash = self.blobs().add_bytes(data).into_future().await?.hash;
         Ok(hash)
     }
@@ -222,6 +468,10 @@ impl BlobsStore {
             peer_ids
         );
 
+        // Held until the download is done
+        let _permit = self.write_permit().await?;
+        let _connections = self.connect_peers(endpoint, &peer_ids).await;
+
         // Create downloader - needs the Store from BlobsProtocol
         let downloader = Downloader::new(self.inner.store(), endpoint);
 
@@ -274,16 +524,252 @@ impl BlobsStore {
         Ok(())
     }
 
+    /// Download many hashes from peers
+    ///
+    /// Hashes that already exist locally are skipped. The rest are requested
+    /// in batches, one request per batch rather than one per hash, which
+    /// matters when catching up on thousands of small blobs.
+    pub async fn download_hashes(
+        &self,
+        hashes: Vec<Hash>,
+        peer_ids: Vec<PublicKey>,
+        endpoint: &Endpoint,
+    ) -> Result<(), BlobsStoreError> {
+        self.download_hashes_with_progress(hashes, peer_ids, endpoint, |_| {})
+            .await
+    }
+
+    /// Download many hashes from peers, reporting progress as it goes
+    ///
+    /// Same as [`Self::download_hashes`], but calls `on_event` with the number
+    /// of blobs to fetch, the providers tried, and running blob and byte
+    /// counts. Byte progress is throttled to one event per
+    /// [`PROGRESS_INTERVAL`]; the count after each batch is always reported.
+    pub async fn download_hashes_with_progress<F>(
+        &self,
+        hashes: Vec<Hash>,
+        peer_ids: Vec<PublicKey>,
+        endpoint: &Endpoint,
+        on_event: F,
+    ) -> Result<(), BlobsStoreError>
+    where
+        F: Fn(DownloadEvent) + Send + Sync,
+    {
+        let mut seen = HashSet::new();
+        let mut missing = Vec::new();
+        for hash in hashes {
+            if seen.insert(hash) && !self.stat(&hash).await? {
+                missing.push(hash);
+            }
+        }
+        let total = missing.len();
+        on_event(DownloadEvent::Started { total });
+        if missing.is_empty() {
+            tracing::debug!("download_hashes: All hashes already exist locally");
+            return Ok(());
+        }
+
+        // Split the work so every parallel slot gets a batch, without making
+        //  batches so small that request overhead dominates
+        let parallelism = self.download_parallelism.max(1);
+        let batch_size = missing
+            .len()
+            .div_ceil(parallelism)
+            .clamp(MIN_DOWNLOAD_BATCH_SIZE, DOWNLOAD_BATCH_SIZE);
+        let batches: Vec<&[Hash]> = missing.chunks(batch_size).collect();
+        let batch_count = batches.len();
+
+        tracing::info!(
+            "download_hashes: Downloading {} hashes in {} batches from {} peers ({} at a time)",
+            missing.len(),
+            batch_count,
+            peer_ids.len(),
+            parallelism
+        );
+
+        // Held until every batch is done
+        let _permit = self.write_permit().await?;
+        let _connections = self.connect_peers(endpoint, &peer_ids).await;
+        let downloader = Downloader::new(self.inner.store(), endpoint);
+        let progress = BatchProgress::new(&on_event, total);
+        let downloads: Vec<_> = batches
+            .into_iter()
+            .enumerate()
+            .map(|(idx, batch)| self.download_batch(&downloader, idx, batch, &peer_ids, &progress))
+            .collect();
+        let mut results = futures::stream::iter(downloads).buffer_unordered(parallelism);
+        let mut failures = Vec::new();
+        while let Some(result) = results.next().await {
+            if let Err(e) = result {
+                failures.push(e);
+            }
+        }
+
+        // Batches that finished are kept, so a retry only fetches the rest
+        if let Some(first) = failures.first() {
+            tracing::error!(
+                "download_hashes: {} of {} batches failed; first error: {}",
+                failures.len(),
+                batch_count,
+                first
+            );
+            return Err(anyhow!(
+                "failed to download {} of {} batches: {}",
+                failures.len(),
+                batch_count,
+                first
+            )
+            .into());
+        }
+
+        // Verify everything arrived
+        for hash in &missing {
+            if !self.stat(hash).await? {
+                tracing::error!("download_hashes: Hash {} NOT found after download!", hash);
+                return Err(anyhow!("Hash {} not found after download", hash).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Dial every provider before a download starts
+    ///
+    /// The downloader gives each connection attempt a single second, which a
+    /// freshly bound endpoint can spend just coming up, and reports running
+    /// out of it as the provider failing. Dialing here first, with a longer
+    /// timeout, leaves a warm path for the downloader's own connections, so
+    /// only providers that can't be reached at all count against their health.
+    /// The connections must be held until the download is done.
+    async fn connect_peers(&self, endpoint: &Endpoint, peer_ids: &[PublicKey]) -> Vec<Connection> {
+        let dials = peer_ids.iter().map(|peer_id| async move {
+            let dial = endpoint.connect(NodeId::from(*peer_id), iroh_blobs::ALPN);
+            let error = match tokio::time::timeout(CONNECT_TIMEOUT, dial).await {
+                Ok(Ok(connection)) => return Some(connection),
+                Ok(Err(e)) => e.to_string(),
+                Err(_) => "timed out".to_string(),
+            };
+            tracing::warn!(
+                "download: Could not connect to peer {}: {}",
+                peer_id.to_hex(),
+                error
+            );
+            self.health.record_failure(peer_id);
+            None
+        });
+        futures::future::join_all(dials)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Download one batch of hashes, trying peers in health order
+    ///
+    /// The downloader moves on to the next peer when one fails, so a batch
+    /// only fails when no peer could serve it. Each outcome updates the
+    /// peer's health score.
+    async fn download_batch<F>(
+        &self,
+        downloader: &Downloader,
+        idx: usize,
+        batch: &[Hash],
+        peer_ids: &[PublicKey],
+        progress: &BatchProgress<'_, F>,
+    ) -> Result<(), BlobsStoreError>
+    where
+        F: Fn(DownloadEvent) + Send + Sync,
+    {
+        let ranked = self.health.rank(peer_ids, idx);
+        tracing::debug!(
+            "download_hashes: Requesting batch {} ({} hashes), trying {} first",
+            idx + 1,
+            batch.len(),
+            ranked
+                .first()
+                .map(|peer_id| peer_id.to_hex())
+                .unwrap_or_default()
+        );
+        let fail = |e: anyhow::Error| {
+            tracing::warn!(
+                "download_hashes: Failed to download batch {} from peers {:?}: {}",
+                idx + 1,
+                peer_ids,
+                e
+            );
+            BlobsStoreError::from(e)
+        };
+
+        let nodes: Vec<NodeId> = ranked
+            .iter()
+            .map(|peer_id| NodeId::from(*peer_id))
+            .collect();
+        let mut items = downloader
+            .download(batch.to_vec(), nodes)
+            .stream()
+            .await
+            .map_err(|e| fail(e.into()))?;
+
+        let mut provider = None;
+        let mut batch_bytes = 0;
+        while let Some(item) = items.next().await {
+            match item {
+                DownloadProgressItem::TryProvider { id, .. } => {
+                    let peer_id = PublicKey::from(id);
+                    provider = Some(peer_id);
+                    progress.emit(DownloadEvent::Provider(peer_id));
+                }
+                DownloadProgressItem::ProviderFailed { id, .. } => {
+                    let peer_id = PublicKey::from(id);
+                    self.health.record_failure(&peer_id);
+                    progress.emit(DownloadEvent::ProviderFailed(peer_id));
+                }
+                DownloadProgressItem::Progress(size) => {
+                    progress.add_bytes(size.saturating_sub(batch_bytes));
+                    batch_bytes = size;
+                }
+                DownloadProgressItem::PartComplete { .. } => {}
+                DownloadProgressItem::Error(e) => return Err(fail(e)),
+                DownloadProgressItem::DownloadError => {
+                    return Err(fail(anyhow!("no provider could serve the batch")));
+                }
+            }
+        }
+
+        if let Some(peer_id) = provider {
+            self.health.record_success(&peer_id);
+        }
+        progress.complete_batch(batch.len());
+        Ok(())
+    }
+
     /// Download a hash list (pinset) and all referenced hashes
     ///
     /// This first downloads the hash list blob, reads the list of hashes,
-    /// then downloads each referenced hash.
+    /// then downloads the referenced hashes with [`Self::download_hashes`].
     pub async fn download_hash_list(
         &self,
         hash_list_hash: Hash,
         peer_ids: Vec<PublicKey>,
         endpoint: &Endpoint,
     ) -> Result<(), BlobsStoreError> {
+        self.download_hash_list_with_progress(hash_list_hash, peer_ids, endpoint, |_| {})
+            .await
+    }
+
+    /// Download a hash list and its referenced hashes, reporting progress
+    ///
+    /// See [`Self::download_hashes_with_progress`] for the events reported.
+    pub async fn download_hash_list_with_progress<F>(
+        &self,
+        hash_list_hash: Hash,
+        peer_ids: Vec<PublicKey>,
+        endpoint: &Endpoint,
+        on_event: F,
+    ) -> Result<(), BlobsStoreError>
+    where
+        F: Fn(DownloadEvent) + Send + Sync,
+    {
         tracing::debug!(
             "download_hash_list: Starting download of hash list {} from {} peers",
             hash_list_hash,
@@ -295,6 +781,7 @@ impl BlobsStore {
         self.download_hash(hash_list_hash, peer_ids.clone(), endpoint)
             .await?;
         tracing::debug!("download_hash_list: Hash list blob downloaded successfully");
+        self.keep_hot(&hash_list_hash).await?;
 
         // Verify it exists
         match self.stat(&hash_list_hash).await {
@@ -325,37 +812,13 @@ impl BlobsStore {
 
         if hashes.is_empty() {
             tracing::warn!("download_hash_list: Hash list is EMPTY - no content to download");
+            on_event(DownloadEvent::Started { total: 0 });
             return Ok(());
         }
 
-        // Download each hash in the list
-        for (idx, hash) in hashes.iter().enumerate() {
-            tracing::debug!(
-                "download_hash_list: Downloading content hash {}/{}: {:?}",
-                idx + 1,
-                hashes.len(),
-                hash
-            );
-            match self.download_hash(*hash, peer_ids.clone(), endpoint).await {
-                Ok(()) => {
-                    tracing::debug!(
-                        "download_hash_list: Content hash {}/{} downloaded successfully",
-                        idx + 1,
-                        hashes.len()
-                    );
-                }
-                Err(e) => {
-                    tracing::error!(
-                        "download_hash_list: Failed to download content hash {}/{} ({:?}): {}",
-                        idx + 1,
-                        hashes.len(),
-                        hash,
-                        e
-                    );
-                    return Err(e);
-                }
-            }
-        }
+        // Fetch the referenced hashes in bulk
+        self.download_hashes_with_progress(hashes.clone(), peer_ids, endpoint, on_event)
+            .await?;
 
         tracing::info!(
             "download_hash_list: Successfully downloaded all {} hashes from hash list",
@@ -409,6 +872,7 @@ impl BlobsStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::peer::peer_health::INITIAL_SCORE;
     use bytes::Bytes;
     use futures::stream;
     use tempfile::TempDir;
@@ -455,6 +919,85 @@ mod tests {
         assert_eq!(retrieved.as_ref(), data);
     }
 
+    #[tokio::test]
+    async fn test_scrub() {
+        let (store, _temp) = setup_test_store().await;
+        store.put(b"scrub me".to_vec()).await.unwrap();
+
+        let report = store.scrub().await.unwrap();
+        assert_eq!(report.checked, 1);
+        assert!(report.is_clean());
+
+        // Legacy iroh stores are scrubbed through their BAO export
+        let legacy = BlobsStore::legacy_memory().await.unwrap();
+        legacy.put(b"scrub me too".to_vec()).await.unwrap();
+        let report = legacy.scrub().await.unwrap();
+        assert_eq!(report.healthy, 1);
+        assert!(report.is_clean());
+    }
+
+    #[tokio::test]
+    async fn test_tiered_keep_hot() {
+        let temp_dir = TempDir::new().unwrap();
+        let policy = object_store::TierPolicy {
+            min_size: 1024,
+            min_age: std::time::Duration::ZERO,
+            min_idle: std::time::Duration::ZERO,
+            max_access_count: 0,
+            ..Default::default()
+        };
+        let tiered = ObjStore::new_tiered(
+            &temp_dir.path().join("blobs.db"),
+            object_store::ObjectStoreConfig::Memory,
+            object_store::ObjectStoreConfig::Memory,
+            policy,
+            None,
+        )
+        .await
+        .unwrap();
+        let store = BlobsStore::from_object_store(tiered.clone());
+
+        let metadata = store.put(vec![1u8; 8 * 1024]).await.unwrap();
+        store.keep_hot(&metadata).await.unwrap();
+        let data = store.put(vec![2u8; 8 * 1024]).await.unwrap();
+
+        assert_eq!(tiered.rebalance().await.unwrap().demoted, 1);
+        let stats = store.tier_stats().await.unwrap().unwrap();
+        assert_eq!(stats.hot_blobs, 1);
+        assert_eq!(stats.cold_blobs, 1);
+
+        // Cold data is still readable through the iroh API
+        assert_eq!(store.get(&data).await.unwrap().len(), 8 * 1024);
+        assert_eq!(store.tier_stats().await.unwrap().unwrap().promotions, 1);
+
+        // Untiered stores report nothing
+        let (plain, _temp) = setup_test_store().await;
+        assert!(plain.tier_stats().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pause_writes() {
+        let (store, _temp) = setup_test_store().await;
+
+        // Puts wait for the pause to end
+        let pause = store.pause_writes().await;
+        let writer = store.clone();
+        let put = tokio::spawn(async move { writer.put(b"later".to_vec()).await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!put.is_finished());
+        drop(pause);
+        let hash = put.await.unwrap().unwrap();
+        assert!(store.stat(&hash).await.unwrap());
+
+        // Once closed, writes are refused but reads still work
+        store.pause_writes().await.close();
+        assert!(matches!(
+            store.put(b"refused".to_vec()).await,
+            Err(BlobsStoreError::WritesClosed)
+        ));
+        assert_eq!(store.get(&hash).await.unwrap().as_ref(), b"later");
+    }
+
     #[tokio::test]
     async fn test_stat() {
         let (store, _temp) = setup_test_store().await;
@@ -520,4 +1063,129 @@ mod tests {
         // Should return an error
         assert!(result.is_err());
     }
+
+    /// A node serving `store` over iroh-blobs, registered with `discovery`
+    async fn provider(
+        store: &BlobsStore,
+        discovery: &iroh::discovery::static_provider::StaticProvider,
+    ) -> (iroh::protocol::Router, PublicKey) {
+        let endpoint = Endpoint::builder()
+            .relay_mode(iroh::RelayMode::Disabled)
+            .bind()
+            .await
+            .unwrap();
+        let addrs = endpoint
+            .bound_sockets()
+            .into_iter()
+            .filter(|addr| addr.is_ipv4())
+            .map(|addr| (std::net::Ipv4Addr::LOCALHOST, addr.port()).into());
+        discovery
+            .add_node_info(iroh::NodeAddr::new(endpoint.node_id()).with_direct_addresses(addrs));
+        let peer_id = PublicKey::from(endpoint.node_id());
+        let router = iroh::protocol::Router::builder(endpoint)
+            .accept(iroh_blobs::ALPN, store.inner.clone())
+            .spawn();
+        (router, peer_id)
+    }
+
+    /// A freshly bound endpoint that finds providers through `discovery`
+    async fn fetcher(discovery: &iroh::discovery::static_provider::StaticProvider) -> Endpoint {
+        Endpoint::builder()
+            .relay_mode(iroh::RelayMode::Disabled)
+            .discovery(discovery.clone())
+            .bind()
+            .await
+            .unwrap()
+    }
+
+    async fn store_with(blobs: &[Vec<u8>]) -> BlobsStore {
+        let store = BlobsStore::memory().await.unwrap();
+        for data in blobs {
+            store.put(data.clone()).await.unwrap();
+        }
+        store
+    }
+
+    #[tokio::test]
+    async fn test_parallel_download_spreads_across_peers() {
+        let blobs: Vec<Vec<u8>> = (0..100u32).map(|i| i.to_le_bytes().to_vec()).collect();
+        let hashes: Vec<Hash> = blobs.iter().map(Hash::new).collect();
+        let discovery = iroh::discovery::static_provider::StaticProvider::new();
+        let (_a, peer_a) = provider(&store_with(&blobs).await, &discovery).await;
+        let (_b, peer_b) = provider(&store_with(&blobs).await, &discovery).await;
+
+        let store = BlobsStore::memory()
+            .await
+            .unwrap()
+            .with_download_parallelism(4);
+        let endpoint = fetcher(&discovery).await;
+        let events = Mutex::new(Vec::new());
+        store
+            .download_hashes_with_progress(hashes.clone(), vec![peer_a, peer_b], &endpoint, |e| {
+                events.lock().unwrap().push(e)
+            })
+            .await
+            .unwrap();
+
+        for hash in &hashes {
+            assert!(store.stat(hash).await.unwrap());
+        }
+        let events = events.into_inner().unwrap();
+        assert_eq!(events[0], DownloadEvent::Started { total: 100 });
+        assert!(events.contains(&DownloadEvent::Provider(peer_a)));
+        assert!(events.contains(&DownloadEvent::Provider(peer_b)));
+        // Both providers are healthy, even to an endpoint that just came up
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, DownloadEvent::ProviderFailed(_))));
+        assert!(matches!(
+            events.last(),
+            Some(DownloadEvent::Progress {
+                done: 100,
+                total: 100,
+                ..
+            })
+        ));
+        assert!(store.peer_health().score(&peer_a) > INITIAL_SCORE);
+        assert!(store.peer_health().score(&peer_b) > INITIAL_SCORE);
+
+        // Everything is local now, so a second run has nothing to fetch
+        let events = Mutex::new(Vec::new());
+        store
+            .download_hashes_with_progress(hashes, vec![peer_a, peer_b], &endpoint, |e| {
+                events.lock().unwrap().push(e)
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            events.into_inner().unwrap(),
+            vec![DownloadEvent::Started { total: 0 }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_fails_over_to_healthy_peer() {
+        let blobs: Vec<Vec<u8>> = (0..40u32).map(|i| i.to_le_bytes().to_vec()).collect();
+        let hashes: Vec<Hash> = blobs.iter().map(Hash::new).collect();
+        let discovery = iroh::discovery::static_provider::StaticProvider::new();
+        let (_empty, empty_peer) = provider(&store_with(&[]).await, &discovery).await;
+        let (_full, full_peer) = provider(&store_with(&blobs).await, &discovery).await;
+
+        let store = BlobsStore::memory()
+            .await
+            .unwrap()
+            .with_download_parallelism(2);
+        let endpoint = fetcher(&discovery).await;
+        store
+            .download_hashes(hashes.clone(), vec![empty_peer, full_peer], &endpoint)
+            .await
+            .unwrap();
+
+        for hash in &hashes {
+            assert!(store.stat(hash).await.unwrap());
+        }
+        let health = store.peer_health();
+        assert!(health.score(&empty_peer) < INITIAL_SCORE);
+        assert!(health.score(&full_peer) > INITIAL_SCORE);
+    }
 }
@@ -0,0 +1,484 @@
+//! Gossip-based head dissemination
+//!
+//! Direct [`Announce`] pushes and pings cost the author one connection per
+//! peer in a bucket's shares. For buckets shared with many peers, heads can
+//! instead travel over an [iroh-gossip] topic per bucket: the author
+//! broadcasts a signed announcement once, and every member relays it to its
+//! neighbors, so peers receive updates from whichever member reaches them
+//! first.
+//!
+//! Gossip is opt-in (see [`PeerBuilder::gossip`]) and layered on top of the
+//! direct protocol rather than replacing it:
+//!
+//! - Announcements are signed by their author and only accepted from keys in
+//!   the bucket's shares. Anyone who knows a bucket ID can join its topic, so
+//!   topic membership itself grants nothing.
+//! - Members periodically broadcast a presence announcement. Peers we have
+//!   heard from recently (see [`MEMBERSHIP_WINDOW`]) are reached through the
+//!   topic; everyone else still gets direct announcements and pings.
+//! - A received head only triggers the regular sync job, which verifies the
+//!   manifest chain and its provenance before anything is applied.
+//!
+//! [`Announce`]: crate::peer::protocol::Announce
+//! [iroh-gossip]: https://docs.rs/iroh-gossip
+//! [`PeerBuilder::gossip`]: crate::peer::PeerBuilder::gossip
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use futures::StreamExt;
+use iroh::Endpoint;
+use iroh_gossip::api::{Event, GossipReceiver, GossipSender};
+use iroh_gossip::net::Gossip;
+use iroh_gossip::proto::TopicId;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::bucket_log::BucketLogProvider;
+use crate::clock::unix_now;
+use crate::crypto::{PublicKey, SecretKey, Signature};
+use crate::linked_data::Link;
+use crate::mount::Manifest;
+use crate::peer::sync::{SyncBucketJob, SyncJob, SyncTarget};
+use crate::peer::Peer;
+
+/// Domain separator for topic IDs and announcement signatures
+const GOSSIP_DOMAIN: &[u8] = b"jax-bucket-heads";
+
+/// How often members announce their presence on a topic
+pub const PRESENCE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// How long a peer counts as a topic member after its last announcement
+///
+/// Longer than [`PRESENCE_INTERVAL`] so a single lost presence message
+/// doesn't drop a peer back to direct notifications.
+pub const MEMBERSHIP_WINDOW: Duration = Duration::from_secs(12 * 60);
+
+/// What a gossip announcement carries
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GossipPayload {
+    /// The author saved a new head
+    Head(Link, u64),
+    /// The author is listening on the topic
+    Present,
+}
+
+/// A signed announcement broadcast on a bucket's gossip topic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GossipAnnouncement {
+    /// The bucket the announcement is about
+    pub bucket_id: Uuid,
+    /// What is announced
+    pub payload: GossipPayload,
+    /// Unix time (seconds) the announcement was signed at
+    pub timestamp: u64,
+    /// The peer that signed the announcement
+    pub author: PublicKey,
+    /// Ed25519 signature over all other fields
+    pub signature: Signature,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GossipError {
+    #[error("failed to encode or decode announcement: {0}")]
+    Encoding(#[from] bincode::Error),
+    #[error("invalid announcement signature")]
+    InvalidSignature,
+    #[error("announcement is too old or too far in the future")]
+    Stale,
+    #[error("gossip topic error: {0}")]
+    Topic(anyhow::Error),
+}
+
+impl GossipAnnouncement {
+    /// Sign an announcement for a bucket
+    pub fn sign(secret_key: &SecretKey, bucket_id: Uuid, payload: GossipPayload) -> Self {
+        let author = secret_key.public();
+        let timestamp = unix_now();
+        let bytes = Self::signable_bytes(&bucket_id, &payload, timestamp, &author);
+        let signature = secret_key.sign(&bytes);
+        Self {
+            bucket_id,
+            payload,
+            timestamp,
+            author,
+            signature,
+        }
+    }
+
+    /// Check the signature and that the announcement is recent
+    pub fn verify(&self) -> Result<(), GossipError> {
+        let bytes =
+            Self::signable_bytes(&self.bucket_id, &self.payload, self.timestamp, &self.author);
+        self.author
+            .verify(&bytes, &self.signature)
+            .map_err(|_| GossipError::InvalidSignature)?;
+
+        // Bound replays of old announcements; a replayed presence would keep
+        //  a peer that went offline counted as a member
+        let now = unix_now();
+        let window = MEMBERSHIP_WINDOW.as_secs();
+        if self.timestamp + window < now || self.timestamp > now + window {
+            return Err(GossipError::Stale);
+        }
+        Ok(())
+    }
+
+    pub fn encode(&self) -> Result<Bytes, GossipError> {
+        Ok(bincode::serialize(self)?.into())
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, GossipError> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+
+    fn signable_bytes(
+        bucket_id: &Uuid,
+        payload: &GossipPayload,
+        timestamp: u64,
+        author: &PublicKey,
+    ) -> Vec<u8> {
+        bincode::serialize(&(GOSSIP_DOMAIN, bucket_id, payload, timestamp, author))
+            .expect("announcement fields are serializable")
+    }
+}
+
+/// The gossip topic ID for a bucket
+pub fn topic_id(bucket_id: Uuid) -> TopicId {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(GOSSIP_DOMAIN);
+    hasher.update(bucket_id.as_bytes());
+    TopicId::from_bytes(*hasher.finalize().as_bytes())
+}
+
+/// Our side of a joined bucket topic
+#[derive(Debug)]
+struct Topic {
+    sender: GossipSender,
+    /// Peers in the bucket's shares we heard from, and when
+    members: HashMap<PublicKey, Instant>,
+    /// When we last announced our presence
+    last_presence: Option<Instant>,
+}
+
+/// Per-bucket gossip topics for head announcements
+#[derive(Debug, Clone)]
+pub struct HeadGossip {
+    gossip: Gossip,
+    topics: Arc<Mutex<HashMap<Uuid, Topic>>>,
+}
+
+impl HeadGossip {
+    /// Start the gossip protocol on an endpoint
+    ///
+    /// The returned handle must also be registered with the endpoint's router
+    /// under [`iroh_gossip::ALPN`], see [`HeadGossip::protocol`].
+    pub fn new(endpoint: &Endpoint) -> Self {
+        Self {
+            gossip: Gossip::builder().spawn(endpoint.clone()),
+            topics: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The protocol handler to accept gossip connections with
+    pub fn protocol(&self) -> Gossip {
+        self.gossip.clone()
+    }
+
+    /* Membership */
+
+    /// Check whether we joined the topic of a bucket
+    pub fn is_joined(&self, bucket_id: Uuid) -> bool {
+        self.topics.lock().unwrap().contains_key(&bucket_id)
+    }
+
+    /// Check whether a peer announced itself on a bucket's topic recently
+    ///
+    /// Members get heads through the topic and need no direct notification.
+    pub fn is_member(&self, bucket_id: Uuid, peer_id: &PublicKey) -> bool {
+        let topics = self.topics.lock().unwrap();
+        topics
+            .get(&bucket_id)
+            .and_then(|topic| topic.members.get(peer_id))
+            .is_some_and(|seen| seen.elapsed() < MEMBERSHIP_WINDOW)
+    }
+
+    fn mark_member(&self, bucket_id: Uuid, peer_id: PublicKey) {
+        let mut topics = self.topics.lock().unwrap();
+        if let Some(topic) = topics.get_mut(&bucket_id) {
+            topic.members.insert(peer_id, Instant::now());
+        }
+    }
+
+    /// Forget a bucket's topic, so the next save or poll joins it again
+    pub fn leave(&self, bucket_id: Uuid) {
+        self.topics.lock().unwrap().remove(&bucket_id);
+    }
+
+    /* Topics */
+
+    /// Join a bucket's topic unless we already did
+    ///
+    /// `bootstrap` should be the other peers in the bucket's shares. Joining
+    /// doesn't wait for any of them to be reachable; the topic connects in
+    /// the background and later broadcasts reach whoever is connected.
+    pub async fn join<L>(
+        &self,
+        peer: &Peer<L>,
+        bucket_id: Uuid,
+        bootstrap: Vec<PublicKey>,
+    ) -> Result<(), GossipError>
+    where
+        L: BucketLogProvider,
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        if self.is_joined(bucket_id) {
+            return Ok(());
+        }
+
+        let bootstrap = bootstrap.into_iter().map(|peer_id| *peer_id).collect();
+        let (sender, receiver) = self
+            .gossip
+            .subscribe(topic_id(bucket_id), bootstrap)
+            .await
+            .map_err(|e| GossipError::Topic(e.into()))?
+            .split();
+
+        {
+            let mut topics = self.topics.lock().unwrap();
+            if topics.contains_key(&bucket_id) {
+                // Joined concurrently; dropping ours leaves the other in place
+                return Ok(());
+            }
+            topics.insert(
+                bucket_id,
+                Topic {
+                    sender,
+                    members: HashMap::new(),
+                    last_presence: None,
+                },
+            );
+        }
+
+        tracing::info!("Joined gossip topic for bucket {}", bucket_id);
+        tokio::spawn(receive(peer.clone(), bucket_id, receiver));
+        Ok(())
+    }
+
+    /// Sign and broadcast an announcement on a joined bucket topic
+    pub async fn broadcast(
+        &self,
+        secret_key: &SecretKey,
+        bucket_id: Uuid,
+        payload: GossipPayload,
+    ) -> Result<(), GossipError> {
+        let sender = {
+            let mut topics = self.topics.lock().unwrap();
+            let Some(topic) = topics.get_mut(&bucket_id) else {
+                return Err(GossipError::Topic(anyhow::anyhow!(
+                    "not joined to the topic of bucket {}",
+                    bucket_id
+                )));
+            };
+            if payload == GossipPayload::Present {
+                topic.last_presence = Some(Instant::now());
+            }
+            topic.sender.clone()
+        };
+
+        let message = GossipAnnouncement::sign(secret_key, bucket_id, payload).encode()?;
+        sender
+            .broadcast(message)
+            .await
+            .map_err(|e| GossipError::Topic(e.into()))
+    }
+
+    /// Check whether our presence on a bucket's topic is due for renewal
+    pub fn presence_due(&self, bucket_id: Uuid) -> bool {
+        let topics = self.topics.lock().unwrap();
+        topics.get(&bucket_id).is_some_and(|topic| {
+            topic
+                .last_presence
+                .is_none_or(|sent| sent.elapsed() >= PRESENCE_INTERVAL)
+        })
+    }
+}
+
+/// Handle events from a bucket topic until it closes
+async fn receive<L>(peer: Peer<L>, bucket_id: Uuid, mut receiver: GossipReceiver)
+where
+    L: BucketLogProvider,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    while let Some(event) = receiver.next().await {
+        match event {
+            Ok(Event::Received(message)) => {
+                let delivered_from = PublicKey::from(message.delivered_from);
+                if let Err(e) =
+                    handle_announcement(&peer, bucket_id, &message.content, delivered_from).await
+                {
+                    tracing::debug!(
+                        "Dropping gossip message for bucket {} from {}: {}",
+                        bucket_id,
+                        delivered_from.to_hex(),
+                        e
+                    );
+                }
+            }
+            Ok(Event::NeighborUp(node_id)) => {
+                tracing::debug!("Gossip neighbor {} up for bucket {}", node_id, bucket_id);
+            }
+            Ok(Event::NeighborDown(node_id)) => {
+                tracing::debug!("Gossip neighbor {} down for bucket {}", node_id, bucket_id);
+            }
+            Ok(Event::Lagged) => {
+                // The topic closes after lagging; missed heads are caught up
+                //  by the next poll
+                tracing::warn!("Gossip receiver lagged for bucket {}", bucket_id);
+            }
+            Err(e) => {
+                tracing::warn!("Gossip topic error for bucket {}: {}", bucket_id, e);
+                break;
+            }
+        }
+    }
+
+    if let Some(gossip) = peer.gossip() {
+        gossip.leave(bucket_id);
+    }
+    tracing::info!("Left gossip topic for bucket {}", bucket_id);
+}
+
+/// Verify an announcement and sync the head it carries if we are behind
+async fn handle_announcement<L>(
+    peer: &Peer<L>,
+    bucket_id: Uuid,
+    content: &[u8],
+    delivered_from: PublicKey,
+) -> anyhow::Result<()>
+where
+    L: BucketLogProvider,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let announcement = GossipAnnouncement::decode(content)?;
+    if announcement.bucket_id != bucket_id {
+        anyhow::bail!("announcement for another bucket");
+    }
+    announcement.verify()?;
+    if announcement.author == peer.secret().public() {
+        return Ok(());
+    }
+
+    // We only join topics of buckets we have, so the head manifest's shares
+    //  decide who may announce
+    let head = peer.logs().head(bucket_id, None).await.ok();
+    let Some((our_link, our_height)) = head else {
+        anyhow::bail!("bucket not found");
+    };
+    let manifest: Manifest = peer.blobs().get_cbor(&our_link.hash()).await?;
+    if manifest.get_share(&announcement.author).is_none() {
+        anyhow::bail!("author {} is not in shares", announcement.author.to_hex());
+    }
+
+    let gossip = peer.gossip().expect("receiving implies gossip is enabled");
+    gossip.mark_member(bucket_id, announcement.author);
+    if manifest.get_share(&delivered_from).is_some() {
+        gossip.mark_member(bucket_id, delivered_from);
+    }
+
+    let GossipPayload::Head(link, height) = announcement.payload else {
+        return Ok(());
+    };
+    // Same rule as a ping: we are behind if lower, or at the same height
+    //  with a lower link
+    let behind = our_height < height || (our_height == height && our_link < link);
+    if !behind {
+        return Ok(());
+    }
+
+    // The author and the relaying peer have the new head; any other peer in
+    //  the shares may have it by now too
+    let mut peer_ids = vec![announcement.author, delivered_from];
+    for peer_id in manifest.get_peer_ids() {
+        if !peer_ids.contains(&peer_id) && peer_id != peer.secret().public() {
+            peer_ids.push(peer_id);
+        }
+    }
+
+    tracing::info!(
+        "Peer {} gossiped head {:?} at height {} for bucket {}, dispatching sync job",
+        announcement.author.to_hex(),
+        link,
+        height,
+        bucket_id
+    );
+    peer.dispatch(SyncJob::SyncBucket(SyncBucketJob {
+        bucket_id,
+        target: SyncTarget {
+            link,
+            height,
+            peer_ids,
+        },
+    }))
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linked_data::Hash;
+
+    #[test]
+    fn test_topic_id_is_per_bucket() {
+        let bucket_id = Uuid::new_v4();
+        assert_eq!(topic_id(bucket_id), topic_id(bucket_id));
+        assert_ne!(topic_id(bucket_id), topic_id(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn test_announcement_roundtrip_and_verify() {
+        let secret_key = SecretKey::generate();
+        let bucket_id = Uuid::new_v4();
+        let link = Link::new(0x71, Hash::from_bytes([7; 32]));
+
+        let announcement =
+            GossipAnnouncement::sign(&secret_key, bucket_id, GossipPayload::Head(link, 3));
+        let decoded = GossipAnnouncement::decode(&announcement.encode().unwrap()).unwrap();
+        decoded.verify().unwrap();
+        assert_eq!(decoded.author, secret_key.public());
+
+        // Any change to the signed fields breaks the signature
+        let mut tampered = decoded.clone();
+        tampered.payload = GossipPayload::Present;
+        assert!(matches!(
+            tampered.verify(),
+            Err(GossipError::InvalidSignature)
+        ));
+        let mut forged = decoded.clone();
+        forged.author = SecretKey::generate().public();
+        assert!(matches!(
+            forged.verify(),
+            Err(GossipError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_old_announcement_is_stale() {
+        let secret_key = SecretKey::generate();
+        let bucket_id = Uuid::new_v4();
+
+        let mut announcement =
+            GossipAnnouncement::sign(&secret_key, bucket_id, GossipPayload::Present);
+        announcement.timestamp -= MEMBERSHIP_WINDOW.as_secs() + 1;
+        let bytes = GossipAnnouncement::signable_bytes(
+            &bucket_id,
+            &announcement.payload,
+            announcement.timestamp,
+            &announcement.author,
+        );
+        announcement.signature = secret_key.sign(&bytes);
+        assert!(matches!(announcement.verify(), Err(GossipError::Stale)));
+    }
+}
@@ -0,0 +1,267 @@
+//! Invite tickets for sharing a bucket with a new peer
+//!
+//! An owner creates an [`InviteTicket`] naming the bucket, the role to grant,
+//! how to reach the owner's node and a one-time token. The recipient redeems
+//! it with the [`AcceptInvite`] protocol message: the connection itself
+//! proves the recipient holds the key it is added under, so no public key
+//! has to be exchanged out of band. The inviting node keeps a hash of each
+//! token in its [`InviteStore`] until the ticket is redeemed or expires;
+//! persistent stores let tickets survive a restart, and a ticket that is
+//! lost can be issued again.
+//!
+//! [`AcceptInvite`]: crate::peer::protocol::AcceptInvite
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use iroh::NodeAddr;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::mount::PrincipalRole;
+
+/// Prefix of an encoded invite ticket
+pub const TICKET_PREFIX: &str = "jaxinvite";
+
+/// How long an invite stays valid when no expiry is given (24 hours)
+pub const DEFAULT_INVITE_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Size of an invite token in bytes
+pub const TOKEN_SIZE: usize = 32;
+
+/// One-time token that lets the holder of a ticket join a bucket
+pub type InviteToken = [u8; TOKEN_SIZE];
+
+/// BLAKE3 hash of an invite token, which is all the inviter stores
+pub type TokenHash = [u8; 32];
+
+#[derive(Debug, thiserror::Error)]
+pub enum InviteError {
+    #[error("not an invite ticket, expected the '{TICKET_PREFIX}' prefix")]
+    Prefix,
+    #[error("invalid ticket encoding: {0}")]
+    Encoding(#[from] data_encoding::DecodeError),
+    #[error("invalid ticket contents: {0}")]
+    Codec(#[from] bincode::Error),
+}
+
+/// Everything a peer needs to join a bucket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InviteTicket {
+    /// The bucket to join
+    pub bucket_id: Uuid,
+    /// Role the peer is added with
+    pub role: PrincipalRole,
+    /// The inviting node, with its relay and direct addresses
+    pub inviter: NodeAddr,
+    /// One-time token the inviter checks before adding the peer
+    pub token: InviteToken,
+}
+
+impl fmt::Display for InviteTicket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = bincode::serialize(self).map_err(|_| fmt::Error)?;
+        let encoded = data_encoding::BASE32_NOPAD.encode(&bytes);
+        write!(f, "{}{}", TICKET_PREFIX, encoded.to_ascii_lowercase())
+    }
+}
+
+impl FromStr for InviteTicket {
+    type Err = InviteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let encoded = s
+            .trim()
+            .strip_prefix(TICKET_PREFIX)
+            .ok_or(InviteError::Prefix)?;
+        let bytes = data_encoding::BASE32_NOPAD.decode(encoded.to_ascii_uppercase().as_bytes())?;
+        Ok(bincode::deserialize(&bytes)?)
+    }
+}
+
+/// An invite waiting to be redeemed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingInvite {
+    /// The bucket the invite is for
+    pub bucket_id: Uuid,
+    /// Role the redeeming peer is added with
+    pub role: PrincipalRole,
+    /// When the invite stops working, in seconds since the Unix epoch
+    pub expires_at: u64,
+}
+
+/// Generate a fresh random invite token
+pub fn generate_token() -> InviteToken {
+    let mut token = [0u8; TOKEN_SIZE];
+    getrandom::getrandom(&mut token).expect("failed to generate random bytes");
+    token
+}
+
+/// Hash a token for storage, so a leaked store can't be used to join
+pub fn hash_token(token: &InviteToken) -> TokenHash {
+    *blake3::hash(token).as_bytes()
+}
+
+/// Storage for the invites a peer issued
+///
+/// Invites are keyed by [`hash_token`]; stores never see a token itself.
+/// Peers keep invites in memory ([`Invites`]) unless built with a
+/// persistent store.
+#[async_trait]
+pub trait InviteStore: Send + Sync + std::fmt::Debug {
+    /// Keep an invite until it is taken or expires
+    async fn insert(&self, token: &TokenHash, invite: &PendingInvite) -> Result<()>;
+
+    /// Look up an invite for a bucket that is still open at `now`
+    async fn get(
+        &self,
+        bucket_id: Uuid,
+        token: &TokenHash,
+        now: u64,
+    ) -> Result<Option<PendingInvite>>;
+
+    /// Remove and return an invite for a bucket that is still open at `now`
+    ///
+    /// Only one caller can take an invite; it can be put back with
+    /// [`InviteStore::insert`] if redeeming it fails.
+    async fn take(
+        &self,
+        bucket_id: Uuid,
+        token: &TokenHash,
+        now: u64,
+    ) -> Result<Option<PendingInvite>>;
+}
+
+/// In-memory store of the invites we issued
+#[derive(Debug, Clone, Default)]
+pub struct Invites {
+    inner: Arc<Mutex<HashMap<TokenHash, PendingInvite>>>,
+}
+
+impl Invites {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl InviteStore for Invites {
+    async fn insert(&self, token: &TokenHash, invite: &PendingInvite) -> Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.insert(*token, invite.clone());
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        bucket_id: Uuid,
+        token: &TokenHash,
+        now: u64,
+    ) -> Result<Option<PendingInvite>> {
+        let inner = self.inner.lock().unwrap();
+        Ok(inner
+            .get(token)
+            .filter(|invite| invite.bucket_id == bucket_id && invite.expires_at > now)
+            .cloned())
+    }
+
+    /// Expired invites are dropped along the way
+    async fn take(
+        &self,
+        bucket_id: Uuid,
+        token: &TokenHash,
+        now: u64,
+    ) -> Result<Option<PendingInvite>> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.retain(|_, invite| invite.expires_at > now);
+        match inner.get(token) {
+            Some(invite) if invite.bucket_id == bucket_id => Ok(inner.remove(token)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::SecretKey;
+
+    fn ticket() -> InviteTicket {
+        let node_id = *SecretKey::generate().public();
+        InviteTicket {
+            bucket_id: Uuid::new_v4(),
+            role: PrincipalRole::Reader,
+            inviter: NodeAddr::new(node_id)
+                .with_direct_addresses(["127.0.0.1:4444".parse().unwrap()]),
+            token: [7u8; TOKEN_SIZE],
+        }
+    }
+
+    #[test]
+    fn test_ticket_roundtrip() {
+        let ticket = ticket();
+        let encoded = ticket.to_string();
+        assert!(encoded.starts_with(TICKET_PREFIX));
+        assert_eq!(encoded, encoded.to_ascii_lowercase());
+        assert_eq!(encoded.parse::<InviteTicket>().unwrap(), ticket);
+    }
+
+    #[test]
+    fn test_ticket_rejects_other_strings() {
+        assert!(matches!(
+            "blobabcdef".parse::<InviteTicket>(),
+            Err(InviteError::Prefix)
+        ));
+        assert!("jaxinvite!!".parse::<InviteTicket>().is_err());
+    }
+
+    async fn issue(invites: &Invites, bucket_id: Uuid, role: PrincipalRole) -> TokenHash {
+        let token = hash_token(&generate_token());
+        let invite = PendingInvite {
+            bucket_id,
+            role,
+            expires_at: 100,
+        };
+        invites.insert(&token, &invite).await.unwrap();
+        token
+    }
+
+    #[tokio::test]
+    async fn test_token_taken_once() {
+        let invites = Invites::new();
+        let bucket_id = Uuid::new_v4();
+        let token = issue(&invites, bucket_id, PrincipalRole::Owner).await;
+
+        // The token only works for its own bucket
+        assert_eq!(
+            invites.take(Uuid::new_v4(), &token, 10).await.unwrap(),
+            None
+        );
+        assert!(invites.get(bucket_id, &token, 10).await.unwrap().is_some());
+
+        let invite = invites.take(bucket_id, &token, 10).await.unwrap().unwrap();
+        assert_eq!(invite.role, PrincipalRole::Owner);
+        assert_eq!(invites.take(bucket_id, &token, 10).await.unwrap(), None);
+
+        // until it is put back
+        invites.insert(&token, &invite).await.unwrap();
+        assert_eq!(
+            invites.take(bucket_id, &token, 10).await.unwrap(),
+            Some(invite)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_is_refused() {
+        let invites = Invites::new();
+        let bucket_id = Uuid::new_v4();
+        let token = issue(&invites, bucket_id, PrincipalRole::Mirror).await;
+
+        assert_eq!(invites.get(bucket_id, &token, 100).await.unwrap(), None);
+        assert_eq!(invites.take(bucket_id, &token, 100).await.unwrap(), None);
+    }
+}
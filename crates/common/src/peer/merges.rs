@@ -0,0 +1,131 @@
+//! Forks left for another owner to merge
+//!
+//! Of two owners that see each other's branch, only one merges; the other
+//! waits for that merge to arrive (see `waits_for_merge` in the sync job).
+//! The merging owner may never see our branch though, for instance when it
+//! went offline or we received its branch through a third peer. So every
+//! fork we wait on is remembered here, and once it has waited for
+//! [`MERGE_WAIT`] without being merged, the periodic poll merges it anyway.
+//! Waiting forks only live in memory; one that is lost is merged by whoever
+//! syncs it next.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::crypto::PublicKey;
+use crate::linked_data::Link;
+
+/// How long to wait for another owner's merge before merging ourselves
+pub const MERGE_WAIT: Duration = Duration::from_secs(10 * 60);
+
+/// A fork we are waiting on another owner to merge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingMerge {
+    /// Our head when the fork was found
+    pub ours: Link,
+    /// The other branch's head
+    pub theirs: Link,
+    /// Peers to fetch the other branch's content from
+    pub peer_ids: Vec<PublicKey>,
+    /// When we started waiting
+    pub since: Instant,
+}
+
+/// Shared store of forks waiting for another owner's merge
+#[derive(Debug, Clone, Default)]
+pub struct PendingMerges {
+    inner: Arc<Mutex<HashMap<Uuid, Vec<PendingMerge>>>>,
+}
+
+impl PendingMerges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check whether to keep waiting on a fork, remembering it the first time
+    ///
+    /// True until the fork has waited for [`MERGE_WAIT`]; after that it is
+    /// forgotten, since the caller merges it now.
+    pub fn wait(
+        &self,
+        bucket_id: Uuid,
+        ours: &Link,
+        theirs: &Link,
+        peer_ids: &[PublicKey],
+    ) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let pending = inner.entry(bucket_id).or_default();
+        let Some(index) = pending
+            .iter()
+            .position(|merge| merge.ours == *ours && merge.theirs == *theirs)
+        else {
+            pending.push(PendingMerge {
+                ours: ours.clone(),
+                theirs: theirs.clone(),
+                peer_ids: peer_ids.to_vec(),
+                since: Instant::now(),
+            });
+            return true;
+        };
+        if pending[index].since.elapsed() < MERGE_WAIT {
+            return true;
+        }
+        pending.remove(index);
+        false
+    }
+
+    /// Take the forks of a bucket that waited for [`MERGE_WAIT`]
+    pub fn take_overdue(&self, bucket_id: Uuid) -> Vec<PendingMerge> {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(pending) = inner.get_mut(&bucket_id) else {
+            return Vec::new();
+        };
+        let (overdue, waiting) = pending
+            .drain(..)
+            .partition(|merge| merge.since.elapsed() >= MERGE_WAIT);
+        *pending = waiting;
+        if pending.is_empty() {
+            inner.remove(&bucket_id);
+        }
+        overdue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::SecretKey;
+    use crate::linked_data::{Hash, LD_CBOR_CODEC};
+
+    fn link(n: u8) -> Link {
+        Link::new(LD_CBOR_CODEC, Hash::from_bytes([n; 32]))
+    }
+
+    #[test]
+    fn test_forks_wait_until_overdue() {
+        let merges = PendingMerges::new();
+        let bucket_id = Uuid::new_v4();
+        let peer_ids = vec![SecretKey::generate().public()];
+
+        assert!(merges.wait(bucket_id, &link(1), &link(2), &peer_ids));
+        assert!(merges.wait(bucket_id, &link(1), &link(2), &peer_ids));
+        assert!(merges.take_overdue(bucket_id).is_empty());
+
+        // Once overdue, a fork is handed out once
+        merges.inner.lock().unwrap().get_mut(&bucket_id).unwrap()[0].since -= MERGE_WAIT;
+        assert!(merges.wait(bucket_id, &link(1), &link(3), &peer_ids));
+        let overdue = merges.take_overdue(bucket_id);
+        assert_eq!(overdue.len(), 1);
+        assert_eq!((&overdue[0].ours, &overdue[0].theirs), (&link(1), &link(2)));
+        assert_eq!(overdue[0].peer_ids, peer_ids);
+        assert!(merges.take_overdue(bucket_id).is_empty());
+
+        // or merged by the next sync that sees it
+        merges.inner.lock().unwrap().get_mut(&bucket_id).unwrap()[0].since -= MERGE_WAIT;
+        assert!(!merges.wait(bucket_id, &link(1), &link(3), &peer_ids));
+        assert!(merges.take_overdue(bucket_id).is_empty());
+    }
+}
@@ -1,14 +1,33 @@
 use iroh::protocol::Router;
 use tokio::sync::watch::Receiver as WatchReceiver;
 
+pub mod access;
 mod blobs_store;
+pub mod gossip;
+pub mod invites;
+pub mod merges;
 mod peer_builder;
+pub mod peer_health;
 mod peer_inner;
+pub mod proposals;
 mod protocol;
+pub mod subscriptions;
 pub mod sync;
 
-pub use blobs_store::{BlobsStore, BlobsStoreError};
-pub use protocol::{PingReplyStatus, ALPN};
+pub use access::{AccessRequest, AccessRequestStore};
+pub use blobs_store::{
+    BlobsStore, BlobsStoreError, DownloadEvent, WritePause, DEFAULT_DOWNLOAD_PARALLELISM,
+};
+pub use gossip::HeadGossip;
+pub use invites::{hash_token, InviteStore, InviteTicket, Invites, PendingInvite, TokenHash};
+pub use merges::PendingMerges;
+pub use peer_health::PeerHealth;
+pub use proposals::{MembershipUpdate, Proposals};
+pub use protocol::{
+    AccessRequestStatus, AnnounceKind, AnnounceStatus, DepositStatus, InviteStatus,
+    MembershipStatus, PingReplyStatus, ALPN,
+};
+pub use subscriptions::Subscriptions;
 pub use sync::{SyncJob, SyncProvider, SyncTarget};
 
 pub use iroh::NodeAddr;
@@ -38,10 +57,14 @@ where
     let endpoint = peer.endpoint().clone();
     let peer_for_router = peer.clone();
 
-    // Build the protocol router with iroh-blobs and our custom protocol
-    let router_builder = Router::builder(endpoint)
+    // Build the protocol router with iroh-blobs, our custom protocol, and
+    //  gossip if enabled
+    let mut router_builder = Router::builder(endpoint)
         .accept(iroh_blobs::ALPN, inner_blobs)
         .accept(ALPN, peer_for_router);
+    if let Some(gossip) = peer.gossip() {
+        router_builder = router_builder.accept(iroh_gossip::ALPN, gossip.protocol());
+    }
 
     let router = router_builder.spawn();
 
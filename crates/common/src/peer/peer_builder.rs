@@ -9,6 +9,9 @@ pub use super::blobs_store::BlobsStore;
 use crate::bucket_log::BucketLogProvider;
 use crate::crypto::SecretKey;
 
+use super::access::AccessRequestStore;
+use super::gossip::HeadGossip;
+use super::invites::{InviteStore, Invites};
 use super::peer_inner::Peer;
 use super::sync::SyncProvider;
 
@@ -33,6 +36,12 @@ pub struct PeerBuilder<L: BucketLogProvider, State = ReadyToBuild> {
     log_provider: Option<L>,
     /// Sync provider implementation (trait object for flexibility)
     sync_provider: Option<Arc<dyn SyncProvider<L>>>,
+    /// where to keep issued invites, in memory if not set
+    invite_store: Option<Arc<dyn InviteStore>>,
+    /// where to queue access requests, refused if not set
+    access_request_store: Option<Arc<dyn AccessRequestStore>>,
+    /// whether to spread heads over per-bucket gossip topics
+    gossip: bool,
     /// State marker (zero-sized type for compile-time guarantees)
     _state: std::marker::PhantomData<State>,
 }
@@ -58,6 +67,30 @@ impl<L: BucketLogProvider, State> PeerBuilder<L, State> {
         self.log_provider = Some(log_provider);
         self
     }
+
+    /// Keep issued invites in `store` instead of in memory
+    ///
+    /// A persistent store lets tickets outlive the peer.
+    pub fn invite_store(mut self, store: Arc<dyn InviteStore>) -> Self {
+        self.invite_store = Some(store);
+        self
+    }
+
+    /// Queue access requests in `store` for owners to review
+    ///
+    /// Without a store, access requests are refused.
+    pub fn access_request_store(mut self, store: Arc<dyn AccessRequestStore>) -> Self {
+        self.access_request_store = Some(store);
+        self
+    }
+
+    /// Spread heads over a gossip topic per bucket
+    ///
+    /// Off by default. See [`crate::peer::gossip`].
+    pub fn gossip(mut self, enabled: bool) -> Self {
+        self.gossip = enabled;
+        self
+    }
 }
 
 // Initial construction - starts in NeedsSyncProvider state for explicit configuration
@@ -78,6 +111,9 @@ impl<L: BucketLogProvider> PeerBuilder<L, NeedsSyncProvider> {
             blobs_store: None,
             log_provider: None,
             sync_provider: None,
+            invite_store: None,
+            access_request_store: None,
+            gossip: false,
             _state: std::marker::PhantomData,
         }
     }
@@ -97,6 +133,9 @@ impl<L: BucketLogProvider> PeerBuilder<L, NeedsSyncProvider> {
             blobs_store: self.blobs_store,
             log_provider: self.log_provider,
             sync_provider: self.sync_provider,
+            invite_store: self.invite_store,
+            access_request_store: self.access_request_store,
+            gossip: self.gossip,
             _state: std::marker::PhantomData,
         }
     }
@@ -148,6 +187,12 @@ impl<L: BucketLogProvider> PeerBuilder<L, ReadyToBuild> {
 
         let sync_provider = self.sync_provider.expect("sync_provider must be set");
 
+        let invites = self
+            .invite_store
+            .unwrap_or_else(|| Arc::new(Invites::new()));
+
+        let gossip = self.gossip.then(|| HeadGossip::new(&endpoint));
+
         Peer::new(
             log_provider,
             socket_addr,
@@ -155,6 +200,9 @@ impl<L: BucketLogProvider> PeerBuilder<L, ReadyToBuild> {
             secret_key,
             endpoint,
             sync_provider,
+            invites,
+            self.access_request_store,
+            gossip,
         )
     }
 }
@@ -0,0 +1,140 @@
+//! Per-peer download health
+//!
+//! Blob downloads are split into batches that run concurrently, and each batch
+//! is offered to providers in order: the downloader tries the next peer when
+//! one fails. [`PeerHealth`] decides that order. Every peer has a score that
+//! moves toward 1 on a successful batch and toward 0 on a failure. Healthy
+//! peers take turns being first so the work spreads across all of them;
+//! unhealthy ones are only tried after the healthy ones, best first.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::crypto::PublicKey;
+
+/// Score of a peer we haven't downloaded from yet
+pub const INITIAL_SCORE: f64 = 0.5;
+
+/// Peers scoring below this are tried only after healthy peers
+pub const HEALTHY_THRESHOLD: f64 = 0.25;
+
+/// Weight of the latest outcome in a peer's score
+const SCORE_WEIGHT: f64 = 0.3;
+
+/// Download health of the peers we fetch blobs from
+#[derive(Debug, Clone, Default)]
+pub struct PeerHealth {
+    scores: Arc<Mutex<HashMap<PublicKey, f64>>>,
+}
+
+impl PeerHealth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current score of a peer, between 0 and 1
+    pub fn score(&self, peer_id: &PublicKey) -> f64 {
+        self.scores
+            .lock()
+            .unwrap()
+            .get(peer_id)
+            .copied()
+            .unwrap_or(INITIAL_SCORE)
+    }
+
+    /// Record a batch the peer served
+    pub fn record_success(&self, peer_id: &PublicKey) {
+        self.record(peer_id, 1.0);
+    }
+
+    /// Record a batch the peer failed to serve
+    pub fn record_failure(&self, peer_id: &PublicKey) {
+        self.record(peer_id, 0.0);
+    }
+
+    fn record(&self, peer_id: &PublicKey, outcome: f64) {
+        let mut scores = self.scores.lock().unwrap();
+        let score = scores.entry(*peer_id).or_insert(INITIAL_SCORE);
+        *score += SCORE_WEIGHT * (outcome - *score);
+    }
+
+    /// Order peers to try for the `turn`-th batch of a download
+    ///
+    /// Healthy peers come first, rotated by `turn` so concurrent batches
+    /// start on different peers. Unhealthy peers follow, best score first,
+    /// as a fallback.
+    pub fn rank(&self, peer_ids: &[PublicKey], turn: usize) -> Vec<PublicKey> {
+        let (mut healthy, mut unhealthy): (Vec<_>, Vec<_>) = peer_ids
+            .iter()
+            .map(|peer_id| (*peer_id, self.score(peer_id)))
+            .partition(|(_, score)| *score >= HEALTHY_THRESHOLD);
+
+        if !healthy.is_empty() {
+            let len = healthy.len();
+            healthy.rotate_left(turn % len);
+        }
+        unhealthy.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        healthy
+            .into_iter()
+            .chain(unhealthy)
+            .map(|(peer_id, _)| peer_id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::SecretKey;
+
+    fn peers(n: usize) -> Vec<PublicKey> {
+        (0..n).map(|_| SecretKey::generate().public()).collect()
+    }
+
+    #[test]
+    fn test_healthy_peers_take_turns() {
+        let health = PeerHealth::new();
+        let peers = peers(3);
+
+        assert_eq!(health.rank(&peers, 0)[0], peers[0]);
+        assert_eq!(health.rank(&peers, 1)[0], peers[1]);
+        assert_eq!(health.rank(&peers, 2)[0], peers[2]);
+        assert_eq!(health.rank(&peers, 3)[0], peers[0]);
+        assert_eq!(health.rank(&peers, 1).len(), 3);
+    }
+
+    #[test]
+    fn test_failing_peer_is_tried_last() {
+        let health = PeerHealth::new();
+        let peers = peers(3);
+
+        health.record_failure(&peers[0]);
+        health.record_failure(&peers[0]);
+        health.record_failure(&peers[1]);
+        health.record_failure(&peers[1]);
+        health.record_failure(&peers[1]);
+        assert!(health.score(&peers[0]) < HEALTHY_THRESHOLD);
+
+        for turn in 0..3 {
+            let ranked = health.rank(&peers, turn);
+            assert_eq!(ranked, vec![peers[2], peers[0], peers[1]]);
+        }
+    }
+
+    #[test]
+    fn test_successes_restore_health() {
+        let health = PeerHealth::new();
+        let peer = peers(1)[0];
+
+        for _ in 0..3 {
+            health.record_failure(&peer);
+        }
+        assert!(health.score(&peer) < HEALTHY_THRESHOLD);
+
+        for _ in 0..3 {
+            health.record_success(&peer);
+        }
+        assert!(health.score(&peer) > INITIAL_SCORE);
+    }
+}
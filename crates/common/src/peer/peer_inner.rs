@@ -1,19 +1,37 @@
 use crate::crypto::{PublicKey, SecretKey};
 
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use iroh::{Endpoint, NodeId};
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 pub use super::blobs_store::BlobsStore;
 
-use crate::bucket_log::BucketLogProvider;
-use crate::linked_data::Link;
-use crate::mount::{Mount, MountError};
+use crate::bucket_log::{BucketLogProvider, SyncMode};
+use crate::clock::unix_now;
+use crate::linked_data::{Hash, Link};
+use crate::mount::{
+    CapabilityError, Deposit, FileCapability, Membership, MembershipChange, Mount, MountError,
+    PrincipalRole,
+};
 
-use super::sync::{PingPeerJob, SyncJob, SyncProvider};
+use super::access::{AccessRequest, AccessRequestStore};
+use super::gossip::{GossipPayload, HeadGossip};
+use super::invites::{generate_token, hash_token, InviteStore, InviteTicket, PendingInvite};
+use super::merges::PendingMerges;
+use super::proposals::{MembershipUpdate, Proposals};
+use super::protocol::{
+    AcceptInvite, AccessRequestMessage, AccessRequestStatus, AnnounceKind, BidirectionalHandler,
+    DepositMessage, DepositStatus, InviteMessage, InviteStatus, MembershipMessage,
+    ProposeMembership, RequestAccess, SubmitDeposit,
+};
+use super::subscriptions::Subscriptions;
+use super::sync::progress::{SyncProgress, SyncProgressEvent, PROGRESS_CHANNEL_CAPACITY};
+use super::sync::{AnnouncePeerJob, MergeForkJob, PingPeerJob, SyncJob, SyncProvider};
 
 /// Overview of a peer's state, generic over a bucket log provider.
 ///  Provides everything that a peer needs in order to
@@ -26,6 +44,13 @@ pub struct Peer<L: BucketLogProvider> {
     secret_key: SecretKey,
     endpoint: Endpoint,
     sync_provider: Arc<dyn SyncProvider<L>>,
+    subscriptions: Subscriptions,
+    proposals: Proposals,
+    merges: PendingMerges,
+    invites: Arc<dyn InviteStore>,
+    access_requests: Option<Arc<dyn AccessRequestStore>>,
+    gossip: Option<HeadGossip>,
+    progress: broadcast::Sender<SyncProgress>,
 }
 
 impl<L: BucketLogProvider> Clone for Peer<L>
@@ -40,11 +65,19 @@ where
             secret_key: self.secret_key.clone(),
             endpoint: self.endpoint.clone(),
             sync_provider: self.sync_provider.clone(),
+            subscriptions: self.subscriptions.clone(),
+            proposals: self.proposals.clone(),
+            merges: self.merges.clone(),
+            invites: self.invites.clone(),
+            access_requests: self.access_requests.clone(),
+            gossip: self.gossip.clone(),
+            progress: self.progress.clone(),
         }
     }
 }
 
 impl<L: BucketLogProvider> Peer<L> {
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         log_provider: L,
         socket_address: SocketAddr,
@@ -52,6 +85,9 @@ impl<L: BucketLogProvider> Peer<L> {
         secret_key: SecretKey,
         endpoint: Endpoint,
         sync_provider: Arc<dyn SyncProvider<L>>,
+        invites: Arc<dyn InviteStore>,
+        access_requests: Option<Arc<dyn AccessRequestStore>>,
+        gossip: Option<HeadGossip>,
     ) -> Peer<L> {
         Self {
             log_provider,
@@ -60,6 +96,13 @@ impl<L: BucketLogProvider> Peer<L> {
             secret_key,
             endpoint,
             sync_provider,
+            subscriptions: Subscriptions::new(),
+            proposals: Proposals::new(),
+            merges: PendingMerges::new(),
+            invites,
+            access_requests,
+            gossip,
+            progress: broadcast::channel(PROGRESS_CHANNEL_CAPACITY).0,
         }
     }
 
@@ -91,6 +134,47 @@ impl<L: BucketLogProvider> Peer<L> {
         self.endpoint.node_id()
     }
 
+    /// Head announcement subscriptions, both ours and our subscribers'
+    pub fn subscriptions(&self) -> &Subscriptions {
+        &self.subscriptions
+    }
+
+    /// Membership changes waiting for owner approvals
+    pub fn proposals(&self) -> &Proposals {
+        &self.proposals
+    }
+
+    /// Forks waiting for another owner's merge
+    pub fn pending_merges(&self) -> &PendingMerges {
+        &self.merges
+    }
+
+    /// Invites we issued that haven't been redeemed yet
+    pub fn invites(&self) -> &dyn InviteStore {
+        self.invites.as_ref()
+    }
+
+    /// Where access requests are queued for review, if anywhere
+    pub fn access_requests(&self) -> Option<&dyn AccessRequestStore> {
+        self.access_requests.as_deref()
+    }
+
+    /// Per-bucket gossip topics, if gossip is enabled
+    pub fn gossip(&self) -> Option<&HeadGossip> {
+        self.gossip.as_ref()
+    }
+
+    /// Subscribe to progress events from sync jobs
+    pub fn subscribe_sync_progress(&self) -> broadcast::Receiver<SyncProgress> {
+        self.progress.subscribe()
+    }
+
+    /// Report sync progress for a bucket to any subscribers
+    pub fn emit_progress(&self, bucket_id: Uuid, event: SyncProgressEvent) {
+        // An error only means no one is listening
+        let _ = self.progress.send(SyncProgress { bucket_id, event });
+    }
+
     // ========================================
     // Sync Operations (dispatch to backend)
     // ========================================
@@ -156,6 +240,97 @@ impl<L: BucketLogProvider> Peer<L> {
         Ok(())
     }
 
+    /// Keep up with the peers in a bucket's shares
+    ///
+    /// Peers that push heads to us are renewed when due and otherwise only
+    /// pinged every [`SUBSCRIBED_PING_INTERVAL`], in case they forgot us.
+    /// Everyone else gets a subscription attempt when one is due (which falls
+    /// back to a ping if it fails), or a ping while backing off, so peers
+    /// without announcement support are still polled.
+    ///
+    /// [`SUBSCRIBED_PING_INTERVAL`]: crate::peer::subscriptions::SUBSCRIBED_PING_INTERVAL
+    ///
+    /// With gossip enabled, this also joins the bucket's topic and renews our
+    /// presence on it; peers heard from on the topic are left alone. Forks
+    /// that waited too long for another owner's merge are merged here too.
+    pub async fn poll(&self, bucket_id: Uuid) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let (head_link, _) = self
+            .logs()
+            .head(bucket_id, None)
+            .await
+            .map_err(|e| anyhow!("Failed to get head for bucket {}: {}", bucket_id, e))?;
+        let manifest: crate::mount::Manifest = self
+            .blobs()
+            .get_cbor(&head_link.hash())
+            .await
+            .map_err(|e| anyhow!("Failed to load manifest: {}", e))?;
+
+        let our_key = self.secret().public();
+        let peer_ids: Vec<_> = manifest
+            .get_peer_ids()
+            .into_iter()
+            .filter(|peer_id| *peer_id != our_key)
+            .collect();
+
+        // Keep our presence on the bucket's gossip topic fresh
+        let gossiping = match self.gossip() {
+            Some(gossip) if gossip.presence_due(bucket_id) || !gossip.is_joined(bucket_id) => {
+                self.gossip_announce(bucket_id, peer_ids.clone(), GossipPayload::Present)
+                    .await
+            }
+            Some(gossip) => gossip.is_joined(bucket_id),
+            None => false,
+        };
+
+        for peer_id in peer_ids {
+            // Topic members announce their heads over gossip
+            if gossiping && self.is_gossip_member(bucket_id, &peer_id) {
+                continue;
+            }
+
+            let job = if self.subscriptions.should_subscribe(bucket_id, &peer_id) {
+                SyncJob::AnnouncePeer(AnnouncePeerJob {
+                    bucket_id,
+                    peer_id,
+                    kind: AnnounceKind::Subscribe,
+                })
+            } else if self.subscriptions.should_ping(bucket_id, &peer_id) {
+                SyncJob::PingPeer(PingPeerJob { bucket_id, peer_id })
+            } else {
+                continue;
+            };
+            if let Err(e) = self.dispatch(job).await {
+                tracing::warn!(
+                    "Failed to dispatch poll of peer {} for bucket {}: {}",
+                    peer_id.to_hex(),
+                    bucket_id,
+                    e
+                );
+            }
+        }
+
+        for merge in self.merges.take_overdue(bucket_id) {
+            let job = SyncJob::MergeFork(MergeForkJob {
+                bucket_id,
+                ours: merge.ours,
+                theirs: merge.theirs,
+                peer_ids: merge.peer_ids,
+            });
+            if let Err(e) = self.dispatch(job).await {
+                tracing::warn!(
+                    "Failed to dispatch overdue merge for bucket {}: {}",
+                    bucket_id,
+                    e
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     /// Ping all peers for a bucket and collect their responses
     ///
     /// Returns a map of peer public key hex to their ping reply status.
@@ -288,17 +463,17 @@ impl<L: BucketLogProvider> Peer<L> {
             .map_err(|e| MountError::Default(anyhow!("Failed to get current head: {}", e)))?;
 
         // Load mount at that link (height is read from manifest)
-        Mount::load(&link, &self.secret_key, &self.blobs_store).await
+        self.load_mount(bucket_id, &link).await
     }
 
     /// Load mount for reading based on the peer's role in the bucket.
     ///
     /// This method determines the appropriate version to load based on the peer's role:
-    /// - **Owners** see HEAD (latest state, including unpublished changes)
+    /// - **Owners** and **Readers** see HEAD (latest state, including unpublished changes)
     /// - **Mirrors** (or unknown roles) see the latest_published version
     ///
     /// This ensures that mirrors only see content that has been explicitly published
-    /// to them, while owners always see the most recent state.
+    /// to them, while owners and readers always see the most recent state.
     ///
     /// # Arguments
     ///
@@ -315,8 +490,6 @@ impl<L: BucketLogProvider> Peer<L> {
     /// - No published version available (for mirrors)
     /// - Failed to load mount from blobs
     pub async fn mount_for_read(&self, bucket_id: Uuid) -> Result<Mount, MountError> {
-        use crate::mount::PrincipalRole;
-
         // Get current head link from log
         let (head_link, _) = self
             .log_provider
@@ -334,8 +507,8 @@ impl<L: BucketLogProvider> Peer<L> {
             });
 
         match our_role {
-            Some(PrincipalRole::Owner) => {
-                // Owners see HEAD (latest state)
+            Some(PrincipalRole::Owner | PrincipalRole::Reader) => {
+                // Owners and readers see HEAD (latest state)
                 self.mount(bucket_id).await
             }
             _ => {
@@ -350,9 +523,156 @@ impl<L: BucketLogProvider> Peer<L> {
                     .ok_or_else(|| {
                         MountError::Default(anyhow!("No published version available"))
                     })?;
-                Mount::load(&link, &self.secret_key, &self.blobs_store).await
+                self.load_mount(bucket_id, &link).await
+            }
+        }
+    }
+
+    /// Load mount at a specific version of a bucket
+    ///
+    /// The mount reads through [`Self::bucket_blobs`], so in a lazily
+    /// synced bucket file contents are fetched from peers when first read.
+    pub async fn load_mount(&self, bucket_id: Uuid, link: &Link) -> Result<Mount, MountError> {
+        let blobs = self.bucket_blobs(bucket_id, link).await;
+        Mount::load(link, &self.secret_key, &blobs).await
+    }
+
+    /// Load only what a version of a bucket publishes
+    ///
+    /// Even on an owner node the mount sees what a mirror would: the whole
+    /// bucket if the version is published, otherwise only its published
+    /// subtrees. Used to serve buckets through the gateway.
+    pub async fn load_published_mount(
+        &self,
+        bucket_id: Uuid,
+        link: &Link,
+    ) -> Result<Mount, MountError> {
+        let blobs = self.bucket_blobs(bucket_id, link).await;
+        Mount::load_published(link, &self.secret_key, &blobs).await
+    }
+
+    /// Blob store to read a version of a bucket through
+    ///
+    /// Fully synced buckets read from the local store. Lazily synced ones
+    /// get a store that fetches missing blobs from the version's other peers.
+    pub async fn bucket_blobs(&self, bucket_id: Uuid, link: &Link) -> BlobsStore {
+        match self.log_provider.sync_mode(bucket_id).await {
+            Ok(SyncMode::Full) => return self.blobs_store.clone(),
+            Ok(SyncMode::Lazy) => {}
+            Err(e) => {
+                tracing::warn!("Failed to get sync mode of bucket {}: {}", bucket_id, e);
+                return self.blobs_store.clone();
             }
         }
+
+        let peer_ids = match Mount::load_manifest(link, &self.blobs_store).await {
+            Ok(manifest) => self.other_peers(manifest.get_peer_ids()),
+            Err(e) => {
+                tracing::warn!("Failed to load manifest {:?} for lazy reads: {}", link, e);
+                Vec::new()
+            }
+        };
+        self.blobs_store
+            .with_remote(self.endpoint.clone(), peer_ids)
+    }
+
+    /// Download a path of a bucket so it can be read without peers
+    ///
+    /// Fetches the contents of every file under `path` at the version this
+    /// peer reads (see [`Self::mount_for_read`]). Returns the number of files.
+    pub async fn make_available_offline(
+        &self,
+        bucket_id: Uuid,
+        path: &Path,
+    ) -> Result<usize, MountError> {
+        let mount = self.mount_for_read(bucket_id).await?;
+        if path != Path::new("/") {
+            mount.get(path).await?;
+        }
+        self.download_paths(bucket_id, &mount, &[path.to_path_buf()])
+            .await
+    }
+
+    /// Download the directory tree of a mount and file contents under `paths`
+    ///
+    /// Directory nodes are fetched a level at a time; files under any of
+    /// `paths` are then downloaded with progress reported for the bucket.
+    /// An empty `paths` fetches the tree only. Returns the number of files
+    /// downloaded or already present.
+    pub async fn download_paths(
+        &self,
+        bucket_id: Uuid,
+        mount: &Mount,
+        paths: &[PathBuf],
+    ) -> Result<usize, MountError> {
+        let peer_ids = self.other_peers(mount.inner().await.manifest().get_peer_ids());
+
+        let files = mount
+            .walk_files(Path::new("/"), |hashes| {
+                let peer_ids = peer_ids.clone();
+                async move {
+                    if let Err(e) = self
+                        .blobs_store
+                        .download_hashes(hashes, peer_ids, &self.endpoint)
+                        .await
+                    {
+                        tracing::warn!("Failed to prefetch directory nodes: {}", e);
+                    }
+                }
+            })
+            .await?;
+
+        let prefixes: Vec<&Path> = paths
+            .iter()
+            .map(|path| path.strip_prefix("/").unwrap_or(path))
+            .collect();
+        let hashes: Vec<_> = files
+            .iter()
+            .filter(|(path, _)| prefixes.iter().any(|prefix| path.starts_with(prefix)))
+            .map(|(_, link)| link.link().hash())
+            .collect();
+        let count = hashes.len();
+
+        self.blobs_store
+            .download_hashes_with_progress(hashes, peer_ids, &self.endpoint, |event| {
+                self.emit_progress(bucket_id, event.into())
+            })
+            .await?;
+        Ok(count)
+    }
+
+    /// Check a file capability and get the blob store to read its contents
+    /// through
+    ///
+    /// The capability must be one we minted or one from an owner in our head
+    /// of its bucket (see [`FileCapability::authorize`]). Contents that
+    /// aren't stored locally are only fetched from that bucket's peers, never
+    /// from a node the capability names.
+    pub async fn capability_blobs(
+        &self,
+        capability: &FileCapability,
+    ) -> Result<BlobsStore, CapabilityError> {
+        let manifest = match self.log_provider.head(capability.bucket_id, None).await {
+            Ok((head, _)) => Mount::load_manifest(&head, &self.blobs_store).await.ok(),
+            Err(_) => None,
+        };
+        capability.authorize(&self.secret_key.public(), manifest.as_ref(), unix_now())?;
+
+        let peer_ids = manifest
+            .map(|manifest| self.other_peers(manifest.get_peer_ids()))
+            .unwrap_or_default();
+        Ok(self
+            .blobs_store
+            .with_remote(self.endpoint.clone(), peer_ids))
+    }
+
+    /// Peers from a list other than ourselves
+    fn other_peers(&self, peer_ids: Vec<PublicKey>) -> Vec<PublicKey> {
+        let our_id = self.secret_key.public();
+        peer_ids
+            .into_iter()
+            .filter(|peer_id| *peer_id != our_id)
+            .collect()
     }
 
     /// Save a mount and append it to the bucket's log
@@ -360,7 +680,9 @@ impl<L: BucketLogProvider> Peer<L> {
     /// This method:
     /// 1. Saves the mount to blobs, getting a new link
     /// 2. Appends the new link to the bucket's log
-    /// 3. Dispatches sync jobs to notify peers
+    /// 3. Broadcasts the new head on the bucket's gossip topic (if enabled)
+    /// 4. Pushes the new head to subscribed peers and pings the rest,
+    ///    skipping peers reached through gossip
     ///
     /// # Arguments
     ///
@@ -391,26 +713,30 @@ impl<L: BucketLogProvider> Peer<L> {
         let name = manifest.name().to_string();
 
         // Get shares from the mount manifest
-        let (link, previous_link, height) = mount.save(self.blobs(), publish).await?;
+        let (link, _, height) = mount.save(self.blobs(), publish).await?;
         let inner = mount.inner().await;
         let manifest = inner.manifest();
         let shares = manifest.shares();
-        let is_published = manifest.is_published();
+        let is_published = manifest.has_published_content();
+        let parents = manifest.parents().to_vec();
         tracing::info!("SAVE_MOUNT: Found {} shares in manifest", shares.len());
 
         // Append to log
         self.log_provider
-            .append(
-                bucket_id,
-                name,
-                link.clone(),
-                Some(previous_link),
-                height,
-                is_published,
-            )
+            .append(bucket_id, name, link.clone(), parents, height, is_published)
             .await
             .map_err(|e| MountError::Default(anyhow!("Failed to append to log: {}", e)))?;
 
+        // Broadcast the head on the bucket's gossip topic, if enabled
+        let others: Vec<_> = manifest
+            .get_peer_ids()
+            .into_iter()
+            .filter(|peer_id| *peer_id != our_public_key)
+            .collect();
+        let gossiped = self
+            .gossip_announce(bucket_id, others, GossipPayload::Head(link.clone(), height))
+            .await;
+
         // Dispatch ping jobs for each peer (except ourselves)
         let mut notified_count = 0;
         for (peer_key_hex, _share) in shares.iter() {
@@ -424,21 +750,36 @@ impl<L: BucketLogProvider> Peer<L> {
                     continue;
                 }
 
+                // Topic members got the head over gossip
+                if gossiped && self.is_gossip_member(bucket_id, &peer_public_key) {
+                    continue;
+                }
+
+                // Subscribers get the head pushed, everyone else a ping
+                // Ignore errors - if we can't notify a peer, they'll catch up on their next poll
+                let job = if self
+                    .subscriptions
+                    .is_subscriber(bucket_id, &peer_public_key)
+                {
+                    SyncJob::AnnouncePeer(AnnouncePeerJob {
+                        bucket_id,
+                        peer_id: peer_public_key,
+                        kind: AnnounceKind::Head(link.clone(), height),
+                    })
+                } else {
+                    SyncJob::PingPeer(PingPeerJob {
+                        bucket_id,
+                        peer_id: peer_public_key,
+                    })
+                };
                 tracing::info!(
-                    "SAVE_MOUNT: Dispatching PingPeer job for bucket {} to peer {}",
+                    "SAVE_MOUNT: Notifying peer {} of bucket {} via {:?}",
+                    peer_key_hex,
                     bucket_id,
-                    peer_key_hex
+                    job
                 );
-                // Dispatch a ping job for this peer
-                // Ignore errors - if we can't notify a peer, they'll catch up on their next ping
-                if let Err(e) = self
-                    .dispatch(SyncJob::PingPeer(PingPeerJob {
-                        bucket_id,
-                        peer_id: peer_public_key,
-                    }))
-                    .await
-                {
-                    tracing::warn!("Failed to dispatch ping: {}", e);
+                if let Err(e) = self.dispatch(job).await {
+                    tracing::warn!("Failed to dispatch notification: {}", e);
                 }
                 notified_count += 1;
             } else {
@@ -449,12 +790,367 @@ impl<L: BucketLogProvider> Peer<L> {
             }
         }
 
+        tracing::info!("notified {} peers of bucket {}", notified_count, bucket_id);
+
+        Ok(link)
+    }
+
+    /// Remove expired shares from a bucket we own
+    ///
+    /// Saves a new version without the expired principals, which re-keys
+    /// the bucket (see [`Mount::save`]). Does nothing unless we are an
+    /// unscoped owner whose own share hasn't expired, and some other share
+    /// has. Returns the number
+    /// of shares removed.
+    pub async fn remove_expired_shares(&self, bucket_id: Uuid) -> Result<usize, MountError>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let (head, _) = self
+            .log_provider
+            .head(bucket_id, None)
+            .await
+            .map_err(|e| MountError::Default(anyhow!("Failed to get current head: {}", e)))?;
+        let manifest = Mount::load_manifest(&head, &self.blobs_store).await?;
+
+        let our_key = self.secret_key.public();
+        let now = unix_now();
+        let is_owner = manifest.get_share(&our_key).is_some_and(|share| {
+            *share.role() == PrincipalRole::Owner && !share.is_scoped() && !share.is_expired(now)
+        });
+        let expired = self.other_peers(manifest.expired_shares(now)).len();
+        if !is_owner || expired == 0 {
+            return Ok(0);
+        }
+
+        let mount = self.mount(bucket_id).await?;
+        let publish = mount.is_published().await;
+        self.save_mount(&mount, publish).await?;
         tracing::info!(
-            "dispatched {} PingPeer jobs for bucket {}",
-            notified_count,
+            "Removed {} expired shares from bucket {}",
+            expired,
             bucket_id
         );
+        Ok(expired)
+    }
+
+    /// Save a mount whose shares may have changed
+    ///
+    /// Buckets without a membership quorum, or mounts that don't change the
+    /// shares, are saved as usual (see [`Self::save_mount`]). Otherwise the
+    /// change is approved with our key and proposed to the bucket's other
+    /// owners, and is saved once enough of them approve it (see
+    /// [`Self::approve_membership_change`]).
+    pub async fn save_membership(
+        &self,
+        mount: &Mount,
+        publish: bool,
+    ) -> Result<MembershipUpdate, MountError>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        match mount.membership_change().await? {
+            None => Ok(MembershipUpdate::Saved(
+                self.save_mount(mount, publish).await?,
+            )),
+            Some(mut change) => {
+                change.approve(&self.secret_key)?;
+                self.submit_membership_change(change).await
+            }
+        }
+    }
 
+    /// Approve a membership change another owner proposed
+    ///
+    /// Applies the change if our approval completes the quorum, and passes
+    /// it on to the other owners otherwise.
+    pub async fn approve_membership_change(
+        &self,
+        bucket_id: Uuid,
+        id: &Hash,
+    ) -> Result<MembershipUpdate, MountError>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let mut change = self
+            .proposals
+            .get(bucket_id, id)
+            .ok_or(MountError::StaleMembershipChange)?;
+        change.approve(&self.secret_key)?;
+        self.submit_membership_change(change).await
+    }
+
+    /// Apply an approved membership change at the bucket's head
+    pub(crate) async fn apply_membership_change(
+        &self,
+        id: Hash,
+        change: &MembershipChange,
+    ) -> Result<Link, MountError>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let bucket_id = *change.bucket_id();
+        let mut mount = self.mount(bucket_id).await?;
+        mount.apply_membership_change(change).await?;
+        let publish = mount.is_published().await;
+        let link = self.save_mount(&mount, publish).await?;
+        self.proposals.remove(bucket_id, &id);
+        tracing::info!(
+            "Applied membership change {} to bucket {} with {} approvals",
+            id,
+            bucket_id,
+            change.approvals().len()
+        );
         Ok(link)
     }
+
+    /// Record a change we approved, then apply it or send it to the other owners
+    async fn submit_membership_change(
+        &self,
+        change: MembershipChange,
+    ) -> Result<MembershipUpdate, MountError>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let id = change.id()?;
+        let change = self.proposals.insert(id, change);
+        if change.is_approved() {
+            let link = self.apply_membership_change(id, &change).await?;
+            return Ok(MembershipUpdate::Saved(link));
+        }
+
+        for owner in self.other_peers(change.from().approvers()) {
+            let message = MembershipMessage {
+                change: change.clone(),
+            };
+            if let Err(e) = ProposeMembership::send::<L>(self, &owner, message).await {
+                tracing::debug!(
+                    "Failed to send membership change {} to {}: {}",
+                    id,
+                    owner.to_hex(),
+                    e
+                );
+            }
+        }
+        Ok(MembershipUpdate::Proposed { id, change })
+    }
+
+    /// Add a peer to a bucket's shares with a role and ping it to sync
+    ///
+    /// A peer already in the shares keeps its role and is just pinged; invite
+    /// redemptions refuse such peers before getting here, so they don't use
+    /// up a ticket whose role would be ignored. In a bucket with an owner
+    /// quorum the addition is proposed to the other owners instead, and the
+    /// peer is pinged by the usual rounds once the change lands.
+    pub async fn add_member(
+        &self,
+        bucket_id: Uuid,
+        member: PublicKey,
+        role: PrincipalRole,
+    ) -> Result<MembershipUpdate, MountError>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let mut mount = self.mount(bucket_id).await?;
+        let existing = mount.inner().await.manifest().get_share(&member).is_some();
+        let update = if existing {
+            MembershipUpdate::Saved(mount.link().await)
+        } else {
+            match role {
+                PrincipalRole::Owner => mount.add_owner(member).await?,
+                PrincipalRole::Reader => mount.add_reader(member).await?,
+                PrincipalRole::Mirror => mount.add_mirror(member).await,
+                PrincipalRole::Depositor => mount.add_depositor(member).await,
+            }
+            let publish = mount.is_published().await;
+            self.save_membership(&mount, publish).await?
+        };
+
+        if let MembershipUpdate::Saved(_) = update {
+            if let Err(e) = self
+                .dispatch(SyncJob::PingPeer(PingPeerJob {
+                    bucket_id,
+                    peer_id: member,
+                }))
+                .await
+            {
+                tracing::warn!(
+                    "Failed to dispatch ping to new member {} of bucket {}: {}",
+                    member.to_hex(),
+                    bucket_id,
+                    e
+                );
+            }
+        }
+        Ok(update)
+    }
+
+    /// Ask an owner of a bucket to add us with a role
+    ///
+    /// The signed request waits in the owner's queue until an owner
+    /// approves it, which adds us to the shares and pings us to sync.
+    pub async fn request_access(
+        &self,
+        bucket_id: Uuid,
+        owner: PublicKey,
+        role: PrincipalRole,
+        note: Option<String>,
+    ) -> Result<AccessRequestStatus>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let now = unix_now();
+        let request = AccessRequest::new(bucket_id, role, note, &self.secret_key, now)?;
+        let reply =
+            RequestAccess::send::<L>(self, &owner, AccessRequestMessage { request }).await?;
+        Ok(reply.status)
+    }
+
+    /// Issue an invite ticket for joining a bucket with a role
+    ///
+    /// Only unscoped owners can invite, since redeeming the ticket changes
+    /// the shares. The ticket carries our current addresses and stays valid
+    /// for `ttl_secs`, or until it is redeemed once.
+    pub async fn invite(
+        &self,
+        bucket_id: Uuid,
+        role: PrincipalRole,
+        ttl_secs: u64,
+    ) -> Result<(InviteTicket, u64), MountError>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let mount = self.mount(bucket_id).await?;
+        let manifest = mount.inner().await.manifest().clone();
+        if !Membership::of(&manifest).can_approve(&self.secret_key.public()) {
+            return Err(MountError::Unauthorized);
+        }
+
+        let now = unix_now();
+        let expires_at = now.saturating_add(ttl_secs);
+        let token = generate_token();
+        let invite = PendingInvite {
+            bucket_id,
+            role: role.clone(),
+            expires_at,
+        };
+        self.invites
+            .insert(&hash_token(&token), &invite)
+            .await
+            .map_err(|e| MountError::Default(anyhow!("failed to store invite: {}", e)))?;
+        let ticket = InviteTicket {
+            bucket_id,
+            role,
+            inviter: self.endpoint.node_addr(),
+            token,
+        };
+        Ok((ticket, expires_at))
+    }
+
+    /// Redeem an invite ticket with the node that issued it
+    ///
+    /// Dials the inviter at the ticket's addresses. Once it has added us to
+    /// the shares it pings us, and we sync the bucket from it.
+    pub async fn accept_invite(&self, ticket: &InviteTicket) -> Result<InviteStatus>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        if let Err(e) = self
+            .endpoint
+            .add_node_addr_with_source(ticket.inviter.clone(), "invite")
+        {
+            return Err(anyhow!("Invalid inviter address: {}", e));
+        }
+        let inviter = PublicKey::from(ticket.inviter.node_id);
+        let message = InviteMessage {
+            bucket_id: ticket.bucket_id,
+            token: ticket.token,
+        };
+        let reply = AcceptInvite::send::<L>(self, &inviter, message).await?;
+        Ok(reply.status)
+    }
+
+    /// Submit a file to a bucket's inbox as a depositor
+    ///
+    /// The file is encrypted to the owners in our copy of the bucket's head
+    /// (see [`Deposit::create`]) and offered to each owner in turn until one
+    /// accepts it. That owner fetches the deposit from us and adds it at
+    /// `/inbox/<path>` in a new version.
+    ///
+    /// Returns the deposit, its link and the owner that accepted it.
+    pub async fn deposit(
+        &self,
+        bucket_id: Uuid,
+        path: &Path,
+        data: &[u8],
+    ) -> Result<(Deposit, Link, PublicKey)>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let (head, _) = self
+            .logs()
+            .head(bucket_id, None)
+            .await
+            .map_err(|e| anyhow!("Failed to get head for bucket {}: {}", bucket_id, e))?;
+        let manifest = Mount::load_manifest(&head, &self.blobs_store).await?;
+        let (deposit, link) =
+            Deposit::create(&manifest, path, data, &self.secret_key, &self.blobs_store).await?;
+
+        for owner in manifest.get_shares_by_role(PrincipalRole::Owner) {
+            let owner_id = owner.principal().identity;
+            let message = DepositMessage {
+                bucket_id,
+                link: link.clone(),
+            };
+            match SubmitDeposit::send::<L>(self, &owner_id, message).await {
+                Ok(reply) if reply.status == DepositStatus::Accepted => {
+                    return Ok((deposit, link, owner_id));
+                }
+                Ok(_) => {
+                    tracing::debug!("Owner {} refused deposit", owner_id.to_hex());
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to reach owner {}: {}", owner_id.to_hex(), e);
+                }
+            }
+        }
+        Err(anyhow!(
+            "No owner of bucket {} accepted the deposit",
+            bucket_id
+        ))
+    }
+
+    /// Join a bucket's gossip topic if needed and broadcast an announcement
+    ///
+    /// Returns whether the announcement went out; false if gossip is
+    /// disabled or failed, in which case peers need direct notifications.
+    async fn gossip_announce(
+        &self,
+        bucket_id: Uuid,
+        bootstrap: Vec<PublicKey>,
+        payload: GossipPayload,
+    ) -> bool
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let Some(gossip) = self.gossip() else {
+            return false;
+        };
+        let result = match gossip.join(self, bucket_id, bootstrap).await {
+            Ok(()) => gossip.broadcast(self.secret(), bucket_id, payload).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("Failed to gossip for bucket {}: {}", bucket_id, e);
+                false
+            }
+        }
+    }
+
+    fn is_gossip_member(&self, bucket_id: Uuid, peer_id: &PublicKey) -> bool {
+        self.gossip()
+            .is_some_and(|gossip| gossip.is_member(bucket_id, peer_id))
+    }
 }
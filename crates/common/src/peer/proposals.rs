@@ -0,0 +1,94 @@
+//! Pending membership changes
+//!
+//! In a bucket with a membership quorum, changes to the shares are proposed
+//! to the other owners with the [`ProposeMembership`] protocol message and
+//! wait here until enough of them approve. Each copy of a proposal that
+//! arrives adds its approvals to the one we hold, so owners converge on the
+//! full set. Proposals only live in memory; one that is lost can be proposed
+//! again.
+//!
+//! [`ProposeMembership`]: crate::peer::protocol::ProposeMembership
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::linked_data::{Hash, Link};
+use crate::mount::{Manifest, Membership, MembershipChange};
+
+/// Result of saving a change to a bucket's shares
+#[derive(Debug, Clone)]
+pub enum MembershipUpdate {
+    /// The change was saved as a new version
+    Saved(Link),
+    /// The change is waiting for more owners to approve it
+    Proposed { id: Hash, change: MembershipChange },
+}
+
+/// Shared store of membership changes waiting for approvals
+#[derive(Debug, Clone, Default)]
+pub struct Proposals {
+    inner: Arc<Mutex<HashMap<Uuid, HashMap<Hash, MembershipChange>>>>,
+}
+
+impl Proposals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a proposal, adding its approvals to any copy we already hold
+    ///
+    /// Returns our copy with every approval collected so far.
+    pub fn insert(&self, id: Hash, change: MembershipChange) -> MembershipChange {
+        let mut inner = self.inner.lock().unwrap();
+        let proposals = inner.entry(*change.bucket_id()).or_default();
+        match proposals.get_mut(&id) {
+            Some(held) => {
+                held.add_approvals(change.approvals());
+                held.clone()
+            }
+            None => {
+                proposals.insert(id, change.clone());
+                change
+            }
+        }
+    }
+
+    /// Get a proposal for a bucket by its id
+    pub fn get(&self, bucket_id: Uuid, id: &Hash) -> Option<MembershipChange> {
+        let inner = self.inner.lock().unwrap();
+        inner.get(&bucket_id)?.get(id).cloned()
+    }
+
+    /// Drop a proposal once it was applied
+    pub fn remove(&self, bucket_id: Uuid, id: &Hash) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(proposals) = inner.get_mut(&bucket_id) {
+            proposals.remove(id);
+        }
+    }
+
+    /// List the proposals that still apply to a bucket's head
+    ///
+    /// Proposals that build on another membership or epoch can never be
+    /// applied, so they are dropped.
+    pub fn list(&self, head: &Manifest) -> Vec<(Hash, MembershipChange)> {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(proposals) = inner.get_mut(head.id()) else {
+            return Vec::new();
+        };
+        proposals.retain(|_, change| applies_to(change, head));
+        proposals
+            .iter()
+            .map(|(id, change)| (*id, change.clone()))
+            .collect()
+    }
+}
+
+/// Whether a change builds on a manifest's membership
+pub fn applies_to(change: &MembershipChange, head: &Manifest) -> bool {
+    change.bucket_id() == head.id()
+        && change.epoch() == head.membership_epoch() + 1
+        && *change.from() == Membership::of(head)
+}
@@ -59,7 +59,9 @@ pub trait BidirectionalHandler: Sized {
         peer: &Peer<L>,
         sender_node_id: &PublicKey,
         message: &Self::Message,
-    ) -> impl std::future::Future<Output = Self::Reply> + Send;
+    ) -> impl std::future::Future<Output = Self::Reply> + Send
+    where
+        L::Error: std::error::Error + Send + Sync + 'static;
 
     /// Handle an incoming response and take action
     ///
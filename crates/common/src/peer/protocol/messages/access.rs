@@ -0,0 +1,158 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::bucket_log::BucketLogProvider;
+use crate::crypto::PublicKey;
+use crate::mount::{Manifest, Membership};
+use crate::peer::access::AccessRequest;
+use crate::peer::protocol::bidirectional::BidirectionalHandler;
+use crate::peer::protocol::messages::Message;
+use crate::peer::Peer;
+
+/// Ask an owner to be added to a bucket's shares
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessRequestMessage {
+    pub request: AccessRequest,
+}
+
+/// Outcome of an access request
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AccessRequestStatus {
+    /// The request waits for an owner to approve or deny it
+    Queued,
+    /// The requester is already in the bucket's shares
+    AlreadyShared,
+    /// We aren't an owner that can change the shares, the request isn't
+    /// signed by the sender, or we don't keep a queue
+    Refused,
+}
+
+/// Response to an access request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessRequestReply {
+    /// The bucket ID being responded to
+    pub bucket_id: Uuid,
+    /// The outcome
+    pub status: AccessRequestStatus,
+}
+
+/// Access request handler for peers asking to join a bucket
+///
+/// Owners verify the request is signed by the connecting peer and queue it
+/// in their [`AccessRequestStore`] for review; peers without one refuse it.
+/// Approving it adds the share and pings the requester, which then syncs
+/// the bucket.
+///
+/// [`AccessRequestStore`]: crate::peer::AccessRequestStore
+pub struct RequestAccess;
+
+impl BidirectionalHandler for RequestAccess {
+    type Message = AccessRequestMessage;
+    type Reply = AccessRequestReply;
+
+    /// Wrap the request in the Message enum for proper serialization
+    fn wrap_request(request: Self::Message) -> Message {
+        Message::RequestAccess(request)
+    }
+
+    // ========================================
+    // RESPONDER SIDE: When a peer asks for access
+    // ========================================
+
+    /// Queue signed requests for buckets we can change the shares of
+    async fn handle_message<L: BucketLogProvider>(
+        peer: &Peer<L>,
+        sender_node_id: &PublicKey,
+        message: &AccessRequestMessage,
+    ) -> AccessRequestReply {
+        let request = &message.request;
+        let bucket_id = request.bucket_id;
+        let reply = |status| AccessRequestReply { bucket_id, status };
+
+        if request.requester != *sender_node_id {
+            return reply(AccessRequestStatus::Refused);
+        }
+        if let Err(e) = request.verify() {
+            tracing::info!(
+                "Rejected access request from {} for bucket {}: {}",
+                sender_node_id.to_hex(),
+                bucket_id,
+                e
+            );
+            return reply(AccessRequestStatus::Refused);
+        }
+
+        let Ok((head, _)) = peer.logs().head(bucket_id, None).await else {
+            return reply(AccessRequestStatus::Refused);
+        };
+        let manifest = match peer.blobs().get_cbor::<Manifest>(&head.hash()).await {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                tracing::warn!("Failed to load head manifest for {}: {}", bucket_id, e);
+                return reply(AccessRequestStatus::Refused);
+            }
+        };
+        if !Membership::of(&manifest).can_approve(&peer.secret().public()) {
+            return reply(AccessRequestStatus::Refused);
+        }
+        if manifest.get_share(sender_node_id).is_some() {
+            return reply(AccessRequestStatus::AlreadyShared);
+        }
+
+        let Some(store) = peer.access_requests() else {
+            return reply(AccessRequestStatus::Refused);
+        };
+        match store.queue(request).await {
+            Ok(true) => {
+                tracing::info!(
+                    "Queued access request from {} for bucket {} as {:?}",
+                    sender_node_id.to_hex(),
+                    bucket_id,
+                    request.role
+                );
+                reply(AccessRequestStatus::Queued)
+            }
+            Ok(false) => reply(AccessRequestStatus::Refused),
+            Err(e) => {
+                tracing::warn!("Failed to queue access request for {}: {}", bucket_id, e);
+                reply(AccessRequestStatus::Refused)
+            }
+        }
+    }
+
+    /// Nothing to do until an owner reviews the request
+    async fn handle_message_side_effect<L: BucketLogProvider>(
+        _peer: &Peer<L>,
+        _sender_node_id: &PublicKey,
+        _message: &AccessRequestMessage,
+        _reply: &AccessRequestReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        Ok(())
+    }
+
+    // ========================================
+    // INITIATOR SIDE: When the owner replies
+    // ========================================
+
+    /// Nothing to track; the owner pings us once the request is approved
+    async fn handle_reply<L: BucketLogProvider>(
+        _peer: &Peer<L>,
+        recipient_node_id: &PublicKey,
+        reply: &AccessRequestReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        tracing::info!(
+            "Peer {} replied {:?} to our access request for bucket {}",
+            recipient_node_id.to_hex(),
+            reply.status,
+            reply.bucket_id
+        );
+        Ok(())
+    }
+}
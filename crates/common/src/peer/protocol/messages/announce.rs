@@ -0,0 +1,423 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::bucket_log::BucketLogProvider;
+use crate::crypto::PublicKey;
+use crate::linked_data::Link;
+use crate::mount::Manifest;
+use crate::peer::protocol::bidirectional::BidirectionalHandler;
+use crate::peer::protocol::messages::Message;
+use crate::peer::subscriptions::SUBSCRIPTION_LEASE;
+use crate::peer::sync::{PingPeerJob, SyncBucketJob, SyncJob, SyncTarget};
+use crate::peer::Peer;
+
+/// What an announce message asks of the receiving peer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AnnounceKind {
+    /// Push new heads of the bucket to the sender until the lease runs out
+    Subscribe,
+    /// Stop pushing heads of the bucket to the sender
+    Unsubscribe,
+    /// The sender saved a new head
+    Head(Link, u64),
+}
+
+/// Subscribe to, or push, head announcements for a bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnounceMessage {
+    /// The bucket ID the announcement is about
+    pub bucket_id: Uuid,
+    /// What the sender wants
+    pub kind: AnnounceKind,
+}
+
+/// Outcome of an announce message
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AnnounceStatus {
+    /// The subscription is active for this many seconds
+    Subscribed(u64),
+    /// The subscription was dropped
+    Unsubscribed,
+    /// The announced head was received
+    Received,
+    /// The announced head was ignored, the sender is not in the bucket's shares
+    Ignored,
+    /// The subscription was refused, the sender is not in the bucket's
+    /// shares (or we don't have the bucket)
+    Denied,
+}
+
+/// Response to an announce message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnounceReply {
+    /// The bucket ID being responded to
+    pub bucket_id: Uuid,
+    /// The outcome
+    pub status: AnnounceStatus,
+}
+
+/// Announce handler for push-based head updates
+///
+/// Subscribers learn about new heads as soon as they are saved. Peers
+/// without a subscription fall back to the periodic [`Ping`] exchange.
+///
+/// [`Ping`]: super::ping::Ping
+pub struct Announce;
+
+impl BidirectionalHandler for Announce {
+    type Message = AnnounceMessage;
+    type Reply = AnnounceReply;
+
+    /// Wrap the request in the Message enum for proper serialization
+    fn wrap_request(request: Self::Message) -> Message {
+        Message::Announce(request)
+    }
+
+    // ========================================
+    // RESPONDER SIDE: When we receive an announcement
+    // ========================================
+
+    /// Manage the sender's subscription or acknowledge its head
+    async fn handle_message<L: BucketLogProvider>(
+        peer: &Peer<L>,
+        sender_node_id: &PublicKey,
+        message: &AnnounceMessage,
+    ) -> AnnounceReply {
+        let bucket_id = message.bucket_id;
+        let reply = |status| AnnounceReply { bucket_id, status };
+
+        // Only peers the bucket is shared with may subscribe or announce;
+        //  a head for a bucket we don't have yet is a new share, and sync
+        //  verifies we are in its shares before accepting it
+        let head = peer.logs().head(bucket_id, None).await.ok();
+        let is_member = match head {
+            Some((head, _)) => match peer.blobs().get_cbor::<Manifest>(&head.hash()).await {
+                Ok(manifest) => manifest.get_share(sender_node_id).is_some(),
+                Err(e) => {
+                    tracing::warn!("Failed to load head manifest for {}: {}", bucket_id, e);
+                    false
+                }
+            },
+            None => matches!(message.kind, AnnounceKind::Head(..)),
+        };
+        if !is_member {
+            return match message.kind {
+                AnnounceKind::Head(..) => reply(AnnounceStatus::Ignored),
+                _ => reply(AnnounceStatus::Denied),
+            };
+        }
+
+        let subscriptions = peer.subscriptions();
+        match &message.kind {
+            AnnounceKind::Subscribe => {
+                subscriptions.add_subscriber(bucket_id, *sender_node_id, SUBSCRIPTION_LEASE);
+                reply(AnnounceStatus::Subscribed(SUBSCRIPTION_LEASE.as_secs()))
+            }
+            AnnounceKind::Unsubscribe => {
+                subscriptions.remove_subscriber(bucket_id, sender_node_id);
+                reply(AnnounceStatus::Unsubscribed)
+            }
+            AnnounceKind::Head(..) => reply(AnnounceStatus::Received),
+        }
+    }
+
+    /// Sync announced heads that are ahead of ours
+    async fn handle_message_side_effect<L: BucketLogProvider>(
+        peer: &Peer<L>,
+        sender_node_id: &PublicKey,
+        message: &AnnounceMessage,
+        reply: &AnnounceReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let AnnounceKind::Head(link, height) = &message.kind else {
+            return Ok(());
+        };
+        if reply.status != AnnounceStatus::Received {
+            return Ok(());
+        }
+
+        // Same rule as a ping: we are behind if lower, or at the same height
+        //  with a lower link
+        let head = peer.logs().head(message.bucket_id, None).await.ok();
+        let peer_ids = match head {
+            Some((our_link, our_height)) => {
+                let behind = our_height < *height || (our_height == *height && our_link < *link);
+                if !behind {
+                    tracing::debug!(
+                        "Ignoring announced head {:?} for bucket {}, not ahead of ours",
+                        link,
+                        message.bucket_id
+                    );
+                    return Ok(());
+                }
+                match peer.blobs().get_cbor::<Manifest>(&our_link.hash()).await {
+                    Ok(manifest) => manifest.get_peer_ids(),
+                    Err(_) => vec![*sender_node_id],
+                }
+            }
+            None => vec![*sender_node_id],
+        };
+
+        tracing::info!(
+            "Peer {} announced head {:?} at height {} for bucket {}, dispatching sync job",
+            sender_node_id.to_hex(),
+            link,
+            height,
+            message.bucket_id
+        );
+        peer.dispatch(SyncJob::SyncBucket(SyncBucketJob {
+            bucket_id: message.bucket_id,
+            target: SyncTarget {
+                link: link.clone(),
+                height: *height,
+                peer_ids,
+            },
+        }))
+        .await
+    }
+
+    // ========================================
+    // INITIATOR SIDE: When we receive a reply
+    // ========================================
+
+    /// Track our subscription to the recipient
+    async fn handle_reply<L: BucketLogProvider>(
+        peer: &Peer<L>,
+        recipient_node_id: &PublicKey,
+        reply: &AnnounceReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let subscriptions = peer.subscriptions();
+        match &reply.status {
+            AnnounceStatus::Subscribed(lease_secs) => {
+                let lease = std::time::Duration::from_secs(*lease_secs);
+                let was_active =
+                    subscriptions.subscribed(reply.bucket_id, *recipient_node_id, lease);
+                if !was_active {
+                    // Heads announced while we weren't subscribed were missed,
+                    //  so compare heads once to catch up
+                    tracing::info!(
+                        "Subscribed to peer {} for bucket {}",
+                        recipient_node_id.to_hex(),
+                        reply.bucket_id
+                    );
+                    peer.dispatch(SyncJob::PingPeer(PingPeerJob {
+                        bucket_id: reply.bucket_id,
+                        peer_id: *recipient_node_id,
+                    }))
+                    .await?;
+                }
+            }
+            AnnounceStatus::Denied => {
+                let backoff = subscriptions.subscribe_failed(reply.bucket_id, *recipient_node_id);
+                tracing::debug!(
+                    "Peer {} denied announcements for bucket {}, retrying in {:?}",
+                    recipient_node_id.to_hex(),
+                    reply.bucket_id,
+                    backoff
+                );
+            }
+            AnnounceStatus::Unsubscribed | AnnounceStatus::Received | AnnounceStatus::Ignored => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::bucket_log::MemoryBucketLogProvider;
+    use crate::crypto::SecretKey;
+    use crate::mount::Mount;
+    use crate::peer::{BlobsStore, PeerBuilder, SyncProvider};
+
+    /// Keeps the jobs a peer dispatches instead of running them
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<SyncJob>>);
+
+    #[async_trait::async_trait]
+    impl SyncProvider<MemoryBucketLogProvider> for Recorder {
+        async fn execute(&self, _peer: &Peer<MemoryBucketLogProvider>, job: SyncJob) -> Result<()> {
+            self.0.lock().unwrap().push(job);
+            Ok(())
+        }
+    }
+
+    /// Alice with a bucket shared with `member`, and the jobs she dispatches
+    async fn alice_sharing_with(
+        member: &PublicKey,
+    ) -> (Peer<MemoryBucketLogProvider>, Arc<Recorder>, Uuid, Link) {
+        let blobs = BlobsStore::memory().await.unwrap();
+        let key = SecretKey::generate();
+        let recorder = Arc::new(Recorder::default());
+        let alice = PeerBuilder::new()
+            .with_sync_provider(recorder.clone())
+            .log_provider(MemoryBucketLogProvider::new())
+            .blobs_store(blobs.clone())
+            .secret_key(key.clone())
+            .build()
+            .await;
+
+        let bucket_id = Uuid::new_v4();
+        let mut mount = Mount::init(bucket_id, "announce".to_string(), &key, &blobs)
+            .await
+            .unwrap();
+        mount.add_owner(*member).await.unwrap();
+        let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+        alice
+            .logs()
+            .append(
+                bucket_id,
+                "announce".to_string(),
+                link.clone(),
+                vec![],
+                0,
+                false,
+            )
+            .await
+            .unwrap();
+        (alice, recorder, bucket_id, link)
+    }
+
+    fn announce(bucket_id: Uuid, kind: AnnounceKind) -> AnnounceMessage {
+        AnnounceMessage { bucket_id, kind }
+    }
+
+    #[tokio::test]
+    async fn test_announce_requires_a_share() {
+        let bob = SecretKey::generate().public();
+        let carol = SecretKey::generate().public();
+        let (alice, _, bucket_id, link) = alice_sharing_with(&bob).await;
+        let subscriptions = alice.subscriptions();
+
+        let reply =
+            Announce::handle_message(&alice, &bob, &announce(bucket_id, AnnounceKind::Subscribe))
+                .await;
+        assert_eq!(
+            reply.status,
+            AnnounceStatus::Subscribed(SUBSCRIPTION_LEASE.as_secs())
+        );
+        assert!(subscriptions.is_subscriber(bucket_id, &bob));
+
+        // Peers outside the shares can't subscribe or announce
+        let reply = Announce::handle_message(
+            &alice,
+            &carol,
+            &announce(bucket_id, AnnounceKind::Subscribe),
+        )
+        .await;
+        assert_eq!(reply.status, AnnounceStatus::Denied);
+        assert!(!subscriptions.is_subscriber(bucket_id, &carol));
+        let head = AnnounceKind::Head(link, 1);
+        let reply =
+            Announce::handle_message(&alice, &carol, &announce(bucket_id, head.clone())).await;
+        assert_eq!(reply.status, AnnounceStatus::Ignored);
+
+        let reply = Announce::handle_message(
+            &alice,
+            &bob,
+            &announce(bucket_id, AnnounceKind::Unsubscribe),
+        )
+        .await;
+        assert_eq!(reply.status, AnnounceStatus::Unsubscribed);
+        assert!(!subscriptions.is_subscriber(bucket_id, &bob));
+
+        // A head for a bucket we don't have may be a new share, sync checks it
+        let unknown = Uuid::new_v4();
+        let reply = Announce::handle_message(&alice, &carol, &announce(unknown, head)).await;
+        assert_eq!(reply.status, AnnounceStatus::Received);
+        let reply =
+            Announce::handle_message(&alice, &carol, &announce(unknown, AnnounceKind::Subscribe))
+                .await;
+        assert_eq!(reply.status, AnnounceStatus::Denied);
+    }
+
+    #[tokio::test]
+    async fn test_announced_head_syncs_when_ahead() {
+        let bob = SecretKey::generate().public();
+        let (alice, recorder, bucket_id, link) = alice_sharing_with(&bob).await;
+        let received = |status| AnnounceReply { bucket_id, status };
+
+        // Our own head isn't news
+        let message = announce(bucket_id, AnnounceKind::Head(link.clone(), 0));
+        Announce::handle_message_side_effect(
+            &alice,
+            &bob,
+            &message,
+            &received(AnnounceStatus::Received),
+        )
+        .await
+        .unwrap();
+        assert!(recorder.0.lock().unwrap().is_empty());
+
+        // An ignored head isn't synced either
+        let message = announce(bucket_id, AnnounceKind::Head(link.clone(), 1));
+        Announce::handle_message_side_effect(
+            &alice,
+            &bob,
+            &message,
+            &received(AnnounceStatus::Ignored),
+        )
+        .await
+        .unwrap();
+        assert!(recorder.0.lock().unwrap().is_empty());
+
+        Announce::handle_message_side_effect(
+            &alice,
+            &bob,
+            &message,
+            &received(AnnounceStatus::Received),
+        )
+        .await
+        .unwrap();
+        let jobs = recorder.0.lock().unwrap();
+        assert!(
+            matches!(
+                jobs.as_slice(),
+                [SyncJob::SyncBucket(job)] if job.target.height == 1 && job.target.peer_ids.contains(&bob)
+            ),
+            "{:?}",
+            jobs
+        );
+    }
+
+    #[tokio::test]
+    async fn test_announce_reply_tracks_subscription() {
+        let bob = SecretKey::generate().public();
+        let (alice, recorder, bucket_id, _) = alice_sharing_with(&bob).await;
+        let subscriptions = alice.subscriptions();
+        let reply = |status| AnnounceReply { bucket_id, status };
+
+        // A new subscription catches up with a ping, a renewal doesn't
+        let subscribed = reply(AnnounceStatus::Subscribed(SUBSCRIPTION_LEASE.as_secs()));
+        Announce::handle_reply(&alice, &bob, &subscribed)
+            .await
+            .unwrap();
+        assert!(subscriptions.is_subscribed(bucket_id, &bob));
+        Announce::handle_reply(&alice, &bob, &subscribed)
+            .await
+            .unwrap();
+        {
+            let jobs = recorder.0.lock().unwrap();
+            assert!(
+                matches!(jobs.as_slice(), [SyncJob::PingPeer(job)] if job.peer_id == bob),
+                "{:?}",
+                jobs
+            );
+        }
+
+        // A denial backs off
+        Announce::handle_reply(&alice, &bob, &reply(AnnounceStatus::Denied))
+            .await
+            .unwrap();
+        assert!(!subscriptions.is_subscribed(bucket_id, &bob));
+        assert!(!subscriptions.should_subscribe(bucket_id, &bob));
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::bucket_log::BucketLogProvider;
+use crate::crypto::PublicKey;
+use crate::linked_data::Link;
+use crate::mount::{Manifest, PrincipalRole, INBOX_PATH};
+use crate::peer::protocol::bidirectional::BidirectionalHandler;
+use crate::peer::protocol::messages::Message;
+use crate::peer::sync::{AcceptDepositJob, SyncJob};
+use crate::peer::Peer;
+
+/// Submit a deposit to an owner of a bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositMessage {
+    /// The bucket the deposit is for
+    pub bucket_id: Uuid,
+    /// Link to the [`Deposit`](crate::mount::Deposit) object on the sender
+    pub link: Link,
+}
+
+/// Outcome of a deposit submission
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DepositStatus {
+    /// The deposit will be fetched and added to the inbox
+    Accepted,
+    /// We can't take the deposit: we don't have the bucket, aren't an owner
+    /// allowed to write the inbox, or the sender is not a depositor
+    Refused,
+}
+
+/// Response to a deposit submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositReply {
+    /// The bucket ID being responded to
+    pub bucket_id: Uuid,
+    /// The outcome
+    pub status: DepositStatus,
+}
+
+/// Deposit handler for files submitted by depositors
+///
+/// The reply only says whether we will take the deposit; it is fetched,
+/// verified and linked into the inbox by an [`AcceptDepositJob`].
+pub struct SubmitDeposit;
+
+impl BidirectionalHandler for SubmitDeposit {
+    type Message = DepositMessage;
+    type Reply = DepositReply;
+
+    /// Wrap the request in the Message enum for proper serialization
+    fn wrap_request(request: Self::Message) -> Message {
+        Message::SubmitDeposit(request)
+    }
+
+    // ========================================
+    // RESPONDER SIDE: When we receive a deposit
+    // ========================================
+
+    /// Accept deposits from depositors of buckets we can write the inbox of
+    async fn handle_message<L: BucketLogProvider>(
+        peer: &Peer<L>,
+        sender_node_id: &PublicKey,
+        message: &DepositMessage,
+    ) -> DepositReply {
+        let bucket_id = message.bucket_id;
+        let reply = |status| DepositReply { bucket_id, status };
+
+        let Ok((head, _)) = peer.logs().head(bucket_id, None).await else {
+            return reply(DepositStatus::Refused);
+        };
+        let manifest = match peer.blobs().get_cbor::<Manifest>(&head.hash()).await {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                tracing::warn!("Failed to load head manifest for {}: {}", bucket_id, e);
+                return reply(DepositStatus::Refused);
+            }
+        };
+
+        let can_accept = manifest
+            .get_share(&peer.secret().public())
+            .is_some_and(|share| {
+                *share.role() == PrincipalRole::Owner
+                    && share.can_write(std::path::Path::new(INBOX_PATH))
+            });
+        let is_depositor = manifest
+            .get_share(sender_node_id)
+            .is_some_and(|share| *share.role() == PrincipalRole::Depositor);
+        if can_accept && is_depositor {
+            reply(DepositStatus::Accepted)
+        } else {
+            reply(DepositStatus::Refused)
+        }
+    }
+
+    /// Fetch and apply accepted deposits in the background
+    async fn handle_message_side_effect<L: BucketLogProvider>(
+        peer: &Peer<L>,
+        sender_node_id: &PublicKey,
+        message: &DepositMessage,
+        reply: &DepositReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        if reply.status != DepositStatus::Accepted {
+            return Ok(());
+        }
+
+        tracing::info!(
+            "Peer {} submitted deposit {:?} for bucket {}, dispatching accept job",
+            sender_node_id.to_hex(),
+            message.link,
+            message.bucket_id
+        );
+        peer.dispatch(SyncJob::AcceptDeposit(AcceptDepositJob {
+            bucket_id: message.bucket_id,
+            link: message.link.clone(),
+            peer_ids: vec![*sender_node_id],
+        }))
+        .await
+    }
+
+    // ========================================
+    // INITIATOR SIDE: When we receive a reply
+    // ========================================
+
+    /// Nothing to track; the depositor reads the status from the reply
+    async fn handle_reply<L: BucketLogProvider>(
+        _peer: &Peer<L>,
+        recipient_node_id: &PublicKey,
+        reply: &DepositReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        tracing::debug!(
+            "Peer {} replied {:?} to deposit for bucket {}",
+            recipient_node_id.to_hex(),
+            reply.status,
+            reply.bucket_id
+        );
+        Ok(())
+    }
+}
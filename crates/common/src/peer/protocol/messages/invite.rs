@@ -0,0 +1,306 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::bucket_log::BucketLogProvider;
+use crate::clock::unix_now;
+use crate::crypto::PublicKey;
+use crate::mount::{Manifest, PrincipalRole};
+use crate::peer::invites::{hash_token, InviteToken};
+use crate::peer::protocol::bidirectional::BidirectionalHandler;
+use crate::peer::protocol::messages::Message;
+use crate::peer::{MembershipUpdate, Peer};
+
+/// Redeem an invite ticket to join a bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteMessage {
+    /// The bucket the ticket is for
+    pub bucket_id: Uuid,
+    /// The ticket's one-time token
+    pub token: InviteToken,
+}
+
+/// Outcome of redeeming an invite
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum InviteStatus {
+    /// We were added with this role, and the inviter pings us to sync; in a
+    /// bucket with an owner quorum, adding us was proposed instead
+    Accepted(PrincipalRole),
+    /// We are already in the bucket's shares; the ticket stays unused
+    AlreadyShared,
+    /// The token is unknown, expired or already used, or for another
+    /// bucket, or adding us failed
+    Refused,
+}
+
+/// Response to redeeming an invite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteReply {
+    /// The bucket ID being responded to
+    pub bucket_id: Uuid,
+    /// The outcome
+    pub status: InviteStatus,
+}
+
+/// Invite handler for adding a peer that redeems a ticket
+///
+/// The sender's node id is authenticated by the connection, so adding it to
+/// the shares proves it holds the matching key. The inviter takes the token
+/// out of its [`InviteStore`], adds the sender with the role the ticket was
+/// issued for and pings it before replying; the new peer doesn't have the
+/// bucket yet, so it syncs straight away. In a bucket with an owner quorum,
+/// adding it is only proposed to the other owners.
+///
+/// Taking the token is atomic, so of two peers redeeming the same ticket only
+/// one is accepted. If adding the sender fails the token is put back, and a
+/// sender that is already in the shares doesn't use it up.
+///
+/// [`InviteStore`]: crate::peer::InviteStore
+pub struct AcceptInvite;
+
+impl BidirectionalHandler for AcceptInvite {
+    type Message = InviteMessage;
+    type Reply = InviteReply;
+
+    /// Wrap the request in the Message enum for proper serialization
+    fn wrap_request(request: Self::Message) -> Message {
+        Message::AcceptInvite(request)
+    }
+
+    // ========================================
+    // RESPONDER SIDE: When a peer redeems a ticket
+    // ========================================
+
+    /// Redeem the token and add the sender to the bucket's shares
+    async fn handle_message<L: BucketLogProvider>(
+        peer: &Peer<L>,
+        sender_node_id: &PublicKey,
+        message: &InviteMessage,
+    ) -> InviteReply
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let bucket_id = message.bucket_id;
+        let reply = |status| InviteReply { bucket_id, status };
+
+        let Ok((head, _)) = peer.logs().head(bucket_id, None).await else {
+            return reply(InviteStatus::Refused);
+        };
+        match peer.blobs().get_cbor::<Manifest>(&head.hash()).await {
+            Ok(manifest) if manifest.get_share(sender_node_id).is_some() => {
+                return reply(InviteStatus::AlreadyShared);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!("Failed to load head manifest for {}: {}", bucket_id, e);
+                return reply(InviteStatus::Refused);
+            }
+        }
+
+        let token_hash = hash_token(&message.token);
+        let invite = match peer
+            .invites()
+            .take(bucket_id, &token_hash, unix_now())
+            .await
+        {
+            Ok(Some(invite)) => invite,
+            Ok(None) => {
+                tracing::info!(
+                    "Peer {} presented an unknown or expired invite for bucket {}",
+                    sender_node_id.to_hex(),
+                    bucket_id
+                );
+                return reply(InviteStatus::Refused);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to redeem invite for {}: {}", bucket_id, e);
+                return reply(InviteStatus::Refused);
+            }
+        };
+
+        let role = invite.role.clone();
+        match peer
+            .add_member(bucket_id, *sender_node_id, role.clone())
+            .await
+        {
+            Ok(update) => {
+                tracing::info!(
+                    "Invited peer {} joins bucket {} as {:?}: {}",
+                    sender_node_id.to_hex(),
+                    bucket_id,
+                    role,
+                    match update {
+                        MembershipUpdate::Saved(_) => "added".to_string(),
+                        MembershipUpdate::Proposed { id, .. } => format!("proposed as {}", id),
+                    }
+                );
+                reply(InviteStatus::Accepted(role))
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to add invited peer {} to bucket {}: {}",
+                    sender_node_id.to_hex(),
+                    bucket_id,
+                    e
+                );
+                if let Err(e) = peer.invites().insert(&token_hash, &invite).await {
+                    tracing::warn!("Failed to restore invite for {}: {}", bucket_id, e);
+                }
+                reply(InviteStatus::Refused)
+            }
+        }
+    }
+
+    /// Nothing to do; the sender was added before replying
+    async fn handle_message_side_effect<L: BucketLogProvider>(
+        _peer: &Peer<L>,
+        _sender_node_id: &PublicKey,
+        _message: &InviteMessage,
+        _reply: &InviteReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        Ok(())
+    }
+
+    // ========================================
+    // INITIATOR SIDE: When the inviter replies
+    // ========================================
+
+    /// Nothing to do; the inviter pings us once we are added
+    async fn handle_reply<L: BucketLogProvider>(
+        _peer: &Peer<L>,
+        recipient_node_id: &PublicKey,
+        reply: &InviteReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        tracing::info!(
+            "Peer {} replied {:?} to our invite for bucket {}",
+            recipient_node_id.to_hex(),
+            reply.status,
+            reply.bucket_id
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::bucket_log::MemoryBucketLogProvider;
+    use crate::crypto::SecretKey;
+    use crate::mount::Mount;
+    use crate::peer::{BlobsStore, PeerBuilder, SyncJob, SyncProvider};
+
+    /// Keeps the jobs a peer dispatches instead of running them
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<SyncJob>>);
+
+    #[async_trait::async_trait]
+    impl SyncProvider<MemoryBucketLogProvider> for Recorder {
+        async fn execute(&self, _peer: &Peer<MemoryBucketLogProvider>, job: SyncJob) -> Result<()> {
+            self.0.lock().unwrap().push(job);
+            Ok(())
+        }
+    }
+
+    /// Alice with a bucket shared with `member`
+    async fn alice_sharing_with(member: &PublicKey) -> (Peer<MemoryBucketLogProvider>, Uuid) {
+        let blobs = BlobsStore::memory().await.unwrap();
+        let key = SecretKey::generate();
+        let alice = PeerBuilder::new()
+            .with_sync_provider(Arc::new(Recorder::default()))
+            .log_provider(MemoryBucketLogProvider::new())
+            .blobs_store(blobs.clone())
+            .secret_key(key.clone())
+            .build()
+            .await;
+
+        let bucket_id = Uuid::new_v4();
+        let mut mount = Mount::init(bucket_id, "invite".to_string(), &key, &blobs)
+            .await
+            .unwrap();
+        alice
+            .logs()
+            .append(
+                bucket_id,
+                "invite".to_string(),
+                mount.link().await,
+                vec![],
+                0,
+                false,
+            )
+            .await
+            .unwrap();
+        mount.add_mirror(*member).await;
+        alice.save_mount(&mount, false).await.unwrap();
+        (alice, bucket_id)
+    }
+
+    async fn is_shared(
+        alice: &Peer<MemoryBucketLogProvider>,
+        bucket_id: Uuid,
+        peer: &PublicKey,
+    ) -> bool {
+        let mount = alice.mount(bucket_id).await.unwrap();
+        let shared = mount.inner().await.manifest().get_share(peer).is_some();
+        shared
+    }
+
+    #[tokio::test]
+    async fn test_existing_member_keeps_the_ticket() {
+        let bob = SecretKey::generate().public();
+        let carol = SecretKey::generate().public();
+        let (alice, bucket_id) = alice_sharing_with(&bob).await;
+        let (ticket, _) = alice
+            .invite(bucket_id, PrincipalRole::Reader, 3600)
+            .await
+            .unwrap();
+        let message = InviteMessage {
+            bucket_id,
+            token: ticket.token,
+        };
+
+        let reply = AcceptInvite::handle_message(&alice, &bob, &message).await;
+        assert_eq!(reply.status, InviteStatus::AlreadyShared);
+
+        // The ticket still works for someone who isn't in the shares yet
+        let reply = AcceptInvite::handle_message(&alice, &carol, &message).await;
+        assert_eq!(reply.status, InviteStatus::Accepted(PrincipalRole::Reader));
+        assert!(is_shared(&alice, bucket_id, &carol).await);
+    }
+
+    #[tokio::test]
+    async fn test_racing_redemptions_accept_one() {
+        let bob = SecretKey::generate().public();
+        let carol = SecretKey::generate().public();
+        let dave = SecretKey::generate().public();
+        let (alice, bucket_id) = alice_sharing_with(&bob).await;
+        let (ticket, _) = alice
+            .invite(bucket_id, PrincipalRole::Mirror, 3600)
+            .await
+            .unwrap();
+        let message = InviteMessage {
+            bucket_id,
+            token: ticket.token,
+        };
+
+        let (carols, daves) = tokio::join!(
+            AcceptInvite::handle_message(&alice, &carol, &message),
+            AcceptInvite::handle_message(&alice, &dave, &message),
+        );
+        let accepted = InviteStatus::Accepted(PrincipalRole::Mirror);
+        let (winner, loser) = match (carols.status, daves.status) {
+            (status, InviteStatus::Refused) if status == accepted => (carol, dave),
+            (InviteStatus::Refused, status) if status == accepted => (dave, carol),
+            statuses => panic!("expected one acceptance, got {:?}", statuses),
+        };
+        assert!(is_shared(&alice, bucket_id, &winner).await);
+        assert!(!is_shared(&alice, bucket_id, &loser).await);
+    }
+}
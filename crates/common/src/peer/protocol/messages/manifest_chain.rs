@@ -0,0 +1,409 @@
+use std::collections::{BinaryHeap, HashSet};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::bucket_log::{BucketLogError, BucketLogProvider};
+use crate::crypto::PublicKey;
+use crate::linked_data::{Link, LD_CBOR_CODEC};
+use crate::mount::Manifest;
+use crate::peer::protocol::bidirectional::BidirectionalHandler;
+use crate::peer::protocol::messages::Message;
+use crate::peer::Peer;
+
+/// Maximum number of manifest links returned in a single reply
+///
+/// Keeps replies well under the protocol's 1 MiB message limit. Longer
+/// histories are truncated to the newest versions; the requester falls back to
+/// walking the remainder one manifest at a time.
+pub const MAX_CHAIN_LENGTH: usize = 4_096;
+
+/// Request the manifest links between a version and our known history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestChainMessage {
+    /// The bucket ID to walk
+    pub bucket_id: Uuid,
+    /// The version to walk back from (typically the responder's head)
+    pub from: Link,
+    /// Versions the requester already has, see [`known_versions`]
+    pub known: Vec<Link>,
+}
+
+/// Response to a manifest chain request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestChainReply {
+    /// The bucket ID being responded to
+    pub bucket_id: Uuid,
+    /// The missing manifest links, oldest first, ending with `from`
+    ///
+    /// Empty if the requester has everything, or the responder doesn't have
+    /// `from` or won't serve the bucket.
+    pub links: Vec<Link>,
+    /// Whether the chain reaches our known history (or genesis)
+    ///
+    /// `false` when it was cut off at [`MAX_CHAIN_LENGTH`], or unavailable.
+    pub complete: bool,
+}
+
+impl ManifestChainReply {
+    /// Create a reply carrying no chain
+    pub fn unavailable(bucket_id: Uuid) -> Self {
+        Self {
+            bucket_id,
+            links: Vec::new(),
+            complete: false,
+        }
+    }
+
+    /// Check the reply before downloading anything it lists
+    ///
+    /// A well-formed chain holds at most [`MAX_CHAIN_LENGTH`] distinct
+    /// DAG-CBOR links (the codec manifests are stored with) and ends with the
+    /// version we asked for.
+    pub fn validate(&self, from: &Link) -> Result<()> {
+        if self.links.len() > MAX_CHAIN_LENGTH {
+            return Err(anyhow!(
+                "chain of {} links exceeds the limit of {}",
+                self.links.len(),
+                MAX_CHAIN_LENGTH
+            ));
+        }
+        if let Some(link) = self.links.iter().find(|link| link.codec() != LD_CBOR_CODEC) {
+            return Err(anyhow!("{:?} is not a manifest link", link));
+        }
+        if self.links.last().is_some_and(|last| last != from) {
+            return Err(anyhow!("chain does not end with {:?}", from));
+        }
+        let distinct: HashSet<&Link> = self.links.iter().collect();
+        if distinct.len() != self.links.len() {
+            return Err(anyhow!("chain lists a link more than once"));
+        }
+        Ok(())
+    }
+}
+
+/// Manifest chain handler for fetching bucket history in one exchange
+///
+/// Instead of downloading one manifest per round trip to discover its
+/// parents, the requester sends a sample of its log and the responder replies
+/// with every manifest link it is missing, which can be fetched in bulk with
+/// [`BlobsStore::download_hashes`]. The links travel in the reply itself, so
+/// serving a request stores nothing.
+///
+/// [`BlobsStore::download_hashes`]: crate::peer::BlobsStore::download_hashes
+pub struct GetManifestChain;
+
+impl BidirectionalHandler for GetManifestChain {
+    type Message = ManifestChainMessage;
+    type Reply = ManifestChainReply;
+
+    /// Wrap the request in the Message enum for proper serialization
+    fn wrap_request(request: Self::Message) -> Message {
+        Message::GetManifestChain(request)
+    }
+
+    // ========================================
+    // RESPONDER SIDE: When we receive a request
+    // ========================================
+
+    /// Walk our log back from `from` and reply with the missing links
+    async fn handle_message<L: BucketLogProvider>(
+        peer: &Peer<L>,
+        sender_node_id: &PublicKey,
+        message: &ManifestChainMessage,
+    ) -> ManifestChainReply {
+        let logs = peer.logs();
+        let bucket_id = message.bucket_id;
+
+        // Only serve history to peers the bucket is shared with
+        let Ok((head, _)) = logs.head(bucket_id, None).await else {
+            return ManifestChainReply::unavailable(bucket_id);
+        };
+        match peer.blobs().get_cbor::<Manifest>(&head.hash()).await {
+            Ok(manifest) if manifest.get_share(sender_node_id).is_some() => {}
+            Ok(_) => {
+                tracing::warn!(
+                    "Peer {} requested manifest chain for bucket {} without a share",
+                    sender_node_id.to_hex(),
+                    bucket_id
+                );
+                return ManifestChainReply::unavailable(bucket_id);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load head manifest for {}: {}", bucket_id, e);
+                return ManifestChainReply::unavailable(bucket_id);
+            }
+        }
+
+        match chain_since(
+            logs,
+            bucket_id,
+            &message.from,
+            &message.known,
+            MAX_CHAIN_LENGTH,
+        )
+        .await
+        {
+            Ok(Some((links, complete))) => ManifestChainReply {
+                bucket_id,
+                links,
+                complete,
+            },
+            Ok(None) => ManifestChainReply::unavailable(bucket_id),
+            Err(e) => {
+                tracing::warn!("Failed to walk manifest chain for {}: {}", bucket_id, e);
+                ManifestChainReply::unavailable(bucket_id)
+            }
+        }
+    }
+
+    // ========================================
+    // INITIATOR SIDE: When we receive a reply
+    // ========================================
+
+    /// The caller downloads the listed manifests; nothing else to do here
+    async fn handle_reply<L: BucketLogProvider>(
+        _peer: &Peer<L>,
+        recipient_node_id: &PublicKey,
+        reply: &ManifestChainReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        tracing::debug!(
+            "Peer {} returned {} manifest links for bucket {} (complete: {})",
+            recipient_node_id.to_hex(),
+            reply.links.len(),
+            reply.bucket_id,
+            reply.complete
+        );
+        Ok(())
+    }
+}
+
+/// Sample our log for a [`ManifestChainMessage`]
+///
+/// Returns the heads at the current height and at exponentially spaced heights
+/// below it (h, h-1, h-2, h-4, ... 0), so the responder can place our history
+/// with a logarithmic number of links.
+pub async fn known_versions<L: BucketLogProvider>(
+    logs: &L,
+    bucket_id: Uuid,
+) -> Result<Vec<Link>, BucketLogError<L::Error>> {
+    let height = logs.height(bucket_id).await?;
+
+    let mut known = Vec::new();
+    let mut offset = 0;
+    loop {
+        let current = height.saturating_sub(offset);
+        known.extend(logs.heads(bucket_id, current).await?);
+        if current == 0 {
+            break;
+        }
+        offset = if offset == 0 { 1 } else { offset * 2 };
+    }
+    Ok(known)
+}
+
+/// Collect the versions reachable from `from` that the requester is missing
+///
+/// Walks the version DAG highest first. Known links the requester sent, and
+/// everything below them, count as shared; the walk stops once only shared
+/// versions remain. Returns `None` if `from` isn't in our log, otherwise the
+/// missing links oldest first and whether the walk finished within `limit`.
+pub async fn chain_since<L: BucketLogProvider>(
+    logs: &L,
+    bucket_id: Uuid,
+    from: &Link,
+    known: &[Link],
+    limit: usize,
+) -> Result<Option<(Vec<Link>, bool)>, BucketLogError<L::Error>> {
+    let Some(from_height) = logs.has(bucket_id, from.clone()).await?.into_iter().max() else {
+        return Ok(None);
+    };
+
+    let mut shared: HashSet<Link> = HashSet::new();
+    let mut queue = BinaryHeap::new();
+    for link in known {
+        if let Some(height) = logs.has(bucket_id, link.clone()).await?.into_iter().max() {
+            shared.insert(link.clone());
+            queue.push((height, link.clone()));
+        }
+    }
+    queue.push((from_height, from.clone()));
+
+    let mut visited = HashSet::new();
+    let mut chain = Vec::new();
+    let mut complete = true;
+    while let Some((_, link)) = queue.pop() {
+        if !visited.insert(link.clone()) {
+            continue;
+        }
+
+        let parents = logs.parents(bucket_id, link.clone()).await?;
+        // Every descendant has been popped already, so a shared link is
+        //  marked by now
+        let is_shared = shared.contains(&link);
+        if is_shared {
+            shared.extend(parents.iter().cloned());
+        } else {
+            if chain.len() == limit {
+                complete = false;
+                break;
+            }
+            chain.push(link);
+        }
+
+        for parent in parents {
+            if visited.contains(&parent) {
+                continue;
+            }
+            if let Some(height) = logs.has(bucket_id, parent.clone()).await?.into_iter().max() {
+                queue.push((height, parent));
+            }
+        }
+
+        // Stop once nothing missing is left to explore
+        if queue.iter().all(|(_, link)| shared.contains(link)) {
+            break;
+        }
+    }
+
+    chain.reverse();
+    Ok(Some((chain, complete)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bucket_log::MemoryBucketLogProvider;
+    use crate::linked_data::Hash;
+
+    fn link(n: u8) -> Link {
+        Link::new(0x71, Hash::from_bytes([n; 32]))
+    }
+
+    /// Append a linear chain of `len` versions, links 1..=len
+    async fn linear_log(len: u8) -> (MemoryBucketLogProvider, Uuid) {
+        let logs = MemoryBucketLogProvider::new();
+        let id = Uuid::new_v4();
+        for n in 1..=len {
+            let parents = if n == 1 { vec![] } else { vec![link(n - 1)] };
+            logs.append(id, "test".into(), link(n), parents, (n - 1) as u64, false)
+                .await
+                .unwrap();
+        }
+        (logs, id)
+    }
+
+    #[tokio::test]
+    async fn test_chain_since_stops_at_known() {
+        let (logs, id) = linear_log(6).await;
+
+        let (chain, complete) = chain_since(&logs, id, &link(6), &[link(3)], MAX_CHAIN_LENGTH)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(chain, vec![link(4), link(5), link(6)]);
+        assert!(complete);
+    }
+
+    #[tokio::test]
+    async fn test_chain_since_without_known_reaches_genesis() {
+        let (logs, id) = linear_log(3).await;
+
+        let (chain, complete) = chain_since(&logs, id, &link(3), &[link(99)], MAX_CHAIN_LENGTH)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(chain, vec![link(1), link(2), link(3)]);
+        assert!(complete);
+
+        // Unknown starting points yield nothing
+        assert!(chain_since(&logs, id, &link(99), &[], MAX_CHAIN_LENGTH)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_chain_since_includes_merged_branch() {
+        // 1 <- 2 <- 3 <- 5 (merge of 3 and 4)
+        //      ^--- 4 --^
+        let (logs, id) = linear_log(3).await;
+        logs.append(id, "test".into(), link(4), vec![link(2)], 2, false)
+            .await
+            .unwrap();
+        logs.append(id, "test".into(), link(5), vec![link(3), link(4)], 3, false)
+            .await
+            .unwrap();
+
+        // The requester knows branch 3 only
+        let (chain, complete) = chain_since(&logs, id, &link(5), &[link(3)], MAX_CHAIN_LENGTH)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(chain, vec![link(4), link(5)]);
+        assert!(complete);
+    }
+
+    #[tokio::test]
+    async fn test_chain_since_truncates_at_limit() {
+        let (logs, id) = linear_log(5).await;
+
+        let (chain, complete) = chain_since(&logs, id, &link(5), &[], 2)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(chain, vec![link(4), link(5)]);
+        assert!(!complete);
+    }
+
+    #[test]
+    fn test_validate_checks_chain_before_download() {
+        let reply = |links: Vec<Link>| ManifestChainReply {
+            bucket_id: Uuid::new_v4(),
+            links,
+            complete: true,
+        };
+
+        assert!(reply(vec![link(1), link(2)]).validate(&link(2)).is_ok());
+        assert!(reply(vec![]).validate(&link(2)).is_ok());
+
+        // Chains must end at the requested version, without repeats
+        assert!(reply(vec![link(1), link(2)]).validate(&link(3)).is_err());
+        assert!(reply(vec![link(2), link(1), link(2)])
+            .validate(&link(2))
+            .is_err());
+
+        // Only manifest links, and no more than the limit
+        let raw = Link::new(crate::linked_data::LD_RAW_CODEC, Hash::from_bytes([1; 32]));
+        assert!(reply(vec![raw, link(2)]).validate(&link(2)).is_err());
+        let long: Vec<Link> = (0..=MAX_CHAIN_LENGTH as u32)
+            .map(|n| Link::new(LD_CBOR_CODEC, Hash::new(n.to_le_bytes())))
+            .collect();
+        let last = long.last().unwrap().clone();
+        assert!(reply(long).validate(&last).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_known_versions_samples_exponentially() {
+        let (logs, id) = linear_log(20).await;
+
+        // Heights 19, 18, 17, 15, 11, 3, 0
+        let known = known_versions(&logs, id).await.unwrap();
+        assert_eq!(
+            known,
+            vec![
+                link(20),
+                link(19),
+                link(18),
+                link(16),
+                link(12),
+                link(4),
+                link(1)
+            ]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::bucket_log::BucketLogProvider;
+use crate::crypto::PublicKey;
+use crate::mount::{Manifest, MembershipChange};
+use crate::peer::proposals::applies_to;
+use crate::peer::protocol::bidirectional::BidirectionalHandler;
+use crate::peer::protocol::messages::Message;
+use crate::peer::Peer;
+
+/// Propose a membership change, with the approvals collected so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MembershipMessage {
+    pub change: MembershipChange,
+}
+
+/// Outcome of a membership proposal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MembershipStatus {
+    /// The proposal is waiting for our approval, or has all it needs
+    Received,
+    /// We aren't an owner that approves changes to the bucket, the sender
+    /// isn't either, or the change doesn't build on our head
+    Refused,
+}
+
+/// Response to a membership proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MembershipReply {
+    /// The bucket ID being responded to
+    pub bucket_id: Uuid,
+    /// The outcome
+    pub status: MembershipStatus,
+}
+
+/// Membership handler for collecting owner approvals
+///
+/// Owners send each other a proposed [`MembershipChange`] whenever they
+/// propose or approve it. We keep the proposal until our owner approves it
+/// (see [`Peer::approve_membership_change`]); once the approvals we hold
+/// meet the quorum, we apply it.
+pub struct ProposeMembership;
+
+impl BidirectionalHandler for ProposeMembership {
+    type Message = MembershipMessage;
+    type Reply = MembershipReply;
+
+    /// Wrap the request in the Message enum for proper serialization
+    fn wrap_request(request: Self::Message) -> Message {
+        Message::ProposeMembership(request)
+    }
+
+    // ========================================
+    // RESPONDER SIDE: When we receive a proposal
+    // ========================================
+
+    /// Keep proposals from other owners that build on our head
+    async fn handle_message<L: BucketLogProvider>(
+        peer: &Peer<L>,
+        sender_node_id: &PublicKey,
+        message: &MembershipMessage,
+    ) -> MembershipReply {
+        let change = &message.change;
+        let bucket_id = *change.bucket_id();
+        let reply = |status| MembershipReply { bucket_id, status };
+
+        let Ok((head, _)) = peer.logs().head(bucket_id, None).await else {
+            return reply(MembershipStatus::Refused);
+        };
+        let manifest = match peer.blobs().get_cbor::<Manifest>(&head.hash()).await {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                tracing::warn!("Failed to load head manifest for {}: {}", bucket_id, e);
+                return reply(MembershipStatus::Refused);
+            }
+        };
+
+        let from = change.from();
+        if !applies_to(change, &manifest)
+            || !from.can_approve(&peer.secret().public())
+            || !from.can_approve(sender_node_id)
+        {
+            return reply(MembershipStatus::Refused);
+        }
+        let Ok(id) = change.id() else {
+            return reply(MembershipStatus::Refused);
+        };
+
+        peer.proposals().insert(id, change.clone());
+        reply(MembershipStatus::Received)
+    }
+
+    /// Apply the proposal once we hold enough approvals
+    async fn handle_message_side_effect<L: BucketLogProvider>(
+        peer: &Peer<L>,
+        sender_node_id: &PublicKey,
+        message: &MembershipMessage,
+        reply: &MembershipReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        if reply.status != MembershipStatus::Received {
+            return Ok(());
+        }
+
+        let id = message.change.id()?;
+        let Some(change) = peer.proposals().get(reply.bucket_id, &id) else {
+            return Ok(());
+        };
+        tracing::info!(
+            "Peer {} sent membership change {} for bucket {} ({} of {} approvals)",
+            sender_node_id.to_hex(),
+            id,
+            reply.bucket_id,
+            change.approvals().len(),
+            change.required()
+        );
+        if change.is_approved() {
+            peer.apply_membership_change(id, &change).await?;
+        }
+        Ok(())
+    }
+
+    // ========================================
+    // INITIATOR SIDE: When we receive a reply
+    // ========================================
+
+    /// Nothing to track; owners that refused just don't approve
+    async fn handle_reply<L: BucketLogProvider>(
+        _peer: &Peer<L>,
+        recipient_node_id: &PublicKey,
+        reply: &MembershipReply,
+    ) -> Result<()>
+    where
+        L::Error: std::error::Error + Send + Sync + 'static,
+    {
+        tracing::debug!(
+            "Peer {} replied {:?} to membership proposal for bucket {}",
+            recipient_node_id.to_hex(),
+            reply.status,
+            reply.bucket_id
+        );
+        Ok(())
+    }
+}
@@ -1,7 +1,19 @@
 #[macro_use]
 mod macros;
+pub mod access;
+pub mod announce;
+pub mod deposit;
+pub mod invite;
+pub mod manifest_chain;
+pub mod membership;
 pub mod ping;
 
+pub use access::RequestAccess;
+pub use announce::Announce;
+pub use deposit::SubmitDeposit;
+pub use invite::AcceptInvite;
+pub use manifest_chain::GetManifestChain;
+pub use membership::ProposeMembership;
 pub use ping::Ping;
 
 // Register all bidirectional message handlers
@@ -9,4 +21,10 @@ pub use ping::Ping;
 //   NewMessage(NewMessageHandler),
 register_handlers! {
     Ping(Ping),
+    GetManifestChain(GetManifestChain),
+    Announce(Announce),
+    SubmitDeposit(SubmitDeposit),
+    ProposeMembership(ProposeMembership),
+    AcceptInvite(AcceptInvite),
+    RequestAccess(RequestAccess),
 }
@@ -30,6 +30,9 @@ pub enum PingReplyStatus {
     ///  report where we are
     Ahead(Link, u64),
     /// We are behind, report where we are
+    ///
+    /// Also used for a different head at the same height when our link
+    /// sorts lower, so only one side of a fork merges.
     Behind(Link, u64),
     /// Both agree on the current link (in sync)
     InSync,
@@ -119,11 +122,17 @@ impl BidirectionalHandler for Ping {
         // Compare heights and determine sync status
         if height < ping.height {
             PingReply::behind(bucket_id, link, height)
-        } else if height == ping.height {
-            // At same height, we're in sync
+        } else if height > ping.height {
+            // We're ahead of the remote peer
+            PingReply::ahead(bucket_id, link, height)
+        } else if link == ping.link {
+            // Same head, we're in sync
             PingReply::in_sync(bucket_id)
+        } else if link < ping.link {
+            // Concurrent heads at the same height: exactly one side must sync
+            //  and merge, so the side with the lower link counts as behind
+            PingReply::behind(bucket_id, link, height)
         } else {
-            // We're ahead of the remote peer
             PingReply::ahead(bucket_id, link, height)
         }
     }
@@ -256,6 +265,18 @@ impl BidirectionalHandler for Ping {
                     link
                 );
 
+                // A subscribed peer should have pushed this head to us; it
+                //  restarted or dropped us, so subscribe again
+                let subscriptions = peer.subscriptions();
+                if subscriptions.is_subscribed(pong.bucket_id, recipient_node_id) {
+                    tracing::info!(
+                        "Peer {} stopped announcing heads for bucket {}, re-subscribing",
+                        recipient_node_id.to_hex(),
+                        pong.bucket_id
+                    );
+                    subscriptions.missed_heads(pong.bucket_id, recipient_node_id);
+                }
+
                 // Load our manifest to get all peer IDs from shares
                 let peer_ids = match peer.logs().head(pong.bucket_id, None).await {
                     Ok((our_link, _)) => {
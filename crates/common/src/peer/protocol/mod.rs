@@ -15,6 +15,12 @@ use messages::Message;
 // Re-export for external users implementing custom handlers
 #[allow(unused_imports)]
 pub use bidirectional::BidirectionalHandler;
+pub use messages::access::{AccessRequestMessage, AccessRequestStatus, RequestAccess};
+pub use messages::announce::{Announce, AnnounceKind, AnnounceMessage, AnnounceStatus};
+pub use messages::deposit::{DepositMessage, DepositStatus, SubmitDeposit};
+pub use messages::invite::{AcceptInvite, InviteMessage, InviteStatus};
+pub use messages::manifest_chain::{GetManifestChain, ManifestChainMessage};
+pub use messages::membership::{MembershipMessage, MembershipStatus, ProposeMembership};
 pub use messages::ping::{Ping, PingMessage, PingReplyStatus};
 
 // TODO ( amiller68): migrate the alpn, idt there's a great
@@ -0,0 +1,339 @@
+//! Head announcement subscriptions
+//!
+//! Peers subscribe to each other per bucket with the [`Announce`] protocol
+//! message. A subscribed peer gets every new head pushed to it as soon as it
+//! is saved, instead of waiting for the next periodic ping.
+//!
+//! This module tracks both directions:
+//!
+//! - **Subscribers**: peers that asked us to push heads for a bucket. Each
+//!   subscription is a lease that expires unless renewed, and is dropped as
+//!   soon as a push to it fails, so a peer that went offline re-subscribes
+//!   (and catches up) when it comes back.
+//! - **Upstream**: our own subscriptions to other peers. Failed attempts back
+//!   off exponentially; while a peer has no active subscription, it is polled
+//!   with pings instead, which covers peers that don't support announcements.
+//!   Subscribed peers are still pinged, at a reduced rate: subscriptions only
+//!   live in memory, so a publisher that restarted (or dropped us after a
+//!   failed push) stops announcing without telling us. A ping that finds such
+//!   a peer ahead of us drops the subscription, and the next poll re-subscribes.
+//!
+//! [`Announce`]: crate::peer::protocol::Announce
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::crypto::PublicKey;
+
+/// How long a subscription lasts without renewal
+pub const SUBSCRIPTION_LEASE: Duration = Duration::from_secs(10 * 60);
+
+/// Renew upstream subscriptions once they are this close to expiring
+pub const RENEW_MARGIN: Duration = Duration::from_secs(3 * 60);
+
+/// Delay before retrying after the first failed subscription attempt
+pub const BACKOFF_BASE: Duration = Duration::from_secs(30);
+
+/// Longest delay between subscription attempts
+pub const BACKOFF_MAX: Duration = Duration::from_secs(30 * 60);
+
+/// How often peers we are subscribed to are still pinged
+pub const SUBSCRIBED_PING_INTERVAL: Duration = Duration::from_secs(3 * 60);
+
+/// State of one of our subscriptions to another peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Upstream {
+    /// The peer pushes heads to us until the lease expires; `pinged` is when
+    /// we last compared heads with it anyway, `since` when it first accepted
+    Active {
+        expires: Instant,
+        since: Instant,
+        pinged: Instant,
+    },
+    /// Subscribing failed; poll the peer and retry after `retry_at`
+    Failing { failures: u32, retry_at: Instant },
+}
+
+#[derive(Debug, Default)]
+struct SubscriptionsInner {
+    /// Peers that receive our heads: bucket -> subscriber -> lease expiry
+    subscribers: HashMap<Uuid, HashMap<PublicKey, Instant>>,
+    /// Our subscriptions to other peers
+    upstream: HashMap<(Uuid, PublicKey), Upstream>,
+}
+
+/// Shared subscription state for a peer
+#[derive(Debug, Clone, Default)]
+pub struct Subscriptions {
+    inner: Arc<Mutex<SubscriptionsInner>>,
+}
+
+impl Subscriptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /* Subscribers */
+
+    /// Record (or renew) a peer's subscription to a bucket
+    pub fn add_subscriber(&self, bucket_id: Uuid, peer_id: PublicKey, lease: Duration) {
+        let mut inner = self.inner.lock().unwrap();
+        inner
+            .subscribers
+            .entry(bucket_id)
+            .or_default()
+            .insert(peer_id, Instant::now() + lease);
+    }
+
+    /// Drop a peer's subscription to a bucket
+    pub fn remove_subscriber(&self, bucket_id: Uuid, peer_id: &PublicKey) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(subscribers) = inner.subscribers.get_mut(&bucket_id) {
+            subscribers.remove(peer_id);
+            if subscribers.is_empty() {
+                inner.subscribers.remove(&bucket_id);
+            }
+        }
+    }
+
+    /// Check whether a peer holds a live subscription to a bucket
+    ///
+    /// Expired leases are pruned along the way.
+    pub fn is_subscriber(&self, bucket_id: Uuid, peer_id: &PublicKey) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(subscribers) = inner.subscribers.get_mut(&bucket_id) else {
+            return false;
+        };
+        let now = Instant::now();
+        subscribers.retain(|_, expires| *expires > now);
+        subscribers.contains_key(peer_id)
+    }
+
+    /* Upstream */
+
+    /// Get the state of our subscription to a peer for a bucket
+    pub fn upstream(&self, bucket_id: Uuid, peer_id: &PublicKey) -> Option<Upstream> {
+        let inner = self.inner.lock().unwrap();
+        inner.upstream.get(&(bucket_id, *peer_id)).copied()
+    }
+
+    /// Check whether we should (re)subscribe to a peer now
+    ///
+    /// True if we never subscribed, the lease is about to expire, or the
+    /// backoff after a failure has elapsed.
+    pub fn should_subscribe(&self, bucket_id: Uuid, peer_id: &PublicKey) -> bool {
+        let now = Instant::now();
+        match self.upstream(bucket_id, peer_id) {
+            None => true,
+            Some(Upstream::Active { expires, .. }) => expires <= now + RENEW_MARGIN,
+            Some(Upstream::Failing { retry_at, .. }) => retry_at <= now,
+        }
+    }
+
+    /// Check whether a peer currently pushes heads for a bucket to us
+    pub fn is_subscribed(&self, bucket_id: Uuid, peer_id: &PublicKey) -> bool {
+        matches!(
+            self.upstream(bucket_id, peer_id),
+            Some(Upstream::Active { expires, .. }) if expires > Instant::now()
+        )
+    }
+
+    /// Check whether we should ping a peer now, noting the ping if so
+    ///
+    /// True if the peer doesn't push heads to us, or it does but hasn't been
+    /// pinged for [`SUBSCRIBED_PING_INTERVAL`].
+    pub fn should_ping(&self, bucket_id: Uuid, peer_id: &PublicKey) -> bool {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        match inner.upstream.get_mut(&(bucket_id, *peer_id)) {
+            Some(Upstream::Active {
+                expires, pinged, ..
+            }) if *expires > now => {
+                if *pinged + SUBSCRIBED_PING_INTERVAL > now {
+                    return false;
+                }
+                *pinged = now;
+                true
+            }
+            _ => true,
+        }
+    }
+
+    /// Record a successful subscription
+    ///
+    /// Returns whether the subscription was already active, so callers can
+    /// catch up on heads missed while it wasn't.
+    pub fn subscribed(&self, bucket_id: Uuid, peer_id: PublicKey, lease: Duration) -> bool {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        // A new subscription is followed by a catch-up ping, so it counts as
+        //  pinged; renewals keep their schedule
+        let (was_active, since, pinged) = match inner.upstream.get(&(bucket_id, peer_id)) {
+            Some(Upstream::Active {
+                expires,
+                since,
+                pinged,
+            }) if *expires > now => (true, *since, *pinged),
+            _ => (false, now, now),
+        };
+        inner.upstream.insert(
+            (bucket_id, peer_id),
+            Upstream::Active {
+                expires: now + lease,
+                since,
+                pinged,
+            },
+        );
+        was_active
+    }
+
+    /// Forget our subscription to a peer that stopped pushing heads to us
+    ///
+    /// Called when a ping finds a subscribed peer ahead of us. The catch-up
+    /// ping right after subscribing is expected to, so only pings since then
+    /// count. Subscribing is idempotent on the other side, so the next poll
+    /// simply re-subscribes.
+    pub fn missed_heads(&self, bucket_id: Uuid, peer_id: &PublicKey) {
+        let mut inner = self.inner.lock().unwrap();
+        if matches!(
+            inner.upstream.get(&(bucket_id, *peer_id)),
+            Some(Upstream::Active { since, pinged, .. }) if pinged > since
+        ) {
+            inner.upstream.remove(&(bucket_id, *peer_id));
+        }
+    }
+
+    /// Record a failed subscription attempt and schedule the next one
+    ///
+    /// Returns the backoff until the next attempt.
+    pub fn subscribe_failed(&self, bucket_id: Uuid, peer_id: PublicKey) -> Duration {
+        let mut inner = self.inner.lock().unwrap();
+        let failures = match inner.upstream.get(&(bucket_id, peer_id)) {
+            Some(Upstream::Failing { failures, .. }) => failures + 1,
+            _ => 1,
+        };
+        let backoff = backoff(failures);
+        inner.upstream.insert(
+            (bucket_id, peer_id),
+            Upstream::Failing {
+                failures,
+                retry_at: Instant::now() + backoff,
+            },
+        );
+        backoff
+    }
+}
+
+/// Exponential backoff for the given number of consecutive failures
+fn backoff(failures: u32) -> Duration {
+    let factor = 1u32 << failures.saturating_sub(1).min(16);
+    BACKOFF_BASE.saturating_mul(factor).min(BACKOFF_MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::SecretKey;
+
+    #[test]
+    fn test_subscriber_lease_expires() {
+        let subscriptions = Subscriptions::new();
+        let bucket_id = Uuid::new_v4();
+        let peer_id = SecretKey::generate().public();
+
+        subscriptions.add_subscriber(bucket_id, peer_id, SUBSCRIPTION_LEASE);
+        assert!(subscriptions.is_subscriber(bucket_id, &peer_id));
+
+        subscriptions.add_subscriber(bucket_id, peer_id, Duration::ZERO);
+        assert!(!subscriptions.is_subscriber(bucket_id, &peer_id));
+
+        subscriptions.add_subscriber(bucket_id, peer_id, SUBSCRIPTION_LEASE);
+        subscriptions.remove_subscriber(bucket_id, &peer_id);
+        assert!(!subscriptions.is_subscriber(bucket_id, &peer_id));
+    }
+
+    #[test]
+    fn test_upstream_backoff_and_renewal() {
+        let subscriptions = Subscriptions::new();
+        let bucket_id = Uuid::new_v4();
+        let peer_id = SecretKey::generate().public();
+
+        assert!(subscriptions.should_subscribe(bucket_id, &peer_id));
+
+        // Failures back off exponentially up to the cap
+        assert_eq!(
+            subscriptions.subscribe_failed(bucket_id, peer_id),
+            BACKOFF_BASE
+        );
+        assert_eq!(
+            subscriptions.subscribe_failed(bucket_id, peer_id),
+            BACKOFF_BASE * 2
+        );
+        assert!(!subscriptions.should_subscribe(bucket_id, &peer_id));
+        assert!(!subscriptions.is_subscribed(bucket_id, &peer_id));
+        for _ in 0..20 {
+            subscriptions.subscribe_failed(bucket_id, peer_id);
+        }
+        assert_eq!(
+            subscriptions.subscribe_failed(bucket_id, peer_id),
+            BACKOFF_MAX
+        );
+
+        // Subscribing resets the failures and reports the reconnect
+        assert!(!subscriptions.subscribed(bucket_id, peer_id, SUBSCRIPTION_LEASE));
+        assert!(subscriptions.is_subscribed(bucket_id, &peer_id));
+        assert!(!subscriptions.should_subscribe(bucket_id, &peer_id));
+        assert!(subscriptions.subscribed(bucket_id, peer_id, RENEW_MARGIN));
+        // Close to expiry, it's time to renew
+        assert!(subscriptions.should_subscribe(bucket_id, &peer_id));
+        assert_eq!(
+            subscriptions.subscribe_failed(bucket_id, peer_id),
+            BACKOFF_BASE
+        );
+    }
+
+    #[test]
+    fn test_subscribed_peers_still_pinged() {
+        let subscriptions = Subscriptions::new();
+        let bucket_id = Uuid::new_v4();
+        let peer_id = SecretKey::generate().public();
+
+        // Without a subscription, every poll pings
+        assert!(subscriptions.should_ping(bucket_id, &peer_id));
+        assert!(subscriptions.should_ping(bucket_id, &peer_id));
+
+        // A new subscription was just caught up, so being behind it is
+        //  expected and it isn't due a ping yet
+        subscriptions.subscribed(bucket_id, peer_id, SUBSCRIPTION_LEASE);
+        assert!(!subscriptions.should_ping(bucket_id, &peer_id));
+        subscriptions.missed_heads(bucket_id, &peer_id);
+        assert!(subscriptions.is_subscribed(bucket_id, &peer_id));
+
+        // Renewing doesn't reset the schedule
+        let overdue = Instant::now() - SUBSCRIBED_PING_INTERVAL;
+        subscriptions.inner.lock().unwrap().upstream.insert(
+            (bucket_id, peer_id),
+            Upstream::Active {
+                expires: Instant::now() + RENEW_MARGIN,
+                since: overdue,
+                pinged: overdue,
+            },
+        );
+        assert!(subscriptions.subscribed(bucket_id, peer_id, SUBSCRIPTION_LEASE));
+        assert!(subscriptions.should_ping(bucket_id, &peer_id));
+        assert!(!subscriptions.should_ping(bucket_id, &peer_id));
+
+        // A publisher found ahead of us since then gets re-subscribed
+        subscriptions.missed_heads(bucket_id, &peer_id);
+        assert!(!subscriptions.is_subscribed(bucket_id, &peer_id));
+        assert!(subscriptions.should_subscribe(bucket_id, &peer_id));
+
+        // but a backoff after failures is kept
+        subscriptions.subscribe_failed(bucket_id, peer_id);
+        subscriptions.missed_heads(bucket_id, &peer_id);
+        assert!(!subscriptions.should_subscribe(bucket_id, &peer_id));
+    }
+}
@@ -0,0 +1,84 @@
+//! Deposit acceptance job and execution logic
+//!
+//! This module fetches a deposit submitted by a depositor and links its file
+//! into the bucket's inbox as a new version.
+
+use anyhow::{anyhow, Result};
+use uuid::Uuid;
+
+use crate::bucket_log::BucketLogProvider;
+use crate::crypto::PublicKey;
+use crate::linked_data::Link;
+use crate::mount::{Deposit, MountError};
+use crate::peer::Peer;
+
+/// Accept deposit job definition
+#[derive(Debug, Clone)]
+pub struct AcceptDepositJob {
+    pub bucket_id: Uuid,
+    /// Link to the deposit object
+    pub link: Link,
+    /// Peers to fetch the deposit and its content from
+    pub peer_ids: Vec<PublicKey>,
+}
+
+/// Execute an accept deposit job
+///
+/// Deposits that fail verification, or whose path is already taken by a
+/// different file, are logged and dropped rather than returned as errors.
+pub async fn execute<L>(peer: &Peer<L>, job: AcceptDepositJob) -> Result<()>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    tracing::debug!(
+        "Processing accept deposit job: bucket_id={}, link={:?}",
+        job.bucket_id,
+        job.link
+    );
+
+    let blobs = peer.blobs();
+    blobs
+        .download_hash(job.link.hash(), job.peer_ids.clone(), peer.endpoint())
+        .await
+        .map_err(|e| anyhow!("Failed to download deposit: {}", e))?;
+    let deposit: Deposit = blobs.get_cbor(&job.link.hash()).await?;
+    blobs
+        .download_hash(deposit.content().hash(), job.peer_ids, peer.endpoint())
+        .await
+        .map_err(|e| anyhow!("Failed to download deposit content: {}", e))?;
+
+    let mut mount = peer.mount(job.bucket_id).await?;
+    match mount.accept_deposit(&deposit).await {
+        Ok(Some(path)) => {
+            tracing::info!(
+                "Accepted deposit from {} into bucket {} at {}",
+                deposit.author().to_hex(),
+                job.bucket_id,
+                path.display()
+            );
+            let publish = mount.is_published().await;
+            peer.save_mount(&mount, publish).await?;
+            Ok(())
+        }
+        Ok(None) => {
+            tracing::debug!(
+                "Deposit {:?} is already in bucket {}",
+                job.link,
+                job.bucket_id
+            );
+            Ok(())
+        }
+        Err(e @ (MountError::Deposit(_) | MountError::PathAlreadyExists(_))) => {
+            tracing::warn!(
+                "Refused deposit {:?} from {} for bucket {}: {}",
+                job.link,
+                deposit.author().to_hex(),
+                job.bucket_id,
+                e
+            );
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,233 @@
+//! Head announcement job and execution logic
+//!
+//! This module contains the logic for subscribing to peers and pushing new
+//! heads to subscribers with the announce protocol message.
+
+use anyhow::Result;
+use uuid::Uuid;
+
+use crate::bucket_log::BucketLogProvider;
+use crate::crypto::PublicKey;
+use crate::peer::protocol::bidirectional::BidirectionalHandler;
+use crate::peer::protocol::{Announce, AnnounceKind, AnnounceMessage};
+use crate::peer::Peer;
+
+use super::{ping_peer, PingPeerJob};
+
+/// Announce peer job definition
+#[derive(Debug, Clone)]
+pub struct AnnouncePeerJob {
+    pub bucket_id: Uuid,
+    pub peer_id: PublicKey,
+    pub kind: AnnounceKind,
+}
+
+/// Execute an announce peer job
+///
+/// Failures are handled here rather than returned:
+/// - A failed subscription backs off and polls the peer with a ping instead,
+///   which also covers peers that don't understand announcements
+/// - A failed push drops the subscriber; it re-subscribes and catches up once
+///   it is reachable again
+pub async fn execute<L>(peer: &Peer<L>, job: AnnouncePeerJob) -> Result<()>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    tracing::debug!(
+        "Processing announce job: bucket_id={}, peer_id={}, kind={:?}",
+        job.bucket_id,
+        job.peer_id.to_hex(),
+        job.kind
+    );
+
+    let message = AnnounceMessage {
+        bucket_id: job.bucket_id,
+        kind: job.kind.clone(),
+    };
+    let Err(e) = Announce::send::<L>(peer, &job.peer_id, message).await else {
+        return Ok(());
+    };
+
+    let subscriptions = peer.subscriptions();
+    match job.kind {
+        AnnounceKind::Subscribe => {
+            let backoff = subscriptions.subscribe_failed(job.bucket_id, job.peer_id);
+            tracing::debug!(
+                "Failed to subscribe to peer {} for bucket {} ({}), polling instead and retrying in {:?}",
+                job.peer_id.to_hex(),
+                job.bucket_id,
+                e,
+                backoff
+            );
+            ping_peer::execute(
+                peer,
+                PingPeerJob {
+                    bucket_id: job.bucket_id,
+                    peer_id: job.peer_id,
+                },
+            )
+            .await
+        }
+        AnnounceKind::Head(..) => {
+            tracing::debug!(
+                "Failed to announce head of bucket {} to peer {} ({}), dropping subscriber",
+                job.bucket_id,
+                job.peer_id.to_hex(),
+                e
+            );
+            subscriptions.remove_subscriber(job.bucket_id, &job.peer_id);
+            Ok(())
+        }
+        AnnounceKind::Unsubscribe => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::bucket_log::MemoryBucketLogProvider;
+    use crate::crypto::SecretKey;
+    use crate::linked_data::Link;
+    use crate::mount::Mount;
+    use crate::peer::subscriptions::{Upstream, SUBSCRIPTION_LEASE};
+    use crate::peer::{BlobsStore, NodeAddr, PeerBuilder, SyncJob, SyncProvider};
+
+    /// Keeps the jobs a peer dispatches instead of running them
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<SyncJob>>);
+
+    #[async_trait::async_trait]
+    impl SyncProvider<MemoryBucketLogProvider> for Recorder {
+        async fn execute(&self, _peer: &Peer<MemoryBucketLogProvider>, job: SyncJob) -> Result<()> {
+            self.0.lock().unwrap().push(job);
+            Ok(())
+        }
+    }
+
+    async fn peer_with(
+        key: &SecretKey,
+        blobs: &BlobsStore,
+        recorder: &Arc<Recorder>,
+    ) -> Peer<MemoryBucketLogProvider> {
+        PeerBuilder::new()
+            .with_sync_provider(recorder.clone())
+            .log_provider(MemoryBucketLogProvider::new())
+            .blobs_store(blobs.clone())
+            .secret_key(key.clone())
+            .build()
+            .await
+    }
+
+    /// Tell `endpoint` where to reach `other` on this machine
+    fn introduce(endpoint: &iroh::Endpoint, other: &iroh::Endpoint) {
+        let addrs = other
+            .bound_sockets()
+            .into_iter()
+            .filter(|addr| addr.is_ipv4())
+            .map(|addr| (std::net::Ipv4Addr::LOCALHOST, addr.port()).into());
+        endpoint
+            .add_node_addr_with_source(
+                NodeAddr::new(other.node_id()).with_direct_addresses(addrs),
+                "test",
+            )
+            .unwrap();
+    }
+
+    /// A node that is reachable but doesn't speak the jax protocol
+    async fn stranger() -> (iroh::protocol::Router, PublicKey) {
+        let endpoint = iroh::Endpoint::builder()
+            .relay_mode(iroh::RelayMode::Disabled)
+            .bind()
+            .await
+            .unwrap();
+        let peer_id = PublicKey::from(endpoint.node_id());
+        let blobs = BlobsStore::memory().await.unwrap();
+        let router = iroh::protocol::Router::builder(endpoint)
+            .accept(iroh_blobs::ALPN, blobs.inner.clone())
+            .spawn();
+        (router, peer_id)
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_and_push_failures() {
+        let blobs = BlobsStore::memory().await.unwrap();
+        let (alice_key, bob_key) = (SecretKey::generate(), SecretKey::generate());
+        let (alice_jobs, bob_jobs) = (Arc::default(), Arc::default());
+        let alice = peer_with(&alice_key, &blobs, &alice_jobs).await;
+        let bob = peer_with(&bob_key, &blobs, &bob_jobs).await;
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(());
+        let running = tokio::spawn(crate::peer::spawn(alice.clone(), shutdown_rx));
+        introduce(bob.endpoint(), alice.endpoint());
+
+        let bucket_id = Uuid::new_v4();
+        let mut mount = Mount::init(bucket_id, "announce".to_string(), &alice_key, &blobs)
+            .await
+            .unwrap();
+        mount.add_owner(bob_key.public()).await.unwrap();
+        let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+        for peer in [&alice, &bob] {
+            peer.logs()
+                .append(
+                    bucket_id,
+                    "announce".to_string(),
+                    link.clone(),
+                    vec![],
+                    0,
+                    false,
+                )
+                .await
+                .unwrap();
+        }
+
+        // Bob subscribes to alice and catches up once
+        let job = |peer_id, kind| AnnouncePeerJob {
+            bucket_id,
+            peer_id,
+            kind,
+        };
+        execute(&bob, job(alice_key.public(), AnnounceKind::Subscribe))
+            .await
+            .unwrap();
+        assert!(alice
+            .subscriptions()
+            .is_subscriber(bucket_id, &bob_key.public()));
+        assert!(bob
+            .subscriptions()
+            .is_subscribed(bucket_id, &alice_key.public()));
+        assert!(matches!(
+            bob_jobs.0.lock().unwrap().as_slice(),
+            [SyncJob::PingPeer(_)]
+        ));
+
+        // Subscribing to a peer without announcements backs off and pings
+        let (router, stranger) = stranger().await;
+        introduce(alice.endpoint(), router.endpoint());
+        let result = execute(&alice, job(stranger, AnnounceKind::Subscribe)).await;
+        assert!(result.is_err(), "the fallback ping can't reach it either");
+        assert!(matches!(
+            alice.subscriptions().upstream(bucket_id, &stranger),
+            Some(Upstream::Failing { failures: 1, .. })
+        ));
+
+        // A subscriber we can't push to is dropped
+        alice
+            .subscriptions()
+            .add_subscriber(bucket_id, stranger, SUBSCRIPTION_LEASE);
+        execute(
+            &alice,
+            job(stranger, AnnounceKind::Head(Link::default(), 1)),
+        )
+        .await
+        .unwrap();
+        assert!(!alice.subscriptions().is_subscriber(bucket_id, &stranger));
+        assert!(alice
+            .subscriptions()
+            .is_subscriber(bucket_id, &bob_key.public()));
+
+        shutdown_tx.send(()).unwrap();
+        running.await.unwrap().unwrap();
+    }
+}
@@ -1,31 +1,95 @@
 //! Pins download job and execution logic
 //!
 //! This module contains the logic for downloading pinned content from peers.
+//! Buckets in [`SyncMode::Lazy`] only fetch the pins list and directory tree
+//! here, plus file contents under their offline paths; everything else is
+//! fetched when first read.
 
 use anyhow::Result;
+use uuid::Uuid;
 
-use crate::bucket_log::BucketLogProvider;
+use crate::bucket_log::{BucketLogProvider, SyncMode};
 use crate::crypto::PublicKey;
 use crate::linked_data::Link;
 use crate::peer::Peer;
 
+use super::SyncProgressEvent;
+
 /// Download pins job definition
 #[derive(Debug, Clone)]
 pub struct DownloadPinsJob {
+    /// The bucket the pins belong to
+    pub bucket_id: Uuid,
     pub pins_link: Link,
     pub peer_ids: Vec<PublicKey>,
 }
 
 /// Execute a pins download job
 ///
-/// This downloads the hash list from the specified peers.
+/// This downloads the hash list from the specified peers, reporting blob
+/// progress for the job's bucket on the peer's sync progress channel.
 pub async fn execute<L>(peer: &Peer<L>, job: DownloadPinsJob) -> Result<()>
 where
     L: BucketLogProvider + Clone + Send + Sync + 'static,
     L::Error: std::error::Error + Send + Sync + 'static,
 {
+    let bucket_id = job.bucket_id;
+    let result = match peer.logs().sync_mode(bucket_id).await? {
+        SyncMode::Full => peer
+            .blobs()
+            .download_hash_list_with_progress(
+                job.pins_link.hash(),
+                job.peer_ids,
+                peer.endpoint(),
+                |event| peer.emit_progress(bucket_id, event.into()),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to download pins: {}", e)),
+        SyncMode::Lazy => download_lazy(peer, job).await,
+    };
+
+    if let Err(e) = &result {
+        peer.emit_progress(
+            bucket_id,
+            SyncProgressEvent::Failed {
+                error: e.to_string(),
+            },
+        );
+    }
+    result
+}
+
+/// Fetch the pins list, the directory tree and offline paths of a lazy bucket
+async fn download_lazy<L>(peer: &Peer<L>, job: DownloadPinsJob) -> Result<()>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let bucket_id = job.bucket_id;
     peer.blobs()
-        .download_hash_list(job.pins_link.hash(), job.peer_ids, peer.endpoint())
+        .download_hash(job.pins_link.hash(), job.peer_ids, peer.endpoint())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to download pins list: {}", e))?;
+
+    // Mirrors of unpublished buckets can't read the tree; the pins list is
+    // all there is to fetch for them
+    let mount = match peer.mount_for_read(bucket_id).await {
+        Ok(mount) => mount,
+        Err(e) => {
+            tracing::debug!("Not fetching tree of lazy bucket {}: {}", bucket_id, e);
+            return Ok(());
+        }
+    };
+
+    let offline_paths = peer.logs().offline_paths(bucket_id).await?;
+    let count = peer
+        .download_paths(bucket_id, &mount, &offline_paths)
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to download pins: {}", e))
+        .map_err(|e| anyhow::anyhow!("Failed to download bucket tree: {}", e))?;
+    tracing::info!(
+        "Fetched tree of lazy bucket {} and {} offline files",
+        bucket_id,
+        count
+    );
+    Ok(())
 }
@@ -0,0 +1,79 @@
+//! Overdue fork merge job and execution logic
+//!
+//! An owner that leaves a fork for another owner to merge remembers it in
+//! [`PendingMerges`]. If that merge hasn't arrived in time, the periodic poll
+//! dispatches this job to merge the fork ourselves.
+//!
+//! [`PendingMerges`]: crate::peer::PendingMerges
+
+use anyhow::Result;
+use uuid::Uuid;
+
+use crate::bucket_log::BucketLogProvider;
+use crate::crypto::PublicKey;
+use crate::linked_data::Link;
+use crate::mount::Manifest;
+use crate::peer::Peer;
+
+use super::sync_bucket::{can_merge, descends_from, merge_branches};
+
+/// Merge fork job definition
+#[derive(Debug, Clone)]
+pub struct MergeForkJob {
+    pub bucket_id: Uuid,
+    /// Our head when the fork was found
+    pub ours: Link,
+    /// The other branch's head
+    pub theirs: Link,
+    /// Peers to fetch the other branch's content from
+    pub peer_ids: Vec<PublicKey>,
+}
+
+/// Execute a merge fork job
+///
+/// Versions saved since the fork was found may build on either branch, so
+/// each side is moved up to our log's head when the head descends from it.
+/// If the head descends from both, someone merged the fork in the meantime
+/// and there is nothing left to do.
+pub async fn execute<L>(peer: &Peer<L>, job: MergeForkJob) -> Result<()>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let bucket_id = job.bucket_id;
+    let (head, _) = peer.logs().head(bucket_id, None).await?;
+    let ours = if descends_from(peer, bucket_id, &job.ours, &head).await? {
+        head.clone()
+    } else {
+        job.ours
+    };
+    let theirs = if descends_from(peer, bucket_id, &job.theirs, &head).await? {
+        head
+    } else {
+        job.theirs
+    };
+    if ours == theirs {
+        tracing::debug!("Fork of bucket {} was merged in the meantime", bucket_id);
+        return Ok(());
+    }
+
+    let our_manifest: Manifest = peer.blobs().get_cbor(&ours.hash()).await?;
+    if !can_merge(peer, &our_manifest) {
+        return Ok(());
+    }
+    let their_manifest: Manifest = peer.blobs().get_cbor(&theirs.hash()).await?;
+    tracing::info!(
+        "No merge of the fork of bucket {} arrived in time, merging {:?} ourselves",
+        bucket_id,
+        theirs
+    );
+    merge_branches(
+        peer,
+        &ours,
+        &our_manifest,
+        &theirs,
+        &their_manifest,
+        &job.peer_ids,
+    )
+    .await
+}
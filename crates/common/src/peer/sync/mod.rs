@@ -19,21 +19,49 @@ pub enum ProvenanceError {
     AuthorNotInShares,
     #[error("author does not have write permission")]
     AuthorNotWriter,
+    #[error("author's share had expired")]
+    AuthorExpired,
+    #[error("author cannot change its own expiry without owner approvals")]
+    SelfExpiryChange,
     #[error("invalid manifest in chain at {link}: {reason}")]
     InvalidManifestInChain { link: Link, reason: String },
     #[error("unauthorized share removal: only owners can remove shares")]
     UnauthorizedShareRemoval,
+    #[error("author's write scope does not cover {0}")]
+    OutOfScope(std::path::PathBuf),
+    #[error("scoped writers cannot change bucket shares")]
+    ScopedShareChange,
+    #[error("scoped writers cannot change the bucket's {0}")]
+    ScopedSettingChange(&'static str),
+    #[error("membership change needs {required} owner approvals, has {approvals}")]
+    QuorumNotMet { required: u32, approvals: usize },
+    #[error("membership epoch {0} does not follow from the parents")]
+    InvalidMembershipEpoch(u64),
+    #[error("version saved at {0} is older than its parents")]
+    SavedBeforeParent(u64),
+    #[error("version saved at {0} is ahead of our clock")]
+    SavedInFuture(u64),
     #[error("{0}")]
     Other(#[from] anyhow::Error),
 }
 
+pub mod accept_deposit;
+pub mod announce_peer;
 pub mod download_pins;
+pub mod merge_fork;
 pub mod ping_peer;
+pub mod progress;
+pub mod scrub_blobs;
 pub mod sync_bucket;
 
 // Re-export job types, helpers, and errors
+pub use accept_deposit::AcceptDepositJob;
+pub use announce_peer::AnnouncePeerJob;
 pub use download_pins::DownloadPinsJob;
+pub use merge_fork::MergeForkJob;
 pub use ping_peer::PingPeerJob;
+pub use progress::{SyncProgress, SyncProgressEvent};
+pub use scrub_blobs::{ScrubBlobsJob, ScrubOutcome};
 pub use sync_bucket::{SyncBucketJob, SyncTarget};
 
 /// Background sync job types
@@ -47,6 +75,14 @@ pub enum SyncJob {
     DownloadPins(DownloadPinsJob),
     /// Ping a peer to check bucket sync status
     PingPeer(PingPeerJob),
+    /// Subscribe to a peer's heads or push ours to a subscriber
+    AnnouncePeer(AnnouncePeerJob),
+    /// Verify the local blob store and re-fetch damaged pinned blobs
+    ScrubBlobs(ScrubBlobsJob),
+    /// Fetch a depositor's file and add it to the bucket's inbox
+    AcceptDeposit(AcceptDepositJob),
+    /// Merge a fork that another owner was expected to merge but didn't
+    MergeFork(MergeForkJob),
 }
 
 /// Execute a sync job by calling the appropriate module's execute function
@@ -62,6 +98,10 @@ where
         SyncJob::DownloadPins(job) => download_pins::execute(peer, job).await,
         SyncJob::SyncBucket(job) => sync_bucket::execute(peer, job).await,
         SyncJob::PingPeer(job) => ping_peer::execute(peer, job).await,
+        SyncJob::AnnouncePeer(job) => announce_peer::execute(peer, job).await,
+        SyncJob::ScrubBlobs(job) => scrub_blobs::execute(peer, job).await,
+        SyncJob::AcceptDeposit(job) => accept_deposit::execute(peer, job).await,
+        SyncJob::MergeFork(job) => merge_fork::execute(peer, job).await,
     }
 }
 
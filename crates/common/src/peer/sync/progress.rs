@@ -0,0 +1,95 @@
+//! Structured sync progress
+//!
+//! Sync jobs report what they are doing as [`SyncProgress`] events on a
+//! broadcast channel held by the [`Peer`]. Nothing in the sync path waits on
+//! listeners: when no one is subscribed, or a subscriber lags behind, events
+//! are simply dropped. Consumers that need durable state (like the daemon's
+//! sync status table) fold events into it as they arrive.
+//!
+//! [`Peer`]: crate::peer::Peer
+
+use uuid::Uuid;
+
+use crate::crypto::PublicKey;
+use crate::linked_data::Link;
+use crate::peer::DownloadEvent;
+
+/// Number of events buffered per subscriber before old ones are dropped
+pub const PROGRESS_CHANNEL_CAPACITY: usize = 256;
+
+/// A progress event for one bucket
+#[derive(Debug, Clone)]
+pub struct SyncProgress {
+    pub bucket_id: Uuid,
+    pub event: SyncProgressEvent,
+}
+
+/// What happened during a bucket sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncProgressEvent {
+    /// A sync toward `link` at `height` started
+    Started { link: Link, height: u64 },
+    /// The manifests missing from our log were downloaded
+    ManifestsFetched { count: usize },
+    /// Pinned content download started with `total` blobs missing locally
+    BlobsStarted { total: usize },
+    /// Pinned content fetched so far
+    BlobsProgress {
+        done: usize,
+        total: usize,
+        bytes: u64,
+    },
+    /// Now downloading from this peer
+    Peer { peer_id: PublicKey },
+    /// A peer failed to serve a download
+    PeerFailed { peer_id: PublicKey },
+    /// The bucket is synced up to `link` at `height`
+    Completed { link: Link, height: u64 },
+    /// The sync stopped with an error
+    Failed { error: String },
+}
+
+impl From<DownloadEvent> for SyncProgressEvent {
+    fn from(event: DownloadEvent) -> Self {
+        match event {
+            DownloadEvent::Started { total } => Self::BlobsStarted { total },
+            DownloadEvent::Provider(peer_id) => Self::Peer { peer_id },
+            DownloadEvent::ProviderFailed(peer_id) => Self::PeerFailed { peer_id },
+            DownloadEvent::Progress { done, total, bytes } => {
+                Self::BlobsProgress { done, total, bytes }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::SecretKey;
+
+    #[test]
+    fn test_download_events_map_to_progress() {
+        let peer_id = SecretKey::generate().public();
+
+        assert_eq!(
+            SyncProgressEvent::from(DownloadEvent::Started { total: 3 }),
+            SyncProgressEvent::BlobsStarted { total: 3 }
+        );
+        assert_eq!(
+            SyncProgressEvent::from(DownloadEvent::Provider(peer_id)),
+            SyncProgressEvent::Peer { peer_id }
+        );
+        assert_eq!(
+            SyncProgressEvent::from(DownloadEvent::Progress {
+                done: 1,
+                total: 3,
+                bytes: 42
+            }),
+            SyncProgressEvent::BlobsProgress {
+                done: 1,
+                total: 3,
+                bytes: 42
+            }
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! Blob store scrub job and execution logic
+//!
+//! This module verifies the local blob store and re-fetches damaged blobs
+//! that are still pinned by a bucket from the other peers in its shares.
+
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use iroh_blobs::Hash;
+use object_store::ScrubReport;
+use serde::{Deserialize, Serialize};
+
+use crate::bucket_log::BucketLogProvider;
+use crate::crypto::PublicKey;
+use crate::mount::Manifest;
+use crate::peer::Peer;
+
+/// Scrub blobs job definition
+#[derive(Debug, Clone, Default)]
+pub struct ScrubBlobsJob;
+
+/// Result of a scrub plus any repairs made from peers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScrubOutcome {
+    /// What the local scrub found
+    pub report: ScrubReport,
+    /// Damaged pinned blobs that were downloaded again
+    pub refetched: Vec<Hash>,
+    /// Damaged blobs that could not be re-fetched (unpinned or no peer had them)
+    pub unrecoverable: Vec<Hash>,
+}
+
+/// Execute a scrub job, logging the outcome
+pub async fn execute<L>(peer: &Peer<L>, _job: ScrubBlobsJob) -> Result<()>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let outcome = scrub_and_repair(peer).await?;
+    tracing::info!(
+        checked = outcome.report.checked,
+        healthy = outcome.report.healthy,
+        refetched = outcome.refetched.len(),
+        unrecoverable = outcome.unrecoverable.len(),
+        "blob scrub finished"
+    );
+    Ok(())
+}
+
+/// Scrub the blob store, then re-fetch damaged pinned blobs from bucket peers.
+pub async fn scrub_and_repair<L>(peer: &Peer<L>) -> Result<ScrubOutcome>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let report = peer
+        .blobs()
+        .scrub()
+        .await
+        .map_err(|e| anyhow!("Failed to scrub blob store: {}", e))?;
+
+    let mut damaged: HashSet<Hash> = report.damaged().copied().collect();
+    let mut refetched = Vec::new();
+
+    if !damaged.is_empty() {
+        let buckets = peer
+            .logs()
+            .list_buckets()
+            .await
+            .map_err(|e| anyhow!("Failed to list buckets: {}", e))?;
+
+        for bucket_id in buckets {
+            if damaged.is_empty() {
+                break;
+            }
+            if let Err(e) = repair_bucket(peer, bucket_id, &mut damaged, &mut refetched).await {
+                tracing::warn!(%bucket_id, error = %e, "failed to repair blobs for bucket");
+            }
+        }
+    }
+
+    Ok(ScrubOutcome {
+        report,
+        refetched,
+        unrecoverable: damaged.into_iter().collect(),
+    })
+}
+
+/// Re-fetch the damaged blobs pinned by a bucket's current head.
+async fn repair_bucket<L>(
+    peer: &Peer<L>,
+    bucket_id: uuid::Uuid,
+    damaged: &mut HashSet<Hash>,
+    refetched: &mut Vec<Hash>,
+) -> Result<()>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let (head_link, _) = peer
+        .logs()
+        .head(bucket_id, None)
+        .await
+        .map_err(|e| anyhow!("Failed to get head: {}", e))?;
+    let manifest: Manifest = peer
+        .blobs()
+        .get_cbor(&head_link.hash())
+        .await
+        .map_err(|e| anyhow!("Failed to load manifest: {}", e))?;
+
+    let our_key = PublicKey::from(*peer.secret().public());
+    let peer_ids: Vec<PublicKey> = manifest
+        .get_peer_ids()
+        .into_iter()
+        .filter(|id| *id != our_key)
+        .collect();
+    if peer_ids.is_empty() {
+        return Ok(());
+    }
+
+    // The pin list itself has to be readable before we can consult it
+    let pins_hash = manifest.pins().hash();
+    let mut pinned = vec![pins_hash, manifest.entry().hash()];
+    if damaged.contains(&pins_hash) {
+        refetch(peer, pins_hash, &peer_ids, damaged, refetched).await;
+    }
+    if let Ok(pins) = peer.blobs().read_hash_list(pins_hash).await {
+        pinned.extend(pins);
+    }
+
+    for hash in pinned {
+        if damaged.contains(&hash) {
+            refetch(peer, hash, &peer_ids, damaged, refetched).await;
+        }
+    }
+    Ok(())
+}
+
+async fn refetch<L>(
+    peer: &Peer<L>,
+    hash: Hash,
+    peer_ids: &[PublicKey],
+    damaged: &mut HashSet<Hash>,
+    refetched: &mut Vec<Hash>,
+) where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    match peer
+        .blobs()
+        .download_hash(hash, peer_ids.to_vec(), peer.endpoint())
+        .await
+    {
+        Ok(()) => {
+            damaged.remove(&hash);
+            refetched.push(hash);
+        }
+        Err(e) => tracing::warn!(%hash, error = %e, "failed to re-fetch damaged blob"),
+    }
+}
@@ -1,18 +1,36 @@
 //! Bucket synchronization job and execution logic
 //!
 //! This module contains the logic for syncing buckets between peers.
+//!
+//! Versions form a DAG. We download every version of the peer's history that
+//! isn't in our log yet and append them, parents first. When the peer's head
+//! builds on ours that's a fast-forward. When it forked from our history
+//! instead, we merge it into our head with the bucket's [`ConflictStrategy`],
+//! saving a merge manifest whose parents are both heads. That merge sits above
+//! both branches, so every peer converges on it.
+//!
+//! [`ConflictStrategy`]: crate::mount::ConflictStrategy
+
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::path::Path;
 
 use anyhow::{anyhow, Result};
 use uuid::Uuid;
 
-use crate::bucket_log::BucketLogProvider;
+use crate::bucket_log::{BucketLogError, BucketLogProvider, SyncMode};
+use crate::clock::unix_now;
 use crate::crypto::PublicKey;
 use crate::linked_data::Link;
-use crate::mount::Manifest;
 use crate::mount::PrincipalRole;
+use crate::mount::{Manifest, Membership, Mount, MountError, OpType, Share, Transition};
+use crate::peer::protocol::messages::manifest_chain::known_versions;
+use crate::peer::protocol::{BidirectionalHandler, GetManifestChain, ManifestChainMessage};
 use crate::peer::Peer;
 
-use super::{DownloadPinsJob, ProvenanceError, SyncJob};
+use super::{download_pins, DownloadPinsJob, ProvenanceError, SyncJob, SyncProgressEvent};
+
+/// How far ahead of our clock a version's `saved_at` may be, in seconds
+const MAX_CLOCK_SKEW_SECS: u64 = 5 * 60;
 
 /// Result of provenance verification for a manifest.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -46,10 +64,52 @@ pub struct SyncBucketJob {
 
 /// Execute a bucket sync job
 ///
-/// This is the main entry point for syncing. It handles both cases:
-/// - Updating an existing bucket we already have
+/// This is the main entry point for syncing. It handles these cases:
+/// - Updating an existing bucket we already have (fast-forward)
+/// - Merging a peer's branch that forked from our history
 /// - Cloning a new bucket we don't have yet
+///
+/// Progress is reported on the peer's sync progress channel: a `Started`
+/// event, then `Completed` with our resulting head, or `Failed` when the
+/// sync errors or the peer's history is refused.
 pub async fn execute<L>(peer: &Peer<L>, job: SyncBucketJob) -> Result<()>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let bucket_id = job.bucket_id;
+    peer.emit_progress(
+        bucket_id,
+        SyncProgressEvent::Started {
+            link: job.target.link.clone(),
+            height: job.target.height,
+        },
+    );
+
+    match sync(peer, job).await {
+        Ok(true) => {
+            let (link, height) = peer.logs().head(bucket_id, None).await?;
+            peer.emit_progress(bucket_id, SyncProgressEvent::Completed { link, height });
+            Ok(())
+        }
+        Ok(false) => Ok(()),
+        Err(e) => {
+            peer.emit_progress(
+                bucket_id,
+                SyncProgressEvent::Failed {
+                    error: e.to_string(),
+                },
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Run a sync, returning `false` when the peer's history was refused
+///
+/// Refusals are reported as a failed sync here, since they aren't errors
+/// worth retrying.
+async fn sync<L>(peer: &Peer<L>, job: SyncBucketJob) -> Result<bool>
 where
     L: BucketLogProvider + Clone + Send + Sync + 'static,
     L::Error: std::error::Error + Send + Sync + 'static,
@@ -66,6 +126,10 @@ where
 
     let exists: bool = peer.logs().exists(job.bucket_id).await?;
 
+    // Fetch the missing history in bulk up front; the walks below then find
+    //  every manifest locally instead of downloading them one by one
+    prefetch_manifest_chain(peer, job.bucket_id, &job.target, exists).await;
+
     let common_ancestor = if exists {
         // find a common ancestor between our log and the
         //  link the peer advertised to us
@@ -74,67 +138,65 @@ where
         None
     };
 
-    // TODO (amiller68): between finding the common ancestor and downloading the manifest chain
-    //  there are redundant operations. We should optimize this.
-
-    // if we know the bucket exists, but we did not find a common ancestor
-    //  then we have diverged / are not talking about the same bucket
-    // for now just log a warning and do nothing
+    // if we know the bucket exists, but the peer's history reaches genesis
+    //  without touching our log, the two histories share nothing - this is
+    //  not the same bucket, so there is nothing we could safely merge
     if exists && common_ancestor.is_none() {
         tracing::warn!(
-            "Bucket {} diverged from peer(s) {:?}",
+            "Bucket {} has no shared history with peer(s) {:?}, refusing to merge",
             job.bucket_id,
             peer_ids_hex
         );
-        return Ok(());
+        peer.emit_progress(
+            job.bucket_id,
+            SyncProgressEvent::Failed {
+                error: "no shared history with peer".to_string(),
+            },
+        );
+        return Ok(false);
     }
 
-    // Determine between what links we should download manifests for
-    let stop_link_owned = common_ancestor.as_ref().map(|ancestor| ancestor.0.clone());
-    let stop_link = stop_link_owned.as_ref();
-
-    if stop_link.is_none() && common_ancestor.is_none() {
-        // No common ancestor - we'll sync everything from the target back to genesis
+    // Remember our head so we can tell a fast-forward from a fork
+    let our_head = if exists {
+        Some(peer.logs().head(job.bucket_id, None).await?.0)
+    } else {
         tracing::info!(
             "No common ancestor for bucket {}, syncing from genesis",
             job.bucket_id
         );
-    }
-
-    // now we know there is a valid list of manifests we should
-    //  fetch and apply to our log
+        None
+    };
 
     // Load the common ancestor manifest as our trusted base (if we have one)
     // This manifest is already in our blobs from find_common_ancestor
-    let trusted_base: Option<Manifest> = if let Some(link) = stop_link {
-        Some(peer.blobs().get_cbor(&link.hash()).await?)
-    } else {
-        None
+    let trusted_base: Option<Manifest> = match &common_ancestor {
+        Some((link, _)) => Some(peer.blobs().get_cbor(&link.hash()).await?),
+        None => None,
     };
 
-    // Download manifest chain from peer (from target back to common ancestor)
-    let manifests = download_manifest_chain(
-        peer,
-        &job.target.link,
-        stop_link,
-        &job.target.peer_ids,
-        trusted_base.as_ref(),
-    )
-    .await?;
+    // Download every version in the peer's history that we don't have yet
+    let manifests =
+        download_manifest_chain(peer, job.bucket_id, &job.target.link, &job.target.peer_ids)
+            .await?;
+    peer.emit_progress(
+        job.bucket_id,
+        SyncProgressEvent::ManifestsFetched {
+            count: manifests.len(),
+        },
+    );
 
     // TODO (amiller68): maybe theres an optimization here in that we should know
     //  we can exit earlier by virtue of finding a common ancestor which is just
     //  our current head
     if manifests.is_empty() {
         tracing::info!("No new manifests to sync, already up to date");
-        return Ok(());
+        return Ok(true);
     };
 
     // Verify provenance of the latest manifest
     // Use our local manifest as the trusted base (previous)
-    let latest_manifest = &manifests.last().unwrap().0;
-    let trusted_base_ref = trusted_base.as_ref();
-    match verify_provenance(peer, latest_manifest, trusted_base_ref)? {
+    let (latest_manifest, latest_link) = manifests.last().unwrap();
+    match verify_provenance(peer, latest_manifest, trusted_base.as_ref())? {
         ProvenanceResult::Valid => {
             tracing::debug!("Provenance verification passed");
         }
@@ -146,122 +208,511 @@ where
         }
         ProvenanceResult::NotAuthorized => {
             tracing::warn!("Provenance verification failed: our key not in bucket shares");
-            return Ok(());
+            peer.emit_progress(
+                job.bucket_id,
+                SyncProgressEvent::Failed {
+                    error: "our key is not in the bucket shares".to_string(),
+                },
+            );
+            return Ok(false);
+        }
+    }
+
+    // A forked branch must be written by principals that can still write by
+    //  our head and our clock, before any of it reaches our log
+    if let Some(our_head) = &our_head {
+        let builds_on_head = manifests
+            .iter()
+            .any(|(manifest, _)| manifest.parents().contains(our_head));
+        if !builds_on_head {
+            let our_manifest: Manifest = peer.blobs().get_cbor(&our_head.hash()).await?;
+            if let Err(e) = verify_branch_authors(&our_manifest, &manifests, unix_now()) {
+                tracing::warn!(
+                    "Refusing to merge fork of bucket {} from peer(s) {:?}: {}",
+                    job.bucket_id,
+                    peer_ids_hex,
+                    e
+                );
+                peer.emit_progress(
+                    job.bucket_id,
+                    SyncProgressEvent::Failed {
+                        error: e.to_string(),
+                    },
+                );
+                return Ok(false);
+            }
         }
     }
 
     // apply the updates to the bucket
     apply_manifest_chain(peer, job.bucket_id, &manifests).await?;
 
+    // If the peer's head doesn't build on ours, the histories forked
+    if let Some(our_head) = our_head {
+        let fast_forward = descends_from(peer, job.bucket_id, &our_head, latest_link).await?;
+        if !fast_forward {
+            tracing::info!(
+                "Bucket {} forked from peer(s) {:?}, merging",
+                job.bucket_id,
+                peer_ids_hex
+            );
+            merge_fork(
+                peer,
+                &our_head,
+                latest_link,
+                latest_manifest,
+                &job.target.peer_ids,
+            )
+            .await?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Ask peers for the manifests we're missing and download them in one batch
+///
+/// Sends a [`GetManifestChain`] request with a sample of our log to each peer
+/// in order, and downloads the listed manifests from the first one that has
+/// the target. Replies are checked with [`ManifestChainReply::validate`]
+/// before anything is downloaded, and the downloads must decode as manifests
+/// of this bucket. This is best-effort: on failure the manifest walks fall
+/// back to fetching one manifest at a time, verifying each as usual.
+///
+/// [`ManifestChainReply::validate`]: crate::peer::protocol::messages::manifest_chain::ManifestChainReply::validate
+async fn prefetch_manifest_chain<L>(
+    peer: &Peer<L>,
+    bucket_id: Uuid,
+    target: &SyncTarget,
+    exists: bool,
+) where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let known = if exists {
+        match known_versions(peer.logs(), bucket_id).await {
+            Ok(known) => known,
+            Err(e) => {
+                tracing::warn!("Failed to sample log for bucket {}: {}", bucket_id, e);
+                return;
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    for peer_id in &target.peer_ids {
+        let message = ManifestChainMessage {
+            bucket_id,
+            from: target.link.clone(),
+            known: known.clone(),
+        };
+        let reply = match GetManifestChain::send::<L>(peer, peer_id, message).await {
+            Ok(reply) => reply,
+            Err(e) => {
+                tracing::debug!(
+                    "Manifest chain request to {} failed: {}",
+                    peer_id.to_hex(),
+                    e
+                );
+                continue;
+            }
+        };
+        if let Err(e) = reply.validate(&target.link) {
+            tracing::warn!("Ignoring manifest chain from {}: {}", peer_id.to_hex(), e);
+            continue;
+        }
+        if reply.links.is_empty() {
+            if reply.complete {
+                return;
+            }
+            continue;
+        }
+
+        peer.emit_progress(bucket_id, SyncProgressEvent::Peer { peer_id: *peer_id });
+        match download_chain(peer, bucket_id, &reply.links, peer_id).await {
+            Ok(()) => {
+                tracing::info!(
+                    "Prefetched {} manifests for bucket {} from {}",
+                    reply.links.len(),
+                    bucket_id,
+                    peer_id.to_hex()
+                );
+                return;
+            }
+            Err(e) => {
+                peer.emit_progress(
+                    bucket_id,
+                    SyncProgressEvent::PeerFailed { peer_id: *peer_id },
+                );
+                tracing::warn!(
+                    "Failed to download manifest chain from {}: {}",
+                    peer_id.to_hex(),
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// Download the manifests of a chain and check they belong to the bucket
+async fn download_chain<L>(
+    peer: &Peer<L>,
+    bucket_id: Uuid,
+    links: &[Link],
+    peer_id: &PublicKey,
+) -> Result<()>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    peer.blobs()
+        .download_hashes(
+            links.iter().map(Link::hash).collect(),
+            vec![*peer_id],
+            peer.endpoint(),
+        )
+        .await?;
+    for link in links {
+        let manifest: Manifest = peer
+            .blobs()
+            .get_cbor(&link.hash())
+            .await
+            .map_err(|e| anyhow!("{:?} is not a manifest: {}", link, e))?;
+        if *manifest.id() != bucket_id {
+            return Err(anyhow!("{:?} belongs to bucket {}", link, manifest.id()));
+        }
+    }
     Ok(())
 }
 
-/// Download a chain of manifests from peers and validate provenance
+/// Check whether `descendant` builds on `ancestor`, both already in our log
 ///
-/// Walks backwards through the manifest chain via `previous` links.
-/// Stops when it reaches `stop_at` link (common ancestor) or genesis (no previous).
-/// Tries multiple peers in order for each download, succeeding on first available.
+/// Jumps down the descendant's first-parent spine with skip pointers, which
+/// settles a fast-forward in a logarithmic number of manifest loads. When the
+/// ancestor isn't on the spine it may still be reachable through a merge
+/// parent, so only then do we fall back to the log's full DAG walk.
+pub(super) async fn descends_from<L>(
+    peer: &Peer<L>,
+    bucket_id: Uuid,
+    ancestor: &Link,
+    descendant: &Link,
+) -> Result<bool>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let Some(target) = height_in_log(peer, bucket_id, ancestor).await? else {
+        return Ok(false);
+    };
+
+    let mut cursor = descendant.clone();
+    let mut manifest: Manifest = peer.blobs().get_cbor(&cursor.hash()).await?;
+    while let Some(next) = manifest.skip_toward(target) {
+        cursor = next.clone();
+        manifest = peer.blobs().get_cbor(&cursor.hash()).await?;
+    }
+    if cursor == *ancestor {
+        return Ok(true);
+    }
+
+    Ok(peer
+        .logs()
+        .is_ancestor(bucket_id, ancestor.clone(), descendant.clone())
+        .await?)
+}
+
+/// Merge a forked branch (already appended to our log) into our head.
+///
+/// The branch's authors were checked against our head by
+/// [`verify_branch_authors`] before it was appended.
 ///
-/// After downloading, validates each manifest's provenance:
-/// - The first manifest (oldest) is validated against `trusted_base` (if any)
-/// - Each subsequent manifest is validated against its predecessor
+/// Only unscoped owners can produce a valid merge; mirrors, readers and
+/// scoped owners keep their head and pick up the merge once an owner makes
+/// it. The merged version is saved through [`Peer::save_mount`], which
+/// appends it to the log and notifies peers.
 ///
-/// Returns manifests in order from oldest to newest with their links.
+/// Of two owners that see each other's branch, only one merges (see
+/// [`waits_for_merge`]): each merge gets a fresh secret and signature, so
+/// merging on both sides would leave two new branches to merge again. The
+/// other owner may never see our branch though, so a fork we wait on is kept
+/// in [`Peer::pending_merges`] and merged by us if no merge arrives within
+/// [`MERGE_WAIT`].
+///
+/// [`MERGE_WAIT`]: crate::peer::merges::MERGE_WAIT
+async fn merge_fork<L>(
+    peer: &Peer<L>,
+    our_head: &Link,
+    their_head: &Link,
+    their_manifest: &Manifest,
+    peer_ids: &[PublicKey],
+) -> Result<()>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let our_manifest: Manifest = peer.blobs().get_cbor(&our_head.hash()).await?;
+    if !can_merge(peer, &our_manifest) {
+        return Ok(());
+    }
+    let bucket_id = *their_manifest.id();
+    if waits_for_merge(our_head, their_head, their_manifest)
+        && peer
+            .pending_merges()
+            .wait(bucket_id, our_head, their_head, peer_ids)
+    {
+        tracing::info!(
+            "Leaving the fork of bucket {} for the owner of {:?} to merge",
+            bucket_id,
+            their_head
+        );
+        return Ok(());
+    }
+
+    merge_branches(
+        peer,
+        our_head,
+        &our_manifest,
+        their_head,
+        their_manifest,
+        peer_ids,
+    )
+    .await
+}
+
+/// Whether we can merge forks of a bucket, which takes an unscoped owner
+pub(super) fn can_merge<L>(peer: &Peer<L>, our_manifest: &Manifest) -> bool
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+{
+    let our_key = peer.secret().public();
+    let is_owner = our_manifest
+        .get_share(&our_key)
+        .is_some_and(|share| *share.role() == PrincipalRole::Owner && !share.is_scoped());
+    if !is_owner {
+        tracing::info!(
+            "Not an owner of bucket {}, leaving the fork for an owner to merge",
+            our_manifest.id()
+        );
+    }
+    is_owner
+}
+
+/// Merge the branch at `their_head` into `our_head` and save the result
+pub(super) async fn merge_branches<L>(
+    peer: &Peer<L>,
+    our_head: &Link,
+    our_manifest: &Manifest,
+    their_head: &Link,
+    their_manifest: &Manifest,
+    peer_ids: &[PublicKey],
+) -> Result<()>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    // The merge reads the other branch's tree and ops logs, so fetch its
+    // pinned content before loading it. Lazily synced buckets fetch what
+    // the merge reads on demand instead.
+    let bucket_id = *their_manifest.id();
+    if peer.logs().sync_mode(bucket_id).await? == SyncMode::Full {
+        download_pins::execute(
+            peer,
+            DownloadPinsJob {
+                bucket_id,
+                pins_link: their_manifest.pins().clone(),
+                peer_ids: peer_ids.to_vec(),
+            },
+        )
+        .await?;
+    }
+
+    let mut ours = peer.load_mount(bucket_id, our_head).await?;
+    let theirs = peer.load_mount(bucket_id, their_head).await?;
+
+    let strategy = ours.conflict_strategy().await;
+    let resolver = strategy.resolver();
+    let result = ours.merge(&theirs, resolver.as_ref(), peer.blobs()).await?;
+
+    for resolved in &result.conflicts_resolved {
+        tracing::info!(
+            "Resolved conflict for {:?} ({}): {:?}",
+            resolved.conflict.path,
+            strategy,
+            resolved.resolution
+        );
+    }
+
+    // Keep mirrors' access if either branch was published
+    let publish = our_manifest.is_published() || their_manifest.is_published();
+    for path in their_manifest
+        .published()
+        .keys()
+        .filter(|path| path.starts_with('/'))
+    {
+        // Saving drops subtrees the merge left without a directory
+        if let Err(e) = ours.publish_path(Path::new(path)).await {
+            tracing::debug!("Not keeping published subtree {}: {}", path, e);
+        }
+    }
+    let link = peer.save_mount(&ours, publish).await?;
+
+    tracing::info!(
+        "Merged {} operations into bucket {} ({} conflicts resolved), new head {:?}",
+        result.operations_added,
+        our_manifest.id(),
+        result.conflicts_resolved.len(),
+        link
+    );
+
+    Ok(())
+}
+
+/// Whether we should leave a fork for the other branch's author to merge
+///
+/// Owners holding the two heads decide the same way: the one whose head
+/// sorts lower merges, the other waits for that merge to arrive and
+/// fast-forwards to it (or merges after all once it waited too long). If the
+/// other branch's author can't merge, we do.
+fn waits_for_merge(our_head: &Link, their_head: &Link, their_manifest: &Manifest) -> bool {
+    let they_can_merge = their_manifest
+        .author()
+        .and_then(|author| their_manifest.get_share(author))
+        .is_some_and(|share| *share.role() == PrincipalRole::Owner && !share.is_scoped());
+    they_can_merge && their_head < our_head
+}
+
+/// Download the manifests we're missing from a peer's history and validate provenance
+///
+/// Walks backwards through the version DAG via each manifest's parents,
+/// stopping at versions already in our log and at genesis. Tries multiple
+/// peers in order for each download, succeeding on first available.
+///
+/// After downloading, validates each manifest's provenance against its
+/// primary parent, which is either in the downloaded set or already ours.
+/// Versions written by a scoped owner are also checked against its write
+/// scope (see [`verify_write_scope`]), and membership changes against the
+/// bucket's quorum (see [`verify_membership`]).
+///
+/// Returns manifests ordered by height (parents before children) with their links.
 async fn download_manifest_chain<L>(
     peer: &Peer<L>,
+    bucket_id: Uuid,
     start_link: &Link,
-    stop_link: Option<&Link>,
     peer_ids: &[PublicKey],
-    trusted_base: Option<&Manifest>,
 ) -> Result<Vec<(Manifest, Link)>>
 where
     L: BucketLogProvider + Clone + Send + Sync + 'static,
     L::Error: std::error::Error + Send + Sync + 'static,
 {
     tracing::debug!(
-        "Downloading manifest chain from {:?}, stop_at: {:?}, using {} peer(s)",
+        "Downloading manifest history from {:?} using {} peer(s)",
         start_link,
-        stop_link,
         peer_ids.len()
     );
 
-    let mut manifests = Vec::new();
-    let mut current_link = start_link.clone();
+    let mut manifests: HashMap<Link, Manifest> = HashMap::new();
+    let mut queue = vec![start_link.clone()];
 
     // Download manifests walking backwards
-    loop {
-        // Download the manifest blob from peers
-        peer.blobs()
-            .download_hash(current_link.hash(), peer_ids.to_vec(), peer.endpoint())
-            .await
-            .map_err(|e| {
-                anyhow!(
-                    "Failed to download manifest {:?} from peers: {}",
-                    current_link,
-                    e
-                )
-            })?;
-
-        // Read and decode the manifest
-        let manifest: Manifest = peer.blobs().get_cbor(&current_link.hash()).await?;
+    while let Some(current_link) = queue.pop() {
+        if manifests.contains_key(&current_link) {
+            continue;
+        }
 
         // Check if we should stop
-        if let Some(stop_link) = stop_link {
-            if &current_link == stop_link {
-                tracing::debug!("Reached stop_at link, stopping download");
-                break;
-            }
+        if !peer
+            .logs()
+            .has(bucket_id, current_link.clone())
+            .await?
+            .is_empty()
+        {
+            tracing::debug!("Reached {:?} in our log, stopping", current_link);
+            continue;
         }
 
-        manifests.push((manifest.clone(), current_link.clone()));
+        // Download the manifest blob from peers
+        let manifest = fetch_manifest(peer, &current_link, peer_ids).await?;
+        peer.blobs().keep_hot(&current_link.hash()).await?;
 
-        // Check for previous link
-        match manifest.previous() {
-            Some(prev_link) => {
-                current_link = prev_link.clone();
-            }
-            None => {
-                // Reached genesis, stop
-                tracing::debug!("Reached genesis manifest, stopping download");
-                break;
-            }
-        }
+        // Reached genesis when there are no parents
+        queue.extend(manifest.parents().iter().cloned());
+        manifests.insert(current_link, manifest);
     }
 
-    // Reverse to get oldest-to-newest order
-    manifests.reverse();
+    // Order from oldest to newest; parents are always lower than children
+    let mut manifests: Vec<(Manifest, Link)> = manifests
+        .into_iter()
+        .map(|(link, manifest)| (manifest, link))
+        .collect();
+    manifests.sort_by(|(a, a_link), (b, b_link)| {
+        a.height().cmp(&b.height()).then_with(|| a_link.cmp(b_link))
+    });
 
     tracing::debug!("Downloaded {} manifests", manifests.len());
 
-    // Validate the chain (walking from oldest to newest)
-    // The first manifest is validated against the trusted_base (common ancestor)
+    // Validate each manifest against its primary parent
     // NOTE: Chain validation only checks author authorization, not receiver authorization.
     // The receiver check is done separately on the final manifest in execute().
-    let mut previous: Option<&Manifest> = trusted_base;
-
     for (manifest, link) in manifests.iter() {
-        verify_author(manifest, previous).map_err(|e| ProvenanceError::InvalidManifestInChain {
+        let mut parents = Vec::new();
+        for parent in manifest.parents() {
+            let parent_manifest = match manifests.iter().find(|(_, l)| l == parent) {
+                Some((parent_manifest, _)) => parent_manifest.clone(),
+                None => peer.blobs().get_cbor(&parent.hash()).await?,
+            };
+            parents.push((parent_manifest, parent.clone()));
+        }
+        let previous = parents.first();
+        let invalid = |e: ProvenanceError| ProvenanceError::InvalidManifestInChain {
             link: link.clone(),
             reason: e.to_string(),
-        })?;
-        previous = Some(manifest);
+        };
+        verify_saved_at(manifest, unix_now()).map_err(invalid)?;
+        verify_author(manifest, previous.map(|(m, _)| m)).map_err(invalid)?;
+        let parent_manifests: Vec<&Manifest> = parents.iter().map(|(m, _)| m).collect();
+        verify_membership(manifest, &parent_manifests).map_err(invalid)?;
+        if let Some((previous, previous_link)) = previous {
+            verify_write_scope(peer, (manifest, link), (previous, previous_link), peer_ids)
+                .await
+                .map_err(invalid)?;
+        }
     }
 
     Ok(manifests)
 }
 
+/// Download a manifest from peers and decode it
+async fn fetch_manifest<L>(peer: &Peer<L>, link: &Link, peer_ids: &[PublicKey]) -> Result<Manifest>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    peer.blobs()
+        .download_hash(link.hash(), peer_ids.to_vec(), peer.endpoint())
+        .await
+        .map_err(|e| anyhow!("Failed to download manifest {:?} from peers: {}", link, e))?;
+
+    Ok(peer.blobs().get_cbor(&link.hash()).await?)
+}
+
 /// Find common ancestor by downloading manifests from peers
 ///
-/// Starting from `start_link`, walks backwards through the peer's manifest chain,
-/// downloading each manifest and checking if it exists in our local log.
-/// Returns the first (most recent) link and height found in our log.
-/// Tries multiple peers in order for each download, succeeding on first available.
+/// First jumps down the peer's first-parent spine with skip pointers to the
+/// highest spine version in our log, which takes a logarithmic number of
+/// downloads (see [`spine_ancestor_in_log`]). If the spine never reaches our
+/// log the histories share nothing. Otherwise that version is a common
+/// ancestor, and a more recent one can only be reached through merge parents
+/// above it: starting from `link`, walks backwards through the peer's version
+/// DAG highest version first, never below the spine ancestor, and returns the
+/// first (most recent) link and height found in our log. Tries multiple peers
+/// in order for each download, succeeding on first available.
 ///
 /// # Arguments
 ///
 /// * `peer` - The peer instance with access to logs and blobs
 /// * `bucket_id` - The bucket to check against our local log
-/// * `link` - The starting point on the peer's chain (typically their head)
+/// * `link` - The starting point on the peer's history (typically their head)
 /// * `peer_ids` - The peers to download manifests from (in priority order)
 ///
 /// # Returns
@@ -285,35 +736,28 @@ where
         peer_ids.len()
     );
 
-    let mut current_link = link.clone();
-    let mut manifests_checked = 0;
+    let Some((spine_link, floor)) = spine_ancestor_in_log(peer, bucket_id, link, peer_ids).await?
+    else {
+        tracing::debug!("Peer's first-parent spine reached genesis outside our log");
+        return Ok(None);
+    };
 
-    loop {
-        manifests_checked += 1;
+    // TODO (amiller68): this should build in memory
+    //  but for now we just download it
+    let start = fetch_manifest(peer, link, peer_ids).await?;
+    let mut queue = BinaryHeap::from([(start.height(), link.clone())]);
+    let mut visited = HashSet::new();
+
+    while let Some((height, current_link)) = queue.pop() {
+        if !visited.insert(current_link.clone()) {
+            continue;
+        }
         tracing::debug!(
             "Checking manifest {} at link {:?}",
-            manifests_checked,
+            visited.len(),
             current_link
         );
 
-        // TODO (amiller68): this should build in memory
-        //  but for now we just download it
-        // Download the manifest from peers
-        peer.blobs()
-            .download_hash(current_link.hash(), peer_ids.to_vec(), peer.endpoint())
-            .await
-            .map_err(|e| {
-                anyhow!(
-                    "Failed to download manifest {:?} from peers: {}",
-                    current_link,
-                    e
-                )
-            })?;
-
-        // Read and decode the manifest
-        let manifest: Manifest = peer.blobs().get_cbor(&current_link.hash()).await?;
-        let height = manifest.height();
-
         // Check if this link exists in our local log
         match peer.logs().has(bucket_id, current_link.clone()).await {
             Ok(heights) if !heights.is_empty() => {
@@ -326,35 +770,113 @@ where
                 return Ok(Some((current_link, height)));
             }
             Ok(_) => {
-                // Link not in our log, check previous
-                tracing::debug!("Link {:?} not in our log, checking previous", current_link);
+                // Link not in our log, check its parents
+                tracing::debug!("Link {:?} not in our log, checking parents", current_link);
             }
             Err(e) => {
                 tracing::warn!("Error checking for link in log: {}", e);
-                // Continue checking previous links despite error
+                // Continue checking parent links despite error
             }
         }
 
-        // Move to previous link
-        match manifest.previous() {
-            Some(prev_link) => {
-                current_link = prev_link.clone();
+        // Queue the parents, highest first; nothing below the spine
+        //  ancestor can beat it
+        let manifest: Manifest = peer.blobs().get_cbor(&current_link.hash()).await?;
+        for parent in manifest.parents() {
+            if !visited.contains(parent) {
+                let parent_manifest = fetch_manifest(peer, parent, peer_ids).await?;
+                if parent_manifest.height() >= floor {
+                    queue.push((parent_manifest.height(), parent.clone()));
+                }
             }
-            None => {
-                // Reached genesis without finding common ancestor
-                tracing::debug!(
-                    "Reached genesis after checking {} manifests, no common ancestor found",
-                    manifests_checked
-                );
-                return Ok(None);
+        }
+    }
+
+    tracing::info!(
+        "Found common ancestor at link {:?} with height {} on the peer's first-parent spine",
+        spine_link,
+        floor
+    );
+    Ok(Some((spine_link, floor)))
+}
+
+/// Find the highest version on a peer's first-parent spine that is in our log
+///
+/// Our log is closed under ancestry, so along the spine the versions we have
+/// form a prefix. From the lowest version known to be missing, we jump to the
+/// farthest skip pointer that is still missing, or step to the previous
+/// version once every skip is ours. Each jump at least halves the remaining
+/// distance, so this downloads a logarithmic number of manifests (linear for
+/// manifests written before skip pointers existed).
+///
+/// Returns `None` if the spine reaches genesis without touching our log.
+async fn spine_ancestor_in_log<L>(
+    peer: &Peer<L>,
+    bucket_id: Uuid,
+    link: &Link,
+    peer_ids: &[PublicKey],
+) -> Result<Option<(Link, u64)>>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    if let Some(height) = height_in_log(peer, bucket_id, link).await? {
+        return Ok(Some((link.clone(), height)));
+    }
+
+    let mut manifest = fetch_manifest(peer, link, peer_ids).await?;
+    let mut downloads = 1;
+    loop {
+        // Farthest skip first
+        let mut jump = None;
+        for skip in manifest.skips().iter().rev() {
+            if height_in_log(peer, bucket_id, skip).await?.is_none() {
+                jump = Some(skip.clone());
+                break;
             }
         }
+
+        let next = match jump {
+            Some(skip) => skip,
+            None => {
+                let Some(previous) = manifest.previous().clone() else {
+                    return Ok(None);
+                };
+                if let Some(height) = height_in_log(peer, bucket_id, &previous).await? {
+                    tracing::debug!(
+                        "Found spine ancestor {:?} after {} manifest downloads",
+                        previous,
+                        downloads
+                    );
+                    return Ok(Some((previous, height)));
+                }
+                previous
+            }
+        };
+        manifest = fetch_manifest(peer, &next, peer_ids).await?;
+        downloads += 1;
     }
 }
 
+/// Get the height of a version in our log, if we have it
+async fn height_in_log<L>(peer: &Peer<L>, bucket_id: Uuid, link: &Link) -> Result<Option<u64>>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    Ok(peer
+        .logs()
+        .has(bucket_id, link.clone())
+        .await?
+        .into_iter()
+        .max())
+}
+
 /// Apply a chain of manifests to the log
 ///
-/// Appends each manifest to the log in order, starting from `start_height`.
+/// Appends each manifest to the log in order (parents first), then dispatches a
+/// pins download for the newest one, unless we are only a depositor of it.
+/// Manifests already in the log are skipped.
 async fn apply_manifest_chain<L>(
     peer: &Peer<L>,
     bucket_id: Uuid,
@@ -366,43 +888,61 @@ where
 {
     tracing::info!("Applying {} manifests to log", manifests.len(),);
 
-    if let Some((_i, (manifest, link))) = manifests.iter().enumerate().next() {
-        let previous = manifest.previous().clone();
+    for (manifest, link) in manifests {
+        let parents = manifest.parents().to_vec();
         let height = manifest.height();
-        let is_published = manifest.is_published();
+        let is_published = manifest.has_published_content();
 
         tracing::info!(
-            "Appending manifest to log: height={}, link={:?}, previous={:?}, published={}",
+            "Appending manifest to log: height={}, link={:?}, parents={:?}, published={}",
             height,
             link,
-            previous,
+            parents,
             is_published
         );
 
-        peer.logs()
+        match peer
+            .logs()
             .append(
                 bucket_id,
                 manifest.name().to_string(),
                 link.clone(),
-                previous,
+                parents,
                 height,
                 is_published,
             )
             .await
-            .map_err(|e| anyhow!("Failed to append manifest at height {}: {}", height, e))?;
+        {
+            Ok(()) | Err(BucketLogError::Conflict) => {}
+            Err(e) => {
+                return Err(anyhow!(
+                    "Failed to append manifest at height {}: {}",
+                    height,
+                    e
+                ))
+            }
+        }
+    }
 
+    // Depositors can't read the bucket; they only need its manifests to
+    // know who the owners are
+    if let Some((manifest, _)) = manifests.last().filter(|(manifest, _)| {
+        manifest
+            .get_share(&peer.secret().public())
+            .is_none_or(|share| *share.role() != PrincipalRole::Depositor)
+    }) {
         let pins_link = manifest.pins().clone();
         let peer_ids = manifest
             .shares()
             .iter()
             .map(|share| share.1.principal().identity)
             .collect();
-        return peer
-            .dispatch(SyncJob::DownloadPins(DownloadPinsJob {
-                pins_link,
-                peer_ids,
-            }))
-            .await;
+        peer.dispatch(SyncJob::DownloadPins(DownloadPinsJob {
+            bucket_id,
+            pins_link,
+            peer_ids,
+        }))
+        .await?;
     }
 
     tracing::info!("Successfully applied {} manifests to log", manifests.len());
@@ -415,6 +955,8 @@ where
 /// 1. The manifest is properly signed (or unsigned during migration)
 /// 2. The author was in the previous manifest's shares (authorized to make changes)
 /// 3. The author has write permission (Owner role)
+/// 4. The author's share hadn't expired by the version's `saved_at`
+/// 5. The author didn't change its own expiry, unless the bucket has a quorum
 ///
 /// This is used for chain validation where we don't yet know if the receiver
 /// is in the final shares.
@@ -465,7 +1007,24 @@ fn verify_author(
         return Err(ProvenanceError::AuthorNotWriter);
     }
 
-    // 5. If shares were removed, verify the author was an owner in the previous manifest
+    // 5. Check the author's share hadn't expired by the time of the version
+    if author_share.is_expired(manifest.saved_at()) {
+        return Err(ProvenanceError::AuthorExpired);
+    }
+
+    // 6. An author can't change its own expiry unless other owners have to
+    //    approve it (see verify_membership)
+    if let Some(prev) = previous {
+        let expiry_changed = manifest
+            .shares()
+            .get(&author_hex)
+            .is_some_and(|share| share.expires_at() != author_share.expires_at());
+        if expiry_changed && prev.quorum() <= 1 {
+            return Err(ProvenanceError::SelfExpiryChange);
+        }
+    }
+
+    // 7. If shares were removed, verify the author was an owner in the previous manifest
     if let Some(prev) = previous {
         let prev_keys: std::collections::HashSet<&String> = prev.shares().keys().collect();
         let current_keys: std::collections::HashSet<&String> = manifest.shares().keys().collect();
@@ -493,6 +1052,265 @@ fn verify_author(
     Ok(ProvenanceResult::Valid)
 }
 
+/// Check that a version isn't dated ahead of our clock
+///
+/// Share expiry is judged by the signed `saved_at`, which the author picks.
+/// A version dated into the future would make shares count as expired, and
+/// so removable without approvals, before they are. Versions more than
+/// [`MAX_CLOCK_SKEW_SECS`] ahead of `now` are refused.
+fn verify_saved_at(manifest: &Manifest, now: u64) -> Result<(), ProvenanceError> {
+    if manifest.saved_at() > now.saturating_add(MAX_CLOCK_SKEW_SECS) {
+        return Err(ProvenanceError::SavedInFuture(manifest.saved_at()));
+    }
+    Ok(())
+}
+
+/// Check that a forked branch was written by principals that can still write
+///
+/// Versions are checked against their parents by their own `saved_at`, so
+/// an owner whose share expired could fork from a version before its expiry
+/// and date its versions back. Before merging a branch, every signed version
+/// on it must be by an owner in our head's shares that hasn't expired by
+/// `now`.
+fn verify_branch_authors(
+    our_manifest: &Manifest,
+    branch: &[(Manifest, Link)],
+    now: u64,
+) -> Result<(), ProvenanceError> {
+    for (manifest, link) in branch {
+        let Some(author) = manifest.author() else {
+            continue;
+        };
+        let reason = match our_manifest.get_share(author) {
+            None => ProvenanceError::AuthorNotInShares,
+            Some(share) if *share.role() != PrincipalRole::Owner => {
+                ProvenanceError::AuthorNotWriter
+            }
+            Some(share) if share.is_expired(now) => ProvenanceError::AuthorExpired,
+            Some(_) => continue,
+        };
+        return Err(ProvenanceError::InvalidManifestInChain {
+            link: link.clone(),
+            reason: reason.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Verify that a version's membership change was approved by a quorum.
+///
+/// Versions must keep the membership epoch that follows from their parents
+/// (see [`Transition::between`]). When a parent has a quorum above one, a
+/// version that changes the shares must also carry approvals from that many
+/// unscoped owners of its primary parent. Buckets without a quorum are left
+/// to the per-author checks in [`verify_author`].
+///
+/// Shares may be dropped without approvals once they have expired by the
+/// version's signed `saved_at`, not by our own clock, so every peer reaches
+/// the same verdict. `saved_at` may not go back before any parent's.
+fn verify_membership(manifest: &Manifest, parents: &[&Manifest]) -> Result<(), ProvenanceError> {
+    if parents
+        .iter()
+        .any(|parent| manifest.saved_at() < parent.saved_at())
+    {
+        return Err(ProvenanceError::SavedBeforeParent(manifest.saved_at()));
+    }
+    if parents.iter().all(|parent| parent.quorum() <= 1) {
+        return Ok(());
+    }
+
+    let transition = Transition::between(
+        *manifest.id(),
+        &Membership::of(manifest),
+        parents,
+        manifest.saved_at(),
+    );
+    if manifest.membership_epoch() != transition.epoch() {
+        return Err(ProvenanceError::InvalidMembershipEpoch(
+            manifest.membership_epoch(),
+        ));
+    }
+
+    if let Transition::Change { mut change, .. } = transition {
+        let approvals = change.add_approvals(manifest.approvals());
+        if !change.is_approved() {
+            return Err(ProvenanceError::QuorumNotMet {
+                required: change.required(),
+                approvals,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Verify that a version by a scoped owner stays inside its write scope.
+///
+/// Versions by unscoped authors pass untouched. For a scoped author the
+/// bucket's settings must be unchanged from `previous` (see
+/// [`verify_scoped_manifest`]), and every path that differs in the tree or
+/// appears in the version's ops log must be in scope. Checking paths means
+/// decrypting both versions, fetching their nodes from `peer_ids` as needed;
+/// a peer that can't decrypt them (a mirror of an unpublished bucket) only
+/// checks the manifest.
+async fn verify_write_scope<L>(
+    peer: &Peer<L>,
+    (manifest, link): (&Manifest, &Link),
+    (previous, previous_link): (&Manifest, &Link),
+    peer_ids: &[PublicKey],
+) -> Result<(), ProvenanceError>
+where
+    L: BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    let Some(author_share) = manifest
+        .author()
+        .and_then(|author| previous.get_share(author))
+        .filter(|share| share.is_scoped())
+    else {
+        return Ok(());
+    };
+
+    verify_scoped_manifest(author_share, manifest, previous)?;
+
+    let blobs = peer
+        .blobs()
+        .with_remote(peer.endpoint().clone(), peer_ids.to_vec());
+    let (new, old) = match (
+        Mount::load(link, peer.secret(), &blobs).await,
+        Mount::load(previous_link, peer.secret(), &blobs).await,
+    ) {
+        (Ok(new), Ok(old)) => (new, old),
+        (Err(MountError::ShareNotFound | MountError::MirrorCannotMount), _)
+        | (_, Err(MountError::ShareNotFound | MountError::MirrorCannotMount)) => {
+            tracing::warn!(
+                "Cannot decrypt bucket {} to check the write scope of {:?}",
+                manifest.id(),
+                link
+            );
+            return Ok(());
+        }
+        (Err(e), _) | (_, Err(e)) => return Err(anyhow!("failed to load version: {}", e).into()),
+    };
+
+    verify_scoped_paths(author_share, &new, &old).await?;
+    verify_scoped_published(manifest, &new).await
+}
+
+/// Check that a scoped author left the bucket's settings alone
+///
+/// Everything but the tree, the history and the signature must match the
+/// primary parent. Saving re-encrypts secret shares and the public secret,
+/// so shares are compared by principal and scope, and publication by
+/// whether the bucket is published. The links of published subtrees are
+/// refreshed on save, so only those overlapping the author's scope may
+/// change (see [`verify_scoped_published`]).
+fn verify_scoped_manifest(
+    author_share: &Share,
+    manifest: &Manifest,
+    previous: &Manifest,
+) -> Result<(), ProvenanceError> {
+    let shares_unchanged = previous.shares().len() == manifest.shares().len()
+        && previous.shares().iter().all(|(key, share)| {
+            manifest.shares().get(key).is_some_and(|new| {
+                new.principal() == share.principal() && new.write_scope() == share.write_scope()
+            })
+        });
+    if !shares_unchanged {
+        return Err(ProvenanceError::ScopedShareChange);
+    }
+
+    let published_unchanged = previous.published().len() == manifest.published().len()
+        && previous.published().iter().all(|(path, link)| {
+            manifest
+                .published()
+                .get(path)
+                .is_some_and(|new| new == link || overlaps_scope(author_share, Path::new(path)))
+        });
+    let settings = [
+        ("id", manifest.id() == previous.id()),
+        ("name", manifest.name() == previous.name()),
+        ("version", manifest.version() == previous.version()),
+        ("public", manifest.is_published() == previous.is_published()),
+        ("published", published_unchanged),
+        (
+            "compression",
+            manifest.compression() == previous.compression(),
+        ),
+        (
+            "conflict_strategy",
+            manifest.conflict_strategy() == previous.conflict_strategy(),
+        ),
+        ("quorum", manifest.quorum() == previous.quorum()),
+        (
+            "membership_epoch",
+            manifest.membership_epoch() == previous.membership_epoch(),
+        ),
+        ("approvals", manifest.approvals().is_empty()),
+    ];
+    if let Some((field, _)) = settings.iter().find(|(_, unchanged)| !unchanged) {
+        return Err(ProvenanceError::ScopedSettingChange(field));
+    }
+    Ok(())
+}
+
+/// Whether a path is inside the author's write scope or above part of it
+fn overlaps_scope(author_share: &Share, path: &Path) -> bool {
+    let path = path.strip_prefix("/").unwrap_or(path);
+    author_share.can_write(path)
+        || author_share
+            .write_scope()
+            .iter()
+            .any(|prefix| prefix.strip_prefix("/").unwrap_or(prefix).starts_with(path))
+}
+
+/// Check that the published subtrees point at the version's own directories
+///
+/// A scoped author's save refreshes these links, so a link it changed could
+/// otherwise hand out the secret of any directory it can read.
+async fn verify_scoped_published(manifest: &Manifest, new: &Mount) -> Result<(), ProvenanceError> {
+    for (path, link) in manifest.published() {
+        let actual = new
+            .get(Path::new(path))
+            .await
+            .map_err(|e| anyhow!("failed to resolve published subtree {}: {}", path, e))?;
+        if actual != *link {
+            return Err(ProvenanceError::ScopedSettingChange("published"));
+        }
+    }
+    Ok(())
+}
+
+/// Check every path a version changed against the author's write scope
+///
+/// Covers the tree's differences from the previous version and the paths in
+/// the version's ops log.
+async fn verify_scoped_paths(
+    author_share: &Share,
+    new: &Mount,
+    old: &Mount,
+) -> Result<(), ProvenanceError> {
+    let ops_log = new.inner().await.ops_log;
+    let op_paths = ops_log.ops_in_order().flat_map(|op| {
+        let from = match &op.op_type {
+            OpType::Mv { from } => Some(from.clone()),
+            _ => None,
+        };
+        std::iter::once(op.path.clone()).chain(from)
+    });
+    let changed = new
+        .diff_paths(old)
+        .await
+        .map_err(|e| anyhow!("failed to diff versions: {}", e))?;
+
+    for path in changed.into_iter().chain(op_paths) {
+        if !author_share.can_write(&path) {
+            return Err(ProvenanceError::OutOfScope(Path::new("/").join(path)));
+        }
+    }
+
+    Ok(())
+}
+
 /// Verify a manifest's full provenance including receiver authorization.
 ///
 /// Checks that:
@@ -550,8 +1368,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::{SecretKey, SecretShare};
-    use crate::mount::Share;
+    use crate::bucket_log::MemoryBucketLogProvider;
+    use crate::crypto::{Secret, SecretKey, SecretShare};
+    use crate::mount::{CompressionPolicy, ConflictStrategy, NodeLink, Share};
 
     fn create_test_manifest(owner: &SecretKey) -> Manifest {
         let share = SecretShare::default();
@@ -589,6 +1408,35 @@ mod tests {
         assert!(matches!(result, Err(ProvenanceError::AuthorNotWriter)));
     }
 
+    #[test]
+    fn test_verify_author_rejects_reader() {
+        let owner = SecretKey::generate();
+        let reader = SecretKey::generate();
+
+        let mut manifest = create_test_manifest(&owner);
+        let share = SecretShare::default();
+        manifest.add_share(Share::new_reader(share, reader.public()));
+        manifest.sign(&reader).unwrap();
+
+        let result = verify_author(&manifest, None);
+        assert!(matches!(result, Err(ProvenanceError::AuthorNotWriter)));
+    }
+
+    #[test]
+    fn test_verify_author_rejects_depositor() {
+        let owner = SecretKey::generate();
+        let depositor = SecretKey::generate();
+
+        // Depositors reach the inbox through an owner; a version they sign
+        // themselves is never valid
+        let mut manifest = create_test_manifest(&owner);
+        manifest.add_share(Share::new_depositor(depositor.public()));
+        manifest.sign(&depositor).unwrap();
+
+        let result = verify_author(&manifest, None);
+        assert!(matches!(result, Err(ProvenanceError::AuthorNotWriter)));
+    }
+
     #[test]
     fn test_verify_author_rejects_unknown_signer() {
         let owner = SecretKey::generate();
@@ -610,4 +1458,469 @@ mod tests {
         let result = verify_author(&manifest, None).unwrap();
         assert_eq!(result, ProvenanceResult::UnsignedLegacy);
     }
+
+    #[test]
+    fn test_verify_author_rejects_expired_owner() {
+        let owner = SecretKey::generate();
+        let second = SecretKey::generate();
+
+        let mut previous = create_test_manifest(&owner);
+        let mut share = Share::new_owner(SecretShare::default(), second.public());
+        share.set_expires_at(Some(1_000));
+        previous.add_share(share);
+
+        let mut manifest = previous.clone();
+        manifest.set_saved_at(999);
+        manifest.sign(&second).unwrap();
+        assert!(verify_author(&manifest, Some(&previous)).is_ok());
+
+        manifest.set_saved_at(1_000);
+        manifest.sign(&second).unwrap();
+        assert!(matches!(
+            verify_author(&manifest, Some(&previous)),
+            Err(ProvenanceError::AuthorExpired)
+        ));
+    }
+
+    #[test]
+    fn test_verify_author_rejects_own_expiry_change() {
+        let owner = SecretKey::generate();
+        let second = SecretKey::generate();
+
+        let mut previous = create_test_manifest(&owner);
+        let mut share = Share::new_owner(SecretShare::default(), second.public());
+        share.set_expires_at(Some(1_000));
+        previous.add_share(share);
+
+        // Clearing its own expiry would keep the owner in for good
+        let mut manifest = previous.clone();
+        manifest
+            .shares_mut()
+            .get_mut(&second.public().to_hex())
+            .unwrap()
+            .set_expires_at(None);
+        manifest.sign(&second).unwrap();
+        assert!(matches!(
+            verify_author(&manifest, Some(&previous)),
+            Err(ProvenanceError::SelfExpiryChange)
+        ));
+
+        // Another owner may change it
+        manifest.sign(&owner).unwrap();
+        assert!(verify_author(&manifest, Some(&previous)).is_ok());
+
+        // With a quorum the change is left to verify_membership's approvals
+        previous.set_quorum(2);
+        manifest.sign(&second).unwrap();
+        assert!(verify_author(&manifest, Some(&previous)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_membership_judges_expiry_by_saved_at() {
+        let owner = SecretKey::generate();
+        let reader = SecretKey::generate();
+
+        let mut parent = create_test_manifest(&owner);
+        parent.set_quorum(2);
+        let mut share = Share::new_reader(SecretShare::default(), reader.public());
+        share.set_expires_at(Some(1_000));
+        parent.add_share(share);
+        parent.set_saved_at(500);
+
+        let mut child = parent.clone();
+        child.shares_mut().remove(&reader.public().to_hex());
+
+        // Dropping the share needs no approvals once it expired by the
+        // version's own time, whatever the verifier's clock says
+        child.set_saved_at(1_000);
+        assert!(verify_membership(&child, &[&parent]).is_ok());
+
+        // Before then it is a membership change, which needs approvals
+        child.set_saved_at(999);
+        child.set_membership_epoch(1);
+        assert!(matches!(
+            verify_membership(&child, &[&parent]),
+            Err(ProvenanceError::QuorumNotMet { .. })
+        ));
+
+        child.set_saved_at(499);
+        assert!(matches!(
+            verify_membership(&child, &[&parent]),
+            Err(ProvenanceError::SavedBeforeParent(499))
+        ));
+    }
+
+    #[test]
+    fn test_verify_saved_at_rejects_future_versions() {
+        let owner = SecretKey::generate();
+        let mut manifest = create_test_manifest(&owner);
+        let now = 1_000_000;
+
+        manifest.set_saved_at(now + MAX_CLOCK_SKEW_SECS);
+        assert!(verify_saved_at(&manifest, now).is_ok());
+
+        // Dating a version ahead would expire co-owners' shares early
+        manifest.set_saved_at(now + MAX_CLOCK_SKEW_SECS + 1);
+        assert!(matches!(
+            verify_saved_at(&manifest, now),
+            Err(ProvenanceError::SavedInFuture(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_branch_authors_rejects_expired_and_removed() {
+        let owner = SecretKey::generate();
+        let contractor = SecretKey::generate();
+        let now = 1_000_000;
+
+        let mut head = create_test_manifest(&owner);
+        let mut share = Share::new_owner(SecretShare::default(), contractor.public());
+        share.set_expires_at(Some(now - 10));
+        head.add_share(share);
+
+        // A backdated version passes verify_author against its parent, but
+        // the contractor's share has expired by now
+        let mut version = head.clone();
+        version.set_saved_at(now - 20);
+        version.sign(&contractor).unwrap();
+        assert!(verify_author(&version, Some(&head)).is_ok());
+        let branch = vec![(version, Link::default())];
+        assert!(matches!(
+            verify_branch_authors(&head, &branch, now),
+            Err(ProvenanceError::InvalidManifestInChain { .. })
+        ));
+        assert!(verify_branch_authors(&head, &branch, now - 11).is_ok());
+
+        // Once removed from our head, it can't merge anything either
+        let mut removed = head.clone();
+        removed.shares_mut().remove(&contractor.public().to_hex());
+        assert!(matches!(
+            verify_branch_authors(&removed, &branch, now - 11),
+            Err(ProvenanceError::InvalidManifestInChain { .. })
+        ));
+
+        let mut version = head.clone();
+        version.sign(&owner).unwrap();
+        assert!(verify_branch_authors(&removed, &[(version, Link::default())], now).is_ok());
+    }
+
+    fn scoped_share(key: &SecretKey, scope: &str) -> Share {
+        let mut share = Share::new_owner(SecretShare::default(), key.public());
+        share.set_write_scope(vec![std::path::PathBuf::from(scope)]);
+        share
+    }
+
+    #[test]
+    fn test_verify_scoped_manifest_rejects_membership_change() {
+        let owner = SecretKey::generate();
+        let ci = SecretKey::generate();
+        let ci_share = scoped_share(&ci, "/artifacts");
+
+        let mut previous = create_test_manifest(&owner);
+        previous.add_share(ci_share.clone());
+
+        let mut manifest = previous.clone();
+        assert!(verify_scoped_manifest(&ci_share, &manifest, &previous).is_ok());
+
+        // Widening its own scope is a membership change too
+        manifest.add_share(scoped_share(&ci, "/"));
+        assert!(matches!(
+            verify_scoped_manifest(&ci_share, &manifest, &previous),
+            Err(ProvenanceError::ScopedShareChange)
+        ));
+
+        let mut manifest = previous.clone();
+        manifest.add_share(Share::new_mirror(SecretKey::generate().public()));
+        assert!(matches!(
+            verify_scoped_manifest(&ci_share, &manifest, &previous),
+            Err(ProvenanceError::ScopedShareChange)
+        ));
+    }
+
+    #[test]
+    fn test_verify_scoped_manifest_rejects_setting_changes() {
+        let owner = SecretKey::generate();
+        let ci = SecretKey::generate();
+        let ci_share = scoped_share(&ci, "/artifacts");
+
+        let mut previous = create_test_manifest(&owner);
+        previous.add_share(ci_share.clone());
+
+        let scoped_change = |change: &dyn Fn(&mut Manifest)| {
+            let mut manifest = previous.clone();
+            change(&mut manifest);
+            match verify_scoped_manifest(&ci_share, &manifest, &previous) {
+                Err(ProvenanceError::ScopedSettingChange(field)) => Some(field),
+                Err(e) => panic!("unexpected error: {}", e),
+                Ok(()) => None,
+            }
+        };
+
+        // Tree and history fields are the author's to change
+        assert_eq!(
+            scoped_change(&|manifest| {
+                manifest.set_height(1);
+                manifest.set_entry(Link::default());
+                manifest.set_saved_at(100);
+            }),
+            None
+        );
+
+        assert_eq!(
+            scoped_change(&|manifest| manifest.publish(&Secret::generate())),
+            Some("public")
+        );
+        assert_eq!(
+            scoped_change(&|manifest| manifest.publish_subtree(
+                "/docs".to_string(),
+                NodeLink::new_dir(Link::default(), Secret::generate())
+            )),
+            Some("published")
+        );
+        assert_eq!(
+            scoped_change(&|manifest| manifest.set_quorum(2)),
+            Some("quorum")
+        );
+        assert_eq!(
+            scoped_change(
+                &|manifest| manifest.set_conflict_strategy(ConflictStrategy::LastWriteWins)
+            ),
+            Some("conflict_strategy")
+        );
+        assert_eq!(
+            scoped_change(&|manifest| manifest.set_compression(Some(CompressionPolicy::default()))),
+            Some("compression")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_scoped_paths() {
+        use std::io::Cursor;
+        use std::path::PathBuf;
+
+        let blobs = crate::peer::BlobsStore::memory().await.unwrap();
+        let owner = SecretKey::generate();
+        let mut mount = Mount::init(uuid::Uuid::new_v4(), "test".to_string(), &owner, &blobs)
+            .await
+            .unwrap();
+        mount
+            .add(
+                &PathBuf::from("/src/main.rs"),
+                Cursor::new(b"fn main() {}".to_vec()),
+            )
+            .await
+            .unwrap();
+        let (base, _, _) = mount.save(&blobs, false).await.unwrap();
+
+        mount
+            .add(
+                &PathBuf::from("/artifacts/build/a.log"),
+                Cursor::new(b"ok".to_vec()),
+            )
+            .await
+            .unwrap();
+        let (in_scope, _, _) = mount.save(&blobs, false).await.unwrap();
+
+        mount
+            .mv(
+                &PathBuf::from("/src/main.rs"),
+                &PathBuf::from("/artifacts/main.rs"),
+            )
+            .await
+            .unwrap();
+        let (out_of_scope, _, _) = mount.save(&blobs, false).await.unwrap();
+
+        let load = |link: Link| {
+            let blobs = blobs.clone();
+            let owner = owner.clone();
+            async move { Mount::load(&link, &owner, &blobs).await.unwrap() }
+        };
+        let share = scoped_share(&SecretKey::generate(), "/artifacts");
+
+        let (old, new) = (load(base).await, load(in_scope.clone()).await);
+        verify_scoped_paths(&share, &new, &old).await.unwrap();
+
+        // Moving a file in from outside the scope removes it there
+        let (old, new) = (load(in_scope).await, load(out_of_scope).await);
+        let result = verify_scoped_paths(&share, &new, &old).await;
+        assert!(
+            matches!(&result, Err(ProvenanceError::OutOfScope(path)) if path == Path::new("/src/main.rs")),
+            "{:?}",
+            result
+        );
+    }
+
+    /// Drops the notifications a save sends, so peers only sync when told to
+    #[derive(Debug)]
+    struct NoNotifications;
+
+    #[async_trait::async_trait]
+    impl super::super::SyncProvider<MemoryBucketLogProvider> for NoNotifications {
+        async fn execute(
+            &self,
+            _peer: &Peer<MemoryBucketLogProvider>,
+            _job: SyncJob,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A peer sharing `blobs` with the others, so syncs find every manifest
+    /// locally instead of dialing anyone
+    async fn peer_with(
+        key: &SecretKey,
+        blobs: &crate::peer::BlobsStore,
+    ) -> Peer<MemoryBucketLogProvider> {
+        crate::peer::PeerBuilder::new()
+            .with_sync_provider(std::sync::Arc::new(NoNotifications))
+            .log_provider(MemoryBucketLogProvider::new())
+            .blobs_store(blobs.clone())
+            .secret_key(key.clone())
+            .build()
+            .await
+    }
+
+    fn sync_to(bucket_id: Uuid, link: &Link, height: u64) -> SyncBucketJob {
+        SyncBucketJob {
+            bucket_id,
+            target: SyncTarget {
+                link: link.clone(),
+                height,
+                peer_ids: Vec::new(),
+            },
+        }
+    }
+
+    /// Two owners that each saved a version the other hasn't seen
+    ///
+    /// Returns the peers, the bucket and their heads, alice's first.
+    async fn forked_owners() -> (
+        crate::peer::BlobsStore,
+        [Peer<MemoryBucketLogProvider>; 2],
+        Uuid,
+        [Link; 2],
+    ) {
+        use std::io::Cursor;
+        use std::path::PathBuf;
+
+        let blobs = crate::peer::BlobsStore::memory().await.unwrap();
+        let alice_key = SecretKey::generate();
+        let bob_key = SecretKey::generate();
+        let alice = peer_with(&alice_key, &blobs).await;
+        let bob = peer_with(&bob_key, &blobs).await;
+
+        // Both owners start from the same version
+        let bucket_id = Uuid::new_v4();
+        let mut mount = Mount::init(bucket_id, "fork".to_string(), &alice_key, &blobs)
+            .await
+            .unwrap();
+        let genesis = mount.link().await;
+        for peer in [&alice, &bob] {
+            peer.logs()
+                .append(
+                    bucket_id,
+                    "fork".to_string(),
+                    genesis.clone(),
+                    vec![],
+                    0,
+                    false,
+                )
+                .await
+                .unwrap();
+        }
+        mount.add_owner(bob_key.public()).await.unwrap();
+        let base = alice.save_mount(&mount, false).await.unwrap();
+        bob.logs()
+            .append(
+                bucket_id,
+                "fork".to_string(),
+                base.clone(),
+                vec![genesis],
+                1,
+                false,
+            )
+            .await
+            .unwrap();
+
+        // Each writes a version the other hasn't seen
+        let mut heads = Vec::new();
+        for (peer, path) in [(&alice, "/alice.txt"), (&bob, "/bob.txt")] {
+            let mut mount = peer.mount(bucket_id).await.unwrap();
+            mount
+                .add(&PathBuf::from(path), Cursor::new(b"x".to_vec()))
+                .await
+                .unwrap();
+            heads.push(peer.save_mount(&mount, false).await.unwrap());
+        }
+        let heads: [Link; 2] = heads.try_into().unwrap();
+        (blobs, [alice, bob], bucket_id, heads)
+    }
+
+    #[tokio::test]
+    async fn test_owners_seeing_each_others_fork_merge_once() {
+        let (blobs, [alice, bob], bucket_id, heads) = forked_owners().await;
+
+        // Each receives the other's head at the same time
+        let (alice_synced, bob_synced) = tokio::join!(
+            sync(&alice, sync_to(bucket_id, &heads[1], 2)),
+            sync(&bob, sync_to(bucket_id, &heads[0], 2)),
+        );
+        assert!(alice_synced.unwrap() && bob_synced.unwrap());
+
+        // Only the owner whose head sorts lower merged
+        let (merger, waiter) = if heads[0] < heads[1] {
+            (&alice, &bob)
+        } else {
+            (&bob, &alice)
+        };
+        let (merge, merge_height) = merger.logs().head(bucket_id, None).await.unwrap();
+        assert_eq!(merge_height, 3);
+        let manifest = Mount::load_manifest(&merge, &blobs).await.unwrap();
+        assert_eq!(manifest.parents().len(), 2);
+        assert_eq!(waiter.logs().height(bucket_id).await.unwrap(), 2);
+
+        // The waiter fast-forwards to that merge instead of merging again
+        assert!(sync(waiter, sync_to(bucket_id, &merge, 3)).await.unwrap());
+        assert_eq!(
+            waiter.logs().head(bucket_id, None).await.unwrap(),
+            (merge.clone(), 3)
+        );
+        assert!(sync(merger, sync_to(bucket_id, &merge, 3)).await.unwrap());
+        assert_eq!(merger.logs().height(bucket_id).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_waiting_owner_merges_when_no_merge_arrives() {
+        use super::super::{merge_fork, MergeForkJob};
+
+        let (blobs, [alice, bob], bucket_id, heads) = forked_owners().await;
+        let (waiter, waiter_head, merger_head) = if heads[0] < heads[1] {
+            (&bob, &heads[1], &heads[0])
+        } else {
+            (&alice, &heads[0], &heads[1])
+        };
+
+        // Only the waiter sees the fork, and leaves it for the other owner
+        assert!(sync(waiter, sync_to(bucket_id, merger_head, 2))
+            .await
+            .unwrap());
+        assert_eq!(waiter.logs().height(bucket_id).await.unwrap(), 2);
+
+        // That merge never comes, so the overdue job merges instead
+        let job = MergeForkJob {
+            bucket_id,
+            ours: waiter_head.clone(),
+            theirs: merger_head.clone(),
+            peer_ids: Vec::new(),
+        };
+        merge_fork::execute(waiter, job.clone()).await.unwrap();
+        let (merge, height) = waiter.logs().head(bucket_id, None).await.unwrap();
+        assert_eq!(height, 3);
+        let manifest = Mount::load_manifest(&merge, &blobs).await.unwrap();
+        assert_eq!(manifest.parents().len(), 2);
+
+        // A fork that was merged in the meantime is left alone
+        merge_fork::execute(waiter, job).await.unwrap();
+        assert_eq!(waiter.logs().height(bucket_id).await.unwrap(), 3);
+    }
 }
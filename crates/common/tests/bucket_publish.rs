@@ -1,6 +1,7 @@
 //! Integration tests for bucket publish operations
 //!
-//! Tests cover owner publish and publish/unpublish round-trip.
+//! Tests cover owner publish, publish/unpublish round-trip and publishing
+//! subtrees.
 
 mod common;
 
@@ -8,7 +9,7 @@ use std::io::Cursor;
 use std::path::PathBuf;
 
 use ::common::crypto::SecretKey;
-use ::common::mount::Mount;
+use ::common::mount::{Mount, MountError};
 
 const TEST_PATH: &str = "/file.txt";
 
@@ -71,3 +72,116 @@ async fn test_publish_then_unpublish_round_trip() {
     let owner_mount2 = Mount::load(&link_unpub, &owner_key, &blobs).await.unwrap();
     assert!(!owner_mount2.is_published().await);
 }
+
+#[tokio::test]
+async fn test_mirror_reads_only_published_subtree() {
+    let (mut mount, blobs, owner_key, _temp_dir) = common::setup_test_env().await;
+
+    mount
+        .add(
+            &PathBuf::from("/public/site/index.html"),
+            Cursor::new(b"<h1>hi</h1>".to_vec()),
+        )
+        .await
+        .unwrap();
+    mount
+        .add(
+            &PathBuf::from("/private.txt"),
+            Cursor::new(b"secret".to_vec()),
+        )
+        .await
+        .unwrap();
+
+    let mirror_key = SecretKey::generate();
+    mount.add_mirror(mirror_key.public()).await;
+
+    mount.publish_path(&PathBuf::from("/public")).await.unwrap();
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+
+    let owner_mount = Mount::load(&link, &owner_key, &blobs).await.unwrap();
+    let manifest = owner_mount.inner().await.manifest().clone();
+    assert!(!manifest.is_published());
+    assert!(manifest.has_published_content());
+
+    let mirror_mount = Mount::load(&link, &mirror_key, &blobs)
+        .await
+        .expect("Mirror should mount a bucket with a published subtree");
+    let data = mirror_mount
+        .cat(&PathBuf::from("/public/site/index.html"))
+        .await
+        .unwrap();
+    assert_eq!(data, b"<h1>hi</h1>");
+
+    // Only the published subtree is visible from the root
+    let root: Vec<PathBuf> = mirror_mount
+        .ls(&PathBuf::from("/"))
+        .await
+        .unwrap()
+        .into_keys()
+        .collect();
+    assert_eq!(root, vec![PathBuf::from("public")]);
+    assert!(mirror_mount
+        .cat(&PathBuf::from("/private.txt"))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_published_subtree_follows_edits() {
+    let (mut mount, blobs, _owner_key, _temp_dir) = common::setup_test_env().await;
+
+    mount
+        .add(&PathBuf::from("/docs/a.txt"), Cursor::new(b"a".to_vec()))
+        .await
+        .unwrap();
+    let mirror_key = SecretKey::generate();
+    mount.add_mirror(mirror_key.public()).await;
+    mount.publish_path(&PathBuf::from("/docs")).await.unwrap();
+    mount.save(&blobs, false).await.unwrap();
+
+    // The next save points the published subtree at the new version
+    mount
+        .add(&PathBuf::from("/docs/b.txt"), Cursor::new(b"b".to_vec()))
+        .await
+        .unwrap();
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+
+    let mirror_mount = Mount::load(&link, &mirror_key, &blobs).await.unwrap();
+    let data = mirror_mount
+        .cat(&PathBuf::from("/docs/b.txt"))
+        .await
+        .unwrap();
+    assert_eq!(data, b"b");
+
+    // Removing the directory stops publishing it
+    mount.rm(&PathBuf::from("/docs")).await.unwrap();
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+    assert!(mount.published_paths().await.is_empty());
+    assert!(matches!(
+        Mount::load(&link, &mirror_key, &blobs).await,
+        Err(MountError::MirrorCannotMount)
+    ));
+}
+
+#[tokio::test]
+async fn test_publish_path_requires_directory() {
+    let (mut mount, _blobs, _owner_key, _temp_dir) = common::setup_test_env().await;
+
+    mount
+        .add(&PathBuf::from(TEST_PATH), Cursor::new(b"hello".to_vec()))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        mount.publish_path(&PathBuf::from(TEST_PATH)).await,
+        Err(MountError::PathNotNode(_))
+    ));
+    assert!(matches!(
+        mount.publish_path(&PathBuf::from("/")).await,
+        Err(MountError::PublishRoot)
+    ));
+    assert!(matches!(
+        mount.publish_path(&PathBuf::from("/missing")).await,
+        Err(MountError::PathNotFound(_))
+    ));
+}
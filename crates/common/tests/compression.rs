@@ -0,0 +1,106 @@
+//! Integration tests for per-bucket compression
+//!
+//! Tests cover compressing text on add, skipping pre-compressed types,
+//! recording compression on the link, and the policy persisting across saves.
+
+mod common;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use ::common::mount::{CompressionPolicy, Mount};
+use ::common::peer::BlobsStore;
+
+fn log_lines() -> Vec<u8> {
+    "2026-10-18T12:00:00Z INFO request served path=/index.html status=200\n"
+        .repeat(500)
+        .into_bytes()
+}
+
+async fn stored_size(mount: &Mount, blobs: &BlobsStore, path: &str) -> usize {
+    let node_link = mount.get(&PathBuf::from(path)).await.unwrap();
+    blobs.get(&node_link.link().hash()).await.unwrap().len()
+}
+
+#[tokio::test]
+async fn test_compression_disabled_by_default() {
+    let (mut mount, blobs, _owner_key, _temp_dir) = common::setup_test_env().await;
+    let data = log_lines();
+
+    assert!(mount.compression().await.is_none());
+    mount
+        .add(&PathBuf::from("/app.log"), Cursor::new(data.clone()))
+        .await
+        .unwrap();
+
+    assert!(stored_size(&mount, &blobs, "/app.log").await > data.len());
+}
+
+#[tokio::test]
+async fn test_compressed_add_and_cat() {
+    let (mut mount, blobs, _owner_key, _temp_dir) = common::setup_test_env().await;
+    let data = log_lines();
+
+    mount
+        .set_compression(Some(CompressionPolicy::default()))
+        .await;
+    mount
+        .add(&PathBuf::from("/app.log"), Cursor::new(data.clone()))
+        .await
+        .unwrap();
+    // Same contents under a type that's already compressed
+    mount
+        .add(&PathBuf::from("/photo.jpg"), Cursor::new(data.clone()))
+        .await
+        .unwrap();
+
+    assert!(stored_size(&mount, &blobs, "/app.log").await < data.len() / 4);
+    assert!(stored_size(&mount, &blobs, "/photo.jpg").await > data.len());
+
+    assert_eq!(mount.cat(&PathBuf::from("/app.log")).await.unwrap(), data);
+    assert_eq!(mount.cat(&PathBuf::from("/photo.jpg")).await.unwrap(), data);
+
+    // The link records which blobs are compressed, and their full size
+    let log_link = mount.get(&PathBuf::from("/app.log")).await.unwrap();
+    assert_eq!(
+        log_link.data().unwrap().compressed(),
+        Some(data.len() as u64)
+    );
+    let photo_link = mount.get(&PathBuf::from("/photo.jpg")).await.unwrap();
+    assert_eq!(photo_link.data().unwrap().compressed(), None);
+
+    // Capabilities carry the flag along
+    let capability = mount
+        .file_capability(&PathBuf::from("/app.log"), None)
+        .await
+        .unwrap();
+    let blob = blobs.get(&log_link.link().hash()).await.unwrap();
+    assert_eq!(capability.decrypt(&blob).unwrap(), data);
+}
+
+#[tokio::test]
+async fn test_policy_persists_across_saves() {
+    let (mount, blobs, owner_key, _temp_dir) = common::setup_test_env().await;
+
+    let mut policy = CompressionPolicy::new(9);
+    policy.set_override("text/*", false);
+    mount.set_compression(Some(policy.clone())).await;
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+
+    let mut reloaded = Mount::load(&link, &owner_key, &blobs).await.unwrap();
+    assert_eq!(reloaded.compression().await, Some(policy));
+
+    // The text override applies to the reloaded mount
+    let data = log_lines();
+    reloaded
+        .add(&PathBuf::from("/notes.txt"), Cursor::new(data.clone()))
+        .await
+        .unwrap();
+    assert!(stored_size(&reloaded, &blobs, "/notes.txt").await > data.len());
+
+    // Disabling sticks too
+    reloaded.set_compression(None).await;
+    let (link, _, _) = reloaded.save(&blobs, false).await.unwrap();
+    let reloaded = Mount::load(&link, &owner_key, &blobs).await.unwrap();
+    assert!(reloaded.compression().await.is_none());
+}
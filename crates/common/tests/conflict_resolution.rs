@@ -5,7 +5,8 @@ mod common;
 use std::io::Cursor;
 use std::path::PathBuf;
 
-use ::common::mount::{merge_logs, ConflictFile, OpType, Resolution};
+use ::common::crypto::SecretKey;
+use ::common::mount::{merge_logs, ConflictFile, Mount, OpType, Resolution};
 
 /// Alice and Bob both create "notes.txt" with different content.
 /// Alice's stays at the original path, Bob's is renamed to notes@<hash>.txt.
@@ -252,6 +253,16 @@ async fn test_multi_version_divergence() {
     assert!(alice.cat(&PathBuf::from("/src/main.rs")).await.is_ok());
     assert!(alice.cat(&PathBuf::from("/src/utils.rs")).await.is_ok());
     assert!(alice.cat(&PathBuf::from("/config.toml")).await.is_ok());
+
+    // And Bob's, with his config.toml kept as a conflict copy
+    assert!(alice.cat(&PathBuf::from("/CONTRIBUTING.md")).await.is_ok());
+    assert!(alice.cat(&PathBuf::from("/src/lib.rs")).await.is_ok());
+    assert!(alice.cat(&PathBuf::from("/.gitignore")).await.is_ok());
+    let conflict_copy = match &resolved.resolution {
+        Resolution::RenameIncoming { new_path } => PathBuf::from("/").join(new_path),
+        _ => unreachable!(),
+    };
+    assert_eq!(alice.cat(&conflict_copy).await.unwrap(), b"[bob]");
 }
 
 /// Test the original low-level merge_logs API still works correctly
@@ -365,3 +376,128 @@ async fn test_multi_version_divergence_low_level() {
     assert!(final_state.contains_key(&PathBuf::from("config.toml")));
     assert!(final_state.contains_key(&PathBuf::from(".gitignore")));
 }
+
+/// Merging links in the incoming branch's files, replays its removals and
+/// moves, and records both heads as parents of the merge manifest.
+#[tokio::test]
+async fn test_merge_applies_incoming_branch() {
+    let (mut alice, blobs, _, _temp) = common::setup_test_env().await;
+    alice
+        .add(&PathBuf::from("/old.txt"), Cursor::new(b"old".to_vec()))
+        .await
+        .unwrap();
+    alice
+        .add(&PathBuf::from("/moveme.txt"), Cursor::new(b"move".to_vec()))
+        .await
+        .unwrap();
+    let (mut bob, _bob_key) = common::fork_mount(&mut alice, &blobs).await;
+
+    // Bob's branch: two versions
+    bob.rm(&PathBuf::from("/old.txt")).await.unwrap();
+    bob.mv(&PathBuf::from("/moveme.txt"), &PathBuf::from("/moved.txt"))
+        .await
+        .unwrap();
+    bob.save(&blobs, false).await.unwrap();
+    bob.add(
+        &PathBuf::from("/bob.txt"),
+        Cursor::new(b"from bob".to_vec()),
+    )
+    .await
+    .unwrap();
+    let (bob_link, _, bob_height) = bob.save(&blobs, false).await.unwrap();
+
+    // Alice's branch: one version
+    alice
+        .add(
+            &PathBuf::from("/alice.txt"),
+            Cursor::new(b"from alice".to_vec()),
+        )
+        .await
+        .unwrap();
+    let (alice_link, _, _) = alice.save(&blobs, false).await.unwrap();
+
+    let resolver = ConflictFile::new();
+    let (_result, merge_link) = alice.merge_from(&bob, &resolver, &blobs).await.unwrap();
+
+    assert_eq!(
+        alice.cat(&PathBuf::from("/bob.txt")).await.unwrap(),
+        b"from bob"
+    );
+    assert_eq!(
+        alice.cat(&PathBuf::from("/alice.txt")).await.unwrap(),
+        b"from alice"
+    );
+    assert_eq!(
+        alice.cat(&PathBuf::from("/moved.txt")).await.unwrap(),
+        b"move"
+    );
+    assert!(alice.get(&PathBuf::from("/moveme.txt")).await.is_err());
+    assert!(alice.get(&PathBuf::from("/old.txt")).await.is_err());
+
+    // The merge builds on the higher head and references the other one
+    let manifest = Mount::load_manifest(&merge_link, &blobs).await.unwrap();
+    assert!(manifest.is_merge());
+    assert_eq!(manifest.previous().as_ref(), Some(&bob_link));
+    assert_eq!(manifest.parents(), &[bob_link, alice_link]);
+    assert_eq!(manifest.height(), bob_height + 1);
+}
+
+/// Members added or removed on the incoming branch carry over into the merge.
+#[tokio::test]
+async fn test_merge_combines_share_changes() {
+    let (mut alice, blobs, _, _temp) = common::setup_test_env().await;
+    let carol = SecretKey::generate();
+    alice.add_mirror(carol.public()).await;
+    let (mut bob, _bob_key) = common::fork_mount(&mut alice, &blobs).await;
+
+    let dave = SecretKey::generate();
+    bob.add_mirror(dave.public()).await;
+    bob.remove_share(carol.public()).await.unwrap();
+    bob.save(&blobs, false).await.unwrap();
+
+    alice
+        .add(&PathBuf::from("/a.txt"), Cursor::new(b"a".to_vec()))
+        .await
+        .unwrap();
+    alice.save(&blobs, false).await.unwrap();
+
+    let (_, merge_link) = alice
+        .merge_from(&bob, &ConflictFile::new(), &blobs)
+        .await
+        .unwrap();
+
+    let manifest = Mount::load_manifest(&merge_link, &blobs).await.unwrap();
+    assert!(manifest.get_share(&dave.public()).is_some());
+    assert!(manifest.get_share(&carol.public()).is_none());
+}
+
+/// When only unsaved local changes differ, the merge builds straight on the
+/// incoming head rather than recording a second parent.
+#[tokio::test]
+async fn test_merge_onto_newer_head_is_not_a_merge() {
+    let (mut alice, blobs, _, _temp) = common::setup_test_env().await;
+    let (mut bob, _bob_key) = common::fork_mount(&mut alice, &blobs).await;
+
+    bob.add(&PathBuf::from("/bob.txt"), Cursor::new(b"bob".to_vec()))
+        .await
+        .unwrap();
+    let (bob_link, _, bob_height) = bob.save(&blobs, false).await.unwrap();
+
+    // Unsaved change on Alice's side
+    alice
+        .add(&PathBuf::from("/alice.txt"), Cursor::new(b"alice".to_vec()))
+        .await
+        .unwrap();
+
+    let (_, link) = alice
+        .merge_from(&bob, &ConflictFile::new(), &blobs)
+        .await
+        .unwrap();
+
+    let manifest = Mount::load_manifest(&link, &blobs).await.unwrap();
+    assert!(!manifest.is_merge());
+    assert_eq!(manifest.previous().as_ref(), Some(&bob_link));
+    assert_eq!(manifest.height(), bob_height + 1);
+    assert!(alice.cat(&PathBuf::from("/bob.txt")).await.is_ok());
+    assert!(alice.cat(&PathBuf::from("/alice.txt")).await.is_ok());
+}
@@ -0,0 +1,109 @@
+//! Integration tests for depositors submitting files to a bucket's inbox
+
+mod common;
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use ::common::crypto::SecretKey;
+use ::common::mount::{Deposit, DepositError, Mount, MountError};
+
+#[tokio::test]
+async fn test_owner_accepts_deposit_into_inbox() {
+    let (mut mount, blobs, owner_key, _temp) = common::setup_test_env().await;
+    let depositor_key = SecretKey::generate();
+    mount.add_depositor(depositor_key.public()).await;
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+
+    // Depositors can't read the bucket
+    let result = Mount::load(&link, &depositor_key, &blobs).await;
+    assert!(matches!(result, Err(MountError::DepositorCannotMount)));
+
+    let manifest = Mount::load_manifest(&link, &blobs).await.unwrap();
+    let (deposit, _) = Deposit::create(
+        &manifest,
+        Path::new("report.txt"),
+        b"quarterly numbers",
+        &depositor_key,
+        &blobs,
+    )
+    .await
+    .unwrap();
+
+    let path = mount.accept_deposit(&deposit).await.unwrap();
+    assert_eq!(path, Some(PathBuf::from("/inbox/report.txt")));
+    assert_eq!(
+        mount.cat(Path::new("/inbox/report.txt")).await.unwrap(),
+        b"quarterly numbers"
+    );
+
+    // Accepting the same deposit again changes nothing
+    assert_eq!(mount.accept_deposit(&deposit).await.unwrap(), None);
+
+    // The file survives a save and reload
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+    let reloaded = Mount::load(&link, &owner_key, &blobs).await.unwrap();
+    assert_eq!(
+        reloaded.cat(Path::new("/inbox/report.txt")).await.unwrap(),
+        b"quarterly numbers"
+    );
+}
+
+#[tokio::test]
+async fn test_deposit_does_not_overwrite_existing_files() {
+    let (mut mount, blobs, _owner_key, _temp) = common::setup_test_env().await;
+    mount
+        .add(
+            &PathBuf::from("/inbox/report.txt"),
+            Cursor::new(b"original".to_vec()),
+        )
+        .await
+        .unwrap();
+    let depositor_key = SecretKey::generate();
+    mount.add_depositor(depositor_key.public()).await;
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+
+    let manifest = Mount::load_manifest(&link, &blobs).await.unwrap();
+    let (deposit, _) = Deposit::create(
+        &manifest,
+        Path::new("report.txt"),
+        b"replacement",
+        &depositor_key,
+        &blobs,
+    )
+    .await
+    .unwrap();
+
+    let result = mount.accept_deposit(&deposit).await;
+    assert!(matches!(result, Err(MountError::PathAlreadyExists(_))));
+    assert_eq!(
+        mount.cat(Path::new("/inbox/report.txt")).await.unwrap(),
+        b"original"
+    );
+}
+
+#[tokio::test]
+async fn test_deposit_from_removed_depositor_is_refused() {
+    let (mut mount, blobs, _owner_key, _temp) = common::setup_test_env().await;
+    let depositor_key = SecretKey::generate();
+    mount.add_depositor(depositor_key.public()).await;
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+
+    let manifest = Mount::load_manifest(&link, &blobs).await.unwrap();
+    let (deposit, _) = Deposit::create(
+        &manifest,
+        Path::new("late.txt"),
+        b"too late",
+        &depositor_key,
+        &blobs,
+    )
+    .await
+    .unwrap();
+
+    mount.remove_share(depositor_key.public()).await.unwrap();
+    let result = mount.accept_deposit(&deposit).await;
+    assert!(matches!(
+        result,
+        Err(MountError::Deposit(DepositError::NotDepositor))
+    ));
+}
@@ -0,0 +1,120 @@
+//! Integration tests for file capability links
+//!
+//! Tests cover reading one file through a capability, what a capability
+//! can't open, and who a serving node trusts to mint one.
+
+mod common;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use ::common::crypto::SecretKey;
+use ::common::mount::{CapabilityError, FileCapability, MountError};
+
+#[tokio::test]
+async fn test_capability_opens_one_file() {
+    let (mut mount, blobs, owner_key, _temp_dir) = common::setup_test_env().await;
+
+    mount
+        .add(
+            &PathBuf::from("/reports/q3.txt"),
+            Cursor::new(b"quarterly numbers".to_vec()),
+        )
+        .await
+        .unwrap();
+    mount.save(&blobs, false).await.unwrap();
+
+    let capability = mount
+        .file_capability(&PathBuf::from("/reports/q3.txt"), None)
+        .await
+        .unwrap();
+    assert_eq!(capability.name, "q3.txt");
+    assert_eq!(capability.issuer, owner_key.public());
+
+    // Whoever holds the encoded link can read the file, and only that file
+    let received: FileCapability = capability.to_string().parse().unwrap();
+    received.verify(0).unwrap();
+    let encrypted = blobs.get(&received.hash).await.unwrap();
+    assert_eq!(
+        received.decrypt(&encrypted).unwrap(),
+        b"quarterly numbers".to_vec()
+    );
+}
+
+#[tokio::test]
+async fn test_capability_requires_a_file() {
+    let (mut mount, _blobs, _owner_key, _temp_dir) = common::setup_test_env().await;
+
+    mount
+        .add(
+            &PathBuf::from("/reports/q3.txt"),
+            Cursor::new(b"x".to_vec()),
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        mount
+            .file_capability(&PathBuf::from("/reports"), None)
+            .await,
+        Err(MountError::Capability(CapabilityError::NotAFile))
+    ));
+    assert!(matches!(
+        mount
+            .file_capability(&PathBuf::from("/reports/q4.txt"), None)
+            .await,
+        Err(MountError::PathNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_tampered_capability_is_rejected() {
+    let (mut mount, blobs, _owner_key, _temp_dir) = common::setup_test_env().await;
+
+    let path = PathBuf::from("/reports/q3.txt");
+    mount
+        .add(&path, Cursor::new(b"quarterly numbers".to_vec()))
+        .await
+        .unwrap();
+    mount.save(&blobs, false).await.unwrap();
+    let inner = mount.inner().await;
+    let manifest = inner.manifest().clone();
+    drop(inner);
+
+    // A gateway following the bucket accepts its owner's links
+    let gateway = SecretKey::generate().public();
+    let capability = mount.file_capability(&path, Some(100)).await.unwrap();
+    capability.authorize(&gateway, Some(&manifest), 50).unwrap();
+
+    // Stripping the expiry breaks the owner's signature
+    let mut stripped = capability.clone();
+    stripped.expires_at = None;
+    assert!(matches!(
+        stripped.authorize(&gateway, Some(&manifest), 200),
+        Err(CapabilityError::InvalidSignature)
+    ));
+
+    // Re-signing it with another key makes the issuer untrusted
+    let stranger = SecretKey::generate();
+    let link = mount.get(&path).await.unwrap();
+    let resigned = FileCapability::new(
+        capability.bucket_id,
+        &link,
+        capability.name.clone(),
+        None,
+        &stranger,
+    )
+    .unwrap();
+    resigned.verify(200).unwrap();
+    assert!(matches!(
+        resigned.authorize(&gateway, Some(&manifest), 200),
+        Err(CapabilityError::UntrustedIssuer)
+    ));
+    assert!(matches!(
+        resigned.authorize(&gateway, None, 200),
+        Err(CapabilityError::UntrustedIssuer)
+    ));
+
+    // The node that minted a capability always trusts it
+    resigned.authorize(&stranger.public(), None, 200).unwrap();
+}
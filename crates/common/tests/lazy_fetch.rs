@@ -0,0 +1,140 @@
+//! Integration tests for reading a bucket through a store that fetches on demand
+
+mod common;
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use ::common::crypto::PublicKey;
+use ::common::mount::{Mount, NodeLink};
+use ::common::peer::BlobsStore;
+use iroh::discovery::static_provider::StaticProvider;
+use iroh::Endpoint;
+
+/// Serve a store's blobs on a local endpoint known to `discovery`
+async fn serve(
+    blobs: &BlobsStore,
+    discovery: &StaticProvider,
+) -> (iroh::protocol::Router, PublicKey) {
+    let endpoint = Endpoint::builder()
+        .relay_mode(iroh::RelayMode::Disabled)
+        .bind()
+        .await
+        .unwrap();
+    let addrs = endpoint
+        .bound_sockets()
+        .into_iter()
+        .filter(|addr| addr.is_ipv4())
+        .map(|addr| (std::net::Ipv4Addr::LOCALHOST, addr.port()).into());
+    discovery.add_node_info(iroh::NodeAddr::new(endpoint.node_id()).with_direct_addresses(addrs));
+    let peer_id = PublicKey::from(endpoint.node_id());
+    let router = iroh::protocol::Router::builder(endpoint)
+        .accept(iroh_blobs::ALPN, blobs.inner.clone())
+        .spawn();
+    (router, peer_id)
+}
+
+/// An endpoint that has already dialed `peer_id`
+///
+/// A fresh endpoint takes a while to come up, which a first download would
+/// spend out of its connect timeout.
+async fn fetcher(discovery: &StaticProvider, peer_id: PublicKey) -> Endpoint {
+    let endpoint = Endpoint::builder()
+        .relay_mode(iroh::RelayMode::Disabled)
+        .discovery(discovery.clone())
+        .bind()
+        .await
+        .unwrap();
+    endpoint
+        .connect(iroh::NodeId::from(peer_id), iroh_blobs::ALPN)
+        .await
+        .unwrap();
+    endpoint
+}
+
+#[tokio::test]
+async fn test_lazy_store_fetches_blobs_on_first_read() {
+    let source = BlobsStore::memory().await.unwrap();
+    let owner_key = ::common::crypto::SecretKey::generate();
+    let mut mount = Mount::init(
+        uuid::Uuid::new_v4(),
+        "test".to_string(),
+        &owner_key,
+        &source,
+    )
+    .await
+    .unwrap();
+    mount
+        .add(
+            &PathBuf::from("/docs/a.txt"),
+            Cursor::new(b"alpha".to_vec()),
+        )
+        .await
+        .unwrap();
+    mount
+        .add(&PathBuf::from("/b.txt"), Cursor::new(b"beta".to_vec()))
+        .await
+        .unwrap();
+    let (link, _, _) = mount.save(&source, false).await.unwrap();
+
+    let discovery = StaticProvider::new();
+    let (_router, source_peer) = serve(&source, &discovery).await;
+
+    // Nothing of the bucket is local; the mount loads by fetching the
+    // manifest, pins list, root node and ops log
+    let local = BlobsStore::memory().await.unwrap();
+    let lazy = local.with_remote(fetcher(&discovery, source_peer).await, vec![source_peer]);
+    let mount = Mount::load(&link, &owner_key, &lazy).await.unwrap();
+    assert!(local.stat(&link.hash()).await.unwrap());
+
+    let NodeLink::Data(data_link, _, _) = mount.get(Path::new("/docs/a.txt")).await.unwrap() else {
+        panic!("expected a file");
+    };
+    assert!(!local.stat(&data_link.hash()).await.unwrap());
+
+    assert_eq!(mount.cat(Path::new("/docs/a.txt")).await.unwrap(), b"alpha");
+    assert!(local.stat(&data_link.hash()).await.unwrap());
+
+    // The plain store doesn't fetch
+    let plain = Mount::load(&link, &owner_key, &local).await.unwrap();
+    assert!(plain.cat(Path::new("/b.txt")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_walk_files_prefetches_directories_by_level() {
+    let (mut mount, _blobs, _owner_key, _temp) = common::setup_test_env().await;
+    for path in ["/a/b/one.txt", "/a/two.txt", "/c/three.txt", "/four.txt"] {
+        mount
+            .add(&PathBuf::from(path), Cursor::new(path.as_bytes().to_vec()))
+            .await
+            .unwrap();
+    }
+
+    let levels = Mutex::new(Vec::new());
+    let files = mount
+        .walk_files(Path::new("/"), |hashes| {
+            levels.lock().unwrap().push(hashes.len());
+            async {}
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        files.keys().cloned().collect::<Vec<_>>(),
+        vec![
+            PathBuf::from("a/b/one.txt"),
+            PathBuf::from("a/two.txt"),
+            PathBuf::from("c/three.txt"),
+            PathBuf::from("four.txt"),
+        ]
+    );
+    // Root's children `a` and `c`, then `a/b`
+    assert_eq!(levels.into_inner().unwrap(), vec![2, 1]);
+
+    let files = mount
+        .walk_files(Path::new("/a/two.txt"), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(files.len(), 1);
+}
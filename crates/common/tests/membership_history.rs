@@ -0,0 +1,99 @@
+//! Integration tests for reading membership history off the manifest chain
+//!
+//! Tests cover attributing changes to the peers that made them, signature
+//! verification, and merges of branches that changed the membership.
+
+mod common;
+
+use ::common::crypto::SecretKey;
+use ::common::mount::{ConflictFile, MembershipEventKind, Mount, PrincipalRole};
+
+#[tokio::test]
+async fn test_history_attributes_changes() {
+    let (mut alice, blobs, alice_key, _temp) = common::setup_test_env().await;
+
+    let reader_key = SecretKey::generate();
+    alice.add_reader(reader_key.public()).await.unwrap();
+    alice.save(&blobs, false).await.unwrap();
+    let (bob, bob_key) = common::fork_mount(&mut alice, &blobs).await;
+
+    bob.remove_share(reader_key.public()).await.unwrap();
+    let (head, _, head_height) = bob.save(&blobs, false).await.unwrap();
+
+    let events = Mount::membership_history(&head, &blobs, true)
+        .await
+        .unwrap();
+    let changes: Vec<_> = events
+        .iter()
+        .map(|event| (event.principal, event.kind, event.author))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (
+                alice_key.public(),
+                MembershipEventKind::Added,
+                Some(alice_key.public())
+            ),
+            (
+                reader_key.public(),
+                MembershipEventKind::Added,
+                Some(alice_key.public())
+            ),
+            (
+                bob_key.public(),
+                MembershipEventKind::Added,
+                Some(alice_key.public())
+            ),
+            (
+                reader_key.public(),
+                MembershipEventKind::Removed,
+                Some(bob_key.public())
+            ),
+        ]
+    );
+
+    let removal = events.last().unwrap();
+    assert_eq!(removal.role, PrincipalRole::Reader);
+    assert_eq!(removal.height, head_height);
+    assert_eq!(removal.link, head);
+    assert!(events.iter().all(|event| event.verified == Some(true)));
+
+    // Without verification no signature result is recorded
+    let events = Mount::membership_history(&head, &blobs, false)
+        .await
+        .unwrap();
+    assert!(events.iter().all(|event| event.verified.is_none()));
+}
+
+#[tokio::test]
+async fn test_merge_does_not_repeat_branch_changes() {
+    let (mut alice, blobs, _alice_key, _temp) = common::setup_test_env().await;
+    let (mut bob, bob_key) = common::fork_mount(&mut alice, &blobs).await;
+
+    let mirror_key = SecretKey::generate();
+    bob.add_mirror(mirror_key.public()).await;
+    let (bob_link, _, _) = bob.save(&blobs, false).await.unwrap();
+
+    let resolver = ConflictFile::new();
+    let (_, head) = alice.merge_from(&bob, &resolver, &blobs).await.unwrap();
+    assert!(alice
+        .inner()
+        .await
+        .manifest()
+        .shares()
+        .contains_key(&mirror_key.public().to_hex()));
+
+    let events = Mount::membership_history(&head, &blobs, false)
+        .await
+        .unwrap();
+    let mirror_events: Vec<_> = events
+        .iter()
+        .filter(|event| event.principal == mirror_key.public())
+        .collect();
+    assert_eq!(mirror_events.len(), 1);
+    assert_eq!(mirror_events[0].kind, MembershipEventKind::Added);
+    assert_eq!(mirror_events[0].role, PrincipalRole::Mirror);
+    assert_eq!(mirror_events[0].author, Some(bob_key.public()));
+    assert_eq!(mirror_events[0].link, bob_link);
+}
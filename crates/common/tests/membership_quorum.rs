@@ -0,0 +1,81 @@
+//! Integration tests for owner quorums on membership changes
+
+mod common;
+
+use ::common::crypto::SecretKey;
+use ::common::mount::{MembershipError, Mount, MountError};
+
+#[tokio::test]
+async fn test_membership_change_needs_quorum_approvals() {
+    let (mut mount, blobs, owner_key, _temp) = common::setup_test_env().await;
+    let (_, second_key) = common::fork_mount(&mut mount, &blobs).await;
+    let reader_key = SecretKey::generate();
+    mount.add_reader(reader_key.public()).await.unwrap();
+    mount.set_quorum(2).await.unwrap();
+    mount.save(&blobs, false).await.unwrap();
+    assert_eq!(mount.quorum().await, 2);
+
+    // Removing the reader alone falls short of the quorum
+    mount.remove_share(reader_key.public()).await.unwrap();
+    let result = mount.save(&blobs, false).await;
+    assert!(matches!(
+        result,
+        Err(MountError::QuorumNotMet {
+            required: 2,
+            approvals: 0
+        })
+    ));
+
+    // Non-owners can't approve the change
+    let mut change = mount.membership_change().await.unwrap().unwrap();
+    assert_eq!(change.required(), 2);
+    assert!(matches!(
+        change.approve(&reader_key),
+        Err(MembershipError::NotApprover(_))
+    ));
+
+    // With both owners' approvals the change saves
+    change.approve(&owner_key).unwrap();
+    assert!(!change.is_approved());
+    change.approve(&second_key).unwrap();
+    assert!(change.is_approved());
+    mount.apply_membership_change(&change).await.unwrap();
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+
+    let manifest = Mount::load_manifest(&link, &blobs).await.unwrap();
+    assert!(manifest.get_share(&reader_key.public()).is_none());
+    assert_eq!(manifest.membership_epoch(), change.epoch());
+    assert_eq!(manifest.approvals().len(), 2);
+}
+
+#[tokio::test]
+async fn test_content_changes_need_no_approvals() {
+    let (mut mount, blobs, _owner_key, _temp) = common::setup_test_env().await;
+    common::fork_mount(&mut mount, &blobs).await;
+    mount.set_quorum(2).await.unwrap();
+    mount.save(&blobs, false).await.unwrap();
+
+    mount
+        .add(
+            std::path::Path::new("/notes.txt"),
+            std::io::Cursor::new(b"hello".to_vec()),
+        )
+        .await
+        .unwrap();
+    assert!(mount.membership_change().await.unwrap().is_none());
+    mount.save(&blobs, false).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_quorum_cannot_exceed_owners() {
+    let (mut mount, blobs, _owner_key, _temp) = common::setup_test_env().await;
+    common::fork_mount(&mut mount, &blobs).await;
+
+    let result = mount.set_quorum(3).await;
+    assert!(matches!(
+        result,
+        Err(MountError::Membership(
+            MembershipError::InvalidQuorum { .. }
+        ))
+    ));
+}
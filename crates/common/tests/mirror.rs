@@ -256,3 +256,31 @@ async fn test_full_fixture_flow() {
         .unwrap();
     assert_eq!(data, b"hello world");
 }
+
+#[tokio::test]
+async fn test_reader_can_mount_unpublished_bucket() {
+    let (mut mount, blobs, _, _, _temp) = setup_mount_with_mirror(b"private data").await;
+    let reader_key = SecretKey::generate();
+    mount.add_reader(reader_key.public()).await.unwrap();
+
+    // Readers have their own share, so no publish is needed
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+    let reader_mount = Mount::load(&link, &reader_key, &blobs)
+        .await
+        .expect("Reader should be able to mount unpublished bucket");
+    let data = reader_mount.cat(&PathBuf::from(TEST_PATH)).await.unwrap();
+    assert_eq!(data, b"private data");
+
+    // The reader's share is re-issued with each new secret
+    mount
+        .add(&PathBuf::from("/second.txt"), Cursor::new(b"more".to_vec()))
+        .await
+        .unwrap();
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+    let reader_mount = Mount::load(&link, &reader_key, &blobs).await.unwrap();
+    let data = reader_mount
+        .cat(&PathBuf::from("/second.txt"))
+        .await
+        .unwrap();
+    assert_eq!(data, b"more");
+}
@@ -128,3 +128,89 @@ async fn test_removed_peer_cannot_load_new_version() {
         "Removed peer should not be able to load mount after removal",
     );
 }
+
+#[tokio::test]
+async fn test_expired_share_is_removed_on_save() {
+    let (mut mount, blobs, owner_key, _temp) = common::setup_test_env().await;
+
+    let reader_key = SecretKey::generate();
+    mount.add_reader(reader_key.public()).await.unwrap();
+    mount
+        .set_share_expiry(reader_key.public(), Some(u64::MAX))
+        .await
+        .unwrap();
+    let (link_before, _, _) = mount.save(&blobs, false).await.unwrap();
+    ::common::mount::Mount::load(&link_before, &reader_key, &blobs)
+        .await
+        .expect("Reader should have access until the share expires");
+
+    // Without a quorum an owner can't change its own expiry
+    let result = mount.set_share_expiry(owner_key.public(), None).await;
+    assert!(matches!(result, Err(MountError::Unauthorized)));
+    mount
+        .set_share_expiry(reader_key.public(), Some(1))
+        .await
+        .unwrap();
+    let (link_after, _, _) = mount.save(&blobs, false).await.unwrap();
+
+    let inner = mount.inner().await;
+    assert!(inner.manifest().get_share(&reader_key.public()).is_none());
+    assert!(inner.manifest().get_share(&owner_key.public()).is_some());
+    let result = ::common::mount::Mount::load(&link_after, &reader_key, &blobs).await;
+    assert!(matches!(result, Err(MountError::ShareNotFound)));
+}
+
+#[tokio::test]
+async fn test_only_owners_remove_expired_shares() {
+    let (mut mount, blobs, _owner_key, _temp) = common::setup_test_env().await;
+
+    let reader_key = SecretKey::generate();
+    let other_key = SecretKey::generate();
+    mount.add_reader(reader_key.public()).await.unwrap();
+    mount.add_reader(other_key.public()).await.unwrap();
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+
+    // A reader's save doesn't touch the shares; peers would reject the
+    // removal anyway
+    let reader_mount = ::common::mount::Mount::load(&link, &reader_key, &blobs)
+        .await
+        .unwrap();
+    reader_mount
+        .set_share_expiry(other_key.public(), Some(1))
+        .await
+        .unwrap();
+    reader_mount.save(&blobs, false).await.unwrap();
+    let inner = reader_mount.inner().await;
+    assert!(inner.manifest().get_share(&other_key.public()).is_some());
+}
+
+#[tokio::test]
+async fn test_expired_owner_cannot_save() {
+    let (mut mount, blobs, owner_key, _temp) = common::setup_test_env().await;
+    let (_, second_key) = common::fork_mount(&mut mount, &blobs).await;
+    mount.set_quorum(2).await.unwrap();
+    mount.save(&blobs, false).await.unwrap();
+
+    // With a quorum an owner may propose its own expiry, but once it has
+    // passed the owner can't save another version
+    mount
+        .set_share_expiry(owner_key.public(), Some(1))
+        .await
+        .unwrap();
+    let mut change = mount.membership_change().await.unwrap().unwrap();
+    change.approve(&owner_key).unwrap();
+    change.approve(&second_key).unwrap();
+    mount.apply_membership_change(&change).await.unwrap();
+    let result = mount.save(&blobs, false).await;
+    assert!(matches!(result, Err(MountError::ShareExpired)));
+}
+
+#[tokio::test]
+async fn test_set_share_expiry_requires_share() {
+    let (mount, _blobs, _owner_key, _temp) = common::setup_test_env().await;
+
+    let result = mount
+        .set_share_expiry(SecretKey::generate().public(), Some(1))
+        .await;
+    assert!(matches!(result, Err(MountError::ShareNotFound)));
+}
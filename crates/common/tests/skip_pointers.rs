@@ -0,0 +1,97 @@
+//! Integration tests for first-parent skip pointers
+//!
+//! Tests cover the skip pointers recorded on save and jumping down a long
+//! history with them.
+
+mod common;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use ::common::linked_data::Link;
+use ::common::mount::{ConflictFile, Mount};
+
+/// Every skip points at the first-parent ancestor 2, 4, 8, ... versions below.
+#[tokio::test]
+async fn test_skips_point_at_power_of_two_ancestors() {
+    let (mut mount, blobs, _, _temp) = common::setup_test_env().await;
+
+    // links[h] is the version at height h
+    let mut links: Vec<Link> = vec![mount.link().await];
+    for i in 0..20 {
+        mount
+            .add(
+                &PathBuf::from(format!("/file-{}.txt", i)),
+                Cursor::new(vec![i as u8]),
+            )
+            .await
+            .unwrap();
+        let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+        links.push(link);
+    }
+
+    for (height, link) in links.iter().enumerate() {
+        let manifest = Mount::load_manifest(link, &blobs).await.unwrap();
+        let expected: Vec<Link> = (1..)
+            .map(|i| 1usize << i)
+            .take_while(|distance| *distance <= height)
+            .map(|distance| links[height - distance].clone())
+            .collect();
+        assert_eq!(manifest.skips(), expected.as_slice(), "height {}", height);
+    }
+
+    // Jumping down from the head reaches any height in a few loads
+    for target in 0..links.len() as u64 {
+        let mut link = links.last().unwrap().clone();
+        let mut loads = 0;
+        loop {
+            let manifest = Mount::load_manifest(&link, &blobs).await.unwrap();
+            match manifest.skip_toward(target) {
+                Some(next) => link = next.clone(),
+                None => break,
+            }
+            loads += 1;
+        }
+        assert_eq!(link, links[target as usize]);
+        assert!(loads <= 5, "{} loads to reach height {}", loads, target);
+    }
+}
+
+/// A merge's skips follow its primary parent.
+#[tokio::test]
+async fn test_merge_skips_follow_primary_parent() {
+    let (mut alice, blobs, _, _temp) = common::setup_test_env().await;
+    let genesis = alice.link().await;
+    let (mut bob, _bob_key) = common::fork_mount(&mut alice, &blobs).await;
+    let fork = alice.link().await;
+
+    alice
+        .add(&PathBuf::from("/a1.txt"), Cursor::new(b"a1".to_vec()))
+        .await
+        .unwrap();
+    let (alice_1, _, _) = alice.save(&blobs, false).await.unwrap();
+
+    bob.add(&PathBuf::from("/b1.txt"), Cursor::new(b"b1".to_vec()))
+        .await
+        .unwrap();
+    bob.save(&blobs, false).await.unwrap();
+    bob.add(&PathBuf::from("/b2.txt"), Cursor::new(b"b2".to_vec()))
+        .await
+        .unwrap();
+    let (bob_2, bob_1, _) = bob.save(&blobs, false).await.unwrap();
+
+    // Bob is higher, so his head becomes the primary parent
+    alice
+        .merge(&bob, &ConflictFile::new(), &blobs)
+        .await
+        .unwrap();
+    let (link, previous, height) = alice.save(&blobs, false).await.unwrap();
+    assert_eq!(previous, bob_2);
+    assert_eq!(height, 4);
+
+    let manifest = Mount::load_manifest(&link, &blobs).await.unwrap();
+    assert_eq!(manifest.parents(), &[bob_2, alice_1]);
+    assert_eq!(manifest.skips(), &[bob_1.clone(), genesis]);
+    let bob_1_manifest = Mount::load_manifest(&bob_1, &blobs).await.unwrap();
+    assert_eq!(bob_1_manifest.previous(), &Some(fork));
+}
@@ -0,0 +1,108 @@
+//! Integration tests for merge manifests with several parents
+//!
+//! Tests cover merging more than one branch into a single version and
+//! finding common ancestors through earlier merges.
+
+mod common;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use ::common::mount::{ConflictFile, Mount};
+
+async fn add(mount: &mut Mount, path: &str, data: &[u8]) {
+    mount
+        .add(&PathBuf::from(path), Cursor::new(data.to_vec()))
+        .await
+        .unwrap();
+}
+
+/// Two branches merged before saving become three parents of one version.
+#[tokio::test]
+async fn test_merge_of_several_branches() {
+    let (mut alice, blobs, _, _temp) = common::setup_test_env().await;
+    let (mut bob, _bob_key) = common::fork_mount(&mut alice, &blobs).await;
+    let (mut carol, _carol_key) = common::fork_mount(&mut alice, &blobs).await;
+
+    add(&mut bob, "/bob.txt", b"bob").await;
+    let (bob_link, _, _) = bob.save(&blobs, false).await.unwrap();
+    add(&mut carol, "/carol.txt", b"carol").await;
+    let (carol_link, _, carol_height) = carol.save(&blobs, false).await.unwrap();
+    add(&mut alice, "/alice.txt", b"alice").await;
+    let (alice_link, _, _) = alice.save(&blobs, false).await.unwrap();
+
+    let resolver = ConflictFile::new();
+    alice.merge(&bob, &resolver, &blobs).await.unwrap();
+    alice.merge(&carol, &resolver, &blobs).await.unwrap();
+    // Merging the same branch again doesn't add another parent
+    alice.merge(&carol, &resolver, &blobs).await.unwrap();
+    let (link, previous, height) = alice.save(&blobs, false).await.unwrap();
+
+    // Ties with our own head keep ours first
+    let manifest = Mount::load_manifest(&link, &blobs).await.unwrap();
+    assert_eq!(previous, alice_link);
+    assert_eq!(manifest.parents(), &[alice_link, bob_link, carol_link]);
+    assert_eq!(height, carol_height + 1);
+
+    for path in ["/alice.txt", "/bob.txt", "/carol.txt"] {
+        assert!(alice.cat(&PathBuf::from(path)).await.is_ok(), "{}", path);
+    }
+
+    // Every branch's operations are reachable through the parents
+    let ops = alice.collect_ops_since(None, &blobs).await.unwrap();
+    for path in ["alice.txt", "bob.txt", "carol.txt"] {
+        assert!(ops.resolve_path(path).is_some(), "{}", path);
+    }
+}
+
+/// After a merge, a branch that keeps going from its merged head shares that
+/// head with us, even though it isn't on our first-parent line.
+#[tokio::test]
+async fn test_common_ancestor_through_merge() {
+    let (mut alice, blobs, _, _temp) = common::setup_test_env().await;
+    let (mut bob, _bob_key) = common::fork_mount(&mut alice, &blobs).await;
+
+    add(&mut bob, "/notes.txt", b"from bob").await;
+    let (bob_first, _, _) = bob.save(&blobs, false).await.unwrap();
+    add(&mut alice, "/notes.txt", b"from alice").await;
+    let (alice_first, _, _) = alice.save(&blobs, false).await.unwrap();
+
+    let resolver = ConflictFile::new();
+    let (first_merge, merge_link) = alice.merge_from(&bob, &resolver, &blobs).await.unwrap();
+    assert_eq!(first_merge.conflicts_resolved.len(), 1);
+    let manifest = Mount::load_manifest(&merge_link, &blobs).await.unwrap();
+    assert_eq!(manifest.previous().as_ref(), Some(&alice_first));
+
+    // Bob keeps working without seeing the merge
+    add(&mut bob, "/more.txt", b"more").await;
+    bob.save(&blobs, false).await.unwrap();
+
+    assert_eq!(
+        alice.find_common_ancestor(&bob, &blobs).await.unwrap(),
+        Some(bob_first.clone())
+    );
+
+    // Only Bob's new version is merged, so the old conflict isn't replayed
+    let (second_merge, _) = alice.merge_from(&bob, &resolver, &blobs).await.unwrap();
+    assert!(second_merge.conflicts_resolved.is_empty());
+    assert_eq!(
+        alice.cat(&PathBuf::from("/more.txt")).await.unwrap(),
+        b"more"
+    );
+    assert_eq!(
+        alice.cat(&PathBuf::from("/notes.txt")).await.unwrap(),
+        b"from alice"
+    );
+
+    // Alice's merge contains Bob's head, so Bob converges on her state
+    let alice_link = alice.link().await;
+    let (bob_result, bob_link) = bob.merge_from(&alice, &resolver, &blobs).await.unwrap();
+    assert!(bob_result.conflicts_resolved.is_empty());
+    let manifest = Mount::load_manifest(&bob_link, &blobs).await.unwrap();
+    assert_eq!(manifest.parents(), &[alice_link]);
+    assert_eq!(
+        bob.cat(&PathBuf::from("/notes.txt")).await.unwrap(),
+        b"from alice"
+    );
+    assert!(bob.cat(&PathBuf::from("/more.txt")).await.is_ok());
+}
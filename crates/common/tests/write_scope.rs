@@ -0,0 +1,83 @@
+//! Integration tests for owners restricted to a write scope
+
+mod common;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use ::common::crypto::SecretKey;
+use ::common::mount::{Mount, MountError};
+
+#[tokio::test]
+async fn test_scoped_owner_writes_only_under_scope() {
+    let (mut mount, blobs, _owner_key, _temp) = common::setup_test_env().await;
+    mount
+        .add(
+            &PathBuf::from("/src/main.rs"),
+            Cursor::new(b"main".to_vec()),
+        )
+        .await
+        .unwrap();
+
+    let ci_key = SecretKey::generate();
+    mount.add_owner(ci_key.public()).await.unwrap();
+    mount
+        .set_write_scope(ci_key.public(), vec![PathBuf::from("/artifacts")])
+        .await
+        .unwrap();
+    let (link, _, _) = mount.save(&blobs, false).await.unwrap();
+
+    let mut ci_mount = Mount::load(&link, &ci_key, &blobs).await.unwrap();
+    ci_mount
+        .add(
+            &PathBuf::from("/artifacts/build.log"),
+            Cursor::new(b"ok".to_vec()),
+        )
+        .await
+        .unwrap();
+
+    let result = ci_mount
+        .add(
+            &PathBuf::from("/src/main.rs"),
+            Cursor::new(b"evil".to_vec()),
+        )
+        .await;
+    assert!(matches!(result, Err(MountError::OutOfScope(_))));
+    let result = ci_mount.rm(&PathBuf::from("/src/main.rs")).await;
+    assert!(matches!(result, Err(MountError::OutOfScope(_))));
+    let result = ci_mount
+        .mv(
+            &PathBuf::from("/src/main.rs"),
+            &PathBuf::from("/artifacts/main.rs"),
+        )
+        .await;
+    assert!(matches!(result, Err(MountError::OutOfScope(_))));
+
+    // Scoped owners can't manage shares either
+    let result = ci_mount.remove_share(ci_key.public()).await;
+    assert!(matches!(result, Err(MountError::Unauthorized)));
+}
+
+#[tokio::test]
+async fn test_write_scope_requires_absolute_owner_paths() {
+    let (mut mount, _blobs, _owner_key, _temp) = common::setup_test_env().await;
+
+    let reader_key = SecretKey::generate();
+    mount.add_reader(reader_key.public()).await.unwrap();
+    let result = mount
+        .set_write_scope(reader_key.public(), vec![PathBuf::from("/artifacts")])
+        .await;
+    assert!(result.is_err());
+
+    let ci_key = SecretKey::generate();
+    mount.add_owner(ci_key.public()).await.unwrap();
+    let result = mount
+        .set_write_scope(ci_key.public(), vec![PathBuf::from("artifacts")])
+        .await;
+    assert!(result.is_err());
+
+    let result = mount
+        .set_write_scope(SecretKey::generate().public(), Vec::new())
+        .await;
+    assert!(matches!(result, Err(MountError::ShareNotFound)));
+}
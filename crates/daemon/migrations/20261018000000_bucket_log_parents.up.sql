@@ -0,0 +1,52 @@
+-- Versions form a DAG: forks put several entries at one height, and merges
+-- have more than one parent. Rebuild bucket_log without the one-entry-per-height
+-- constraint (SQLite can't drop a constraint in place).
+CREATE TABLE bucket_log_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- The UUID of the bucket this log entry belongs to
+    bucket_id TEXT NOT NULL,
+    -- The friendly name of the bucket at this point in time
+    name TEXT NOT NULL,
+    -- The current link at this log entry (stored as base32 CID string)
+    current_link VARCHAR(255) NOT NULL,
+    -- The first parent link (null for genesis); all parents are in bucket_log_parents
+    previous_link VARCHAR(255),
+    -- The height of this entry in the log (one above its highest parent)
+    height INTEGER NOT NULL,
+    -- When this log entry was created
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Whether this version is published
+    published BOOLEAN NOT NULL DEFAULT FALSE,
+
+    -- Ensure one entry per link per bucket
+    UNIQUE(bucket_id, current_link)
+);
+
+INSERT INTO bucket_log_new (id, bucket_id, name, current_link, previous_link, height, created_at, published)
+SELECT id, bucket_id, name, current_link, previous_link, height, created_at, published
+FROM bucket_log;
+
+DROP TABLE bucket_log;
+ALTER TABLE bucket_log_new RENAME TO bucket_log;
+
+CREATE INDEX idx_bucket_log_bucket_height ON bucket_log(bucket_id, height DESC);
+CREATE INDEX idx_bucket_log_bucket_link ON bucket_log(bucket_id, current_link);
+CREATE INDEX idx_bucket_log_bucket_published ON bucket_log(bucket_id, published, height DESC);
+
+-- Every parent of each log entry, in manifest order (position 0 is previous_link)
+CREATE TABLE bucket_log_parents (
+    bucket_id TEXT NOT NULL,
+    current_link VARCHAR(255) NOT NULL,
+    parent_link VARCHAR(255) NOT NULL,
+    position INTEGER NOT NULL,
+
+    PRIMARY KEY (bucket_id, current_link, position)
+);
+
+CREATE INDEX idx_bucket_log_parents_parent ON bucket_log_parents(bucket_id, parent_link);
+
+-- Existing entries have a single parent
+INSERT INTO bucket_log_parents (bucket_id, current_link, parent_link, position)
+SELECT bucket_id, current_link, previous_link, 0
+FROM bucket_log
+WHERE previous_link IS NOT NULL;
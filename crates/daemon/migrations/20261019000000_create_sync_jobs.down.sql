@@ -0,0 +1,3 @@
+-- Drop sync_jobs table and indexes
+DROP INDEX IF EXISTS idx_sync_jobs_due;
+DROP TABLE IF EXISTS sync_jobs;
@@ -0,0 +1,31 @@
+-- Durable queue for sync jobs that must survive restarts and be retried
+CREATE TABLE sync_jobs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Job type: sync_bucket, download_pins or accept_deposit
+    kind TEXT NOT NULL,
+    -- The bucket the job is for
+    bucket_id TEXT NOT NULL,
+    -- The version to sync to, the pins hash list to download, or the deposit to accept
+    target_link VARCHAR(255) NOT NULL,
+    -- Height of the target version (sync_bucket only)
+    target_height INTEGER,
+    -- Peers to fetch from, in priority order (comma separated hex keys)
+    peer_ids TEXT NOT NULL,
+    -- Current status: pending, running, failed, cancelled
+    status TEXT NOT NULL DEFAULT 'pending',
+    -- Number of failed attempts so far
+    attempts INTEGER NOT NULL DEFAULT 0,
+    -- Unix time (seconds) the job becomes due
+    next_retry_at INTEGER NOT NULL,
+    -- Error message of the last failed attempt
+    last_error TEXT,
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- One job per bucket and target
+    UNIQUE(kind, bucket_id, target_link)
+);
+
+-- Index for picking the next due job
+CREATE INDEX idx_sync_jobs_due ON sync_jobs(status, next_retry_at);
@@ -0,0 +1,2 @@
+-- Drop bucket_sync_state table
+DROP TABLE IF EXISTS bucket_sync_state;
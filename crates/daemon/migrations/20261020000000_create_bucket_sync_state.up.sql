@@ -0,0 +1,22 @@
+-- Latest sync progress per bucket, folded from the peer's progress events
+CREATE TABLE bucket_sync_state (
+    bucket_id TEXT PRIMARY KEY,
+    -- Current status: syncing, downloading, synced, failed
+    status TEXT NOT NULL DEFAULT 'syncing',
+    -- The version being synced to, then the version we reached
+    target_link VARCHAR(255),
+    target_height INTEGER,
+    -- Manifests downloaded by the current sync
+    manifests_fetched INTEGER NOT NULL DEFAULT 0,
+    -- Pinned blobs missing locally, and how many have been fetched
+    blobs_total INTEGER NOT NULL DEFAULT 0,
+    blobs_done INTEGER NOT NULL DEFAULT 0,
+    bytes_downloaded INTEGER NOT NULL DEFAULT 0,
+    -- Peer currently being downloaded from (hex key)
+    current_peer TEXT,
+    -- Error message of the last failed sync
+    last_error TEXT,
+    -- Timestamps
+    started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
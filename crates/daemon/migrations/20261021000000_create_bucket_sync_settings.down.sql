@@ -0,0 +1,3 @@
+-- Drop bucket sync settings tables
+DROP TABLE IF EXISTS offline_paths;
+DROP TABLE IF EXISTS bucket_sync_modes;
@@ -0,0 +1,17 @@
+-- How much of each bucket this node replicates; buckets without a row are synced fully
+CREATE TABLE bucket_sync_modes (
+    bucket_id TEXT PRIMARY KEY,
+    -- Sync mode: full or lazy
+    mode TEXT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Paths of lazily synced buckets whose contents are always downloaded
+CREATE TABLE offline_paths (
+    bucket_id TEXT NOT NULL,
+    -- Absolute path within the bucket
+    path TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (bucket_id, path)
+);
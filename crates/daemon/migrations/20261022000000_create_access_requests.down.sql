@@ -0,0 +1,2 @@
+-- Drop access requests table
+DROP TABLE IF EXISTS access_requests;
@@ -0,0 +1,23 @@
+-- Requests from peers to be added to a bucket's shares, waiting for an owner
+CREATE TABLE access_requests (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    bucket_id TEXT NOT NULL,
+    -- Hex public key of the requesting peer
+    requester TEXT NOT NULL,
+    -- Requested role: Owner, Reader, Mirror or Depositor
+    role TEXT NOT NULL,
+    -- Free-form message from the requester
+    note TEXT,
+    -- Unix time (seconds) the requester signed the request
+    requested_at INTEGER NOT NULL,
+    -- Ed25519 signature of the requester over the request (64 bytes)
+    signature BLOB NOT NULL,
+    -- Current status: pending, approved, denied
+    status TEXT NOT NULL DEFAULT 'pending',
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- One request per bucket and requester; a new request replaces the old one
+    UNIQUE(bucket_id, requester)
+);
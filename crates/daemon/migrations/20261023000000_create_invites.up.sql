@@ -0,0 +1,11 @@
+-- Invite tokens we issued, waiting to be redeemed once
+CREATE TABLE invites (
+    -- BLAKE3 hash of the ticket's token; the token itself is never stored
+    token_hash BLOB PRIMARY KEY,
+    bucket_id TEXT NOT NULL,
+    -- Role granted on redemption: Owner, Reader, Mirror or Depositor
+    role TEXT NOT NULL,
+    -- Unix time (seconds) the token stops working
+    expires_at INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,552 @@
+//! Migration of blobs between store backends.
+//!
+//! Streams every complete blob together with its outboard from the current
+//! store into a new one, verifying hashes on the way. This runs either
+//! offline against the configured store or inside a running daemon against
+//! the store it serves, as a background job whose progress can be polled.
+//! Blobs already present in the target are skipped, so an interrupted
+//! migration can simply be re-run. Only once everything has been copied and
+//! verified is config.toml switched over.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use futures::StreamExt;
+use iroh::endpoint::VarInt;
+use iroh_blobs::api::blobs::BlobStatus;
+use iroh_blobs::protocol::ChunkRanges;
+use iroh_blobs::util::{AsyncReadRecvStream, AsyncReadRecvStreamExtra};
+use iroh_blobs::{Hash, HashAndFormat};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncWriteExt, DuplexStream};
+
+use common::peer::{BlobsStore, BlobsStoreError, WritePause};
+
+use crate::state::{AppState, BlobStoreConfig, StateError};
+
+use super::setup::setup_blobs_store;
+use super::BlobsSetupError;
+
+/// Passes over a live source before holding off writes for the last one.
+const MAX_PASSES: usize = 5;
+
+/// Size of the in-memory pipe each blob is streamed through.
+const COPY_BUFFER: usize = 64 * 1024;
+
+/// Progress of a running migration.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct MigrateProgress {
+    /// Total number of blobs to migrate
+    pub total: u64,
+    /// Blobs copied in this run
+    pub copied: u64,
+    /// Blobs already present in the target (from an earlier run)
+    pub skipped: u64,
+}
+
+impl MigrateProgress {
+    /// Number of blobs processed so far.
+    pub fn done(&self) -> u64 {
+        self.copied + self.skipped
+    }
+}
+
+/// Result of a migration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrateSummary {
+    /// Final progress counters
+    pub progress: MigrateProgress,
+    /// Bytes copied in this run
+    pub bytes: u64,
+    /// Tags copied to the target store
+    pub tags: u64,
+    /// Blobs whose source data did not match their hash (not copied)
+    pub failed: Vec<Hash>,
+    /// Whether config.toml now points at the new store
+    pub switched: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrateError {
+    #[error("target blob store is the same as the current one")]
+    SameStore,
+    #[error("a blob store migration is already running")]
+    Running,
+    #[error("failed to open blob store: {0}")]
+    Setup(#[from] BlobsSetupError),
+    #[error("blob store error: {0}")]
+    Store(#[from] BlobsStoreError),
+    #[error("blob store request failed: {0}")]
+    Request(String),
+    #[error("blob {0} missing from target after copy")]
+    NotCopied(Hash),
+    #[error("failed to update config: {0}")]
+    Config(#[from] StateError),
+}
+
+/// Migrate all blobs from the configured store to `target`.
+///
+/// Opens both stores directly, so the daemon must be stopped; while it runs,
+/// use [`migrate_live_blobs`] instead. `on_progress` is called after each
+/// blob. If any source blob fails hash verification the config is left
+/// untouched; run `jax blobs scrub` to repair it and migrate again.
+pub async fn migrate_blobs(
+    state: &mut AppState,
+    target: BlobStoreConfig,
+    on_progress: impl FnMut(&MigrateProgress),
+) -> Result<MigrateSummary, MigrateError> {
+    if state.config.blob_store == target {
+        return Err(MigrateError::SameStore);
+    }
+
+    let max_import_size = state.config.max_import_size;
+    let source =
+        setup_blobs_store(&state.config.blob_store, &state.jax_dir, max_import_size).await?;
+    let dest = setup_blobs_store(&target, &state.jax_dir, max_import_size).await?;
+
+    let result = copy_store(&source, &dest, on_progress)
+        .await
+        .map(|(summary, _)| summary);
+    shutdown(&source).await;
+    shutdown(&dest).await;
+
+    let mut summary = result?;
+    switch_config(state, target, &mut summary)?;
+    Ok(summary)
+}
+
+/// Migrate all blobs out of `source`, the store a running daemon serves.
+///
+/// Blobs written while the copy runs are picked up by further passes. The
+/// last pass runs with writes to `source` paused, so nothing stored before
+/// the config is switched can be left behind. Once switched, `source` refuses
+/// writes until the daemon restarts on the new store; otherwise writes
+/// resume.
+pub async fn migrate_live_blobs(
+    source: &BlobsStore,
+    jax_dir: &Path,
+    target: BlobStoreConfig,
+    on_progress: impl FnMut(&MigrateProgress),
+) -> Result<MigrateSummary, MigrateError> {
+    let mut state = AppState::load(Some(jax_dir.to_path_buf()))?;
+    if state.config.blob_store == target {
+        return Err(MigrateError::SameStore);
+    }
+
+    let dest = setup_blobs_store(&target, &state.jax_dir, state.config.max_import_size).await?;
+    let result = copy_store(source, &dest, on_progress).await;
+    shutdown(&dest).await;
+
+    let (mut summary, pause) = result?;
+    switch_config(&mut state, target, &mut summary)?;
+    if summary.switched {
+        pause.close();
+    }
+    Ok(summary)
+}
+
+/// State of a running daemon's blob store migration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrateStatus {
+    /// Whether a migration is running
+    pub running: bool,
+    /// Progress of the current or last migration
+    pub progress: MigrateProgress,
+    /// Result of the last migration, once it finished
+    pub summary: Option<MigrateSummary>,
+    /// Why the last migration failed, if it did
+    pub error: Option<String>,
+}
+
+/// The blob store migration of a running daemon.
+///
+/// Runs [`migrate_live_blobs`] in the background and keeps its status for
+/// the API to report.
+#[derive(Debug, Clone, Default)]
+pub struct LiveMigration(Arc<Mutex<MigrateStatus>>);
+
+impl LiveMigration {
+    /// Status of the current or last migration.
+    pub fn status(&self) -> MigrateStatus {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Start migrating `source` to `target` in the background.
+    ///
+    /// Fails if a migration is already running or `target` is the store in
+    /// use; any other error is reported through [`Self::status`].
+    #[allow(clippy::result_large_err)]
+    pub fn start(
+        &self,
+        source: BlobsStore,
+        jax_dir: PathBuf,
+        target: BlobStoreConfig,
+    ) -> Result<(), MigrateError> {
+        let state = AppState::load(Some(jax_dir.clone()))?;
+        if state.config.blob_store == target {
+            return Err(MigrateError::SameStore);
+        }
+        {
+            let mut status = self.0.lock().unwrap();
+            if status.running {
+                return Err(MigrateError::Running);
+            }
+            *status = MigrateStatus {
+                running: true,
+                ..Default::default()
+            };
+        }
+
+        let migration = self.clone();
+        tokio::spawn(async move {
+            let result = migrate_live_blobs(&source, &jax_dir, target, |progress| {
+                migration.0.lock().unwrap().progress = *progress;
+            })
+            .await;
+            let mut status = migration.0.lock().unwrap();
+            status.running = false;
+            match result {
+                Ok(summary) => {
+                    status.progress = summary.progress;
+                    status.summary = Some(summary);
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "blob store migration failed");
+                    status.error = Some(e.to_string());
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Copy every complete blob and all tags from `source` into `dest`.
+///
+/// Returns with writes to `source` paused, so the caller decides whether
+/// they resume.
+async fn copy_store(
+    source: &BlobsStore,
+    dest: &BlobsStore,
+    mut on_progress: impl FnMut(&MigrateProgress),
+) -> Result<(MigrateSummary, WritePause), MigrateError> {
+    let mut summary = MigrateSummary::default();
+    let mut seen = HashSet::new();
+    let mut partial = HashSet::new();
+    on_progress(&summary.progress);
+
+    // Keep passing over the source until a pass turns up no new blobs
+    for _ in 0..MAX_PASSES {
+        let fresh = copy_pass(
+            source,
+            dest,
+            (&mut seen, &mut partial),
+            &mut summary,
+            &mut on_progress,
+        )
+        .await?;
+        if fresh == 0 {
+            break;
+        }
+    }
+
+    // Catch what arrived since, and partial blobs that completed, with
+    //  writes held off so nothing is left behind
+    let pause = source.pause_writes().await;
+    partial.clear();
+    copy_pass(
+        source,
+        dest,
+        (&mut seen, &mut partial),
+        &mut summary,
+        &mut on_progress,
+    )
+    .await?;
+
+    summary.tags = copy_tags(source, dest).await?;
+
+    // Final verification pass before committing to the new store
+    for hash in &seen {
+        if !summary.failed.contains(hash) && !dest.stat(hash).await? {
+            return Err(MigrateError::NotCopied(*hash));
+        }
+    }
+
+    Ok((summary, pause))
+}
+
+/// Copy the blobs of `source` not yet `seen` or known to be `partial`.
+///
+/// Complete blobs are added to `seen` and partial ones to `partial`.
+/// Returns how many blobs were new to this pass.
+async fn copy_pass(
+    source: &BlobsStore,
+    dest: &BlobsStore,
+    (seen, partial): (&mut HashSet<Hash>, &mut HashSet<Hash>),
+    summary: &mut MigrateSummary,
+    on_progress: &mut impl FnMut(&MigrateProgress),
+) -> Result<usize, MigrateError> {
+    let hashes = source
+        .blobs()
+        .list()
+        .hashes()
+        .await
+        .map_err(|e| MigrateError::Request(e.to_string()))?;
+    let fresh: Vec<Hash> = hashes
+        .into_iter()
+        .filter(|h| !seen.contains(h) && !partial.contains(h))
+        .collect();
+    let count = fresh.len();
+    summary.progress.total += count as u64;
+    on_progress(&summary.progress);
+
+    for hash in fresh {
+        if dest.stat(&hash).await? {
+            summary.progress.skipped += 1;
+            seen.insert(hash);
+        } else if let Some(size) = complete_size(source, hash).await? {
+            match copy_blob(source, dest, hash, size).await? {
+                Some(bytes) => {
+                    summary.progress.copied += 1;
+                    summary.bytes += bytes;
+                }
+                None => summary.failed.push(hash),
+            }
+            seen.insert(hash);
+        } else {
+            // Partial downloads are not worth carrying over, unless they
+            //  finish before the last pass
+            summary.progress.total -= 1;
+            partial.insert(hash);
+        }
+        on_progress(&summary.progress);
+    }
+
+    Ok(count)
+}
+
+/// Point config.toml at `target`, unless some blobs failed to copy.
+fn switch_config(
+    state: &mut AppState,
+    target: BlobStoreConfig,
+    summary: &mut MigrateSummary,
+) -> Result<(), StateError> {
+    if summary.failed.is_empty() {
+        state.config.blob_store = target;
+        state.save_config()?;
+        summary.switched = true;
+        tracing::info!(
+            copied = summary.progress.copied,
+            skipped = summary.progress.skipped,
+            "blob store migration complete, config switched"
+        );
+    } else {
+        tracing::warn!(
+            failed = summary.failed.len(),
+            "blob store migration incomplete, config left unchanged"
+        );
+    }
+    Ok(())
+}
+
+/// Size of a blob if the store holds all of it.
+async fn complete_size(store: &BlobsStore, hash: Hash) -> Result<Option<u64>, MigrateError> {
+    let status = store
+        .blobs()
+        .status(hash)
+        .await
+        .map_err(|e| MigrateError::Request(e.to_string()))?;
+    Ok(match status {
+        BlobStatus::Complete { size } => Some(size),
+        _ => None,
+    })
+}
+
+/// Copy one blob, returning its size, or None if the source data is corrupt.
+///
+/// The blob is streamed as BAO (data interleaved with its outboard) from the
+/// source export into the target import, which checks every chunk against
+/// the hash, so neither side holds more than a pipe buffer in memory.
+async fn copy_blob(
+    source: &BlobsStore,
+    dest: &BlobsStore,
+    hash: Hash,
+    size: u64,
+) -> Result<Option<u64>, MigrateError> {
+    if size == 0 {
+        // BAO import has nothing to write for the empty blob
+        dest.put(Vec::new()).await?;
+        return Ok(Some(0));
+    }
+
+    let (mut writer, reader) = tokio::io::duplex(COPY_BUFFER);
+    let export = async {
+        let mut stream = std::pin::pin!(source
+            .blobs()
+            .export_bao(hash, ChunkRanges::all())
+            .into_byte_stream());
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(std::io::Error::other)?;
+            writer.write_all(&chunk).await?;
+        }
+        // Close the pipe so the import sees the end of the stream
+        writer.shutdown().await
+    };
+    let import = dest.blobs().import_bao_reader(
+        hash,
+        ChunkRanges::all(),
+        AsyncReadRecvStream::new(PipeReader(reader)),
+    );
+    let (exported, imported) = tokio::join!(export, import);
+
+    if let Err(e) = imported {
+        tracing::warn!(%hash, error = %e, "source blob failed verification, skipping");
+        return Ok(None);
+    }
+    if let Err(e) = exported {
+        tracing::warn!(%hash, error = %e, "failed to read blob from source store");
+        return Ok(None);
+    }
+    if !dest.stat(&hash).await? {
+        return Err(MigrateError::NotCopied(hash));
+    }
+    Ok(Some(size))
+}
+
+/// Read half of the in-memory pipe a blob is copied through.
+struct PipeReader(DuplexStream);
+
+impl AsyncReadRecvStreamExtra for PipeReader {
+    fn inner(&mut self) -> &mut (impl AsyncRead + Unpin + Send) {
+        &mut self.0
+    }
+
+    fn stop(&mut self, _code: VarInt) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn id(&self) -> u64 {
+        0
+    }
+}
+
+async fn copy_tags(source: &BlobsStore, dest: &BlobsStore) -> Result<u64, MigrateError> {
+    let request = |e: iroh_blobs::api::RequestError| MigrateError::Request(e.to_string());
+    let mut tags = std::pin::pin!(source
+        .inner
+        .store()
+        .tags()
+        .list()
+        .await
+        .map_err(|e| MigrateError::Request(e.to_string()))?);
+
+    let mut count = 0;
+    while let Some(tag) = tags.next().await {
+        let tag = tag.map_err(|e| MigrateError::Request(e.to_string()))?;
+        dest.inner
+            .store()
+            .tags()
+            .set(&tag.name, HashAndFormat::new(tag.hash, tag.format))
+            .await
+            .map_err(request)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+async fn shutdown(store: &BlobsStore) {
+    if let Err(e) = store.inner.store().shutdown().await {
+        tracing::warn!(error = %e, "failed to shut down blob store cleanly");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AppConfig;
+
+    #[tokio::test]
+    async fn test_migrate_legacy_to_filesystem() {
+        let temp = tempfile::tempdir().unwrap();
+        let jax_dir = temp.path().join("jax");
+        let mut state = AppState::init(Some(jax_dir.clone()), Some(AppConfig::default())).unwrap();
+
+        let data = b"migrate me".to_vec();
+        let hash = {
+            let legacy = BlobsStore::legacy_fs(&state.blobs_path).await.unwrap();
+            let hash = legacy.put(data.clone()).await.unwrap();
+            shutdown(&legacy).await;
+            hash
+        };
+
+        let target = BlobStoreConfig::Filesystem {
+            path: temp.path().join("objects"),
+            db_path: None,
+        };
+        let mut reports = 0;
+        let summary = migrate_blobs(&mut state, target.clone(), |_| reports += 1)
+            .await
+            .unwrap();
+        assert!(summary.switched);
+        assert_eq!(summary.progress.copied, summary.progress.total);
+        assert!(reports > 0);
+
+        // Config on disk now points at the new store
+        let reloaded = AppState::load(Some(jax_dir.clone())).unwrap();
+        assert_eq!(reloaded.config.blob_store, target);
+
+        let store = setup_blobs_store(&target, &jax_dir, reloaded.config.max_import_size)
+            .await
+            .unwrap();
+        assert_eq!(store.get(&hash).await.unwrap().as_ref(), data.as_slice());
+
+        // Migrating to the store we already use is rejected
+        let mut state = reloaded;
+        let result = migrate_blobs(&mut state, target, |_| {}).await;
+        assert!(matches!(result, Err(MigrateError::SameStore)));
+    }
+
+    #[tokio::test]
+    async fn test_migrate_live_store_streams_large_blobs() {
+        let temp = tempfile::tempdir().unwrap();
+        let jax_dir = temp.path().join("jax");
+        let state = AppState::init(Some(jax_dir.clone()), Some(AppConfig::default())).unwrap();
+
+        // The source stays open throughout, as it does inside the daemon
+        let source = BlobsStore::legacy_fs(&state.blobs_path).await.unwrap();
+        // Large enough to carry an outboard
+        let large: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let large_hash = source.put(large.clone()).await.unwrap();
+
+        let target = BlobStoreConfig::Filesystem {
+            path: temp.path().join("objects"),
+            db_path: None,
+        };
+        let summary = migrate_live_blobs(&source, &jax_dir, target.clone(), |_| {})
+            .await
+            .unwrap();
+        assert!(summary.switched);
+        assert!(summary.failed.is_empty());
+        assert_eq!(summary.progress.copied, 1);
+        assert_eq!(summary.bytes, large.len() as u64);
+
+        // The source can still be read, but refuses writes that the daemon
+        // would lose on restarting with the new store
+        assert!(source.stat(&large_hash).await.unwrap());
+        assert!(matches!(
+            source.put(b"too late".to_vec()).await,
+            Err(BlobsStoreError::WritesClosed)
+        ));
+        shutdown(&source).await;
+
+        let reloaded = AppState::load(Some(jax_dir.clone())).unwrap();
+        assert_eq!(reloaded.config.blob_store, target);
+        let store = setup_blobs_store(&target, &jax_dir, reloaded.config.max_import_size)
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get(&large_hash).await.unwrap().as_ref(),
+            large.as_slice()
+        );
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! This module provides the blob store setup following the same pattern as the database module.
 
+pub mod migrate;
 mod setup;
 
 use std::path::Path;
@@ -21,7 +22,7 @@ impl Blobs {
     /// Setup blob storage based on configuration.
     ///
     /// # Arguments
-    /// * `config` - Blob store configuration (Legacy, Filesystem, or S3)
+    /// * `config` - Blob store configuration (Legacy, Filesystem, S3, or Tiered)
     /// * `jax_dir` - Path to the jax directory (used for legacy blobs and cache)
     /// * `max_import_size` - Maximum blob size allowed for BAO imports
     pub async fn setup(
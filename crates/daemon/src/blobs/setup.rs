@@ -3,6 +3,7 @@
 use std::path::Path;
 
 use common::peer::BlobsStore;
+use object_store::{DiskCacheConfig, ObjectStore, ObjectStoreConfig, TierPolicy};
 
 use crate::state::BlobStoreConfig;
 
@@ -10,10 +11,12 @@ use super::BlobsSetupError;
 
 /// Setup the blob store based on configuration.
 ///
-/// Supports three modes:
+/// Supports four modes:
 /// - Legacy: Uses iroh's FsStore (default, for backwards compatibility)
 /// - Filesystem: Uses SQLite + local filesystem via ObjectStore
-/// - S3: Uses SQLite + S3/MinIO via ObjectStore
+/// - S3: Uses SQLite + S3/MinIO via ObjectStore, optionally fronted by a
+///   local LRU disk cache
+/// - Tiered: Uses SQLite + local filesystem, offloading cold blobs to S3/MinIO
 pub async fn setup_blobs_store(
     config: &BlobStoreConfig,
     jax_dir: &Path,
@@ -43,7 +46,7 @@ pub async fn setup_blobs_store(
                 .map_err(|e| BlobsSetupError::StoreError(e.to_string()))
         }
 
-        BlobStoreConfig::S3 { url } => {
+        BlobStoreConfig::S3 { url, cache } => {
             // Parse S3 URL
             let s3_config = BlobStoreConfig::parse_s3_url(url)
                 .map_err(|e| BlobsSetupError::StoreError(e.to_string()))?;
@@ -58,6 +61,40 @@ pub async fn setup_blobs_store(
             // SQLite database goes in jax_dir
             let db_path = jax_dir.join("blobs.db");
 
+            if let Some(cache) = cache {
+                let cache_config = DiskCacheConfig {
+                    path: cache
+                        .path
+                        .clone()
+                        .unwrap_or_else(|| jax_dir.join("blob-cache")),
+                    max_size: cache.max_size,
+                    max_entry_size: cache.max_entry_size,
+                };
+                tracing::info!(
+                    path = %cache_config.path.display(),
+                    max_size = cache_config.max_size,
+                    max_entry_size = cache_config.max_entry_size,
+                    "Using local disk cache for S3 blobs"
+                );
+
+                let store_config = ObjectStoreConfig::S3 {
+                    endpoint: s3_config.endpoint,
+                    access_key: s3_config.access_key,
+                    secret_key: s3_config.secret_key,
+                    bucket: s3_config.bucket,
+                    region: None,
+                };
+                let store = ObjectStore::new_cached(
+                    &db_path,
+                    store_config,
+                    cache_config,
+                    Some(max_import_size),
+                )
+                .await
+                .map_err(|e| BlobsSetupError::StoreError(e.to_string()))?;
+                return Ok(BlobsStore::from_object_store(store));
+            }
+
             BlobsStore::s3(
                 &db_path,
                 &s3_config.endpoint,
@@ -70,5 +107,40 @@ pub async fn setup_blobs_store(
             .await
             .map_err(|e| BlobsSetupError::StoreError(e.to_string()))
         }
+
+        BlobStoreConfig::Tiered {
+            path,
+            db_path,
+            cold_url,
+            policy,
+        } => {
+            let s3_config = BlobStoreConfig::parse_s3_url(cold_url)
+                .map_err(|e| BlobsSetupError::StoreError(e.to_string()))?;
+            let db_path = db_path.clone().unwrap_or_else(|| path.join("blobs.db"));
+            let policy = TierPolicy::from(policy);
+
+            tracing::info!(
+                hot_path = %path.display(),
+                db_path = %db_path.display(),
+                cold_endpoint = %s3_config.endpoint,
+                cold_bucket = %s3_config.bucket,
+                min_size = policy.min_size,
+                max_import_size,
+                "Using tiered blob store (local hot tier, S3 cold tier)"
+            );
+
+            let hot = ObjectStoreConfig::Local { path: path.clone() };
+            let cold = ObjectStoreConfig::S3 {
+                endpoint: s3_config.endpoint,
+                access_key: s3_config.access_key,
+                secret_key: s3_config.secret_key,
+                bucket: s3_config.bucket,
+                region: None,
+            };
+            let store = ObjectStore::new_tiered(&db_path, hot, cold, policy, Some(max_import_size))
+                .await
+                .map_err(|e| BlobsSetupError::StoreError(e.to_string()))?;
+            Ok(BlobsStore::from_object_store(store))
+        }
     }
 }
@@ -4,4 +4,4 @@ pub mod ops;
 
 #[cfg(feature = "fuse")]
 pub use ops::Mount;
-pub use ops::{Bucket, Daemon, Health, Init, Update, Version};
+pub use ops::{Blobs, Bucket, Daemon, Health, Init, Update, Version};
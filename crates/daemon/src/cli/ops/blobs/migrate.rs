@@ -0,0 +1,173 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Args;
+use indicatif::{ProgressBar, ProgressStyle};
+use owo_colors::OwoColorize;
+
+use jax_daemon::blobs::migrate::{migrate_blobs, MigrateError, MigrateSummary};
+use jax_daemon::http_server::api::client::{ApiClient, ApiError};
+use jax_daemon::http_server::api::v0::blobs::migrate::{MigrateRequest, MigrateStatusRequest};
+use jax_daemon::state::{AppState, BlobStoreConfig, StateError};
+
+/// How often a migration running in the daemon is polled
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Args, Debug, Clone)]
+pub struct Migrate {
+    /// TOML file describing the target blob store, in the same format as the
+    /// `[blob_store]` table of config.toml, e.g.
+    ///   type = "filesystem"
+    ///   path = "/data/jax-blobs"
+    #[arg(long)]
+    pub to: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct MigrateOutput {
+    pub summary: MigrateSummary,
+    pub config_path: PathBuf,
+}
+
+impl fmt::Display for MigrateOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let summary = &self.summary;
+        if summary.switched {
+            writeln!(f, "{} blob store", "Migrated".green().bold())?;
+        } else {
+            writeln!(f, "{} blob store migration", "Incomplete".yellow().bold())?;
+        }
+        writeln!(f, "  {} {}", "copied:".dimmed(), summary.progress.copied)?;
+        writeln!(
+            f,
+            "  {} {}",
+            "already present:".dimmed(),
+            summary.progress.skipped
+        )?;
+        writeln!(f, "  {} {}", "bytes:".dimmed(), summary.bytes)?;
+        writeln!(f, "  {} {}", "tags:".dimmed(), summary.tags)?;
+        if summary.switched {
+            write!(
+                f,
+                "  {} {} updated; restart the daemon to use the new store",
+                "config:".dimmed(),
+                self.config_path.display()
+            )
+        } else {
+            for hash in &summary.failed {
+                writeln!(f, "  {} {}", "failed:".red(), hash)?;
+            }
+            write!(
+                f,
+                "  {} config unchanged; run `jax blobs scrub` and migrate again",
+                "config:".dimmed()
+            )
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrateCmdError {
+    #[error("failed to load state: {0}")]
+    State(#[from] StateError),
+    #[error("failed to read target config: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("invalid target config: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("invalid target config: {0}")]
+    Invalid(String),
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+    #[error(transparent)]
+    Migrate(#[from] MigrateError),
+    #[error("migration failed in the daemon: {0}")]
+    Daemon(String),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Migrate {
+    type Error = MigrateCmdError;
+    type Output = MigrateOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut state = AppState::load(ctx.config_path.clone())?;
+        let target: BlobStoreConfig = toml::from_str(&std::fs::read_to_string(&self.to)?)?;
+        validate_target(&target).map_err(MigrateCmdError::Invalid)?;
+
+        let bar = ctx.progress.add(ProgressBar::new(0));
+        bar.set_style(
+            ProgressStyle::with_template("{spinner} migrating blobs [{bar:40}] {pos}/{len}")
+                .unwrap()
+                .progress_chars("=> "),
+        );
+
+        // A running daemon holds the current store open, so let it do the
+        //  copy in the background and follow its progress
+        let livez = ctx.client.base_url().join("/_status/livez").unwrap();
+        if ctx.client.http_client().get(livez).send().await.is_ok() {
+            let mut client = ctx.client.clone();
+            let result = follow_live_migration(&mut client, target, &bar).await;
+            bar.finish_and_clear();
+            return Ok(MigrateOutput {
+                summary: result?,
+                config_path: state.config_path,
+            });
+        }
+
+        let summary = migrate_blobs(&mut state, target, |progress| {
+            bar.set_length(progress.total);
+            bar.set_position(progress.done());
+        })
+        .await;
+        bar.finish_and_clear();
+
+        Ok(MigrateOutput {
+            summary: summary?,
+            config_path: state.config_path,
+        })
+    }
+}
+
+/// Start the migration in the daemon and poll it until it finishes
+async fn follow_live_migration(
+    client: &mut ApiClient,
+    target: BlobStoreConfig,
+    bar: &ProgressBar,
+) -> Result<MigrateSummary, MigrateCmdError> {
+    client.call(MigrateRequest { target }).await?;
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let status = client.call(MigrateStatusRequest::default()).await?.status;
+        bar.set_length(status.progress.total);
+        bar.set_position(status.progress.done());
+        if status.running {
+            continue;
+        }
+        if let Some(error) = status.error {
+            return Err(MigrateCmdError::Daemon(error));
+        }
+        return status
+            .summary
+            .ok_or_else(|| MigrateCmdError::Daemon("migration ended without a result".into()));
+    }
+}
+
+fn validate_target(target: &BlobStoreConfig) -> Result<(), String> {
+    match target {
+        BlobStoreConfig::Legacy => Ok(()),
+        BlobStoreConfig::Filesystem { path, .. } if !path.is_absolute() => {
+            Err("filesystem path must be absolute".to_string())
+        }
+        BlobStoreConfig::Filesystem { .. } => Ok(()),
+        BlobStoreConfig::S3 { url, .. } => BlobStoreConfig::parse_s3_url(url)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        BlobStoreConfig::Tiered { path, .. } if !path.is_absolute() => {
+            Err("tiered hot path must be absolute".to_string())
+        }
+        BlobStoreConfig::Tiered { cold_url, .. } => BlobStoreConfig::parse_s3_url(cold_url)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+
+use common::peer::sync::ScrubOutcome;
+use jax_daemon::http_server::api::client::ApiError;
+use jax_daemon::http_server::api::v0::blobs::scrub::{ScrubRequest, ScrubResponse};
+
+#[derive(Args, Debug, Clone)]
+pub struct Scrub {
+    /// Queue the scrub in the background instead of waiting for the report
+    #[arg(long)]
+    pub background: bool,
+}
+
+#[derive(Debug)]
+pub struct ScrubOutput {
+    pub outcome: Option<ScrubOutcome>,
+}
+
+impl fmt::Display for ScrubOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(outcome) = &self.outcome else {
+            return write!(
+                f,
+                "{} blob scrub in the background",
+                "Queued".green().bold()
+            );
+        };
+        let report = &outcome.report;
+
+        let status = if outcome.unrecoverable.is_empty() {
+            "Scrubbed".green().bold().to_string()
+        } else {
+            "Scrubbed with errors".yellow().bold().to_string()
+        };
+        writeln!(f, "{} {} blobs", status, report.checked)?;
+        writeln!(f, "  {} {}", "healthy:".dimmed(), report.healthy)?;
+        writeln!(f, "  {} {}", "corrupt:".dimmed(), report.corrupt.len())?;
+        writeln!(f, "  {} {}", "missing:".dimmed(), report.missing.len())?;
+        writeln!(
+            f,
+            "  {} {}",
+            "outboards repaired:".dimmed(),
+            report.outboards_repaired
+        )?;
+        writeln!(
+            f,
+            "  {} {}",
+            "rows repaired:".dimmed(),
+            report.rows_repaired
+        )?;
+        writeln!(
+            f,
+            "  {} {} recovered, {} removed",
+            "orphans:".dimmed(),
+            report.orphans_recovered,
+            report.orphans_removed
+        )?;
+        write!(
+            f,
+            "  {} {}",
+            "re-fetched:".dimmed(),
+            outcome.refetched.len()
+        )?;
+        for hash in &outcome.unrecoverable {
+            write!(f, "\n  {} {}", "unrecoverable:".red(), hash)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScrubError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Scrub {
+    type Error = ScrubError;
+    type Output = ScrubOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let request = ScrubRequest {
+            background: self.background,
+        };
+        let response: ScrubResponse = client.call(request).await?;
+
+        Ok(ScrubOutput {
+            outcome: response.outcome,
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+
+use clap::Args;
+use indicatif::HumanBytes;
+use owo_colors::OwoColorize;
+
+use jax_daemon::http_server::api::client::ApiError;
+use jax_daemon::http_server::api::v0::blobs::tiers::{TiersRequest, TiersResponse};
+use object_store::TierStats;
+
+#[derive(Args, Debug, Clone)]
+pub struct Tiers;
+
+#[derive(Debug)]
+pub struct TiersOutput {
+    pub stats: Option<TierStats>,
+}
+
+impl fmt::Display for TiersOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(stats) = &self.stats else {
+            return write!(f, "Blob store is not tiered");
+        };
+
+        writeln!(f, "{}", "Blob storage tiers".bold())?;
+        writeln!(
+            f,
+            "  {} {} blobs, {}",
+            "hot:".dimmed(),
+            stats.hot_blobs,
+            HumanBytes(stats.hot_bytes)
+        )?;
+        writeln!(
+            f,
+            "  {} {} blobs, {}",
+            "cold:".dimmed(),
+            stats.cold_blobs,
+            HumanBytes(stats.cold_bytes)
+        )?;
+        write!(
+            f,
+            "  {} {} promoted, {} demoted since start",
+            "moves:".dimmed(),
+            stats.promotions,
+            stats.demotions
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TiersError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Tiers {
+    type Error = TiersError;
+    type Output = TiersOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let response: TiersResponse = client.call(TiersRequest {}).await?;
+        Ok(TiersOutput {
+            stats: response.stats,
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use jax_daemon::http_server::api::client::ApiError;
+use jax_daemon::http_server::api::v0::bucket::accept::{AcceptRequest, AcceptResponse};
+
+#[derive(Args, Debug, Clone)]
+pub struct Accept {
+    /// Invite ticket from `jax bucket invite`
+    pub ticket: String,
+}
+
+#[derive(Debug)]
+pub struct AcceptOutput {
+    pub bucket_id: Uuid,
+    pub role: String,
+    pub inviter: String,
+}
+
+impl fmt::Display for AcceptOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} invite to bucket {} as {}",
+            "Accepted".green().bold(),
+            self.bucket_id.bold(),
+            self.role
+        )?;
+        write!(
+            f,
+            "  {} {} adds us and starts the sync; with an owner quorum this waits for approvals",
+            "inviter:".dimmed(),
+            self.inviter
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AcceptError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Accept {
+    type Error = AcceptError;
+    type Output = AcceptOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let request = AcceptRequest {
+            ticket: self.ticket.clone(),
+        };
+        let response: AcceptResponse = client.call(request).await?;
+
+        Ok(AcceptOutput {
+            bucket_id: response.bucket_id,
+            role: response.role,
+            inviter: response.inviter,
+        })
+    }
+}
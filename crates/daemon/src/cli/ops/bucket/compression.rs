@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use common::mount::CompressionPolicy;
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::compression::{
+    CompressionRequest, CompressionResponse,
+};
+
+#[derive(Args, Debug, Clone)]
+pub struct Compression {
+    /// Bucket name or UUID
+    pub bucket: String,
+
+    /// Turn compression on
+    #[arg(long, conflicts_with = "off")]
+    pub on: bool,
+
+    /// Turn compression off
+    #[arg(long)]
+    pub off: bool,
+
+    /// zstd level (1-22, default 3)
+    #[arg(long, conflicts_with = "off")]
+    pub level: Option<i32>,
+
+    /// Per-MIME override as PATTERN=on|off|default, e.g. `text/*=off` (repeatable)
+    #[arg(long = "mime", value_name = "PATTERN=MODE", value_parser = parse_override, conflicts_with = "off")]
+    pub overrides: Vec<(String, Option<bool>)>,
+}
+
+fn parse_override(s: &str) -> Result<(String, Option<bool>), String> {
+    let (pattern, mode) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected PATTERN=MODE, got '{}'", s))?;
+    if !pattern.contains('/') {
+        return Err(format!(
+            "'{}' is not a MIME type or pattern like text/*",
+            pattern
+        ));
+    }
+    let mode = match mode {
+        "on" => Some(true),
+        "off" => Some(false),
+        "default" => None,
+        other => {
+            return Err(format!(
+                "unknown mode '{}', expected on, off or default",
+                other
+            ))
+        }
+    };
+    Ok((pattern.to_string(), mode))
+}
+
+#[derive(Debug)]
+pub struct CompressionOutput {
+    pub bucket_id: Uuid,
+    pub policy: Option<CompressionPolicy>,
+    pub changed: bool,
+}
+
+impl fmt::Display for CompressionOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changed {
+            writeln!(
+                f,
+                "{} compression for bucket {}",
+                "Updated".green().bold(),
+                self.bucket_id.bold()
+            )?;
+        }
+        match &self.policy {
+            None => write!(f, "  {} off", "compression:".dimmed()),
+            Some(policy) => {
+                write!(
+                    f,
+                    "  {} zstd level {}",
+                    "compression:".dimmed(),
+                    policy.level()
+                )?;
+                for (pattern, compress) in policy.overrides() {
+                    let mode = if *compress { "on" } else { "off" };
+                    write!(f, "\n  {} {} {}", "override:".dimmed(), pattern, mode)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CompressionError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Compression {
+    type Error = CompressionError;
+    type Output = CompressionOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let request = CompressionRequest {
+            bucket_id,
+            disable: self.off,
+            enable: self.on,
+            level: self.level,
+            overrides: self.overrides.iter().cloned().collect::<BTreeMap<_, _>>(),
+        };
+        let response: CompressionResponse = client.call(request).await?;
+
+        Ok(CompressionOutput {
+            bucket_id: response.bucket_id,
+            policy: response.policy,
+            changed: response.new_bucket_link.is_some(),
+        })
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use common::mount::ConflictStrategy as Strategy;
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::conflict_strategy::{
+    ConflictStrategyRequest, ConflictStrategyResponse,
+};
+
+#[derive(Args, Debug, Clone)]
+pub struct ConflictStrategy {
+    /// Bucket name or UUID
+    pub bucket: String,
+
+    /// New strategy: conflict-file, last-write-wins or base-wins (omit to show the current one)
+    pub strategy: Option<Strategy>,
+}
+
+#[derive(Debug)]
+pub struct ConflictStrategyOutput {
+    pub bucket_id: Uuid,
+    pub strategy: Strategy,
+    pub changed: bool,
+}
+
+impl fmt::Display for ConflictStrategyOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changed {
+            writeln!(
+                f,
+                "{} conflict strategy for bucket {}",
+                "Updated".green().bold(),
+                self.bucket_id.bold()
+            )?;
+        }
+        write!(f, "  {} {}", "conflict strategy:".dimmed(), self.strategy)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConflictStrategyError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for ConflictStrategy {
+    type Error = ConflictStrategyError;
+    type Output = ConflictStrategyOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let request = ConflictStrategyRequest {
+            bucket_id,
+            strategy: self.strategy,
+        };
+        let response: ConflictStrategyResponse = client.call(request).await?;
+
+        Ok(ConflictStrategyOutput {
+            bucket_id: response.bucket_id,
+            strategy: response.strategy,
+            changed: response.new_bucket_link.is_some(),
+        })
+    }
+}
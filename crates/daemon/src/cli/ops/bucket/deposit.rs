@@ -0,0 +1,94 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::deposit::DepositResponse;
+use reqwest::multipart;
+
+#[derive(Args, Debug, Clone)]
+pub struct Deposit {
+    /// Bucket name or UUID
+    pub bucket: String,
+
+    /// Path to file on filesystem
+    pub file: PathBuf,
+
+    /// Path inside the inbox (defaults to the file's name)
+    #[arg(long)]
+    pub path: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct DepositOutput {
+    pub path: String,
+    pub accepted_by: String,
+}
+
+impl fmt::Display for DepositOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", "Deposited".green().bold(), self.path)?;
+        write!(f, "  {} {}", "accepted by:".dimmed(), self.accepted_by)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DepositError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("HTTP error: {0}")]
+    Reqwest(#[from] reqwest::Error),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Deposit {
+    type Error = DepositError;
+    type Output = DepositOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let file_data = std::fs::read(&self.file)?;
+        let file_name = self
+            .file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unnamed".to_string());
+
+        let mut form = multipart::Form::new()
+            .text("bucket_id", bucket_id.to_string())
+            .part(
+                "file",
+                multipart::Part::bytes(file_data).file_name(file_name),
+            );
+        if let Some(path) = &self.path {
+            form = form.text("path", path.clone());
+        }
+
+        let url = client.base_url().join("/api/v0/bucket/deposit").unwrap();
+        let response = client
+            .http_client()
+            .post(url)
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await?;
+            return Err(DepositError::Api(ApiError::HttpStatus(status, body)));
+        }
+
+        let response: DepositResponse = response.json().await?;
+
+        Ok(DepositOutput {
+            path: response.path,
+            accepted_by: response.accepted_by,
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::invite::{InviteRequest, InviteResponse};
+use jax_daemon::http_server::api::v0::bucket::share::ShareRole;
+
+use super::shares::create::parse_duration;
+
+#[derive(Args, Debug, Clone)]
+pub struct Invite {
+    /// Bucket name or UUID
+    pub bucket: String,
+
+    /// Role for the invited peer (owner, mirror, reader or depositor, defaults to owner)
+    #[arg(long, default_value = "owner")]
+    pub role: ShareRole,
+
+    /// How long the ticket stays valid, e.g. 90m, 12h or 7d (defaults to 24h)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub expires_in: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct InviteOutput {
+    pub bucket_id: Uuid,
+    pub role: ShareRole,
+    pub ticket: String,
+    pub expires_at: u64,
+}
+
+impl fmt::Display for InviteOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expires = time::OffsetDateTime::from_unix_timestamp(self.expires_at as i64)
+            .ok()
+            .and_then(|t| {
+                t.format(&time::format_description::well_known::Rfc3339)
+                    .ok()
+            })
+            .unwrap_or_else(|| self.expires_at.to_string());
+        writeln!(
+            f,
+            "{} to bucket {} as {:?}, valid once until {}",
+            "Invite".green().bold(),
+            self.bucket_id.bold(),
+            self.role,
+            expires
+        )?;
+        writeln!(
+            f,
+            "  {} jax bucket accept <TICKET>",
+            "redeem with:".dimmed()
+        )?;
+        write!(f, "{}", self.ticket)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum InviteError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Invite {
+    type Error = InviteError;
+    type Output = InviteOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let request = InviteRequest {
+            bucket_id,
+            role: self.role,
+            expires_in: self.expires_in,
+        };
+        let response: InviteResponse = client.call(request).await?;
+
+        Ok(InviteOutput {
+            bucket_id: response.bucket_id,
+            role: response.role,
+            ticket: response.ticket,
+            expires_at: response.expires_at,
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::link::{LinkRequest, LinkResponse};
+
+use super::shares::create::parse_duration;
+
+#[derive(Args, Debug, Clone)]
+pub struct Link {
+    /// Bucket name or UUID
+    pub bucket: String,
+
+    /// Absolute path of the file to link
+    pub path: PathBuf,
+
+    /// How long the link works, e.g. 90m, 12h or 7d (never expires if unset)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub expires_in: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct LinkOutput {
+    pub path: PathBuf,
+    pub capability: String,
+    pub url: Option<String>,
+    pub expires_at: Option<u64>,
+}
+
+impl fmt::Display for LinkOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expires = match self.expires_at {
+            Some(expires_at) => time::OffsetDateTime::from_unix_timestamp(expires_at as i64)
+                .ok()
+                .and_then(|t| {
+                    t.format(&time::format_description::well_known::Rfc3339)
+                        .ok()
+                })
+                .unwrap_or_else(|| expires_at.to_string()),
+            None => "never".to_string(),
+        };
+        writeln!(
+            f,
+            "{} to {}, expires {}",
+            "Link".green().bold(),
+            self.path.display().bold(),
+            expires
+        )?;
+        match &self.url {
+            Some(url) => write!(f, "{}", url),
+            None => {
+                writeln!(f, "  {} <GATEWAY>/gw/cap/<CAPABILITY>", "open at:".dimmed())?;
+                write!(f, "{}", self.capability)
+            }
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LinkError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Link {
+    type Error = LinkError;
+    type Output = LinkOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let request = LinkRequest {
+            bucket_id,
+            path: self.path.clone(),
+            expires_in: self.expires_in,
+        };
+        let response: LinkResponse = client.call(request).await?;
+
+        Ok(LinkOutput {
+            path: response.path,
+            capability: response.capability,
+            url: response.url,
+            expires_at: response.expires_at,
+        })
+    }
+}
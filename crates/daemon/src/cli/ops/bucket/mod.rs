@@ -1,14 +1,25 @@
 use clap::{Args, Subcommand};
 
+pub mod accept;
 pub mod add;
 pub mod cat;
 pub mod clone;
 pub mod clone_state;
+pub mod compression;
+pub mod conflict_strategy;
 pub mod create;
+pub mod deposit;
+pub mod invite;
+pub mod link;
 pub mod list;
 pub mod ls;
+pub mod offline;
+pub mod proposals;
 pub mod publish;
+pub mod quorum;
+pub mod requests;
 pub mod shares;
+pub mod sync_mode;
 
 use crate::cli::op::Op;
 
@@ -16,11 +27,22 @@ crate::command_enum! {
     (Create, create::Create),
     (List, list::List),
     (Add, add::Add),
+    (Deposit, deposit::Deposit),
     (Ls, ls::Ls),
     (Cat, cat::Cat),
     (Shares, shares::Shares),
+    (Invite, invite::Invite),
+    (Accept, accept::Accept),
+    (Quorum, quorum::Quorum),
+    (Proposals, proposals::Proposals),
+    (Requests, requests::Requests),
     (Clone, clone::Clone),
     (Publish, publish::Publish),
+    (Link, link::Link),
+    (Compression, compression::Compression),
+    (ConflictStrategy, conflict_strategy::ConflictStrategy),
+    (SyncMode, sync_mode::SyncMode),
+    (Offline, offline::Offline),
 }
 
 // Rename the generated Command to BucketCommand for clarity
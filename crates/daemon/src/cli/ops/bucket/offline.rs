@@ -0,0 +1,103 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::offline::{OfflineRequest, OfflineResponse};
+
+#[derive(Args, Debug, Clone)]
+pub struct Offline {
+    /// Bucket name or UUID
+    pub bucket: String,
+
+    /// Path to make available offline (omit to list offline paths)
+    pub path: Option<String>,
+
+    /// Stop keeping the path available offline
+    #[arg(long, requires = "path")]
+    pub remove: bool,
+}
+
+#[derive(Debug)]
+pub struct OfflineOutput {
+    pub bucket_id: Uuid,
+    pub paths: Vec<String>,
+    pub added: Option<(String, usize)>,
+    pub removed: Option<String>,
+}
+
+impl fmt::Display for OfflineOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((path, files)) = &self.added {
+            writeln!(
+                f,
+                "{} {} available offline ({} files)",
+                "Made".green().bold(),
+                path.bold(),
+                files
+            )?;
+        }
+        if let Some(path) = &self.removed {
+            writeln!(
+                f,
+                "{} keeping {} offline",
+                "Stopped".green().bold(),
+                path.bold()
+            )?;
+        }
+
+        if self.paths.is_empty() {
+            return write!(f, "No offline paths for bucket {}", self.bucket_id);
+        }
+        write!(f, "{}", "offline paths:".dimmed())?;
+        for path in &self.paths {
+            write!(f, "\n  {}", path)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OfflineError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Offline {
+    type Error = OfflineError;
+    type Output = OfflineOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let path = self.path.as_ref().map(|path| {
+            if path.starts_with('/') {
+                path.clone()
+            } else {
+                format!("/{}", path)
+            }
+        });
+        let request = OfflineRequest {
+            bucket_id,
+            path: path.clone(),
+            remove: self.remove,
+        };
+        let response: OfflineResponse = client.call(request).await?;
+
+        let (added, removed) = match (path, response.files) {
+            (Some(path), Some(files)) => (Some((path, files)), None),
+            (Some(path), None) => (None, Some(path)),
+            (None, _) => (None, None),
+        };
+        Ok(OfflineOutput {
+            bucket_id: response.bucket_id,
+            paths: response.paths,
+            added,
+            removed,
+        })
+    }
+}
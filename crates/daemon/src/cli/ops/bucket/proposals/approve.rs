@@ -0,0 +1,69 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::approve::{ApproveRequest, ApproveResponse};
+use jax_daemon::http_server::api::v0::bucket::proposals::ProposalInfo;
+
+#[derive(Args, Debug, Clone)]
+pub struct Approve {
+    /// Bucket name or UUID
+    pub bucket: String,
+
+    /// Id of the proposal, as shown by `jax bucket proposals ls`
+    pub proposal_id: String,
+}
+
+#[derive(Debug)]
+pub struct ApproveOutput {
+    pub bucket_id: Uuid,
+    pub proposal_id: String,
+    pub new_link: Option<String>,
+    pub proposal: Option<ProposalInfo>,
+}
+
+impl fmt::Display for ApproveOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} proposal {} for bucket {}",
+            "Approved".green().bold(),
+            self.proposal_id.bold(),
+            self.bucket_id.bold()
+        )?;
+        super::write_outcome(f, self.new_link.as_deref(), self.proposal.as_ref())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApproveError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Approve {
+    type Error = ApproveError;
+    type Output = ApproveOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let request = ApproveRequest {
+            bucket_id,
+            proposal_id: self.proposal_id.clone(),
+        };
+        let response: ApproveResponse = client.call(request).await?;
+
+        Ok(ApproveOutput {
+            bucket_id: response.bucket_id,
+            proposal_id: self.proposal_id.clone(),
+            new_link: response.new_bucket_link,
+            proposal: response.proposal,
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+
+use clap::Args;
+use comfy_table::Table;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::proposals::{
+    ProposalInfo, ProposalsRequest, ProposalsResponse,
+};
+
+#[derive(Args, Debug, Clone)]
+pub struct Ls {
+    /// Bucket name or UUID
+    pub bucket: String,
+}
+
+#[derive(Debug)]
+pub struct ProposalsLsOutput {
+    pub bucket_id: Uuid,
+    pub quorum: u32,
+    pub proposals: Vec<ProposalInfo>,
+}
+
+impl fmt::Display for ProposalsLsOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.proposals.is_empty() {
+            return write!(
+                f,
+                "No pending proposals for bucket {} (quorum {})",
+                self.bucket_id.bold(),
+                self.quorum
+            );
+        }
+
+        let mut table = Table::new();
+        table.set_header(vec!["ID", "CHANGES", "APPROVALS"]);
+        for proposal in &self.proposals {
+            let mut changes: Vec<String> = proposal
+                .members
+                .iter()
+                .map(|member| {
+                    format!(
+                        "{}: {} -> {}",
+                        member.public_key,
+                        member.before.as_deref().unwrap_or("none"),
+                        member.after.as_deref().unwrap_or("none")
+                    )
+                })
+                .collect();
+            if proposal.quorum_before != proposal.quorum_after {
+                changes.push(format!(
+                    "quorum: {} -> {}",
+                    proposal.quorum_before, proposal.quorum_after
+                ));
+            }
+            table.add_row(vec![
+                proposal.id.clone(),
+                changes.join("\n"),
+                format!("{} of {}", proposal.approved_by.len(), proposal.required),
+            ]);
+        }
+        write!(f, "{table}")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProposalsLsError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Ls {
+    type Error = ProposalsLsError;
+    type Output = ProposalsLsOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let response: ProposalsResponse = client.call(ProposalsRequest { bucket_id }).await?;
+
+        Ok(ProposalsLsOutput {
+            bucket_id: response.bucket_id,
+            quorum: response.quorum,
+            proposals: response.proposals,
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+
+use clap::{Args, Subcommand};
+use owo_colors::OwoColorize;
+
+use jax_daemon::http_server::api::v0::bucket::proposals::ProposalInfo;
+
+pub mod approve;
+pub mod ls;
+
+use crate::cli::op::Op;
+
+crate::command_enum! {
+    (Ls, ls::Ls),
+    (Approve, approve::Approve),
+}
+
+// Rename the generated Command to ProposalsCommand for clarity
+pub type ProposalsCommand = Command;
+
+#[derive(Args, Debug, Clone)]
+pub struct Proposals {
+    #[command(subcommand)]
+    pub command: ProposalsCommand,
+}
+
+#[async_trait::async_trait]
+impl Op for Proposals {
+    type Error = OpError;
+    type Output = OpOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        self.command.execute(ctx).await
+    }
+}
+
+/// Write the new link of a saved membership change, or the approvals a
+/// proposed one is still waiting for
+pub fn write_outcome(
+    f: &mut fmt::Formatter<'_>,
+    new_link: Option<&str>,
+    proposal: Option<&ProposalInfo>,
+) -> fmt::Result {
+    match (new_link, proposal) {
+        (_, Some(proposal)) => write!(
+            f,
+            "  {} {} of {} owner approvals, approve with `jax bucket proposals approve <bucket> {}`",
+            "pending:".yellow(),
+            proposal.approved_by.len(),
+            proposal.required,
+            proposal.id
+        ),
+        (Some(link), None) => write!(f, "  {} {}", "link:".dimmed(), link),
+        (None, None) => Ok(()),
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::path::PathBuf;
 
 use clap::Args;
 use owo_colors::OwoColorize;
@@ -11,22 +12,40 @@ use jax_daemon::http_server::api::v0::bucket::publish::{PublishRequest, PublishR
 pub struct Publish {
     /// Bucket name or UUID
     pub bucket: String,
+
+    /// Publish only this directory, e.g. /public (repeatable)
+    #[arg(long = "path")]
+    pub paths: Vec<PathBuf>,
 }
 
 #[derive(Debug)]
 pub struct PublishOutput {
     pub bucket_id: Uuid,
+    pub published: bool,
+    pub published_paths: Vec<PathBuf>,
     pub new_link: String,
 }
 
 impl fmt::Display for PublishOutput {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(
-            f,
-            "{} bucket {}",
-            "Published".green().bold(),
-            self.bucket_id.bold()
-        )?;
+        if self.published {
+            writeln!(
+                f,
+                "{} bucket {}",
+                "Published".green().bold(),
+                self.bucket_id.bold()
+            )?;
+        } else {
+            writeln!(
+                f,
+                "{} in bucket {}",
+                "Published".green().bold(),
+                self.bucket_id.bold()
+            )?;
+            for path in &self.published_paths {
+                writeln!(f, "  {}", path.display())?;
+            }
+        }
         write!(f, "  {} {}", "link:".dimmed(), self.new_link)
     }
 }
@@ -46,11 +65,16 @@ impl crate::cli::op::Op for Publish {
         let mut client = ctx.client.clone();
         let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
 
-        let request = PublishRequest { bucket_id };
+        let request = PublishRequest {
+            bucket_id,
+            paths: self.paths.clone(),
+        };
         let response: PublishResponse = client.call(request).await?;
 
         Ok(PublishOutput {
             bucket_id: response.bucket_id,
+            published: response.published,
+            published_paths: response.published_paths,
             new_link: response.new_bucket_link,
         })
     }
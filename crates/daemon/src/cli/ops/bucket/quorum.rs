@@ -0,0 +1,80 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::proposals::ProposalInfo;
+use jax_daemon::http_server::api::v0::bucket::quorum::{QuorumRequest, QuorumResponse};
+
+#[derive(Args, Debug, Clone)]
+pub struct Quorum {
+    /// Bucket name or UUID
+    pub bucket: String,
+
+    /// Owners that must approve changes to the shares (omit to show the current quorum)
+    pub quorum: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct QuorumOutput {
+    pub bucket_id: Uuid,
+    pub quorum: u32,
+    pub new_link: Option<String>,
+    pub proposal: Option<ProposalInfo>,
+}
+
+impl fmt::Display for QuorumOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(proposal) = &self.proposal {
+            writeln!(
+                f,
+                "{} quorum {} for bucket {}",
+                "Proposed".yellow().bold(),
+                proposal.quorum_after,
+                self.bucket_id.bold()
+            )?;
+            return super::proposals::write_outcome(f, None, Some(proposal));
+        }
+        if self.new_link.is_some() {
+            writeln!(
+                f,
+                "{} quorum for bucket {}",
+                "Updated".green().bold(),
+                self.bucket_id.bold()
+            )?;
+        }
+        write!(f, "  {} {}", "quorum:".dimmed(), self.quorum)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum QuorumError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Quorum {
+    type Error = QuorumError;
+    type Output = QuorumOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let request = QuorumRequest {
+            bucket_id,
+            quorum: self.quorum,
+        };
+        let response: QuorumResponse = client.call(request).await?;
+
+        Ok(QuorumOutput {
+            bucket_id: response.bucket_id,
+            quorum: response.quorum,
+            new_link: response.new_bucket_link,
+            proposal: response.proposal,
+        })
+    }
+}
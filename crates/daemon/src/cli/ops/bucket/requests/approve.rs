@@ -0,0 +1,62 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use jax_daemon::http_server::api::client::ApiError;
+use jax_daemon::http_server::api::v0::access_requests::approve::ApproveAccessResponse;
+use jax_daemon::http_server::api::v0::access_requests::ApproveAccessRequest;
+use jax_daemon::http_server::api::v0::bucket::proposals::ProposalInfo;
+
+#[derive(Args, Debug, Clone)]
+pub struct Approve {
+    /// Id of the request, as shown by `jax bucket requests ls`
+    pub id: i64,
+}
+
+#[derive(Debug)]
+pub struct ApproveOutput {
+    pub id: i64,
+    pub bucket_id: Uuid,
+    pub new_link: Option<String>,
+    pub proposal: Option<ProposalInfo>,
+}
+
+impl fmt::Display for ApproveOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} access request {} for bucket {}",
+            "Approved".green().bold(),
+            self.id.bold(),
+            self.bucket_id.bold()
+        )?;
+        super::super::proposals::write_outcome(f, self.new_link.as_deref(), self.proposal.as_ref())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApproveError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Approve {
+    type Error = ApproveError;
+    type Output = ApproveOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let response: ApproveAccessResponse =
+            client.call(ApproveAccessRequest { id: self.id }).await?;
+
+        Ok(ApproveOutput {
+            id: response.id,
+            bucket_id: response.bucket_id,
+            new_link: response.new_bucket_link,
+            proposal: response.proposal,
+        })
+    }
+}
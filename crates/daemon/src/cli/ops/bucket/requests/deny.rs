@@ -0,0 +1,61 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+
+use jax_daemon::http_server::api::client::ApiError;
+use jax_daemon::http_server::api::v0::access_requests::deny::DenyAccessResponse;
+use jax_daemon::http_server::api::v0::access_requests::DenyAccessRequest;
+
+#[derive(Args, Debug, Clone)]
+pub struct Deny {
+    /// Id of the request, as shown by `jax bucket requests ls`
+    pub id: i64,
+}
+
+#[derive(Debug)]
+pub struct DenyOutput {
+    pub id: i64,
+    pub denied: bool,
+}
+
+impl fmt::Display for DenyOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denied {
+            write!(
+                f,
+                "{} access request {}",
+                "Denied".red().bold(),
+                self.id.bold()
+            )
+        } else {
+            write!(
+                f,
+                "No pending access request {}, it may already be settled",
+                self.id.bold()
+            )
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DenyError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Deny {
+    type Error = DenyError;
+    type Output = DenyOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let response: DenyAccessResponse = client.call(DenyAccessRequest { id: self.id }).await?;
+
+        Ok(DenyOutput {
+            id: response.id,
+            denied: response.denied,
+        })
+    }
+}
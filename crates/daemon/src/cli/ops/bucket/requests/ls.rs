@@ -0,0 +1,76 @@
+use std::fmt;
+
+use clap::Args;
+use comfy_table::Table;
+
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::access_requests::list::AccessRequestsResponse;
+use jax_daemon::http_server::api::v0::access_requests::{AccessRequestInfo, AccessRequestsRequest};
+use jax_daemon::AccessRequestStatus;
+
+#[derive(Args, Debug, Clone)]
+pub struct Ls {
+    /// Only show requests for this bucket (name or UUID)
+    pub bucket: Option<String>,
+
+    /// Include approved and denied requests
+    #[arg(long)]
+    pub all: bool,
+}
+
+#[derive(Debug)]
+pub struct RequestsLsOutput {
+    pub requests: Vec<AccessRequestInfo>,
+}
+
+impl fmt::Display for RequestsLsOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.requests.is_empty() {
+            return write!(f, "No access requests");
+        }
+
+        let mut table = Table::new();
+        table.set_header(vec!["ID", "BUCKET", "REQUESTER", "ROLE", "STATUS", "NOTE"]);
+        for request in &self.requests {
+            table.add_row(vec![
+                request.id.to_string(),
+                request.bucket_id.to_string(),
+                request.requester.clone(),
+                request.role.clone(),
+                request.status.to_string(),
+                request.note.clone().unwrap_or_default(),
+            ]);
+        }
+        write!(f, "{table}")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RequestsLsError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Ls {
+    type Error = RequestsLsError;
+    type Output = RequestsLsOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = match &self.bucket {
+            Some(bucket) => Some(resolve_bucket(&mut client, bucket).await?),
+            None => None,
+        };
+
+        let request = AccessRequestsRequest {
+            bucket_id,
+            status: (!self.all).then_some(AccessRequestStatus::Pending),
+        };
+        let response: AccessRequestsResponse = client.call(request).await?;
+
+        Ok(RequestsLsOutput {
+            requests: response.requests,
+        })
+    }
+}
@@ -0,0 +1,34 @@
+use clap::{Args, Subcommand};
+
+pub mod approve;
+pub mod deny;
+pub mod ls;
+pub mod send;
+
+use crate::cli::op::Op;
+
+crate::command_enum! {
+    (Ls, ls::Ls),
+    (Approve, approve::Approve),
+    (Deny, deny::Deny),
+    (Send, send::Send),
+}
+
+// Rename the generated Command to RequestsCommand for clarity
+pub type RequestsCommand = Command;
+
+#[derive(Args, Debug, Clone)]
+pub struct Requests {
+    #[command(subcommand)]
+    pub command: RequestsCommand,
+}
+
+#[async_trait::async_trait]
+impl Op for Requests {
+    type Error = OpError;
+    type Output = OpOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        self.command.execute(ctx).await
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use jax_daemon::http_server::api::client::ApiError;
+use jax_daemon::http_server::api::v0::access_requests::send::SendAccessResponse;
+use jax_daemon::http_server::api::v0::access_requests::SendAccessRequest;
+use jax_daemon::http_server::api::v0::bucket::share::ShareRole;
+
+#[derive(Args, Debug, Clone)]
+pub struct Send {
+    /// UUID of the bucket to ask for
+    pub bucket_id: Uuid,
+
+    /// Public key of an owner of the bucket (hex-encoded)
+    #[arg(long)]
+    pub peer_public_key: String,
+
+    /// Role to ask for (owner, mirror, reader or depositor, defaults to reader)
+    #[arg(long, default_value = "reader")]
+    pub role: ShareRole,
+
+    /// Message for the owners
+    #[arg(long)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SendOutput {
+    pub bucket_id: Uuid,
+    pub status: String,
+}
+
+impl fmt::Display for SendOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.status.as_str() {
+            "already_shared" => write!(
+                f,
+                "Already a member of bucket {}, nothing to request",
+                self.bucket_id.bold()
+            ),
+            _ => write!(
+                f,
+                "{} access to bucket {}, you will be synced once an owner approves",
+                "Requested".green().bold(),
+                self.bucket_id.bold()
+            ),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SendError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Send {
+    type Error = SendError;
+    type Output = SendOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let request = SendAccessRequest {
+            bucket_id: self.bucket_id,
+            peer_public_key: self.peer_public_key.clone(),
+            role: self.role,
+            note: self.note.clone(),
+        };
+        let response: SendAccessResponse = client.call(request).await?;
+
+        Ok(SendOutput {
+            bucket_id: response.bucket_id,
+            status: response.status,
+        })
+    }
+}
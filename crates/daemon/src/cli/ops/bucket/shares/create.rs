@@ -1,10 +1,12 @@
 use std::fmt;
+use std::path::PathBuf;
 
 use clap::Args;
 use owo_colors::OwoColorize;
 use uuid::Uuid;
 
 use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::proposals::ProposalInfo;
 use jax_daemon::http_server::api::v0::bucket::share::{ShareRequest, ShareResponse, ShareRole};
 
 #[derive(Args, Debug, Clone)]
@@ -16,16 +18,50 @@ pub struct Create {
     #[arg(long)]
     pub peer_public_key: String,
 
-    /// Role for the peer (owner or mirror, defaults to owner)
+    /// Role for the peer (owner, mirror, reader or depositor, defaults to owner)
     #[arg(long, default_value = "owner")]
     pub role: ShareRole,
+
+    /// Restrict an owner to writing under this absolute path (repeatable)
+    #[arg(long = "scope", value_name = "PATH")]
+    pub write_scope: Vec<PathBuf>,
+
+    /// End access after this long, e.g. 90m, 12h, 30d or 2w
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub expires_in: Option<u64>,
+}
+
+/// Parse a duration like `30d` into seconds
+pub fn parse_duration(s: &str) -> Result<u64, String> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (amount, unit) = s.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("expected a number followed by a unit, got '{}'", s))?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        other => {
+            return Err(format!(
+                "unknown unit '{}', expected s, m, h, d or w",
+                other
+            ))
+        }
+    };
+    amount
+        .checked_mul(unit_secs)
+        .ok_or_else(|| format!("duration '{}' is too long", s))
 }
 
 #[derive(Debug)]
 pub struct ShareCreateOutput {
     pub bucket_id: Uuid,
     pub peer_key: String,
-    pub new_link: String,
+    pub new_link: Option<String>,
+    pub proposal: Option<ProposalInfo>,
 }
 
 impl fmt::Display for ShareCreateOutput {
@@ -33,11 +69,15 @@ impl fmt::Display for ShareCreateOutput {
         writeln!(
             f,
             "{} bucket {} with {}",
-            "Shared".green().bold(),
+            if self.proposal.is_some() {
+                "Proposed".yellow().bold().to_string()
+            } else {
+                "Shared".green().bold().to_string()
+            },
             self.bucket_id.bold(),
             self.peer_key.bold()
         )?;
-        write!(f, "  {} {}", "link:".dimmed(), self.new_link)
+        super::super::proposals::write_outcome(f, self.new_link.as_deref(), self.proposal.as_ref())
     }
 }
 
@@ -60,6 +100,10 @@ impl crate::cli::op::Op for Create {
             bucket_id,
             peer_public_key: self.peer_public_key.clone(),
             role: self.role,
+            write_scope: self.write_scope.clone(),
+            expires_at: self
+                .expires_in
+                .map(|secs| time::OffsetDateTime::now_utc().unix_timestamp() as u64 + secs),
         };
         let response: ShareResponse = client.call(request).await?;
 
@@ -67,6 +111,7 @@ impl crate::cli::op::Op for Create {
             bucket_id: response.bucket_id,
             peer_key: response.peer_public_key,
             new_link: response.new_bucket_link,
+            proposal: response.proposal,
         })
     }
 }
@@ -0,0 +1,113 @@
+use std::fmt;
+
+use clap::Args;
+use comfy_table::Table;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::membership_history::{
+    MembershipEventInfo, MembershipHistoryRequest, MembershipHistoryResponse,
+};
+
+#[derive(Args, Debug, Clone)]
+pub struct History {
+    /// Bucket name or UUID
+    pub bucket: String,
+
+    /// Check the signature on every version along the way
+    #[arg(long)]
+    pub verify: bool,
+}
+
+#[derive(Debug)]
+pub struct SharesHistoryOutput {
+    pub bucket_id: Uuid,
+    pub verify: bool,
+    pub events: Vec<MembershipEventInfo>,
+}
+
+/// Shorten a hex key or hash for display
+fn short(hex: &str) -> String {
+    hex.chars().take(12).collect()
+}
+
+impl fmt::Display for SharesHistoryOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.events.is_empty() {
+            return write!(
+                f,
+                "No membership history for bucket {}",
+                self.bucket_id.bold()
+            );
+        }
+
+        let mut table = Table::new();
+        let mut header = vec!["HEIGHT", "KEY", "CHANGE", "ROLE", "AUTHOR", "VERSION"];
+        if self.verify {
+            header.push("VERIFIED");
+        }
+        table.set_header(header);
+        for event in &self.events {
+            let role = match &event.previous_role {
+                Some(previous) if *previous != event.role => {
+                    format!("{} -> {}", previous, event.role)
+                }
+                _ => event.role.clone(),
+            };
+            let mut author = event
+                .author
+                .as_deref()
+                .map(short)
+                .unwrap_or_else(|| "-".to_string());
+            if !event.approved_by.is_empty() {
+                author = format!("{} (+{} approvals)", author, event.approved_by.len());
+            }
+            let mut row = vec![
+                event.height.to_string(),
+                event.principal.clone(),
+                event.change.clone(),
+                role,
+                author,
+                short(&event.link_hash),
+            ];
+            if self.verify {
+                row.push(match event.verified {
+                    Some(true) => "yes".green().to_string(),
+                    _ => "no".red().to_string(),
+                });
+            }
+            table.add_row(row);
+        }
+        write!(f, "{table}")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SharesHistoryError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for History {
+    type Error = SharesHistoryError;
+    type Output = SharesHistoryOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let request = MembershipHistoryRequest {
+            bucket_id,
+            verify: self.verify,
+        };
+        let response: MembershipHistoryResponse = client.call(request).await?;
+
+        Ok(SharesHistoryOutput {
+            bucket_id: response.bucket_id,
+            verify: self.verify,
+            events: response.events,
+        })
+    }
+}
@@ -27,14 +27,48 @@ impl fmt::Display for SharesLsOutput {
         }
 
         let mut table = Table::new();
-        table.set_header(vec!["KEY", "ROLE", ""]);
+        let now = time::OffsetDateTime::now_utc().unix_timestamp();
+        table.set_header(vec!["KEY", "ROLE", "SCOPE", "EXPIRES", ""]);
         for share in &self.shares {
             let marker = if share.is_self {
                 "(you)".dimmed().to_string()
             } else {
                 String::new()
             };
-            table.add_row(vec![share.public_key.clone(), share.role.clone(), marker]);
+            let scope = if share.write_scope.is_empty() {
+                "-".to_string()
+            } else {
+                share
+                    .write_scope
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let expires = match share.expires_at {
+                None => "-".to_string(),
+                Some(expires_at) => {
+                    let formatted = time::OffsetDateTime::from_unix_timestamp(expires_at as i64)
+                        .ok()
+                        .and_then(|t| {
+                            t.format(&time::format_description::well_known::Rfc3339)
+                                .ok()
+                        })
+                        .unwrap_or_else(|| expires_at.to_string());
+                    if expires_at as i64 <= now {
+                        format!("{} {}", formatted, "(expired)".red())
+                    } else {
+                        formatted
+                    }
+                }
+            };
+            table.add_row(vec![
+                share.public_key.clone(),
+                share.role.clone(),
+                scope,
+                expires,
+                marker,
+            ]);
         }
         write!(f, "{table}")
     }
@@ -1,6 +1,7 @@
 use clap::{Args, Subcommand};
 
 pub mod create;
+pub mod history;
 pub mod ls;
 pub mod remove;
 
@@ -8,6 +9,7 @@ use crate::cli::op::Op;
 
 crate::command_enum! {
     (Create, create::Create),
+    (History, history::History),
     (Ls, ls::Ls),
     (Remove, remove::Remove),
 }
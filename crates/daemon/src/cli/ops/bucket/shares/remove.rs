@@ -5,6 +5,7 @@ use owo_colors::OwoColorize;
 use uuid::Uuid;
 
 use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::proposals::ProposalInfo;
 use jax_daemon::http_server::api::v0::bucket::unshare::{UnshareRequest, UnshareResponse};
 
 #[derive(Args, Debug, Clone)]
@@ -21,7 +22,8 @@ pub struct Remove {
 pub struct ShareRemoveOutput {
     pub bucket_id: Uuid,
     pub peer_key: String,
-    pub new_link: String,
+    pub new_link: Option<String>,
+    pub proposal: Option<ProposalInfo>,
 }
 
 impl fmt::Display for ShareRemoveOutput {
@@ -29,11 +31,15 @@ impl fmt::Display for ShareRemoveOutput {
         writeln!(
             f,
             "{} peer {} from bucket {}",
-            "Removed".green().bold(),
+            if self.proposal.is_some() {
+                "Proposed".yellow().bold().to_string()
+            } else {
+                "Removed".green().bold().to_string()
+            },
             self.peer_key.bold(),
             self.bucket_id.bold()
         )?;
-        write!(f, "  {} {}", "link:".dimmed(), self.new_link)
+        super::super::proposals::write_outcome(f, self.new_link.as_deref(), self.proposal.as_ref())
     }
 }
 
@@ -62,6 +68,7 @@ impl crate::cli::op::Op for Remove {
             bucket_id: response.bucket_id,
             peer_key: response.peer_public_key,
             new_link: response.new_bucket_link,
+            proposal: response.proposal,
         })
     }
 }
@@ -0,0 +1,68 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+use uuid::Uuid;
+
+use common::bucket_log::SyncMode as Mode;
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiError};
+use jax_daemon::http_server::api::v0::bucket::sync_mode::{SyncModeRequest, SyncModeResponse};
+
+#[derive(Args, Debug, Clone)]
+pub struct SyncMode {
+    /// Bucket name or UUID
+    pub bucket: String,
+
+    /// New mode: full or lazy (omit to show the current one)
+    pub mode: Option<Mode>,
+}
+
+#[derive(Debug)]
+pub struct SyncModeOutput {
+    pub bucket_id: Uuid,
+    pub mode: Mode,
+    pub changed: bool,
+}
+
+impl fmt::Display for SyncModeOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changed {
+            writeln!(
+                f,
+                "{} sync mode for bucket {}",
+                "Updated".green().bold(),
+                self.bucket_id.bold()
+            )?;
+        }
+        write!(f, "  {} {}", "sync mode:".dimmed(), self.mode)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SyncModeError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for SyncMode {
+    type Error = SyncModeError;
+    type Output = SyncModeOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = resolve_bucket(&mut client, &self.bucket).await?;
+
+        let request = SyncModeRequest {
+            bucket_id,
+            mode: self.mode,
+        };
+        let response: SyncModeResponse = client.call(request).await?;
+
+        Ok(SyncModeOutput {
+            bucket_id: response.bucket_id,
+            mode: response.mode,
+            changed: response.changed,
+        })
+    }
+}
@@ -58,9 +58,11 @@ impl crate::cli::op::Op for Daemon {
         let config = ServiceConfig {
             node_listen_addr,
             node_secret: Some(secret_key),
+            gossip: state.config.gossip,
             blob_store: state.config.blob_store.clone(),
             jax_dir: state.jax_dir.clone(),
             max_import_size: state.config.max_import_size,
+            download_parallelism: state.config.download_parallelism,
             api_port,
             gateway_port,
             sqlite_path: Some(state.db_path),
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use clap::{Args, ValueEnum};
 use owo_colors::OwoColorize;
 
-use jax_daemon::state::{AppConfig, AppState, BlobStoreConfig};
+use jax_daemon::state::{AppConfig, AppState, BlobCacheConfig, BlobStoreConfig, TierPolicyConfig};
 
 /// Blob store backend type for CLI selection
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -16,6 +16,8 @@ pub enum BlobStoreType {
     Filesystem,
     /// S3-compatible object storage
     S3,
+    /// Local filesystem with cold blobs offloaded to S3
+    Tiered,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -36,13 +38,18 @@ pub struct Init {
     #[arg(long, value_enum, default_value_t = BlobStoreType::Legacy)]
     pub blob_store: BlobStoreType,
 
-    /// S3/MinIO URL (required for --blob-store s3)
+    /// S3/MinIO URL (required for --blob-store s3 and tiered)
     /// Format: s3://access_key:secret_key@host:port/bucket
     /// Example: s3://minioadmin:minioadmin@localhost:9000/jax-blobs
     #[arg(long)]
     pub s3_url: Option<String>,
 
-    /// Filesystem blob store path (required for --blob-store filesystem)
+    /// Enable a local disk cache in front of S3 with this size limit (bytes)
+    /// Only used with --blob-store s3
+    #[arg(long)]
+    pub s3_cache_size: Option<u64>,
+
+    /// Filesystem blob store path (used by --blob-store filesystem and tiered)
     /// Must be an absolute path
     #[arg(long)]
     pub blobs_path: Option<PathBuf>,
@@ -104,23 +111,10 @@ impl Init {
         match self.blob_store {
             BlobStoreType::Legacy => Ok(BlobStoreConfig::Legacy),
 
-            BlobStoreType::Filesystem => {
-                let path = match &self.blobs_path {
-                    Some(p) => {
-                        if !p.is_absolute() {
-                            return Err(InitError::InvalidPath(
-                                "--blobs-path must be an absolute path".to_string(),
-                            ));
-                        }
-                        p.clone()
-                    }
-                    None => jax_dir.join("blobs-store"),
-                };
-                Ok(BlobStoreConfig::Filesystem {
-                    path,
-                    db_path: None,
-                })
-            }
+            BlobStoreType::Filesystem => Ok(BlobStoreConfig::Filesystem {
+                path: self.local_blobs_path(jax_dir)?,
+                db_path: None,
+            }),
 
             BlobStoreType::S3 => {
                 let url = self.s3_url.clone().ok_or_else(|| {
@@ -129,10 +123,39 @@ impl Init {
 
                 BlobStoreConfig::parse_s3_url(&url)?;
 
-                Ok(BlobStoreConfig::S3 { url })
+                Ok(BlobStoreConfig::S3 {
+                    url,
+                    cache: self.s3_cache_size.map(BlobCacheConfig::with_max_size),
+                })
+            }
+
+            BlobStoreType::Tiered => {
+                let cold_url = self.s3_url.clone().ok_or_else(|| {
+                    InitError::MissingConfig("--s3-url required for tiered backend".to_string())
+                })?;
+
+                BlobStoreConfig::parse_s3_url(&cold_url)?;
+
+                Ok(BlobStoreConfig::Tiered {
+                    path: self.local_blobs_path(jax_dir)?,
+                    db_path: None,
+                    cold_url,
+                    policy: TierPolicyConfig::default(),
+                })
             }
         }
     }
+
+    /// Local object storage path from --blobs-path, or a default under jax_dir.
+    fn local_blobs_path(&self, jax_dir: &std::path::Path) -> Result<PathBuf, InitError> {
+        match &self.blobs_path {
+            Some(p) if !p.is_absolute() => Err(InitError::InvalidPath(
+                "--blobs-path must be an absolute path".to_string(),
+            )),
+            Some(p) => Ok(p.clone()),
+            None => Ok(jax_dir.join("blobs-store")),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -163,9 +186,16 @@ impl crate::cli::op::Op for Init {
                 };
                 format!("filesystem ({}{})", path.display(), db_info)
             }
-            BlobStoreConfig::S3 { url } => {
+            BlobStoreConfig::S3 { url, cache } => {
                 // Mask credentials in output
-                format!("s3 ({})", mask_s3_url(url))
+                let cache_info = match cache {
+                    Some(c) => format!(", cache: {} bytes", c.max_size),
+                    None => String::new(),
+                };
+                format!("s3 ({}{})", mask_s3_url(url), cache_info)
+            }
+            BlobStoreConfig::Tiered { path, cold_url, .. } => {
+                format!("tiered ({} -> {})", path.display(), mask_s3_url(cold_url))
             }
         };
 
@@ -1,12 +1,15 @@
+pub mod blobs;
 pub mod bucket;
 pub mod daemon;
 pub mod health;
 pub mod init;
 #[cfg(feature = "fuse")]
 pub mod mount;
+pub mod sync;
 pub mod update;
 pub mod version;
 
+pub use blobs::Blobs;
 pub use bucket::Bucket;
 pub use daemon::Daemon;
 pub use health::Health;
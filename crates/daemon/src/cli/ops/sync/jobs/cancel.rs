@@ -0,0 +1,56 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+
+use jax_daemon::http_server::api::client::ApiError;
+use jax_daemon::http_server::api::v0::sync::cancel::{CancelJobRequest, CancelJobResponse};
+
+#[derive(Args, Debug, Clone)]
+pub struct Cancel {
+    /// ID of the job to stop retrying
+    pub id: i64,
+}
+
+#[derive(Debug)]
+pub struct CancelOutput {
+    pub id: i64,
+    pub cancelled: bool,
+}
+
+impl fmt::Display for CancelOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.cancelled {
+            write!(f, "{} sync job {}", "Cancelled".green().bold(), self.id)
+        } else {
+            write!(
+                f,
+                "{} sync job {} not found or already cancelled",
+                "Skipped".yellow().bold(),
+                self.id
+            )
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CancelError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Cancel {
+    type Error = CancelError;
+    type Output = CancelOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let response: CancelJobResponse = client.call(CancelJobRequest { id: self.id }).await?;
+
+        Ok(CancelOutput {
+            id: response.id,
+            cancelled: response.cancelled,
+        })
+    }
+}
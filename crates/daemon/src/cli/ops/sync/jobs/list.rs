@@ -0,0 +1,85 @@
+use std::fmt;
+
+use clap::Args;
+use comfy_table::Table;
+
+use jax_daemon::http_server::api::client::ApiError;
+use jax_daemon::http_server::api::v0::sync::jobs::{JobsRequest, JobsResponse, SyncJobInfo};
+use jax_daemon::SyncJobStatus;
+
+#[derive(Args, Debug, Clone)]
+pub struct List {
+    /// Only list jobs with this status (pending, running, failed, cancelled)
+    #[arg(long)]
+    pub status: Option<SyncJobStatus>,
+}
+
+#[derive(Debug)]
+pub struct ListOutput {
+    pub jobs: Vec<SyncJobInfo>,
+}
+
+impl fmt::Display for ListOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.jobs.is_empty() {
+            return write!(f, "No sync jobs found");
+        }
+
+        let mut table = Table::new();
+        table.set_header(vec![
+            "ID",
+            "KIND",
+            "BUCKET",
+            "TARGET",
+            "STATUS",
+            "ATTEMPTS",
+            "NEXT RUN",
+            "LAST ERROR",
+        ]);
+        for job in &self.jobs {
+            let target = match job.target_height {
+                Some(height) => format!("{} @ {}", job.target_link.hash(), height),
+                None => job.target_link.hash().to_string(),
+            };
+            let next_run = match job.status {
+                SyncJobStatus::Pending => job.next_retry_at.to_string(),
+                _ => "-".to_string(),
+            };
+            table.add_row(vec![
+                job.id.to_string(),
+                job.kind.to_string(),
+                job.bucket_id.to_string(),
+                target,
+                job.status.to_string(),
+                format!("{}/{}", job.attempts, job.max_attempts),
+                next_run,
+                job.last_error.clone().unwrap_or_default(),
+            ]);
+        }
+        write!(f, "{table}")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for List {
+    type Error = ListError;
+    type Output = ListOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let request = JobsRequest {
+            status: self.status,
+        };
+        let response: JobsResponse = client.call(request).await?;
+
+        Ok(ListOutput {
+            jobs: response.jobs,
+        })
+    }
+}
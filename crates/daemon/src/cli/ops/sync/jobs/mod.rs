@@ -0,0 +1,33 @@
+use clap::{Args, Subcommand};
+
+pub mod cancel;
+pub mod list;
+pub mod retry;
+
+use crate::cli::op::Op;
+
+crate::command_enum! {
+    (Cancel, cancel::Cancel),
+    (List, list::List),
+    (Retry, retry::Retry),
+}
+
+// Rename the generated Command to JobsCommand for clarity
+pub type JobsCommand = Command;
+
+/// Inspect and manage durable sync jobs
+#[derive(Args, Debug, Clone)]
+pub struct Jobs {
+    #[command(subcommand)]
+    pub command: JobsCommand,
+}
+
+#[async_trait::async_trait]
+impl Op for Jobs {
+    type Error = OpError;
+    type Output = OpOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        self.command.execute(ctx).await
+    }
+}
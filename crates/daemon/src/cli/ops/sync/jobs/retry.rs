@@ -0,0 +1,61 @@
+use std::fmt;
+
+use clap::Args;
+use owo_colors::OwoColorize;
+
+use jax_daemon::http_server::api::client::ApiError;
+use jax_daemon::http_server::api::v0::sync::retry::{RetryJobRequest, RetryJobResponse};
+
+#[derive(Args, Debug, Clone)]
+pub struct Retry {
+    /// ID of the failed or cancelled job to run again
+    pub id: i64,
+}
+
+#[derive(Debug)]
+pub struct RetryOutput {
+    pub id: i64,
+    pub retried: bool,
+}
+
+impl fmt::Display for RetryOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.retried {
+            write!(
+                f,
+                "{} sync job {} to run now",
+                "Queued".green().bold(),
+                self.id
+            )
+        } else {
+            write!(
+                f,
+                "{} sync job {} not found or still running",
+                "Skipped".yellow().bold(),
+                self.id
+            )
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RetryError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Retry {
+    type Error = RetryError;
+    type Output = RetryOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let response: RetryJobResponse = client.call(RetryJobRequest { id: self.id }).await?;
+
+        Ok(RetryOutput {
+            id: response.id,
+            retried: response.retried,
+        })
+    }
+}
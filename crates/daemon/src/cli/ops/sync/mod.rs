@@ -0,0 +1,30 @@
+use clap::{Args, Subcommand};
+
+pub mod jobs;
+pub mod status;
+
+use crate::cli::op::Op;
+
+crate::command_enum! {
+    (Jobs, jobs::Jobs),
+    (Status, status::Status),
+}
+
+// Rename the generated Command to SyncCommand for clarity
+pub type SyncCommand = Command;
+
+#[derive(Args, Debug, Clone)]
+pub struct Sync {
+    #[command(subcommand)]
+    pub command: SyncCommand,
+}
+
+#[async_trait::async_trait]
+impl Op for Sync {
+    type Error = OpError;
+    type Output = OpOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        self.command.execute(ctx).await
+    }
+}
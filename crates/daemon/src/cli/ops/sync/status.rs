@@ -0,0 +1,159 @@
+use std::fmt;
+use std::time::Duration;
+
+use clap::Args;
+use comfy_table::Table;
+use indicatif::{HumanBytes, ProgressBar, ProgressStyle};
+use uuid::Uuid;
+
+use jax_daemon::http_server::api::client::{resolve_bucket, ApiClient, ApiError};
+use jax_daemon::http_server::api::v0::bucket::sync_status::{
+    BucketSyncInfo, SyncStatusRequest, SyncStatusResponse,
+};
+use jax_daemon::BucketSyncStatus;
+
+/// How often `--watch` polls the daemon
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Args, Debug, Clone)]
+pub struct Status {
+    /// Bucket name or ID; all buckets that have synced otherwise
+    pub bucket: Option<String>,
+
+    /// Follow the bucket's sync with a progress bar until it finishes
+    #[arg(long, requires = "bucket")]
+    pub watch: bool,
+}
+
+#[derive(Debug)]
+pub struct StatusOutput {
+    pub buckets: Vec<BucketSyncInfo>,
+}
+
+impl fmt::Display for StatusOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.buckets.is_empty() {
+            return write!(f, "No sync status recorded");
+        }
+
+        let mut table = Table::new();
+        table.set_header(vec![
+            "BUCKET",
+            "STATUS",
+            "TARGET",
+            "MANIFESTS",
+            "BLOBS",
+            "DOWNLOADED",
+            "PEER",
+            "LAST ERROR",
+        ]);
+        for bucket in &self.buckets {
+            let target = match (&bucket.target_link, bucket.target_height) {
+                (Some(link), Some(height)) => format!("{} @ {}", link.hash(), height),
+                (Some(link), None) => link.hash().to_string(),
+                _ => "-".to_string(),
+            };
+            table.add_row(vec![
+                bucket.bucket_id.to_string(),
+                bucket.status.to_string(),
+                target,
+                bucket.manifests_fetched.to_string(),
+                format!("{}/{}", bucket.blobs_done, bucket.blobs_total),
+                HumanBytes(bucket.bytes_downloaded).to_string(),
+                bucket
+                    .current_peer
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                bucket.last_error.clone().unwrap_or_default(),
+            ]);
+        }
+        write!(f, "{table}")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StatusError {
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+}
+
+#[async_trait::async_trait]
+impl crate::cli::op::Op for Status {
+    type Error = StatusError;
+    type Output = StatusOutput;
+
+    async fn execute(&self, ctx: &crate::cli::op::OpContext) -> Result<Self::Output, Self::Error> {
+        let mut client = ctx.client.clone();
+        let bucket_id = match &self.bucket {
+            Some(bucket) => Some(resolve_bucket(&mut client, bucket).await?),
+            None => None,
+        };
+
+        let buckets = match bucket_id {
+            Some(bucket_id) if self.watch => watch(ctx, &mut client, bucket_id).await?,
+            _ => fetch(&mut client, bucket_id).await?,
+        };
+
+        Ok(StatusOutput { buckets })
+    }
+}
+
+async fn fetch(
+    client: &mut ApiClient,
+    bucket_id: Option<Uuid>,
+) -> Result<Vec<BucketSyncInfo>, ApiError> {
+    let response: SyncStatusResponse = client.call(SyncStatusRequest { bucket_id }).await?;
+    Ok(response.buckets)
+}
+
+/// Poll a bucket's sync status, showing progress until it is synced or failed
+async fn watch(
+    ctx: &crate::cli::op::OpContext,
+    client: &mut ApiClient,
+    bucket_id: Uuid,
+) -> Result<Vec<BucketSyncInfo>, ApiError> {
+    let bar = ctx.progress.add(ProgressBar::new(0));
+    bar.set_style(
+        ProgressStyle::with_template("{spinner} {msg} [{bar:40}] {pos}/{len} blobs")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+    bar.enable_steady_tick(Duration::from_millis(100));
+
+    let buckets = loop {
+        let buckets = fetch(client, Some(bucket_id)).await?;
+        if let Some(state) = buckets.first() {
+            bar.set_length(state.blobs_total);
+            bar.set_position(state.blobs_done);
+            bar.set_message(progress_message(state));
+            if state.is_finished() {
+                break buckets;
+            }
+        } else {
+            bar.set_message("waiting for sync");
+        }
+        tokio::time::sleep(WATCH_INTERVAL).await;
+    };
+    bar.finish_and_clear();
+
+    Ok(buckets)
+}
+
+fn progress_message(state: &BucketSyncInfo) -> String {
+    let peer = state
+        .current_peer
+        .as_deref()
+        .map(|peer| format!(" from {}", &peer[..peer.len().min(8)]))
+        .unwrap_or_default();
+    match state.status {
+        BucketSyncStatus::Syncing => format!(
+            "syncing manifests ({} fetched){}",
+            state.manifests_fetched, peer
+        ),
+        BucketSyncStatus::Downloading => {
+            format!("downloading {}{}", HumanBytes(state.bytes_downloaded), peer)
+        }
+        BucketSyncStatus::Synced => "synced".to_string(),
+        BucketSyncStatus::Failed => "failed".to_string(),
+    }
+}
@@ -0,0 +1,15 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use common::peer::{AccessRequest, AccessRequestStore};
+
+use crate::database::models::AccessRequestRecord;
+use crate::database::Database;
+
+#[async_trait]
+impl AccessRequestStore for Database {
+    async fn queue(&self, request: &AccessRequest) -> Result<bool> {
+        let id = AccessRequestRecord::upsert(request, self).await?;
+        Ok(id.is_some())
+    }
+}
@@ -1,9 +1,12 @@
+use std::path::PathBuf;
+
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use common::bucket_log::BucketLogProvider;
+use common::bucket_log::{BucketLogProvider, SyncMode};
 use common::linked_data::Link;
 
+use crate::database::models::{BucketSyncMode, OfflinePath};
 use crate::database::{types::DCid, Database};
 
 #[async_trait]
@@ -59,16 +62,16 @@ impl BucketLogProvider for Database {
         id: Uuid,
         name: String,
         current: Link,
-        previous: Option<Link>,
+        parents: Vec<Link>,
         height: u64,
         published: bool,
     ) -> Result<(), common::bucket_log::BucketLogError<Self::Error>> {
         let current_dcid: DCid = current.clone().into();
-        let previous_dcid: Option<DCid> = previous.clone().map(Into::into);
+        let previous_dcid: Option<DCid> = parents.first().cloned().map(Into::into);
         let height_i64 = height as i64;
 
-        // Validate: For genesis (previous_link is None), height should be 0
-        if previous.is_none() && height != 0 {
+        // Validate: For genesis (no parents), height should be 0
+        if parents.is_empty() && height != 0 {
             return Err(common::bucket_log::BucketLogError::InvalidAppend(
                 current,
                 Link::default(),
@@ -76,8 +79,8 @@ impl BucketLogProvider for Database {
             ));
         }
 
-        // For non-genesis, validate that previous link exists at height - 1
-        if let Some(prev_link) = previous.clone() {
+        // For non-genesis, validate that the primary parent exists at height - 1
+        if let Some(prev_link) = parents.first().cloned() {
             if height == 0 {
                 return Err(common::bucket_log::BucketLogError::InvalidAppend(
                     current, prev_link, height,
@@ -109,6 +112,37 @@ impl BucketLogProvider for Database {
             }
         }
 
+        // Any other (merged) parents must exist below this height
+        for parent in parents.iter().skip(1) {
+            let parent_dcid: DCid = parent.clone().into();
+            let count: i64 = sqlx::query_scalar(
+                r#"
+                SELECT COUNT(*)
+                FROM bucket_log
+                WHERE bucket_id = $1 AND current_link = $2 AND height < $3
+                "#,
+            )
+            .bind(id.to_string())
+            .bind(parent_dcid)
+            .bind(height_i64)
+            .fetch_one(&**self)
+            .await
+            .map_err(common::bucket_log::BucketLogError::Provider)?;
+
+            if count == 0 {
+                return Err(common::bucket_log::BucketLogError::InvalidAppend(
+                    current,
+                    parent.clone(),
+                    height,
+                ));
+            }
+        }
+
+        let mut tx = self
+            .begin()
+            .await
+            .map_err(common::bucket_log::BucketLogError::Provider)?;
+
         // Insert the log entry with name
         let id_str = id.to_string();
         sqlx::query!(
@@ -123,7 +157,7 @@ impl BucketLogProvider for Database {
             height_i64,
             published
         )
-        .execute(&**self)
+        .execute(&mut *tx)
         .await
         .map_err(|e| match e {
             sqlx::Error::Database(ref db_error) => {
@@ -136,9 +170,55 @@ impl BucketLogProvider for Database {
             _ => common::bucket_log::BucketLogError::Provider(e),
         })?;
 
+        // Record every parent, in order
+        for (position, parent) in parents.into_iter().enumerate() {
+            let parent_dcid: DCid = parent.into();
+            sqlx::query(
+                r#"
+                INSERT INTO bucket_log_parents (bucket_id, current_link, parent_link, position)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(&id_str)
+            .bind(current_dcid)
+            .bind(parent_dcid)
+            .bind(position as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(common::bucket_log::BucketLogError::Provider)?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(common::bucket_log::BucketLogError::Provider)?;
+
         Ok(())
     }
 
+    async fn parents(
+        &self,
+        id: Uuid,
+        link: Link,
+    ) -> Result<Vec<Link>, common::bucket_log::BucketLogError<Self::Error>> {
+        let dcid: DCid = link.into();
+
+        let parents: Vec<DCid> = sqlx::query_scalar(
+            r#"
+            SELECT parent_link
+            FROM bucket_log_parents
+            WHERE bucket_id = $1 AND current_link = $2
+            ORDER BY position
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(dcid)
+        .fetch_all(&**self)
+        .await
+        .map_err(common::bucket_log::BucketLogError::Provider)?;
+
+        Ok(parents.into_iter().map(Into::into).collect())
+    }
+
     async fn height(
         &self,
         id: Uuid,
@@ -228,4 +308,23 @@ impl BucketLogProvider for Database {
 
         Ok(result.map(|r| (r.current_link.into(), r.height as u64)))
     }
+
+    async fn sync_mode(
+        &self,
+        id: Uuid,
+    ) -> Result<SyncMode, common::bucket_log::BucketLogError<Self::Error>> {
+        BucketSyncMode::get(id, self)
+            .await
+            .map_err(common::bucket_log::BucketLogError::Provider)
+    }
+
+    async fn offline_paths(
+        &self,
+        id: Uuid,
+    ) -> Result<Vec<PathBuf>, common::bucket_log::BucketLogError<Self::Error>> {
+        let paths = OfflinePath::list(id, self)
+            .await
+            .map_err(common::bucket_log::BucketLogError::Provider)?;
+        Ok(paths.into_iter().map(|p| PathBuf::from(p.path)).collect())
+    }
 }
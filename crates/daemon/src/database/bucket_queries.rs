@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use time::OffsetDateTime;
 use uuid::Uuid;
 
@@ -20,6 +22,8 @@ pub struct BucketLogEntry {
     pub name: String,
     pub current_link: Link,
     pub previous_link: Option<Link>,
+    /// Every parent, primary (`previous_link`) first; more than one for merges
+    pub parents: Vec<Link>,
     pub height: u64,
     pub published: bool,
     pub created_at: OffsetDateTime,
@@ -132,6 +136,7 @@ impl Database {
         )
         .fetch_all(&**self)
         .await?;
+        let mut parents = self.bucket_log_parents(bucket_id).await?;
 
         Ok(rows
             .into_iter()
@@ -140,6 +145,9 @@ impl Database {
                     .expect("invalid bucket_id UUID in database"),
                 name: r.name,
                 current_link: r.current_link.into(),
+                parents: parents
+                    .remove(&r.current_link)
+                    .unwrap_or_else(|| r.previous_link.into_iter().map(Into::into).collect()),
                 previous_link: r.previous_link.map(Into::into),
                 height: r.height as u64,
                 published: r.published,
@@ -173,6 +181,7 @@ impl Database {
         )
         .fetch_all(&**self)
         .await?;
+        let mut parents = self.bucket_log_parents(bucket_id).await?;
 
         Ok(rows
             .into_iter()
@@ -181,6 +190,9 @@ impl Database {
                     .expect("invalid bucket_id UUID in database"),
                 name: r.name,
                 current_link: r.current_link.into(),
+                parents: parents
+                    .remove(&r.current_link)
+                    .unwrap_or_else(|| r.previous_link.into_iter().map(Into::into).collect()),
                 previous_link: r.previous_link.map(Into::into),
                 height: r.height as u64,
                 published: r.published,
@@ -189,6 +201,30 @@ impl Database {
             .collect())
     }
 
+    /// Get the parents of every log entry for a bucket, keyed by entry link
+    async fn bucket_log_parents(
+        &self,
+        bucket_id: &Uuid,
+    ) -> Result<HashMap<DCid, Vec<Link>>, sqlx::Error> {
+        let rows: Vec<(DCid, DCid)> = sqlx::query_as(
+            r#"
+            SELECT current_link, parent_link
+            FROM bucket_log_parents
+            WHERE bucket_id = ?1
+            ORDER BY current_link, position
+            "#,
+        )
+        .bind(bucket_id.to_string())
+        .fetch_all(&**self)
+        .await?;
+
+        let mut parents: HashMap<DCid, Vec<Link>> = HashMap::new();
+        for (current, parent) in rows {
+            parents.entry(current).or_default().push(parent.into());
+        }
+        Ok(parents)
+    }
+
     /// Get total count of log entries for a bucket
     pub async fn get_bucket_log_count(&self, bucket_id: &Uuid) -> Result<i64, sqlx::Error> {
         let bucket_id_str = bucket_id.to_string();
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use common::peer::invites::{InviteStore, PendingInvite, TokenHash};
+
+use crate::database::models::InviteRecord;
+use crate::database::Database;
+
+#[async_trait]
+impl InviteStore for Database {
+    async fn insert(&self, token: &TokenHash, invite: &PendingInvite) -> Result<()> {
+        Ok(InviteRecord::insert(token, invite, self).await?)
+    }
+
+    async fn get(
+        &self,
+        bucket_id: Uuid,
+        token: &TokenHash,
+        now: u64,
+    ) -> Result<Option<PendingInvite>> {
+        Ok(InviteRecord::get(bucket_id, token, now, self).await?)
+    }
+
+    async fn take(
+        &self,
+        bucket_id: Uuid,
+        token: &TokenHash,
+        now: u64,
+    ) -> Result<Option<PendingInvite>> {
+        Ok(InviteRecord::take(bucket_id, token, now, self).await?)
+    }
+}
@@ -1,5 +1,7 @@
+mod access_request_store;
 pub mod bucket_log_provider;
 mod bucket_queries;
+mod invite_store;
 pub mod models;
 mod sqlite;
 pub mod types;
@@ -0,0 +1,293 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+use common::crypto::{PublicKey, Signature};
+use common::mount::PrincipalRole;
+use common::peer::AccessRequest;
+
+use crate::database::types::{AccessRequestStatus, DUuid};
+use crate::database::Database;
+
+/// A peer's request to join a bucket, queued for the owners to review
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AccessRequestRecord {
+    pub id: i64,
+    pub bucket_id: DUuid,
+    pub requester: String,
+    pub role: String,
+    pub note: Option<String>,
+    pub requested_at: i64,
+    pub signature: Vec<u8>,
+    pub status: AccessRequestStatus,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+const COLUMNS: &str = "id, bucket_id, requester, role, note, requested_at, signature, status, \
+    created_at, updated_at";
+
+impl AccessRequestRecord {
+    /// Queue a verified request, replacing an older one from the same peer
+    ///
+    /// Only a request signed later than the queued one replaces it, so
+    /// replaying an old request changes nothing. A replaced request goes back
+    /// to pending if it was approved, but a denied request stays denied.
+    /// Returns the request's ID, or `None` if it was ignored.
+    pub async fn upsert(
+        request: &AccessRequest,
+        db: &Database,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        let signature = request
+            .signature()
+            .map(|signature| signature.to_bytes().to_vec())
+            .unwrap_or_default();
+
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO access_requests (bucket_id, requester, role, note, requested_at, signature)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT (bucket_id, requester) DO UPDATE SET
+                role = excluded.role,
+                note = excluded.note,
+                requested_at = excluded.requested_at,
+                signature = excluded.signature,
+                status = 'pending',
+                updated_at = CURRENT_TIMESTAMP
+            WHERE excluded.requested_at > access_requests.requested_at
+                AND access_requests.status != 'denied'
+            RETURNING id
+            "#,
+        )
+        .bind(DUuid::from(request.bucket_id))
+        .bind(request.requester.to_hex())
+        .bind(request.role.to_string())
+        .bind(request.note.as_deref())
+        .bind(request.requested_at as i64)
+        .bind(signature)
+        .fetch_optional(&**db)
+        .await
+    }
+
+    /// Get a request by ID
+    pub async fn get(id: i64, db: &Database) -> Result<Option<AccessRequestRecord>, sqlx::Error> {
+        sqlx::query_as::<_, AccessRequestRecord>(&format!(
+            "SELECT {} FROM access_requests WHERE id = ?1",
+            COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&**db)
+        .await
+    }
+
+    /// List requests, optionally for one bucket and with one status
+    pub async fn list(
+        bucket_id: Option<uuid::Uuid>,
+        status: Option<AccessRequestStatus>,
+        db: &Database,
+    ) -> Result<Vec<AccessRequestRecord>, sqlx::Error> {
+        sqlx::query_as::<_, AccessRequestRecord>(&format!(
+            r#"
+            SELECT {} FROM access_requests
+            WHERE (?1 IS NULL OR bucket_id = ?1) AND (?2 IS NULL OR status = ?2)
+            ORDER BY id ASC
+            "#,
+            COLUMNS
+        ))
+        .bind(bucket_id.map(DUuid::from))
+        .bind(status)
+        .fetch_all(&**db)
+        .await
+    }
+
+    /// Settle a pending request
+    ///
+    /// Returns false if the request doesn't exist or was already settled.
+    pub async fn resolve(
+        id: i64,
+        status: AccessRequestStatus,
+        db: &Database,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE access_requests
+            SET status = ?1, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?2 AND status = 'pending'
+            "#,
+        )
+        .bind(status)
+        .bind(id)
+        .execute(&**db)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// The requesting peer's key
+    pub fn requester_key(&self) -> Option<PublicKey> {
+        PublicKey::from_hex(&self.requester).ok()
+    }
+
+    /// The requested role
+    pub fn principal_role(&self) -> Option<PrincipalRole> {
+        parse_role(&self.role)
+    }
+
+    /// Rebuild the signed request, to check its signature again
+    pub fn to_request(&self) -> Option<AccessRequest> {
+        let signature: [u8; 64] = self.signature.as_slice().try_into().ok()?;
+        Some(AccessRequest::from_parts(
+            *self.bucket_id,
+            self.requester_key()?,
+            self.principal_role()?,
+            self.note.clone(),
+            self.requested_at as u64,
+            Signature::from_bytes(&signature),
+        ))
+    }
+}
+
+/// Parse a role stored with its `Display` name
+pub(crate) fn parse_role(role: &str) -> Option<PrincipalRole> {
+    match role {
+        "Owner" => Some(PrincipalRole::Owner),
+        "Reader" => Some(PrincipalRole::Reader),
+        "Mirror" => Some(PrincipalRole::Mirror),
+        "Depositor" => Some(PrincipalRole::Depositor),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::crypto::SecretKey;
+    use uuid::Uuid;
+
+    async fn test_db(dir: &tempfile::TempDir) -> Database {
+        let path = dir.path().join("db.sqlite");
+        let url = url::Url::parse(&format!("sqlite://{}", path.display())).unwrap();
+        Database::connect(&url).await.unwrap()
+    }
+
+    fn request(
+        bucket_id: Uuid,
+        requester: &SecretKey,
+        role: PrincipalRole,
+        requested_at: u64,
+    ) -> AccessRequest {
+        AccessRequest::new(
+            bucket_id,
+            role,
+            Some("please".to_string()),
+            requester,
+            requested_at,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_request_round_trips_with_signature() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(&dir).await;
+        let requester = SecretKey::generate();
+        let signed = request(
+            Uuid::new_v4(),
+            &requester,
+            PrincipalRole::Reader,
+            1_700_000_000,
+        );
+
+        let id = AccessRequestRecord::upsert(&signed, &db)
+            .await
+            .unwrap()
+            .unwrap();
+        let record = AccessRequestRecord::get(id, &db).await.unwrap().unwrap();
+        assert_eq!(record.status, AccessRequestStatus::Pending);
+        assert_eq!(record.principal_role(), Some(PrincipalRole::Reader));
+
+        let rebuilt = record.to_request().unwrap();
+        assert_eq!(rebuilt, signed);
+        assert!(rebuilt.verify().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_newer_request_reopens_approved_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(&dir).await;
+        let bucket_id = Uuid::new_v4();
+        let requester = SecretKey::generate();
+
+        let first = request(bucket_id, &requester, PrincipalRole::Owner, 1_700_000_000);
+        let id = AccessRequestRecord::upsert(&first, &db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            AccessRequestRecord::resolve(id, AccessRequestStatus::Approved, &db)
+                .await
+                .unwrap()
+        );
+        // Settled requests can't be settled again
+        assert!(
+            !AccessRequestRecord::resolve(id, AccessRequestStatus::Denied, &db)
+                .await
+                .unwrap()
+        );
+
+        // Replaying the request changes nothing
+        assert_eq!(
+            AccessRequestRecord::upsert(&first, &db).await.unwrap(),
+            None
+        );
+        let record = AccessRequestRecord::get(id, &db).await.unwrap().unwrap();
+        assert_eq!(record.status, AccessRequestStatus::Approved);
+
+        // Asking again later replaces the request and makes it pending
+        let again = request(bucket_id, &requester, PrincipalRole::Mirror, 1_700_000_001);
+        assert_eq!(
+            AccessRequestRecord::upsert(&again, &db).await.unwrap(),
+            Some(id)
+        );
+        let pending =
+            AccessRequestRecord::list(Some(bucket_id), Some(AccessRequestStatus::Pending), &db)
+                .await
+                .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].principal_role(), Some(PrincipalRole::Mirror));
+        assert!(AccessRequestRecord::list(Some(Uuid::new_v4()), None, &db)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_denied_request_stays_denied() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(&dir).await;
+        let bucket_id = Uuid::new_v4();
+        let requester = SecretKey::generate();
+
+        let first = request(bucket_id, &requester, PrincipalRole::Owner, 1_700_000_000);
+        let id = AccessRequestRecord::upsert(&first, &db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            AccessRequestRecord::resolve(id, AccessRequestStatus::Denied, &db)
+                .await
+                .unwrap()
+        );
+
+        for requested_at in [1_700_000_000, 1_700_000_001] {
+            let again = request(bucket_id, &requester, PrincipalRole::Owner, requested_at);
+            assert_eq!(
+                AccessRequestRecord::upsert(&again, &db).await.unwrap(),
+                None
+            );
+        }
+        let record = AccessRequestRecord::get(id, &db).await.unwrap().unwrap();
+        assert_eq!(record.status, AccessRequestStatus::Denied);
+        assert_eq!(record.requested_at, 1_700_000_000);
+    }
+}
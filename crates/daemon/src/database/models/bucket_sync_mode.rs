@@ -0,0 +1,47 @@
+use uuid::Uuid;
+
+use common::bucket_log::SyncMode;
+
+use crate::database::types::DUuid;
+use crate::database::Database;
+
+/// Per-bucket sync mode
+///
+/// Buckets without a stored mode are synced fully.
+pub struct BucketSyncMode;
+
+impl BucketSyncMode {
+    /// Get the sync mode of a bucket
+    pub async fn get(bucket_id: Uuid, db: &Database) -> Result<SyncMode, sqlx::Error> {
+        let mode: Option<String> =
+            sqlx::query_scalar("SELECT mode FROM bucket_sync_modes WHERE bucket_id = ?1")
+                .bind(DUuid::from(bucket_id))
+                .fetch_optional(&**db)
+                .await?;
+
+        match mode {
+            Some(mode) => mode
+                .parse()
+                .map_err(|e: String| sqlx::Error::Decode(e.into())),
+            None => Ok(SyncMode::default()),
+        }
+    }
+
+    /// Set the sync mode of a bucket
+    pub async fn set(bucket_id: Uuid, mode: SyncMode, db: &Database) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO bucket_sync_modes (bucket_id, mode, updated_at)
+            VALUES (?1, ?2, CURRENT_TIMESTAMP)
+            ON CONFLICT (bucket_id) DO UPDATE SET
+                mode = excluded.mode,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(DUuid::from(bucket_id))
+        .bind(mode.as_str())
+        .execute(&**db)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use common::peer::sync::SyncProgressEvent;
+
+use crate::database::types::{BucketSyncStatus, DCid, DUuid};
+use crate::database::Database;
+
+/// Latest sync progress for a bucket
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BucketSyncState {
+    pub bucket_id: DUuid,
+    pub status: BucketSyncStatus,
+    pub target_link: Option<DCid>,
+    pub target_height: Option<i64>,
+    pub manifests_fetched: i64,
+    pub blobs_total: i64,
+    pub blobs_done: i64,
+    pub bytes_downloaded: i64,
+    pub current_peer: Option<String>,
+    pub last_error: Option<String>,
+    pub started_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+const COLUMNS: &str = "bucket_id, status, target_link, target_height, manifests_fetched, \
+    blobs_total, blobs_done, bytes_downloaded, current_peer, last_error, started_at, updated_at";
+
+impl BucketSyncState {
+    /// A fresh state for a bucket that hasn't reported any progress yet
+    fn new(bucket_id: Uuid) -> Self {
+        let now = OffsetDateTime::now_utc();
+        Self {
+            bucket_id: bucket_id.into(),
+            status: BucketSyncStatus::Syncing,
+            target_link: None,
+            target_height: None,
+            manifests_fetched: 0,
+            blobs_total: 0,
+            blobs_done: 0,
+            bytes_downloaded: 0,
+            current_peer: None,
+            last_error: None,
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Get the sync state of a bucket
+    pub async fn get(bucket_id: Uuid, db: &Database) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(&format!(
+            "SELECT {} FROM bucket_sync_state WHERE bucket_id = ?1",
+            COLUMNS
+        ))
+        .bind(DUuid::from(bucket_id))
+        .fetch_optional(&**db)
+        .await
+    }
+
+    /// List the sync state of every bucket that has synced
+    pub async fn list(db: &Database) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(&format!(
+            "SELECT {} FROM bucket_sync_state ORDER BY updated_at DESC",
+            COLUMNS
+        ))
+        .fetch_all(&**db)
+        .await
+    }
+
+    /// Fold a progress event into the bucket's stored state
+    pub async fn record(
+        bucket_id: Uuid,
+        event: &SyncProgressEvent,
+        db: &Database,
+    ) -> Result<Self, sqlx::Error> {
+        let mut state = Self::get(bucket_id, db)
+            .await?
+            .unwrap_or_else(|| Self::new(bucket_id));
+        state.apply(event);
+
+        sqlx::query(
+            r#"
+            INSERT INTO bucket_sync_state (bucket_id, status, target_link, target_height,
+                manifests_fetched, blobs_total, blobs_done, bytes_downloaded, current_peer,
+                last_error, started_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+            ON CONFLICT (bucket_id) DO UPDATE SET
+                status = excluded.status,
+                target_link = excluded.target_link,
+                target_height = excluded.target_height,
+                manifests_fetched = excluded.manifests_fetched,
+                blobs_total = excluded.blobs_total,
+                blobs_done = excluded.blobs_done,
+                bytes_downloaded = excluded.bytes_downloaded,
+                current_peer = excluded.current_peer,
+                last_error = excluded.last_error,
+                started_at = excluded.started_at,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(state.bucket_id)
+        .bind(state.status)
+        .bind(state.target_link)
+        .bind(state.target_height)
+        .bind(state.manifests_fetched)
+        .bind(state.blobs_total)
+        .bind(state.blobs_done)
+        .bind(state.bytes_downloaded)
+        .bind(&state.current_peer)
+        .bind(&state.last_error)
+        .bind(state.started_at)
+        .bind(state.updated_at)
+        .execute(&**db)
+        .await?;
+
+        Ok(state)
+    }
+
+    /// Update the state in memory for a progress event
+    ///
+    /// A sync that completes while pinned content is still downloading stays
+    /// `downloading`; it becomes `synced` once every blob has arrived.
+    pub fn apply(&mut self, event: &SyncProgressEvent) {
+        match event {
+            SyncProgressEvent::Started { link, height } => {
+                *self = Self::new(*self.bucket_id);
+                self.target_link = Some(link.clone().into());
+                self.target_height = Some(*height as i64);
+            }
+            SyncProgressEvent::ManifestsFetched { count } => {
+                self.manifests_fetched = *count as i64;
+            }
+            SyncProgressEvent::BlobsStarted { total } => {
+                self.blobs_total = *total as i64;
+                self.blobs_done = 0;
+                self.bytes_downloaded = 0;
+                if *total > 0 {
+                    self.status = BucketSyncStatus::Downloading;
+                    self.last_error = None;
+                }
+            }
+            SyncProgressEvent::BlobsProgress { done, total, bytes } => {
+                self.blobs_done = *done as i64;
+                self.blobs_total = *total as i64;
+                self.bytes_downloaded = *bytes as i64;
+                if done >= total {
+                    self.status = BucketSyncStatus::Synced;
+                    self.current_peer = None;
+                }
+            }
+            SyncProgressEvent::Peer { peer_id } => {
+                self.current_peer = Some(peer_id.to_hex());
+            }
+            SyncProgressEvent::PeerFailed { peer_id } => {
+                if self.current_peer.as_deref() == Some(peer_id.to_hex().as_str()) {
+                    self.current_peer = None;
+                }
+            }
+            SyncProgressEvent::Completed { link, height } => {
+                self.target_link = Some(link.clone().into());
+                self.target_height = Some(*height as i64);
+                if self.status != BucketSyncStatus::Downloading {
+                    self.status = BucketSyncStatus::Synced;
+                    self.current_peer = None;
+                }
+            }
+            SyncProgressEvent::Failed { error } => {
+                self.status = BucketSyncStatus::Failed;
+                self.last_error = Some(error.clone());
+                self.current_peer = None;
+            }
+        }
+        self.updated_at = OffsetDateTime::now_utc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::crypto::SecretKey;
+    use common::linked_data::{Hash, Link};
+
+    async fn test_db(dir: &tempfile::TempDir) -> Database {
+        let path = dir.path().join("db.sqlite");
+        let url = url::Url::parse(&format!("sqlite://{}", path.display())).unwrap();
+        Database::connect(&url).await.unwrap()
+    }
+
+    fn link(n: u8) -> Link {
+        Link::new(0x71, Hash::from_bytes([n; 32]))
+    }
+
+    #[tokio::test]
+    async fn test_record_folds_progress_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(&dir).await;
+        let bucket_id = Uuid::new_v4();
+        let peer_id = SecretKey::generate().public();
+
+        let events = [
+            SyncProgressEvent::Started {
+                link: link(2),
+                height: 2,
+            },
+            SyncProgressEvent::ManifestsFetched { count: 2 },
+            SyncProgressEvent::Completed {
+                link: link(2),
+                height: 2,
+            },
+            SyncProgressEvent::BlobsStarted { total: 4 },
+            SyncProgressEvent::Peer { peer_id },
+            SyncProgressEvent::BlobsProgress {
+                done: 2,
+                total: 4,
+                bytes: 100,
+            },
+        ];
+        for event in &events {
+            BucketSyncState::record(bucket_id, event, &db)
+                .await
+                .unwrap();
+        }
+
+        let state = BucketSyncState::get(bucket_id, &db).await.unwrap().unwrap();
+        assert_eq!(state.status, BucketSyncStatus::Downloading);
+        assert_eq!(state.manifests_fetched, 2);
+        assert_eq!(state.target_height, Some(2));
+        assert_eq!((state.blobs_done, state.blobs_total), (2, 4));
+        assert_eq!(state.bytes_downloaded, 100);
+        assert_eq!(state.current_peer, Some(peer_id.to_hex()));
+
+        let event = SyncProgressEvent::BlobsProgress {
+            done: 4,
+            total: 4,
+            bytes: 200,
+        };
+        let state = BucketSyncState::record(bucket_id, &event, &db)
+            .await
+            .unwrap();
+        assert_eq!(state.status, BucketSyncStatus::Synced);
+        assert_eq!(state.current_peer, None);
+        assert_eq!(BucketSyncState::list(&db).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failure_is_cleared_by_next_sync() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(&dir).await;
+        let bucket_id = Uuid::new_v4();
+
+        let failed = SyncProgressEvent::Failed {
+            error: "no peers".to_string(),
+        };
+        let state = BucketSyncState::record(bucket_id, &failed, &db)
+            .await
+            .unwrap();
+        assert_eq!(state.status, BucketSyncStatus::Failed);
+        assert_eq!(state.last_error.as_deref(), Some("no peers"));
+
+        let started = SyncProgressEvent::Started {
+            link: link(1),
+            height: 1,
+        };
+        let state = BucketSyncState::record(bucket_id, &started, &db)
+            .await
+            .unwrap();
+        assert_eq!(state.status, BucketSyncStatus::Syncing);
+        assert_eq!(state.last_error, None);
+    }
+}
@@ -0,0 +1,196 @@
+use uuid::Uuid;
+
+use common::peer::invites::{PendingInvite, TokenHash};
+
+use super::access_request::parse_role;
+use crate::database::types::DUuid;
+use crate::database::Database;
+
+/// Invites we issued, kept by token hash until they are redeemed or expire
+pub struct InviteRecord;
+
+impl InviteRecord {
+    /// Store an invite, dropping the ones that already expired
+    pub async fn insert(
+        token_hash: &TokenHash,
+        invite: &PendingInvite,
+        db: &Database,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "DELETE FROM invites WHERE expires_at <= CAST(strftime('%s', 'now') AS INTEGER)",
+        )
+        .execute(&**db)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO invites (token_hash, bucket_id, role, expires_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+        )
+        .bind(token_hash.as_slice())
+        .bind(DUuid::from(invite.bucket_id))
+        .bind(invite.role.to_string())
+        .bind(invite.expires_at as i64)
+        .execute(&**db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Look up an invite for a bucket that is still open at `now`
+    pub async fn get(
+        bucket_id: Uuid,
+        token_hash: &TokenHash,
+        now: u64,
+        db: &Database,
+    ) -> Result<Option<PendingInvite>, sqlx::Error> {
+        let row: Option<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT role, expires_at FROM invites
+            WHERE token_hash = ?1 AND bucket_id = ?2 AND expires_at > ?3
+            "#,
+        )
+        .bind(token_hash.as_slice())
+        .bind(DUuid::from(bucket_id))
+        .bind(now as i64)
+        .fetch_optional(&**db)
+        .await?;
+
+        row.map(|row| Self::pending(bucket_id, row)).transpose()
+    }
+
+    /// Remove and return an invite for a bucket that is still open at `now`
+    ///
+    /// The invite is removed in the same statement, so only one caller gets
+    /// it. Expired invites are refused.
+    pub async fn take(
+        bucket_id: Uuid,
+        token_hash: &TokenHash,
+        now: u64,
+        db: &Database,
+    ) -> Result<Option<PendingInvite>, sqlx::Error> {
+        let row: Option<(String, i64)> = sqlx::query_as(
+            r#"
+            DELETE FROM invites
+            WHERE token_hash = ?1 AND bucket_id = ?2 AND expires_at > ?3
+            RETURNING role, expires_at
+            "#,
+        )
+        .bind(token_hash.as_slice())
+        .bind(DUuid::from(bucket_id))
+        .bind(now as i64)
+        .fetch_optional(&**db)
+        .await?;
+
+        row.map(|row| Self::pending(bucket_id, row)).transpose()
+    }
+
+    fn pending(
+        bucket_id: Uuid,
+        (role, expires_at): (String, i64),
+    ) -> Result<PendingInvite, sqlx::Error> {
+        let role = parse_role(&role)
+            .ok_or_else(|| sqlx::Error::Decode(format!("unknown role: {}", role).into()))?;
+        Ok(PendingInvite {
+            bucket_id,
+            role,
+            expires_at: expires_at as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::mount::PrincipalRole;
+    use common::peer::invites::{generate_token, hash_token};
+
+    async fn test_db(dir: &tempfile::TempDir) -> Database {
+        let path = dir.path().join("db.sqlite");
+        let url = url::Url::parse(&format!("sqlite://{}", path.display())).unwrap();
+        Database::connect(&url).await.unwrap()
+    }
+
+    fn invite(bucket_id: Uuid, role: PrincipalRole, expires_at: u64) -> PendingInvite {
+        PendingInvite {
+            bucket_id,
+            role,
+            expires_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invite_survives_reconnect() {
+        let dir = tempfile::tempdir().unwrap();
+        let bucket_id = Uuid::new_v4();
+        let token_hash = hash_token(&generate_token());
+        let now = 1_700_000_000;
+        let pending = invite(bucket_id, PrincipalRole::Mirror, u64::MAX >> 1);
+
+        let db = test_db(&dir).await;
+        InviteRecord::insert(&token_hash, &pending, &db)
+            .await
+            .unwrap();
+        db.close().await;
+
+        // A fresh connection, as after a daemon restart, still knows the token
+        let db = test_db(&dir).await;
+        // but only for the bucket it was issued for
+        assert_eq!(
+            InviteRecord::get(Uuid::new_v4(), &token_hash, now, &db)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            InviteRecord::get(bucket_id, &token_hash, now, &db)
+                .await
+                .unwrap(),
+            Some(pending.clone())
+        );
+
+        // Looking it up leaves it in place; taking it removes it
+        assert_eq!(
+            InviteRecord::take(bucket_id, &token_hash, now, &db)
+                .await
+                .unwrap(),
+            Some(pending)
+        );
+        assert_eq!(
+            InviteRecord::take(bucket_id, &token_hash, now, &db)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_invite_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(&dir).await;
+        let bucket_id = Uuid::new_v4();
+        let token_hash = hash_token(&generate_token());
+        let now = 1_700_000_000;
+
+        InviteRecord::insert(
+            &token_hash,
+            &invite(bucket_id, PrincipalRole::Reader, now),
+            &db,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            InviteRecord::get(bucket_id, &token_hash, now, &db)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            InviteRecord::take(bucket_id, &token_hash, now, &db)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -1,3 +1,15 @@
+mod access_request;
+mod bucket_sync_mode;
+mod bucket_sync_state;
 mod fuse_mount;
+mod invite;
+mod offline_path;
+mod sync_job;
 
+pub use access_request::AccessRequestRecord;
+pub use bucket_sync_mode::BucketSyncMode;
+pub use bucket_sync_state::BucketSyncState;
 pub use fuse_mount::FuseMount;
+pub use invite::InviteRecord;
+pub use offline_path::OfflinePath;
+pub use sync_job::{SyncJobRecord, MAX_ATTEMPTS};
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::database::types::DUuid;
+use crate::database::Database;
+
+/// A path of a lazily synced bucket kept available offline
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OfflinePath {
+    pub bucket_id: DUuid,
+    pub path: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl OfflinePath {
+    /// Keep a path available offline; returns false if it already was
+    pub async fn add(bucket_id: Uuid, path: &str, db: &Database) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO offline_paths (bucket_id, path)
+            VALUES (?1, ?2)
+            ON CONFLICT (bucket_id, path) DO NOTHING
+            "#,
+        )
+        .bind(DUuid::from(bucket_id))
+        .bind(path)
+        .execute(&**db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Stop keeping a path available offline; returns false if it wasn't
+    pub async fn remove(bucket_id: Uuid, path: &str, db: &Database) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM offline_paths WHERE bucket_id = ?1 AND path = ?2")
+            .bind(DUuid::from(bucket_id))
+            .bind(path)
+            .execute(&**db)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List the offline paths of a bucket
+    pub async fn list(bucket_id: Uuid, db: &Database) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            r#"
+            SELECT bucket_id, path, created_at
+            FROM offline_paths
+            WHERE bucket_id = ?1
+            ORDER BY path
+            "#,
+        )
+        .bind(DUuid::from(bucket_id))
+        .fetch_all(&**db)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::BucketSyncMode;
+    use common::bucket_log::{BucketLogProvider, SyncMode};
+
+    async fn test_db(dir: &tempfile::TempDir) -> Database {
+        let path = dir.path().join("db.sqlite");
+        let url = url::Url::parse(&format!("sqlite://{}", path.display())).unwrap();
+        Database::connect(&url).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sync_mode_and_offline_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(&dir).await;
+        let bucket_id = Uuid::new_v4();
+
+        assert_eq!(db.sync_mode(bucket_id).await.unwrap(), SyncMode::Full);
+        BucketSyncMode::set(bucket_id, SyncMode::Lazy, &db)
+            .await
+            .unwrap();
+        assert_eq!(db.sync_mode(bucket_id).await.unwrap(), SyncMode::Lazy);
+
+        assert!(OfflinePath::add(bucket_id, "/docs", &db).await.unwrap());
+        assert!(!OfflinePath::add(bucket_id, "/docs", &db).await.unwrap());
+        assert!(OfflinePath::add(bucket_id, "/a.txt", &db).await.unwrap());
+        assert_eq!(
+            db.offline_paths(bucket_id).await.unwrap(),
+            vec![
+                std::path::PathBuf::from("/a.txt"),
+                std::path::PathBuf::from("/docs")
+            ]
+        );
+
+        assert!(OfflinePath::remove(bucket_id, "/docs", &db).await.unwrap());
+        assert!(!OfflinePath::remove(bucket_id, "/docs", &db).await.unwrap());
+        assert_eq!(OfflinePath::list(bucket_id, &db).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,459 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+use common::clock::unix_now;
+use common::crypto::PublicKey;
+use common::linked_data::Link;
+use common::peer::sync::{AcceptDepositJob, DownloadPinsJob, SyncBucketJob, SyncJob, SyncTarget};
+
+use crate::database::types::{DCid, DUuid, SyncJobKind, SyncJobStatus};
+use crate::database::Database;
+
+/// Number of attempts before a job is marked failed
+pub const MAX_ATTEMPTS: i64 = 8;
+
+/// Delay before the first retry; doubles with every failed attempt
+pub const RETRY_BASE: Duration = Duration::from_secs(30);
+
+/// Longest delay between retries
+pub const RETRY_MAX: Duration = Duration::from_secs(60 * 60);
+
+/// A sync job persisted in the durable queue
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SyncJobRecord {
+    pub id: i64,
+    pub kind: SyncJobKind,
+    pub bucket_id: DUuid,
+    pub target_link: DCid,
+    pub target_height: Option<i64>,
+    pub peer_ids: String,
+    pub status: SyncJobStatus,
+    pub attempts: i64,
+    pub next_retry_at: i64,
+    pub last_error: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+const COLUMNS: &str = "id, kind, bucket_id, target_link, target_height, peer_ids, status, \
+    attempts, next_retry_at, last_error, created_at, updated_at";
+
+impl SyncJobRecord {
+    /// Persist a job, or refresh the queued job with the same bucket and target
+    ///
+    /// Only bucket syncs, pin downloads and deposit acceptances are durable;
+    /// other jobs are cheap to redo and return `None`. A job that is already
+    /// queued or running just takes the new peers; a failed or cancelled one
+    /// is queued again.
+    pub async fn enqueue(job: &SyncJob, db: &Database) -> Result<Option<i64>, sqlx::Error> {
+        let (kind, bucket_id, target_link, target_height, peer_ids) = match job {
+            SyncJob::SyncBucket(job) => (
+                SyncJobKind::SyncBucket,
+                job.bucket_id,
+                job.target.link.clone(),
+                Some(job.target.height as i64),
+                &job.target.peer_ids,
+            ),
+            SyncJob::DownloadPins(job) => (
+                SyncJobKind::DownloadPins,
+                job.bucket_id,
+                job.pins_link.clone(),
+                None,
+                &job.peer_ids,
+            ),
+            SyncJob::AcceptDeposit(job) => (
+                SyncJobKind::AcceptDeposit,
+                job.bucket_id,
+                job.link.clone(),
+                None,
+                &job.peer_ids,
+            ),
+            _ => return Ok(None),
+        };
+
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO sync_jobs (kind, bucket_id, target_link, target_height, peer_ids, next_retry_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT (kind, bucket_id, target_link) DO UPDATE SET
+                peer_ids = excluded.peer_ids,
+                status = CASE WHEN status IN ('pending', 'running') THEN status ELSE 'pending' END,
+                attempts = CASE WHEN status IN ('pending', 'running') THEN attempts ELSE 0 END,
+                next_retry_at = CASE WHEN status IN ('pending', 'running') THEN next_retry_at
+                    ELSE excluded.next_retry_at END,
+                updated_at = CURRENT_TIMESTAMP
+            RETURNING id
+            "#,
+        )
+        .bind(kind)
+        .bind(DUuid::from(bucket_id))
+        .bind(DCid::from(target_link))
+        .bind(target_height)
+        .bind(encode_peer_ids(peer_ids))
+        .bind(unix_now() as i64)
+        .fetch_one(&**db)
+        .await?;
+
+        Ok(Some(id))
+    }
+
+    /// Get a job by ID
+    pub async fn get(id: i64, db: &Database) -> Result<Option<SyncJobRecord>, sqlx::Error> {
+        sqlx::query_as::<_, SyncJobRecord>(&format!(
+            "SELECT {} FROM sync_jobs WHERE id = ?1",
+            COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&**db)
+        .await
+    }
+
+    /// List jobs, optionally only those with a given status
+    pub async fn list(
+        status: Option<SyncJobStatus>,
+        db: &Database,
+    ) -> Result<Vec<SyncJobRecord>, sqlx::Error> {
+        sqlx::query_as::<_, SyncJobRecord>(&format!(
+            "SELECT {} FROM sync_jobs WHERE ?1 IS NULL OR status = ?1 ORDER BY id ASC",
+            COLUMNS
+        ))
+        .bind(status)
+        .fetch_all(&**db)
+        .await
+    }
+
+    /// Take the next due job and mark it running
+    pub async fn claim_due(db: &Database) -> Result<Option<SyncJobRecord>, sqlx::Error> {
+        sqlx::query_as::<_, SyncJobRecord>(&format!(
+            r#"
+            UPDATE sync_jobs
+            SET status = 'running', updated_at = CURRENT_TIMESTAMP
+            WHERE id = (
+                SELECT id FROM sync_jobs
+                WHERE status = 'pending' AND next_retry_at <= ?1
+                ORDER BY next_retry_at ASC, id ASC
+                LIMIT 1
+            )
+            RETURNING {}
+            "#,
+            COLUMNS
+        ))
+        .bind(unix_now() as i64)
+        .fetch_optional(&**db)
+        .await
+    }
+
+    /// Remove a job that finished successfully
+    pub async fn complete(id: i64, db: &Database) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM sync_jobs WHERE id = ?1")
+            .bind(id)
+            .execute(&**db)
+            .await?;
+        Ok(())
+    }
+
+    /// Record a failed attempt and schedule the next one
+    ///
+    /// Returns the new status: pending with a backoff, or failed once
+    /// [`MAX_ATTEMPTS`] is reached. A job cancelled while it was running
+    /// stays cancelled.
+    pub async fn fail(
+        id: i64,
+        error: &str,
+        db: &Database,
+    ) -> Result<Option<SyncJobStatus>, sqlx::Error> {
+        let Some(job) = Self::get(id, db).await? else {
+            return Ok(None);
+        };
+        if job.status != SyncJobStatus::Running {
+            return Ok(Some(job.status));
+        }
+
+        let attempts = job.attempts + 1;
+        let status = if attempts >= MAX_ATTEMPTS {
+            SyncJobStatus::Failed
+        } else {
+            SyncJobStatus::Pending
+        };
+        let next_retry_at = unix_now() as i64 + retry_backoff(attempts).as_secs() as i64;
+
+        sqlx::query(
+            r#"
+            UPDATE sync_jobs
+            SET status = ?1, attempts = ?2, next_retry_at = ?3, last_error = ?4,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?5
+            "#,
+        )
+        .bind(status)
+        .bind(attempts)
+        .bind(next_retry_at)
+        .bind(error)
+        .bind(id)
+        .execute(&**db)
+        .await?;
+
+        Ok(Some(status))
+    }
+
+    /// Queue a job to run right away, resetting its attempts
+    ///
+    /// Returns false if the job doesn't exist or is running.
+    pub async fn retry(id: i64, db: &Database) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE sync_jobs
+            SET status = 'pending', attempts = 0, next_retry_at = ?1,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?2 AND status != 'running'
+            "#,
+        )
+        .bind(unix_now() as i64)
+        .bind(id)
+        .execute(&**db)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Stop a job from being (re)tried
+    ///
+    /// A running attempt is not interrupted, but won't be retried if it
+    /// fails. Returns false if the job doesn't exist or is already cancelled.
+    pub async fn cancel(id: i64, db: &Database) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE sync_jobs
+            SET status = 'cancelled', updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?1 AND status != 'cancelled'
+            "#,
+        )
+        .bind(id)
+        .execute(&**db)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Requeue jobs left running by a previous process
+    pub async fn requeue_interrupted(db: &Database) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE sync_jobs
+            SET status = 'pending', updated_at = CURRENT_TIMESTAMP
+            WHERE status = 'running'
+            "#,
+        )
+        .execute(&**db)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// The peers to fetch from, in priority order
+    pub fn peer_ids(&self) -> Vec<PublicKey> {
+        self.peer_ids
+            .split(',')
+            .filter_map(|hex| PublicKey::from_hex(hex).ok())
+            .collect()
+    }
+
+    /// Rebuild the sync job to execute
+    pub fn to_job(&self) -> SyncJob {
+        let link = Link::from(self.target_link);
+        match self.kind {
+            SyncJobKind::SyncBucket => SyncJob::SyncBucket(SyncBucketJob {
+                bucket_id: *self.bucket_id,
+                target: SyncTarget {
+                    link,
+                    height: self.target_height.unwrap_or_default() as u64,
+                    peer_ids: self.peer_ids(),
+                },
+            }),
+            SyncJobKind::DownloadPins => SyncJob::DownloadPins(DownloadPinsJob {
+                bucket_id: *self.bucket_id,
+                pins_link: link,
+                peer_ids: self.peer_ids(),
+            }),
+            SyncJobKind::AcceptDeposit => SyncJob::AcceptDeposit(AcceptDepositJob {
+                bucket_id: *self.bucket_id,
+                link,
+                peer_ids: self.peer_ids(),
+            }),
+        }
+    }
+}
+
+/// Delay before the next attempt after `attempts` failures
+pub fn retry_backoff(attempts: i64) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    RETRY_BASE.saturating_mul(1 << exponent).min(RETRY_MAX)
+}
+
+fn encode_peer_ids(peer_ids: &[PublicKey]) -> String {
+    peer_ids
+        .iter()
+        .map(|peer_id| peer_id.to_hex())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::crypto::SecretKey;
+    use common::linked_data::Hash;
+    use uuid::Uuid;
+
+    async fn test_db(dir: &tempfile::TempDir) -> Database {
+        let path = dir.path().join("db.sqlite");
+        let url = url::Url::parse(&format!("sqlite://{}", path.display())).unwrap();
+        Database::connect(&url).await.unwrap()
+    }
+
+    fn sync_job(bucket_id: Uuid, n: u8, peer_ids: Vec<PublicKey>) -> SyncJob {
+        SyncJob::SyncBucket(SyncBucketJob {
+            bucket_id,
+            target: SyncTarget {
+                link: Link::new(0x71, Hash::from_bytes([n; 32])),
+                height: n as u64,
+                peer_ids,
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_dedups_per_bucket_and_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(&dir).await;
+        let bucket_id = Uuid::new_v4();
+        let alice = SecretKey::generate().public();
+        let bob = SecretKey::generate().public();
+
+        let first = SyncJobRecord::enqueue(&sync_job(bucket_id, 1, vec![alice]), &db)
+            .await
+            .unwrap()
+            .unwrap();
+        let again = SyncJobRecord::enqueue(&sync_job(bucket_id, 1, vec![bob, alice]), &db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first, again);
+        let other = SyncJobRecord::enqueue(&sync_job(bucket_id, 2, vec![alice]), &db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(first, other);
+
+        // The latest peers win, and the job round-trips
+        let job = SyncJobRecord::get(first, &db).await.unwrap().unwrap();
+        assert_eq!(job.peer_ids(), vec![bob, alice]);
+        let SyncJob::SyncBucket(rebuilt) = job.to_job() else {
+            panic!("expected a bucket sync");
+        };
+        assert_eq!(rebuilt.bucket_id, bucket_id);
+        assert_eq!(rebuilt.target.height, 1);
+
+        // Transient jobs aren't persisted
+        let ping = SyncJob::PingPeer(common::peer::sync::PingPeerJob {
+            bucket_id,
+            peer_id: alice,
+        });
+        assert!(SyncJobRecord::enqueue(&ping, &db).await.unwrap().is_none());
+        assert_eq!(SyncJobRecord::list(None, &db).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_attempts_back_off_until_failed() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(&dir).await;
+        let peer_id = SecretKey::generate().public();
+        let id = SyncJobRecord::enqueue(&sync_job(Uuid::new_v4(), 1, vec![peer_id]), &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let claimed = SyncJobRecord::claim_due(&db).await.unwrap().unwrap();
+        assert_eq!(claimed.id, id);
+        assert!(SyncJobRecord::claim_due(&db).await.unwrap().is_none());
+
+        // A failure schedules a retry in the future
+        let status = SyncJobRecord::fail(id, "peer unreachable", &db)
+            .await
+            .unwrap();
+        assert_eq!(status, Some(SyncJobStatus::Pending));
+        let job = SyncJobRecord::get(id, &db).await.unwrap().unwrap();
+        assert_eq!(job.attempts, 1);
+        assert_eq!(job.last_error.as_deref(), Some("peer unreachable"));
+        assert!(SyncJobRecord::claim_due(&db).await.unwrap().is_none());
+
+        // The last allowed attempt marks the job failed
+        sqlx::query("UPDATE sync_jobs SET status = 'running', attempts = ?1 WHERE id = ?2")
+            .bind(MAX_ATTEMPTS - 1)
+            .bind(id)
+            .execute(&*db)
+            .await
+            .unwrap();
+        let status = SyncJobRecord::fail(id, "still unreachable", &db)
+            .await
+            .unwrap();
+        assert_eq!(status, Some(SyncJobStatus::Failed));
+
+        // A manual retry makes it due right away
+        assert!(SyncJobRecord::retry(id, &db).await.unwrap());
+        let claimed = SyncJobRecord::claim_due(&db).await.unwrap().unwrap();
+        assert_eq!(claimed.attempts, 0);
+        SyncJobRecord::complete(id, &db).await.unwrap();
+        assert!(SyncJobRecord::get(id, &db).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_and_requeue_interrupted() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(&dir).await;
+        let bucket_id = Uuid::new_v4();
+        let peer_id = SecretKey::generate().public();
+        let cancelled = SyncJobRecord::enqueue(&sync_job(bucket_id, 1, vec![peer_id]), &db)
+            .await
+            .unwrap()
+            .unwrap();
+        let running = SyncJobRecord::enqueue(&sync_job(bucket_id, 2, vec![peer_id]), &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(SyncJobRecord::cancel(cancelled, &db).await.unwrap());
+        assert!(!SyncJobRecord::cancel(cancelled, &db).await.unwrap());
+        assert_eq!(
+            SyncJobRecord::claim_due(&db).await.unwrap().unwrap().id,
+            running
+        );
+
+        // A restart puts the interrupted job back in the queue
+        assert_eq!(SyncJobRecord::requeue_interrupted(&db).await.unwrap(), 1);
+        let pending = SyncJobRecord::list(Some(SyncJobStatus::Pending), &db)
+            .await
+            .unwrap();
+        assert_eq!(
+            pending.iter().map(|job| job.id).collect::<Vec<_>>(),
+            vec![running]
+        );
+
+        // Dispatching a cancelled job's target again revives it
+        SyncJobRecord::enqueue(&sync_job(bucket_id, 1, vec![peer_id]), &db)
+            .await
+            .unwrap();
+        let job = SyncJobRecord::get(cancelled, &db).await.unwrap().unwrap();
+        assert_eq!(job.status, SyncJobStatus::Pending);
+    }
+
+    #[test]
+    fn test_retry_backoff_is_capped() {
+        assert_eq!(retry_backoff(1), RETRY_BASE);
+        assert_eq!(retry_backoff(2), RETRY_BASE * 2);
+        assert_eq!(retry_backoff(40), RETRY_MAX);
+    }
+}
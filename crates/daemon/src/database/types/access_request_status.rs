@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::{SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Decode, Encode, Sqlite, Type};
+
+/// Access request status enumeration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessRequestStatus {
+    /// Waiting for an owner to review it
+    Pending,
+    /// An owner added the requester, or proposed adding them
+    Approved,
+    /// An owner turned the request down
+    Denied,
+}
+
+impl AccessRequestStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessRequestStatus::Pending => "pending",
+            AccessRequestStatus::Approved => "approved",
+            AccessRequestStatus::Denied => "denied",
+        }
+    }
+}
+
+impl std::str::FromStr for AccessRequestStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(AccessRequestStatus::Pending),
+            "approved" => Ok(AccessRequestStatus::Approved),
+            "denied" => Ok(AccessRequestStatus::Denied),
+            _ => Err(format!("unknown access request status: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for AccessRequestStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Decode<'_, Sqlite> for AccessRequestStatus {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        let s = <String as Decode<Sqlite>>::decode(value)?;
+        Ok(s.parse()?)
+    }
+}
+
+impl Encode<'_, Sqlite> for AccessRequestStatus {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'_>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Text(self.as_str().into()));
+        Ok(IsNull::No)
+    }
+}
+
+impl Type<Sqlite> for AccessRequestStatus {
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <String as Type<Sqlite>>::compatible(ty)
+    }
+
+    fn type_info() -> SqliteTypeInfo {
+        <String as Type<Sqlite>>::type_info()
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::{SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Decode, Encode, Sqlite, Type};
+
+/// Sync status of a bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BucketSyncStatus {
+    /// Fetching and applying manifests
+    Syncing,
+    /// Fetching pinned content
+    Downloading,
+    /// Up to date with the last version we synced to
+    Synced,
+    /// The last sync failed
+    Failed,
+}
+
+impl BucketSyncStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BucketSyncStatus::Syncing => "syncing",
+            BucketSyncStatus::Downloading => "downloading",
+            BucketSyncStatus::Synced => "synced",
+            BucketSyncStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for BucketSyncStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "syncing" => Ok(BucketSyncStatus::Syncing),
+            "downloading" => Ok(BucketSyncStatus::Downloading),
+            "synced" => Ok(BucketSyncStatus::Synced),
+            "failed" => Ok(BucketSyncStatus::Failed),
+            _ => Err(format!("unknown bucket sync status: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for BucketSyncStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Decode<'_, Sqlite> for BucketSyncStatus {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        let s = <String as Decode<Sqlite>>::decode(value)?;
+        Ok(s.parse()?)
+    }
+}
+
+impl Encode<'_, Sqlite> for BucketSyncStatus {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'_>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Text(self.as_str().into()));
+        Ok(IsNull::No)
+    }
+}
+
+impl Type<Sqlite> for BucketSyncStatus {
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <String as Type<Sqlite>>::compatible(ty)
+    }
+
+    fn type_info() -> SqliteTypeInfo {
+        <String as Type<Sqlite>>::type_info()
+    }
+}
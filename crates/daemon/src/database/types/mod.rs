@@ -1,9 +1,17 @@
+mod access_request_status;
+mod bucket_sync_status;
 mod dbool;
 mod dcid;
 mod duuid;
 mod mount_status;
+mod sync_job_kind;
+mod sync_job_status;
 
+pub use access_request_status::AccessRequestStatus;
+pub use bucket_sync_status::BucketSyncStatus;
 pub use dbool::DBool;
 pub use dcid::DCid;
 pub use duuid::DUuid;
 pub use mount_status::MountStatus;
+pub use sync_job_kind::SyncJobKind;
+pub use sync_job_status::SyncJobStatus;
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::{SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Decode, Encode, Sqlite, Type};
+
+/// Kinds of sync jobs kept in the durable queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncJobKind {
+    SyncBucket,
+    DownloadPins,
+    AcceptDeposit,
+}
+
+impl SyncJobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncJobKind::SyncBucket => "sync_bucket",
+            SyncJobKind::DownloadPins => "download_pins",
+            SyncJobKind::AcceptDeposit => "accept_deposit",
+        }
+    }
+}
+
+impl std::str::FromStr for SyncJobKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sync_bucket" => Ok(SyncJobKind::SyncBucket),
+            "download_pins" => Ok(SyncJobKind::DownloadPins),
+            "accept_deposit" => Ok(SyncJobKind::AcceptDeposit),
+            _ => Err(format!("unknown sync job kind: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for SyncJobKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Decode<'_, Sqlite> for SyncJobKind {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        let s = <String as Decode<Sqlite>>::decode(value)?;
+        Ok(s.parse()?)
+    }
+}
+
+impl Encode<'_, Sqlite> for SyncJobKind {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'_>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Text(self.as_str().into()));
+        Ok(IsNull::No)
+    }
+}
+
+impl Type<Sqlite> for SyncJobKind {
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <String as Type<Sqlite>>::compatible(ty)
+    }
+
+    fn type_info() -> SqliteTypeInfo {
+        <String as Type<Sqlite>>::type_info()
+    }
+}
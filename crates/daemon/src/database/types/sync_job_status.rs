@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::{SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Decode, Encode, Sqlite, Type};
+
+/// Sync job status enumeration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncJobStatus {
+    /// Waiting to run once `next_retry_at` has passed
+    Pending,
+    /// Being executed by the worker
+    Running,
+    /// Gave up after too many attempts; can be retried manually
+    Failed,
+    /// Cancelled by the user; can be retried manually
+    Cancelled,
+}
+
+impl SyncJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncJobStatus::Pending => "pending",
+            SyncJobStatus::Running => "running",
+            SyncJobStatus::Failed => "failed",
+            SyncJobStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl std::str::FromStr for SyncJobStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(SyncJobStatus::Pending),
+            "running" => Ok(SyncJobStatus::Running),
+            "failed" => Ok(SyncJobStatus::Failed),
+            "cancelled" => Ok(SyncJobStatus::Cancelled),
+            _ => Err(format!("unknown sync job status: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for SyncJobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Decode<'_, Sqlite> for SyncJobStatus {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        let s = <String as Decode<Sqlite>>::decode(value)?;
+        Ok(s.parse()?)
+    }
+}
+
+impl Encode<'_, Sqlite> for SyncJobStatus {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'_>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Text(self.as_str().into()));
+        Ok(IsNull::No)
+    }
+}
+
+impl Type<Sqlite> for SyncJobStatus {
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <String as Type<Sqlite>>::compatible(ty)
+    }
+
+    fn type_info() -> SqliteTypeInfo {
+        <String as Type<Sqlite>>::type_info()
+    }
+}
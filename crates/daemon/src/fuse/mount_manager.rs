@@ -17,7 +17,7 @@ use crate::fuse::cache::FileCacheConfig;
 use crate::fuse::jax_fs::JaxFs;
 use crate::fuse::sync_events::{SaveRequest, SyncEvent};
 use crate::fuse::FileCache;
-use common::mount::{ConflictFile, Mount};
+use common::mount::Mount;
 use common::peer::Peer;
 
 /// Configuration for mount manager
@@ -160,16 +160,18 @@ impl MountManager {
                         .await
                         .map_err(|e| MountError::MountLoad(e.into()))?;
 
-                    // Use ConflictFile resolver to create conflict copies for concurrent edits
-                    let resolver = ConflictFile::new();
+                    // Resolve concurrent edits with the bucket's configured strategy
+                    //  (conflict copies unless configured otherwise)
+                    let strategy = incoming.conflict_strategy().await;
+                    let resolver = strategy.resolver();
 
                     // Merge incoming changes into local mount
                     let mut mount_guard = live_mount.mount.write().await;
                     match mount_guard
-                        .merge_from(&incoming, &resolver, self.peer.blobs())
+                        .merge(&incoming, resolver.as_ref(), self.peer.blobs())
                         .await
                     {
-                        Ok((result, link)) => {
+                        Ok(result) => {
                             // Log any conflicts that were resolved
                             for resolved in &result.conflicts_resolved {
                                 tracing::info!(
@@ -179,16 +181,19 @@ impl MountManager {
                                 );
                             }
 
-                            tracing::info!(
-                                "Merged {} operations, {} conflicts resolved, new link: {}",
-                                result.operations_added,
-                                result.conflicts_resolved.len(),
-                                link.hash()
-                            );
-
                             // Save the merged result
-                            if let Err(e) = self.peer.save_mount(&mount_guard, false).await {
-                                tracing::error!("Failed to save merged mount {}: {}", mount_id, e);
+                            match self.peer.save_mount(&mount_guard, false).await {
+                                Ok(link) => tracing::info!(
+                                    "Merged {} operations, {} conflicts resolved, new link: {}",
+                                    result.operations_added,
+                                    result.conflicts_resolved.len(),
+                                    link.hash()
+                                ),
+                                Err(e) => tracing::error!(
+                                    "Failed to save merged mount {}: {}",
+                                    mount_id,
+                                    e
+                                ),
                             }
                         }
                         Err(e) => {
@@ -0,0 +1,152 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::mount::MembershipError;
+use common::peer::MembershipUpdate;
+use common::prelude::MountError;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::database::models::AccessRequestRecord;
+use crate::database::types::AccessRequestStatus;
+use crate::http_server::api::client::ApiRequest;
+use crate::http_server::api::v0::bucket::proposals::{update_outcome, ProposalInfo};
+use crate::ServiceState;
+
+/// Approve a pending access request, adding the requester to the bucket
+///
+/// In a bucket with an owner quorum the addition is only proposed, and the
+/// request stays pending until the change is saved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApproveAccessRequest {
+    /// ID of the request, see `/api/v0/access-requests`
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApproveAccessResponse {
+    pub id: i64,
+    pub bucket_id: Uuid,
+    /// New bucket link, if the requester was added
+    pub new_bucket_link: Option<String>,
+    /// The change, if it waits for other owners to approve it
+    pub proposal: Option<ProposalInfo>,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<ApproveAccessRequest>,
+) -> Result<impl IntoResponse, ApproveAccessError> {
+    let record = AccessRequestRecord::get(req.id, state.database())
+        .await
+        .map_err(|e| ApproveAccessError::Database(e.to_string()))?
+        .ok_or(ApproveAccessError::NotFound(req.id))?;
+    if record.status != AccessRequestStatus::Pending {
+        return Err(ApproveAccessError::AlreadySettled(record.status));
+    }
+
+    // The stored request must still carry the requester's signature
+    let request = record
+        .to_request()
+        .filter(|request| request.verify().is_ok())
+        .ok_or(ApproveAccessError::InvalidRequest)?;
+
+    let update = state
+        .peer()
+        .add_member(request.bucket_id, request.requester, request.role.clone())
+        .await?;
+    if let MembershipUpdate::Saved(_) = update {
+        AccessRequestRecord::resolve(req.id, AccessRequestStatus::Approved, state.database())
+            .await
+            .map_err(|e| ApproveAccessError::Database(e.to_string()))?;
+    }
+    let (new_bucket_link, proposal) = update_outcome(update);
+
+    tracing::info!(
+        "ACCESS API: Approved request {} from {} for bucket {} as {:?}, proposed: {}",
+        req.id,
+        record.requester,
+        request.bucket_id,
+        request.role,
+        proposal.is_some()
+    );
+
+    Ok((
+        http::StatusCode::OK,
+        Json(ApproveAccessResponse {
+            id: req.id,
+            bucket_id: request.bucket_id,
+            new_bucket_link,
+            proposal,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApproveAccessError {
+    #[error("Access request not found: {0}")]
+    NotFound(i64),
+    #[error("Access request is already {0}")]
+    AlreadySettled(AccessRequestStatus),
+    #[error("Access request is not signed by its requester")]
+    InvalidRequest,
+    #[error("Database error: {0}")]
+    Database(String),
+    #[error("Mount error: {0}")]
+    Mount(#[from] MountError),
+}
+
+impl IntoResponse for ApproveAccessError {
+    fn into_response(self) -> Response {
+        match self {
+            ApproveAccessError::NotFound(id) => (
+                http::StatusCode::NOT_FOUND,
+                format!("Access request not found: {}", id),
+            )
+                .into_response(),
+            ApproveAccessError::AlreadySettled(status) => (
+                http::StatusCode::CONFLICT,
+                format!("Access request is already {}", status),
+            )
+                .into_response(),
+            ApproveAccessError::InvalidRequest => (
+                http::StatusCode::UNPROCESSABLE_ENTITY,
+                "Access request is not signed by its requester".to_string(),
+            )
+                .into_response(),
+            ApproveAccessError::Mount(MountError::Unauthorized) => (
+                http::StatusCode::FORBIDDEN,
+                "Unauthorized: only unscoped owners can add peers".to_string(),
+            )
+                .into_response(),
+            ApproveAccessError::Mount(MountError::Membership(
+                e @ MembershipError::InvalidQuorum { .. },
+            )) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Invalid quorum: {}", e),
+            )
+                .into_response(),
+            ApproveAccessError::Database(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", msg),
+            )
+                .into_response(),
+            ApproveAccessError::Mount(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Unexpected error".to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for ApproveAccessRequest {
+    type Response = ApproveAccessResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/access-requests/approve").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -0,0 +1,67 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::database::models::AccessRequestRecord;
+use crate::database::types::AccessRequestStatus;
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// Deny a pending access request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DenyAccessRequest {
+    /// ID of the request, see `/api/v0/access-requests`
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DenyAccessResponse {
+    pub id: i64,
+    /// False if the request doesn't exist or was already settled
+    pub denied: bool,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<DenyAccessRequest>,
+) -> Result<impl IntoResponse, DenyAccessError> {
+    let denied =
+        AccessRequestRecord::resolve(req.id, AccessRequestStatus::Denied, state.database())
+            .await
+            .map_err(|e| DenyAccessError::Database(e.to_string()))?;
+
+    Ok((
+        http::StatusCode::OK,
+        Json(DenyAccessResponse { id: req.id, denied }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DenyAccessError {
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl IntoResponse for DenyAccessError {
+    fn into_response(self) -> Response {
+        match self {
+            DenyAccessError::Database(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", msg),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for DenyAccessRequest {
+    type Response = DenyAccessResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/access-requests/deny").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::bucket_log::BucketLogProvider;
+use common::mount::{Manifest, Mount};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::AccessRequestInfo;
+use crate::database::models::AccessRequestRecord;
+use crate::database::types::AccessRequestStatus;
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// List access requests from peers, optionally for one bucket or status
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccessRequestsRequest {
+    #[serde(default)]
+    pub bucket_id: Option<Uuid>,
+    #[serde(default)]
+    pub status: Option<AccessRequestStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessRequestsResponse {
+    pub requests: Vec<AccessRequestInfo>,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<AccessRequestsRequest>,
+) -> Result<impl IntoResponse, AccessRequestsError> {
+    settle_added(&state, req.bucket_id)
+        .await
+        .map_err(|e| AccessRequestsError::Database(e.to_string()))?;
+    let records = AccessRequestRecord::list(req.bucket_id, req.status, state.database())
+        .await
+        .map_err(|e| AccessRequestsError::Database(e.to_string()))?;
+
+    Ok((
+        http::StatusCode::OK,
+        Json(AccessRequestsResponse {
+            requests: records.into_iter().map(AccessRequestInfo::from).collect(),
+        }),
+    )
+        .into_response())
+}
+
+/// Mark pending requests approved once their requester is in the bucket
+///
+/// Approving a request in a bucket with an owner quorum only proposes adding
+/// the requester. Whichever owner completes the quorum saves the change,
+/// possibly on another node, so the request is settled when we see it land.
+async fn settle_added(state: &ServiceState, bucket_id: Option<Uuid>) -> Result<(), sqlx::Error> {
+    let pending = AccessRequestRecord::list(
+        bucket_id,
+        Some(AccessRequestStatus::Pending),
+        state.database(),
+    )
+    .await?;
+    let mut manifests: HashMap<Uuid, Option<Manifest>> = HashMap::new();
+    for record in pending {
+        let Some(requester) = record.requester_key() else {
+            continue;
+        };
+        let bucket_id = *record.bucket_id;
+        if let Entry::Vacant(entry) = manifests.entry(bucket_id) {
+            let manifest = match state.peer().logs().head(bucket_id, None).await {
+                Ok((head, _)) => Mount::load_manifest(&head, state.peer().blobs()).await.ok(),
+                Err(_) => None,
+            };
+            entry.insert(manifest);
+        }
+        let added = manifests[&bucket_id]
+            .as_ref()
+            .is_some_and(|manifest| manifest.get_share(&requester).is_some());
+        if added {
+            AccessRequestRecord::resolve(
+                record.id,
+                AccessRequestStatus::Approved,
+                state.database(),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AccessRequestsError {
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl IntoResponse for AccessRequestsError {
+    fn into_response(self) -> Response {
+        match self {
+            AccessRequestsError::Database(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", msg),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for AccessRequestsRequest {
+    type Response = AccessRequestsResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/access-requests").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -0,0 +1,55 @@
+use axum::routing::post;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::database::models::AccessRequestRecord;
+use crate::database::types::AccessRequestStatus;
+use crate::ServiceState;
+
+pub mod approve;
+pub mod deny;
+pub mod list;
+pub mod send;
+
+pub use approve::ApproveAccessRequest;
+pub use deny::DenyAccessRequest;
+pub use list::AccessRequestsRequest;
+pub use send::SendAccessRequest;
+
+pub fn router(state: ServiceState) -> Router<ServiceState> {
+    Router::new()
+        .route("/", post(list::handler))
+        .route("/approve", post(approve::handler))
+        .route("/deny", post(deny::handler))
+        .route("/send", post(send::handler))
+        .with_state(state)
+}
+
+/// A queued access request as reported by the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessRequestInfo {
+    pub id: i64,
+    pub bucket_id: Uuid,
+    /// Hex public key of the requesting peer
+    pub requester: String,
+    pub role: String,
+    pub note: Option<String>,
+    /// When the requester signed the request, in seconds since the Unix epoch
+    pub requested_at: i64,
+    pub status: AccessRequestStatus,
+}
+
+impl From<AccessRequestRecord> for AccessRequestInfo {
+    fn from(record: AccessRequestRecord) -> Self {
+        Self {
+            id: record.id,
+            bucket_id: *record.bucket_id,
+            requester: record.requester,
+            role: record.role.to_lowercase(),
+            note: record.note,
+            requested_at: record.requested_at,
+            status: record.status,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::crypto::PublicKey;
+use common::peer::access::MAX_NOTE_LEN;
+use common::peer::AccessRequestStatus;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::http_server::api::client::ApiRequest;
+use crate::http_server::api::v0::bucket::share::ShareRole;
+use crate::ServiceState;
+
+/// Ask an owner of a bucket we aren't in to add us
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendAccessRequest {
+    pub bucket_id: Uuid,
+    /// Hex public key of the owner to ask
+    pub peer_public_key: String,
+    /// Role to ask for, defaults to reader
+    #[serde(default = "default_role")]
+    pub role: ShareRole,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+fn default_role() -> ShareRole {
+    ShareRole::Reader
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendAccessResponse {
+    pub bucket_id: Uuid,
+    /// `queued` or `already_shared`
+    pub status: String,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<SendAccessRequest>,
+) -> Result<impl IntoResponse, SendAccessError> {
+    let owner = PublicKey::from_hex(&req.peer_public_key)
+        .map_err(|e| SendAccessError::InvalidPublicKey(e.to_string()))?;
+    if req
+        .note
+        .as_ref()
+        .is_some_and(|note| note.len() > MAX_NOTE_LEN)
+    {
+        return Err(SendAccessError::NoteTooLong);
+    }
+
+    let status = state
+        .peer()
+        .request_access(req.bucket_id, owner, req.role.into(), req.note)
+        .await
+        .map_err(|e| SendAccessError::Unreachable(e.to_string()))?;
+
+    tracing::info!(
+        "ACCESS API: Requested {:?} access to bucket {} from {}: {:?}",
+        req.role,
+        req.bucket_id,
+        req.peer_public_key,
+        status
+    );
+
+    let status = match status {
+        AccessRequestStatus::Queued => "queued",
+        AccessRequestStatus::AlreadyShared => "already_shared",
+        AccessRequestStatus::Refused => return Err(SendAccessError::Refused),
+    };
+
+    Ok((
+        http::StatusCode::OK,
+        Json(SendAccessResponse {
+            bucket_id: req.bucket_id,
+            status: status.to_string(),
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SendAccessError {
+    #[error("Invalid public key: {0}")]
+    InvalidPublicKey(String),
+    #[error("Note is longer than {MAX_NOTE_LEN} bytes")]
+    NoteTooLong,
+    #[error("Could not reach the owner: {0}")]
+    Unreachable(String),
+    #[error("The peer refused the request")]
+    Refused,
+}
+
+impl IntoResponse for SendAccessError {
+    fn into_response(self) -> Response {
+        match self {
+            SendAccessError::InvalidPublicKey(msg) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Invalid public key: {}", msg),
+            )
+                .into_response(),
+            SendAccessError::NoteTooLong => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Note is longer than {} bytes", MAX_NOTE_LEN),
+            )
+                .into_response(),
+            SendAccessError::Unreachable(msg) => (
+                http::StatusCode::BAD_GATEWAY,
+                format!("Could not reach the owner: {}", msg),
+            )
+                .into_response(),
+            SendAccessError::Refused => (
+                http::StatusCode::FORBIDDEN,
+                "The peer isn't an owner of the bucket that accepts requests".to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for SendAccessRequest {
+    type Response = SendAccessResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/access-requests/send").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -0,0 +1,99 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::blobs::migrate::{MigrateError, MigrateStatus};
+use crate::http_server::api::client::ApiRequest;
+use crate::state::BlobStoreConfig;
+use crate::ServiceState;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrateRequest {
+    /// Blob store to copy everything into
+    pub target: BlobStoreConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrateResponse {
+    /// Status right after the migration started
+    pub status: MigrateStatus,
+}
+
+/// Start migrating the blob store in the background
+///
+/// Poll [`MigrateStatusRequest`] for progress and the result.
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<MigrateRequest>,
+) -> Result<impl IntoResponse, MigrateHandlerError> {
+    state.migration().start(
+        state.peer().blobs().clone(),
+        state.jax_dir().to_path_buf(),
+        req.target,
+    )?;
+    let status = state.migration().status();
+    Ok((http::StatusCode::ACCEPTED, Json(MigrateResponse { status })).into_response())
+}
+
+/// Request the status of the blob store migration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrateStatusRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrateStatusResponse {
+    pub status: MigrateStatus,
+}
+
+/// Report the progress of the current or last migration
+pub async fn status_handler(State(state): State<ServiceState>) -> impl IntoResponse {
+    let status = state.migration().status();
+    (http::StatusCode::OK, Json(MigrateStatusResponse { status })).into_response()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrateHandlerError {
+    #[error(transparent)]
+    Migrate(#[from] MigrateError),
+}
+
+impl IntoResponse for MigrateHandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            MigrateHandlerError::Migrate(MigrateError::SameStore) => (
+                http::StatusCode::BAD_REQUEST,
+                MigrateError::SameStore.to_string(),
+            )
+                .into_response(),
+            MigrateHandlerError::Migrate(MigrateError::Running) => (
+                http::StatusCode::CONFLICT,
+                MigrateError::Running.to_string(),
+            )
+                .into_response(),
+            MigrateHandlerError::Migrate(e) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Migration failed: {}", e),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for MigrateRequest {
+    type Response = MigrateResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/blobs/migrate").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
+
+impl ApiRequest for MigrateStatusRequest {
+    type Response = MigrateStatusResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/blobs/migrate").unwrap();
+        client.get(full_url)
+    }
+}
@@ -0,0 +1,23 @@
+use axum::routing::{get, post};
+use axum::Router;
+
+use crate::ServiceState;
+
+pub mod migrate;
+pub mod scrub;
+pub mod tiers;
+
+pub use migrate::{MigrateRequest, MigrateStatusRequest};
+pub use scrub::ScrubRequest;
+pub use tiers::TiersRequest;
+
+pub fn router(state: ServiceState) -> Router<ServiceState> {
+    Router::new()
+        .route(
+            "/migrate",
+            post(migrate::handler).get(migrate::status_handler),
+        )
+        .route("/scrub", post(scrub::handler))
+        .route("/tiers", get(tiers::handler))
+        .with_state(state)
+}
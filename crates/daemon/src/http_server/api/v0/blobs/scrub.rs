@@ -0,0 +1,72 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+
+use common::peer::sync::{scrub_blobs, ScrubBlobsJob, ScrubOutcome, SyncJob};
+
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Args)]
+pub struct ScrubRequest {
+    /// Queue the scrub as a background job instead of waiting for the result
+    #[arg(long)]
+    #[serde(default)]
+    pub background: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrubResponse {
+    /// Scrub and repair results (absent when run in the background)
+    pub outcome: Option<ScrubOutcome>,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<ScrubRequest>,
+) -> Result<impl IntoResponse, ScrubError> {
+    let outcome = if req.background {
+        state
+            .peer()
+            .dispatch(SyncJob::ScrubBlobs(ScrubBlobsJob))
+            .await
+            .map_err(|e| ScrubError::Failed(e.to_string()))?;
+        None
+    } else {
+        let outcome = scrub_blobs::scrub_and_repair(state.peer())
+            .await
+            .map_err(|e| ScrubError::Failed(e.to_string()))?;
+        Some(outcome)
+    };
+
+    Ok((http::StatusCode::OK, Json(ScrubResponse { outcome })).into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScrubError {
+    #[error("Scrub failed: {0}")]
+    Failed(String),
+}
+
+impl IntoResponse for ScrubError {
+    fn into_response(self) -> Response {
+        match self {
+            ScrubError::Failed(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Scrub failed: {}", msg),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for ScrubRequest {
+    type Response = ScrubResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/blobs/scrub").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
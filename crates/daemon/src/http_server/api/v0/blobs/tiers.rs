@@ -0,0 +1,68 @@
+//! Blob storage tier usage API endpoint
+
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use object_store::TierStats;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// Request per-tier usage of the blob store
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TiersRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TiersResponse {
+    /// Whether the blob store is tiered
+    pub enabled: bool,
+    /// Per-tier usage (absent when the store is not tiered)
+    pub stats: Option<TierStats>,
+}
+
+pub async fn handler(State(state): State<ServiceState>) -> Result<impl IntoResponse, TiersError> {
+    let stats = state
+        .peer()
+        .blobs()
+        .tier_stats()
+        .await
+        .map_err(|e| TiersError::Failed(e.to_string()))?;
+
+    Ok((
+        http::StatusCode::OK,
+        Json(TiersResponse {
+            enabled: stats.is_some(),
+            stats,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TiersError {
+    #[error("Failed to read tier usage: {0}")]
+    Failed(String),
+}
+
+impl IntoResponse for TiersError {
+    fn into_response(self) -> Response {
+        match self {
+            TiersError::Failed(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to read tier usage: {}", msg),
+            )
+                .into_response(),
+        }
+    }
+}
+
+impl ApiRequest for TiersRequest {
+    type Response = TiersResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/blobs/tiers").unwrap();
+        client.get(full_url)
+    }
+}
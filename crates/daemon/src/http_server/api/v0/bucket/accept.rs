@@ -0,0 +1,113 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::peer::{InviteStatus, InviteTicket};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// Redeem an invite ticket to join a bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcceptRequest {
+    pub ticket: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcceptResponse {
+    pub bucket_id: Uuid,
+    /// Role we join with
+    pub role: String,
+    /// Public key of the node that issued the ticket
+    pub inviter: String,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<AcceptRequest>,
+) -> Result<impl IntoResponse, AcceptError> {
+    let ticket: InviteTicket =
+        req.ticket
+            .parse()
+            .map_err(|e: common::peer::invites::InviteError| {
+                AcceptError::InvalidTicket(e.to_string())
+            })?;
+    let inviter = ticket.inviter.node_id.to_string();
+
+    tracing::info!(
+        "ACCEPT API: Redeeming invite for bucket {} with {}",
+        ticket.bucket_id,
+        inviter
+    );
+
+    let status = state
+        .peer()
+        .accept_invite(&ticket)
+        .await
+        .map_err(|e| AcceptError::Unreachable(e.to_string()))?;
+    let role = match status {
+        InviteStatus::Accepted(role) => role,
+        InviteStatus::AlreadyShared => return Err(AcceptError::AlreadyShared),
+        InviteStatus::Refused => return Err(AcceptError::Refused),
+    };
+
+    Ok((
+        http::StatusCode::OK,
+        Json(AcceptResponse {
+            bucket_id: ticket.bucket_id,
+            role: role.to_string(),
+            inviter,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AcceptError {
+    #[error("Invalid ticket: {0}")]
+    InvalidTicket(String),
+    #[error("Could not reach the inviter: {0}")]
+    Unreachable(String),
+    #[error("We are already in the bucket's shares")]
+    AlreadyShared,
+    #[error("The invite is unknown, expired or already used")]
+    Refused,
+}
+
+impl IntoResponse for AcceptError {
+    fn into_response(self) -> Response {
+        match self {
+            AcceptError::InvalidTicket(msg) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Invalid ticket: {}", msg),
+            )
+                .into_response(),
+            AcceptError::Unreachable(msg) => (
+                http::StatusCode::BAD_GATEWAY,
+                format!("Could not reach the inviter: {}", msg),
+            )
+                .into_response(),
+            AcceptError::AlreadyShared => (
+                http::StatusCode::CONFLICT,
+                "We are already in the bucket's shares".to_string(),
+            )
+                .into_response(),
+            AcceptError::Refused => (
+                http::StatusCode::FORBIDDEN,
+                "The invite is unknown, expired or already used".to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for AcceptRequest {
+    type Response = AcceptResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/accept").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
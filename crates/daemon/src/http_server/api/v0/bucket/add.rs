@@ -243,6 +243,11 @@ impl IntoResponse for AddError {
                 format!("Bad request: {}", msg),
             )
                 .into_response(),
+            AddError::Mount(MountError::OutOfScope(path)) => (
+                http::StatusCode::FORBIDDEN,
+                format!("Outside your write scope: {}", path.display()),
+            )
+                .into_response(),
             AddError::Mount(_) => (
                 http::StatusCode::INTERNAL_SERVER_ERROR,
                 "Unexpected error".to_string(),
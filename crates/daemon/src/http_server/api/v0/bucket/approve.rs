@@ -0,0 +1,117 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::prelude::MountError;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use common::linked_data::Hash;
+
+use super::proposals::{update_outcome, ProposalInfo};
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// Approve a membership change another owner proposed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApproveRequest {
+    pub bucket_id: Uuid,
+    /// Id of the proposal, as listed by `/api/v0/bucket/proposals`
+    pub proposal_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApproveResponse {
+    pub bucket_id: Uuid,
+    /// New bucket link, if our approval completed the quorum
+    pub new_bucket_link: Option<String>,
+    /// The proposal, if it still needs more approvals
+    pub proposal: Option<ProposalInfo>,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<ApproveRequest>,
+) -> Result<impl IntoResponse, ApproveError> {
+    let id: Hash = req
+        .proposal_id
+        .parse()
+        .map_err(|_| ApproveError::InvalidProposalId(req.proposal_id.clone()))?;
+    if state.peer().proposals().get(req.bucket_id, &id).is_none() {
+        return Err(ApproveError::ProposalNotFound(req.proposal_id));
+    }
+
+    let update = state
+        .peer()
+        .approve_membership_change(req.bucket_id, &id)
+        .await?;
+    let (new_bucket_link, proposal) = update_outcome(update);
+
+    tracing::info!(
+        "APPROVE API: Approved membership change {} for bucket {}, applied: {}",
+        req.proposal_id,
+        req.bucket_id,
+        new_bucket_link.is_some()
+    );
+
+    Ok((
+        http::StatusCode::OK,
+        Json(ApproveResponse {
+            bucket_id: req.bucket_id,
+            new_bucket_link,
+            proposal,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApproveError {
+    #[error("Invalid proposal id: {0}")]
+    InvalidProposalId(String),
+    #[error("Proposal not found: {0}")]
+    ProposalNotFound(String),
+    #[error("Mount error: {0}")]
+    Mount(#[from] MountError),
+}
+
+impl IntoResponse for ApproveError {
+    fn into_response(self) -> Response {
+        match self {
+            ApproveError::InvalidProposalId(id) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Invalid proposal id: {}", id),
+            )
+                .into_response(),
+            ApproveError::ProposalNotFound(id) => (
+                http::StatusCode::NOT_FOUND,
+                format!("Proposal not found: {}", id),
+            )
+                .into_response(),
+            ApproveError::Mount(MountError::Membership(e)) => (
+                http::StatusCode::FORBIDDEN,
+                format!("Cannot approve: {}", e),
+            )
+                .into_response(),
+            ApproveError::Mount(MountError::StaleMembershipChange) => (
+                http::StatusCode::CONFLICT,
+                "The bucket's shares changed since the proposal was made".to_string(),
+            )
+                .into_response(),
+            ApproveError::Mount(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Unexpected error".to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for ApproveRequest {
+    type Response = ApproveResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/approve").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -106,9 +106,7 @@ async fn handle_cat_request(state: ServiceState, req: CatRequest) -> Result<CatR
         match hash_str.parse::<common::linked_data::Hash>() {
             Ok(hash) => {
                 let link = common::linked_data::Link::new(common::linked_data::LD_RAW_CODEC, hash);
-                match common::mount::Mount::load(&link, state.peer().secret(), state.peer().blobs())
-                    .await
-                {
+                match state.peer().load_mount(req.bucket_id, &link).await {
                     Ok(mount) => mount,
                     Err(e) => {
                         tracing::error!("Failed to load mount from link: {}", e);
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::mount::CompressionPolicy;
+use common::prelude::MountError;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// View or change a bucket's compression policy.
+///
+/// A request with no changes just returns the current policy. Otherwise the
+/// changes are applied on top of the current policy (or the default one when
+/// compression was off) and the bucket is saved.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompressionRequest {
+    pub bucket_id: Uuid,
+    /// Turn compression off entirely
+    #[serde(default)]
+    pub disable: bool,
+    /// Turn compression on (with the default policy if it was off)
+    #[serde(default)]
+    pub enable: bool,
+    /// zstd level (1-22)
+    #[serde(default)]
+    pub level: Option<i32>,
+    /// Per-MIME overrides: `true` forces compression, `false` disables it,
+    /// `null` removes the override
+    #[serde(default)]
+    pub overrides: BTreeMap<String, Option<bool>>,
+}
+
+impl CompressionRequest {
+    fn has_changes(&self) -> bool {
+        self.disable || self.enable || self.level.is_some() || !self.overrides.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionResponse {
+    pub bucket_id: Uuid,
+    /// Policy now in effect; `None` means compression is off
+    pub policy: Option<CompressionPolicy>,
+    /// New bucket link, if the policy changed
+    pub new_bucket_link: Option<String>,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<CompressionRequest>,
+) -> Result<impl IntoResponse, CompressionError> {
+    let mount = state.peer().mount(req.bucket_id).await?;
+    let current = mount.compression().await;
+
+    if !req.has_changes() {
+        return Ok((
+            http::StatusCode::OK,
+            Json(CompressionResponse {
+                bucket_id: req.bucket_id,
+                policy: current,
+                new_bucket_link: None,
+            }),
+        )
+            .into_response());
+    }
+
+    if req.disable && (req.enable || req.level.is_some() || !req.overrides.is_empty()) {
+        return Err(CompressionError::Conflicting);
+    }
+
+    let policy = if req.disable {
+        None
+    } else {
+        let mut policy = current.clone().unwrap_or_default();
+        if let Some(level) = req.level {
+            policy.set_level(level);
+        }
+        for (pattern, compress) in &req.overrides {
+            match compress {
+                Some(compress) => policy.set_override(pattern.clone(), *compress),
+                None => policy.remove_override(pattern),
+            }
+        }
+        Some(policy)
+    };
+
+    tracing::info!(
+        "COMPRESSION API: Setting compression for bucket {} to {:?}",
+        req.bucket_id,
+        policy
+    );
+
+    mount.set_compression(policy.clone()).await;
+    let new_bucket_link = state.peer().save_mount(&mount, false).await?;
+
+    Ok((
+        http::StatusCode::OK,
+        Json(CompressionResponse {
+            bucket_id: req.bucket_id,
+            policy,
+            new_bucket_link: Some(new_bucket_link.hash().to_string()),
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CompressionError {
+    #[error("Mount error: {0}")]
+    Mount(#[from] MountError),
+    #[error("Cannot disable compression and change its settings at the same time")]
+    Conflicting,
+}
+
+impl IntoResponse for CompressionError {
+    fn into_response(self) -> Response {
+        match self {
+            CompressionError::Mount(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Unexpected error".to_string(),
+            )
+                .into_response(),
+            CompressionError::Conflicting => {
+                (http::StatusCode::BAD_REQUEST, self.to_string()).into_response()
+            }
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for CompressionRequest {
+    type Response = CompressionResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/compression").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
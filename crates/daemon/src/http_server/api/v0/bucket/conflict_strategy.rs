@@ -0,0 +1,88 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::mount::ConflictStrategy;
+use common::prelude::MountError;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// View or set how sync resolves conflicts when merging diverged histories.
+///
+/// Without a `strategy` this just returns the current one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictStrategyRequest {
+    pub bucket_id: Uuid,
+    #[serde(default)]
+    pub strategy: Option<ConflictStrategy>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictStrategyResponse {
+    pub bucket_id: Uuid,
+    pub strategy: ConflictStrategy,
+    /// New bucket link, if the strategy changed
+    pub new_bucket_link: Option<String>,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<ConflictStrategyRequest>,
+) -> Result<impl IntoResponse, ConflictStrategyError> {
+    let mount = state.peer().mount(req.bucket_id).await?;
+    let current = mount.conflict_strategy().await;
+
+    let new_bucket_link = match req.strategy {
+        Some(strategy) if strategy != current => {
+            tracing::info!(
+                "CONFLICT STRATEGY API: Setting strategy for bucket {} to {}",
+                req.bucket_id,
+                strategy
+            );
+            mount.set_conflict_strategy(strategy).await;
+            let link = state.peer().save_mount(&mount, false).await?;
+            Some(link.hash().to_string())
+        }
+        _ => None,
+    };
+
+    Ok((
+        http::StatusCode::OK,
+        Json(ConflictStrategyResponse {
+            bucket_id: req.bucket_id,
+            strategy: req.strategy.unwrap_or(current),
+            new_bucket_link,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConflictStrategyError {
+    #[error("Mount error: {0}")]
+    Mount(#[from] MountError),
+}
+
+impl IntoResponse for ConflictStrategyError {
+    fn into_response(self) -> Response {
+        match self {
+            ConflictStrategyError::Mount(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Unexpected error".to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for ConflictStrategyRequest {
+    type Response = ConflictStrategyResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/conflict-strategy").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
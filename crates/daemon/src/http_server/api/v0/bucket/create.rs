@@ -75,7 +75,7 @@ pub async fn handler(
     state
         .peer()
         .logs()
-        .append(id, req.name.clone(), genesis_link.clone(), None, 0, false)
+        .append(id, req.name.clone(), genesis_link.clone(), vec![], 0, false)
         .await
         .map_err(|e| {
             tracing::error!(
@@ -102,6 +102,11 @@ impl IntoResponse for DeleteError {
                 format!("Path not found: {}", msg),
             )
                 .into_response(),
+            DeleteError::Mount(MountError::OutOfScope(path)) => (
+                http::StatusCode::FORBIDDEN,
+                format!("Outside your write scope: {}", path.display()),
+            )
+                .into_response(),
             DeleteError::Mount(_) => (
                 http::StatusCode::INTERNAL_SERVER_ERROR,
                 "Unexpected error".to_string(),
@@ -0,0 +1,157 @@
+use std::path::PathBuf;
+
+use axum::extract::{Multipart, State};
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use common::mount::DepositError as MountDepositError;
+use common::prelude::Link;
+
+use crate::ServiceState;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositResponse {
+    pub bucket_id: Uuid,
+    /// Link to the deposit object, fetched from us by the accepting owner
+    pub deposit_link: Link,
+    /// Where the file will appear in the bucket
+    pub path: String,
+    /// Public key of the owner that accepted the deposit
+    pub accepted_by: String,
+}
+
+/// Submit a file to a bucket's inbox as a depositor.
+///
+/// Multipart fields: `bucket_id`, `file`, and an optional `path` inside the
+/// inbox (defaults to the file's name).
+pub async fn handler(
+    State(state): State<ServiceState>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, DepositError> {
+    let mut bucket_id: Option<Uuid> = None;
+    let mut path: Option<String> = None;
+    let mut file: Option<(String, Vec<u8>)> = None;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| DepositError::MultipartError(e.to_string()))?
+    {
+        let field_name = field.name().unwrap_or("").to_string();
+
+        match field_name.as_str() {
+            "bucket_id" => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|e| DepositError::MultipartError(e.to_string()))?;
+                bucket_id = Some(
+                    Uuid::parse_str(&text)
+                        .map_err(|_| DepositError::InvalidRequest("Invalid bucket_id".into()))?,
+                );
+            }
+            "path" => {
+                path = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(|e| DepositError::MultipartError(e.to_string()))?,
+                );
+            }
+            "file" => {
+                let filename = field
+                    .file_name()
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "unnamed".to_string());
+                let data = field
+                    .bytes()
+                    .await
+                    .map_err(|e| DepositError::MultipartError(e.to_string()))?
+                    .to_vec();
+                file = Some((filename, data));
+            }
+            _ => {
+                tracing::warn!("Ignoring unknown field: {}", field_name);
+            }
+        }
+    }
+
+    let bucket_id =
+        bucket_id.ok_or_else(|| DepositError::InvalidRequest("bucket_id is required".into()))?;
+    let (filename, data) =
+        file.ok_or_else(|| DepositError::InvalidRequest("file is required".into()))?;
+    let path = PathBuf::from(path.unwrap_or(filename));
+
+    let (deposit, deposit_link, owner) = state
+        .peer()
+        .deposit(bucket_id, &path, &data)
+        .await
+        .map_err(|e| match e.downcast::<MountDepositError>() {
+            Ok(e) => DepositError::Deposit(e),
+            Err(e) => DepositError::NotAccepted(e.to_string()),
+        })?;
+
+    tracing::info!(
+        "DEPOSIT API: Deposited {} ({} bytes) to bucket {}, accepted by {}",
+        deposit.inbox_path().display(),
+        data.len(),
+        bucket_id,
+        owner.to_hex()
+    );
+
+    Ok((
+        http::StatusCode::OK,
+        axum::Json(DepositResponse {
+            bucket_id,
+            deposit_link,
+            path: deposit.inbox_path().display().to_string(),
+            accepted_by: owner.to_hex(),
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DepositError {
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("Multipart error: {0}")]
+    MultipartError(String),
+    #[error("Deposit error: {0}")]
+    Deposit(#[from] MountDepositError),
+    #[error("Deposit not accepted: {0}")]
+    NotAccepted(String),
+}
+
+impl IntoResponse for DepositError {
+    fn into_response(self) -> Response {
+        match self {
+            DepositError::InvalidRequest(msg) | DepositError::MultipartError(msg) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Bad request: {}", msg),
+            )
+                .into_response(),
+            DepositError::Deposit(MountDepositError::InvalidPath(path)) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Invalid inbox path: {}", path.display()),
+            )
+                .into_response(),
+            DepositError::Deposit(MountDepositError::NotDepositor) => (
+                http::StatusCode::FORBIDDEN,
+                "You are not a depositor of this bucket".to_string(),
+            )
+                .into_response(),
+            DepositError::Deposit(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Unexpected error".to_string(),
+            )
+                .into_response(),
+            DepositError::NotAccepted(msg) => (
+                http::StatusCode::BAD_GATEWAY,
+                format!("Deposit not accepted: {}", msg),
+            )
+                .into_response(),
+        }
+    }
+}
@@ -54,13 +54,14 @@ pub async fn handler(
         .await
         .map_err(|e| ExportError::BucketLog(e.to_string()))?;
 
-    let blobs = state.node().blobs();
-    let secret_key = state.node().secret();
-
-    // Load the mount
-    let mount = Mount::load(&head_link, secret_key, blobs)
+    // Load the mount, reading through the bucket's blobs so lazily
+    // synced content is fetched as it is exported
+    let mount = state
+        .peer()
+        .load_mount(req.bucket_id, &head_link)
         .await
         .map_err(ExportError::Mount)?;
+    let blobs = mount.blobs();
 
     // Get the bucket name from the mount's manifest
     let mount_inner = mount.inner().await;
@@ -72,7 +73,7 @@ pub async fn handler(
     // Export all files from the mount to the filesystem
     let mut hash_map = PathHashMap::new();
     let files_exported =
-        export_mount_to_filesystem(&mount, &req.target_dir, blobs, &mut hash_map).await?;
+        export_mount_to_filesystem(&mount, &req.target_dir, &blobs, &mut hash_map).await?;
 
     tracing::info!(
         "EXPORT: Successfully exported {} files from bucket {}",
@@ -110,7 +111,7 @@ async fn export_mount_to_filesystem(
 
     for (path, node_link) in items {
         match node_link {
-            NodeLink::Data(link, secret, _) => {
+            NodeLink::Data(ref link, ref secret, _) => {
                 // This is a file - export it
                 let target_path = target_dir.join(&path);
 
@@ -131,7 +132,7 @@ async fn export_mount_to_filesystem(
                     .map_err(|e| ExportError::Decryption(e.to_string()))?;
 
                 // Decrypt and write file
-                let decrypted_data = secret
+                let decrypted_data = node_link
                     .decrypt(&encrypted_data)
                     .map_err(|e| ExportError::Decryption(e.to_string()))?;
 
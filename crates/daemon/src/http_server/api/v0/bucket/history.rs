@@ -38,6 +38,9 @@ pub struct HistoryEntry {
     pub published: bool,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
+    /// Parent version hashes, primary parent first; more than one means a merge
+    #[serde(default)]
+    pub parents: Vec<String>,
 }
 
 pub async fn handler(
@@ -60,6 +63,7 @@ pub async fn handler(
             height: e.height,
             published: e.published,
             created_at: e.created_at,
+            parents: e.parents.iter().map(|p| p.to_string()).collect(),
         })
         .collect();
 
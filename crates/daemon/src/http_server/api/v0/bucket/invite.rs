@@ -0,0 +1,106 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::peer::invites::DEFAULT_INVITE_TTL_SECS;
+use common::prelude::MountError;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::share::ShareRole;
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// Issue a one-time ticket another peer can redeem to join a bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteRequest {
+    pub bucket_id: Uuid,
+    /// Role the peer joins with, defaults to owner
+    #[serde(default)]
+    pub role: ShareRole,
+    /// Seconds until the ticket expires, defaults to 24 hours
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteResponse {
+    pub bucket_id: Uuid,
+    pub role: ShareRole,
+    /// The ticket to hand to the peer
+    pub ticket: String,
+    /// When the ticket expires, in seconds since the Unix epoch
+    pub expires_at: u64,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<InviteRequest>,
+) -> Result<impl IntoResponse, InviteError> {
+    let ttl = req.expires_in.unwrap_or(DEFAULT_INVITE_TTL_SECS);
+    if ttl == 0 {
+        return Err(InviteError::InvalidExpiry);
+    }
+
+    let (ticket, expires_at) = state
+        .peer()
+        .invite(req.bucket_id, req.role.into(), ttl)
+        .await?;
+
+    tracing::info!(
+        "INVITE API: Issued {:?} invite for bucket {}, expires at {}",
+        req.role,
+        req.bucket_id,
+        expires_at
+    );
+
+    Ok((
+        http::StatusCode::OK,
+        Json(InviteResponse {
+            bucket_id: req.bucket_id,
+            role: req.role,
+            ticket: ticket.to_string(),
+            expires_at,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum InviteError {
+    #[error("Invalid expiry: must be at least one second")]
+    InvalidExpiry,
+    #[error("Mount error: {0}")]
+    Mount(#[from] MountError),
+}
+
+impl IntoResponse for InviteError {
+    fn into_response(self) -> Response {
+        match self {
+            InviteError::InvalidExpiry => (
+                http::StatusCode::BAD_REQUEST,
+                "Invalid expiry: must be at least one second".to_string(),
+            )
+                .into_response(),
+            InviteError::Mount(MountError::Unauthorized) => (
+                http::StatusCode::FORBIDDEN,
+                "Unauthorized: only unscoped owners can invite peers".to_string(),
+            )
+                .into_response(),
+            InviteError::Mount(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Unexpected error".to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for InviteRequest {
+    type Response = InviteResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/invite").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+use common::clock::unix_now;
+use common::mount::CapabilityError;
+use common::prelude::MountError;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::http_server::api::client::ApiRequest;
+use crate::http_server::Config;
+use crate::ServiceState;
+
+/// Mint a capability link that lets anyone holding it read one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkRequest {
+    pub bucket_id: Uuid,
+    /// Absolute path of the file
+    pub path: PathBuf,
+    /// Seconds until the link stops working, never if unset
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkResponse {
+    pub bucket_id: Uuid,
+    pub path: PathBuf,
+    /// The encoded capability, served by gateways at `/gw/cap/<capability>`
+    pub capability: String,
+    /// Gateway URL for the capability, if the daemon knows its gateway URL
+    pub url: Option<String>,
+    /// When the link stops working, in seconds since the Unix epoch
+    pub expires_at: Option<u64>,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Extension(config): Extension<Config>,
+    Json(req): Json<LinkRequest>,
+) -> Result<impl IntoResponse, LinkError> {
+    if !req.path.is_absolute() {
+        return Err(LinkError::InvalidPath(req.path));
+    }
+    let expires_at = match req.expires_in {
+        Some(0) => return Err(LinkError::InvalidExpiry),
+        Some(ttl) => Some(unix_now() + ttl),
+        None => None,
+    };
+
+    let mount = state.peer().mount_for_read(req.bucket_id).await?;
+    let capability = mount
+        .file_capability(&req.path, expires_at)
+        .await?
+        .to_string();
+    let url = config.gateway_url.as_ref().map(|gateway_url| {
+        format!(
+            "{}/gw/cap/{}",
+            gateway_url.trim_end_matches('/'),
+            capability
+        )
+    });
+
+    tracing::info!(
+        "LINK API: Minted capability for {} in bucket {}, expires at {:?}",
+        req.path.display(),
+        req.bucket_id,
+        expires_at
+    );
+
+    Ok((
+        http::StatusCode::OK,
+        Json(LinkResponse {
+            bucket_id: req.bucket_id,
+            path: req.path,
+            capability,
+            url,
+            expires_at,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LinkError {
+    #[error("Path must be absolute: {0}")]
+    InvalidPath(PathBuf),
+    #[error("Invalid expiry: must be at least one second")]
+    InvalidExpiry,
+    #[error("Mount error: {0}")]
+    Mount(#[from] MountError),
+}
+
+impl IntoResponse for LinkError {
+    fn into_response(self) -> Response {
+        match self {
+            LinkError::InvalidPath(path) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Path must be absolute: {}", path.display()),
+            )
+                .into_response(),
+            LinkError::InvalidExpiry => (
+                http::StatusCode::BAD_REQUEST,
+                "Invalid expiry: must be at least one second".to_string(),
+            )
+                .into_response(),
+            LinkError::Mount(MountError::PathNotFound(path)) => (
+                http::StatusCode::NOT_FOUND,
+                format!("Path not found: {}", path.display()),
+            )
+                .into_response(),
+            LinkError::Mount(MountError::Capability(CapabilityError::NotAFile)) => (
+                http::StatusCode::BAD_REQUEST,
+                "Links can only be made for files".to_string(),
+            )
+                .into_response(),
+            LinkError::Mount(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Unexpected error".to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for LinkRequest {
+    type Response = LinkResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/link").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
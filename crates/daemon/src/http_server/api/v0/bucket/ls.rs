@@ -58,7 +58,7 @@ pub async fn handler(
             .parse::<common::linked_data::Hash>()
             .map_err(|e| LsError::InvalidHash(format!("Invalid hash format: {}", e)))?;
         let link = common::linked_data::Link::new(common::linked_data::LD_RAW_CODEC, hash);
-        common::mount::Mount::load(&link, state.peer().secret(), state.peer().blobs()).await?
+        state.peer().load_mount(req.bucket_id, &link).await?
     } else {
         // Load mount based on role (owners see HEAD, mirrors see latest_published)
         state.peer().mount_for_read(req.bucket_id).await?
@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::bucket_log::BucketLogProvider;
+use common::prelude::{Mount, MountError};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// Walk a bucket's manifest chain and list every change to its membership.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MembershipHistoryRequest {
+    pub bucket_id: Uuid,
+    /// Check the signature on each version along the way
+    #[serde(default)]
+    pub verify: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MembershipHistoryResponse {
+    pub bucket_id: Uuid,
+    /// Changes, oldest first
+    pub events: Vec<MembershipEventInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MembershipEventInfo {
+    /// Hex-encoded public key whose access changed
+    pub principal: String,
+    /// One of `added`, `removed`, `role_changed` or `updated`
+    pub change: String,
+    /// Role after the change, or before it for removals
+    pub role: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_role: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub write_scope: Vec<PathBuf>,
+    /// When access ends, in seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    /// Hex-encoded key of the peer that signed the version
+    pub author: Option<String>,
+    /// Owners who approved the change, if the bucket has a quorum; when
+    /// verifying, only those whose approval signature is valid
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approved_by: Vec<String>,
+    pub height: u64,
+    /// Hash of the version that made the change
+    pub link_hash: String,
+    /// Whether the version's signature checked out; unset unless verifying
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<MembershipHistoryRequest>,
+) -> Result<impl IntoResponse, MembershipHistoryError> {
+    let (head, _) = state
+        .peer()
+        .logs()
+        .head(req.bucket_id, None)
+        .await
+        .map_err(|_| MembershipHistoryError::BucketNotFound(req.bucket_id))?;
+
+    let events = Mount::membership_history(&head, state.peer().blobs(), req.verify)
+        .await?
+        .into_iter()
+        .map(|event| MembershipEventInfo {
+            principal: event.principal.to_hex(),
+            change: event.kind.to_string(),
+            role: event.role.to_string(),
+            previous_role: event.previous_role.map(|role| role.to_string()),
+            write_scope: event.write_scope,
+            expires_at: event.expires_at,
+            author: event.author.map(|author| author.to_hex()),
+            approved_by: event.approved_by.iter().map(|key| key.to_hex()).collect(),
+            height: event.height,
+            link_hash: event.link.hash().to_string(),
+            verified: event.verified,
+        })
+        .collect();
+
+    Ok((
+        http::StatusCode::OK,
+        Json(MembershipHistoryResponse {
+            bucket_id: req.bucket_id,
+            events,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MembershipHistoryError {
+    #[error("Bucket not found: {0}")]
+    BucketNotFound(Uuid),
+    #[error("Mount error: {0}")]
+    Mount(#[from] MountError),
+}
+
+impl IntoResponse for MembershipHistoryError {
+    fn into_response(self) -> Response {
+        match self {
+            MembershipHistoryError::BucketNotFound(id) => (
+                http::StatusCode::NOT_FOUND,
+                format!("Bucket not found: {}", id),
+            )
+                .into_response(),
+            MembershipHistoryError::Mount(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Error: {}", self),
+            )
+                .into_response(),
+        }
+    }
+}
+
+impl ApiRequest for MembershipHistoryRequest {
+    type Response = MembershipHistoryResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/membership-history").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -56,11 +56,18 @@ pub enum MkdirError {
 
 impl IntoResponse for MkdirError {
     fn into_response(self) -> axum::response::Response {
-        (
-            http::StatusCode::INTERNAL_SERVER_ERROR,
-            "Unexpected error".to_string(),
-        )
-            .into_response()
+        match self {
+            MkdirError::Mount(MountError::OutOfScope(path)) => (
+                http::StatusCode::FORBIDDEN,
+                format!("Outside your write scope: {}", path.display()),
+            )
+                .into_response(),
+            MkdirError::Mount(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Unexpected error".to_string(),
+            )
+                .into_response(),
+        }
     }
 }
 
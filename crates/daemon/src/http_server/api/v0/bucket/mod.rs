@@ -3,23 +3,36 @@ use axum::Router;
 
 use crate::ServiceState;
 
+pub mod accept;
 pub mod add;
+pub mod approve;
 pub mod cat;
+pub mod compression;
+pub mod conflict_strategy;
 pub mod create;
 pub mod delete;
+pub mod deposit;
 pub mod export;
 pub mod history;
+pub mod invite;
 pub mod latest_published;
+pub mod link;
 pub mod list;
 pub mod ls;
+pub mod membership_history;
 pub mod mkdir;
 pub mod mv;
+pub mod offline;
 pub mod ping;
+pub mod proposals;
 pub mod publish;
+pub mod quorum;
 pub mod rename;
 pub mod share;
 pub mod shares;
 pub mod stat;
+pub mod sync_mode;
+pub mod sync_status;
 pub mod unshare;
 pub mod update;
 
@@ -35,6 +48,7 @@ pub fn router(state: ServiceState) -> Router<ServiceState> {
         .route("/", post(create::handler))
         .route("/list", post(list::handler))
         .route("/add", post(add::handler))
+        .route("/deposit", post(deposit::handler))
         .route("/update", post(update::handler))
         .route("/rename", post(rename::handler))
         .route("/mv", post(mv::handler))
@@ -46,10 +60,22 @@ pub fn router(state: ServiceState) -> Router<ServiceState> {
         .route("/share", post(share::handler))
         .route("/shares", post(shares::handler))
         .route("/unshare", post(unshare::handler))
+        .route("/quorum", post(quorum::handler))
+        .route("/proposals", post(proposals::handler))
+        .route("/approve", post(approve::handler))
+        .route("/invite", post(invite::handler))
+        .route("/accept", post(accept::handler))
         .route("/publish", post(publish::handler))
+        .route("/link", post(link::handler))
+        .route("/compression", post(compression::handler))
+        .route("/conflict-strategy", post(conflict_strategy::handler))
         .route("/export", post(export::handler))
         .route("/latest-published", post(latest_published::handler))
         .route("/history", post(history::handler))
+        .route("/membership-history", post(membership_history::handler))
         .route("/stat", post(stat::handler))
+        .route("/sync-status", post(sync_status::handler))
+        .route("/sync-mode", post(sync_mode::handler))
+        .route("/offline", post(offline::handler))
         .with_state(state)
 }
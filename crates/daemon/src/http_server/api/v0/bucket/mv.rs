@@ -124,6 +124,11 @@ impl IntoResponse for MvError {
                 ),
             )
                 .into_response(),
+            MvError::Mount(MountError::OutOfScope(path)) => (
+                http::StatusCode::FORBIDDEN,
+                format!("Outside your write scope: {}", path.display()),
+            )
+                .into_response(),
             MvError::Mount(_) => (
                 http::StatusCode::INTERNAL_SERVER_ERROR,
                 "Unexpected error".to_string(),
@@ -0,0 +1,135 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::prelude::MountError;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::database::models::OfflinePath;
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// Manage the paths of a lazily synced bucket that are kept available offline.
+///
+/// Adding a path downloads its contents right away and again on every sync.
+/// Removing one stops future downloads but keeps what is already local.
+/// Without a `path` this just lists the offline paths.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineRequest {
+    pub bucket_id: Uuid,
+    /// Absolute path of a file or directory in the bucket
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Stop keeping `path` offline instead of adding it
+    #[serde(default)]
+    pub remove: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineResponse {
+    pub bucket_id: Uuid,
+    /// The bucket's offline paths after the change
+    pub paths: Vec<String>,
+    /// Files downloaded or already local under an added path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<usize>,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<OfflineRequest>,
+) -> Result<impl IntoResponse, OfflineError> {
+    let db = state.database();
+
+    let files = match &req.path {
+        Some(path) if !path.starts_with('/') => {
+            return Err(OfflineError::InvalidPath(format!(
+                "Path must be absolute: {}",
+                path
+            )))
+        }
+        Some(path) if req.remove => {
+            OfflinePath::remove(req.bucket_id, path, db)
+                .await
+                .map_err(|e| OfflineError::Database(e.to_string()))?;
+            None
+        }
+        Some(path) => {
+            let files = state
+                .peer()
+                .make_available_offline(req.bucket_id, std::path::Path::new(path))
+                .await?;
+            OfflinePath::add(req.bucket_id, path, db)
+                .await
+                .map_err(|e| OfflineError::Database(e.to_string()))?;
+            tracing::info!(
+                "OFFLINE API: Made {} ({} files) of bucket {} available offline",
+                path,
+                files,
+                req.bucket_id
+            );
+            Some(files)
+        }
+        None => None,
+    };
+
+    let paths = OfflinePath::list(req.bucket_id, db)
+        .await
+        .map_err(|e| OfflineError::Database(e.to_string()))?
+        .into_iter()
+        .map(|p| p.path)
+        .collect();
+
+    Ok((
+        http::StatusCode::OK,
+        Json(OfflineResponse {
+            bucket_id: req.bucket_id,
+            paths,
+            files,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OfflineError {
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
+    #[error("Database error: {0}")]
+    Database(String),
+    #[error("Mount error: {0}")]
+    Mount(#[from] MountError),
+}
+
+impl IntoResponse for OfflineError {
+    fn into_response(self) -> Response {
+        match self {
+            OfflineError::InvalidPath(msg) => (http::StatusCode::BAD_REQUEST, msg).into_response(),
+            OfflineError::Mount(MountError::PathNotFound(path)) => (
+                http::StatusCode::NOT_FOUND,
+                format!("Path not found: {}", path.display()),
+            )
+                .into_response(),
+            OfflineError::Database(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", msg),
+            )
+                .into_response(),
+            OfflineError::Mount(e) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to download path: {}", e),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for OfflineRequest {
+    type Response = OfflineResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/offline").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -0,0 +1,158 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::prelude::MountError;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use common::linked_data::Hash;
+use common::mount::{Member, MembershipChange};
+use common::peer::MembershipUpdate;
+
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// List the membership changes waiting for owner approvals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalsRequest {
+    pub bucket_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalsResponse {
+    pub bucket_id: Uuid,
+    /// Owner approvals needed to change the shares
+    pub quorum: u32,
+    pub proposals: Vec<ProposalInfo>,
+}
+
+/// A proposed membership change and the approvals it has so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalInfo {
+    /// Identifies the change when approving it
+    pub id: String,
+    /// Membership epoch the change moves the bucket to
+    pub epoch: u64,
+    /// Principals the change adds, removes or alters
+    pub members: Vec<MemberChange>,
+    pub quorum_before: u32,
+    pub quorum_after: u32,
+    /// Public keys of the owners that approved the change
+    pub approved_by: Vec<String>,
+    /// Approvals the change needs
+    pub required: u32,
+}
+
+/// A principal's access before and after a change, `None` when absent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberChange {
+    pub public_key: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl ProposalInfo {
+    pub fn new(id: &Hash, change: &MembershipChange) -> Self {
+        let (from, to) = (change.from().members(), change.to().members());
+        let members = from
+            .keys()
+            .chain(to.keys().filter(|key| !from.contains_key(*key)))
+            .filter(|key| from.get(*key) != to.get(*key))
+            .map(|key| MemberChange {
+                public_key: key.clone(),
+                before: from.get(key).map(describe),
+                after: to.get(key).map(describe),
+            })
+            .collect();
+
+        Self {
+            id: id.to_string(),
+            epoch: change.epoch(),
+            members,
+            quorum_before: change.from().quorum(),
+            quorum_after: change.to().quorum(),
+            approved_by: change.approvals().keys().cloned().collect(),
+            required: change.required(),
+        }
+    }
+}
+
+/// Split the result of a membership change into the new bucket link, if it
+/// was saved, and the proposal, if it waits for approvals
+pub fn update_outcome(update: MembershipUpdate) -> (Option<String>, Option<ProposalInfo>) {
+    match update {
+        MembershipUpdate::Saved(link) => (Some(link.hash().to_string()), None),
+        MembershipUpdate::Proposed { id, change } => (None, Some(ProposalInfo::new(&id, &change))),
+    }
+}
+
+/// Describe a member's role, write scope and expiry
+fn describe(member: &Member) -> String {
+    let mut description = member.role.to_string();
+    if !member.write_scope.is_empty() {
+        let scope: Vec<String> = member
+            .write_scope
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        description.push_str(&format!(" scoped to {}", scope.join(", ")));
+    }
+    if let Some(expires_at) = member.expires_at {
+        description.push_str(&format!(" until {}", expires_at));
+    }
+    description
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<ProposalsRequest>,
+) -> Result<impl IntoResponse, ProposalsError> {
+    let mount = state.peer().mount(req.bucket_id).await?;
+    let manifest = mount.inner().await.manifest().clone();
+
+    let proposals = state
+        .peer()
+        .proposals()
+        .list(&manifest)
+        .iter()
+        .map(|(id, change)| ProposalInfo::new(id, change))
+        .collect();
+
+    Ok((
+        http::StatusCode::OK,
+        Json(ProposalsResponse {
+            bucket_id: req.bucket_id,
+            quorum: manifest.quorum(),
+            proposals,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProposalsError {
+    #[error("Mount error: {0}")]
+    Mount(#[from] MountError),
+}
+
+impl IntoResponse for ProposalsError {
+    fn into_response(self) -> Response {
+        match self {
+            ProposalsError::Mount(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Unexpected error".to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for ProposalsRequest {
+    type Response = ProposalsResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/proposals").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use axum::extract::{Json, State};
 use axum::response::{IntoResponse, Response};
 use common::mount::PrincipalRole;
@@ -14,12 +16,21 @@ pub struct PublishRequest {
     /// Bucket ID to publish
     #[arg(long)]
     pub bucket_id: Uuid,
+
+    /// Publish only these directories instead of the whole bucket (repeatable)
+    #[arg(long = "path")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublishResponse {
     pub bucket_id: Uuid,
+    /// Whether the whole bucket is published
     pub published: bool,
+    /// Directories published on their own
+    #[serde(default)]
+    pub published_paths: Vec<PathBuf>,
     pub new_bucket_link: String,
 }
 
@@ -49,13 +60,28 @@ pub async fn handler(
         // Still return success, just note it's already published
     }
 
-    // Save mount with publish=true (grants decryption to all mirrors)
+    // Publishing subtrees leaves the rest of the bucket as it was
+    for path in &req.paths {
+        if !path.is_absolute() {
+            return Err(PublishError::InvalidPath(path.clone()));
+        }
+        mount.publish_path(path).await?;
+    }
+    let publish = req.paths.is_empty() || mount.is_published().await;
+
+    // Save mount, publishing the whole bucket (grants decryption to all
+    // mirrors) unless only subtrees were asked for
     // This saves to blobs, appends to log, and notifies peers
-    let new_bucket_link = state.peer().save_mount(&mount, true).await?;
+    let new_bucket_link = state.peer().save_mount(&mount, publish).await?;
 
     tracing::info!(
-        "PUBLISH API: Bucket {} published, new link: {}",
+        "PUBLISH API: Bucket {} published{}, new link: {}",
         req.bucket_id,
+        if req.paths.is_empty() {
+            String::new()
+        } else {
+            format!(" at {:?}", req.paths)
+        },
         new_bucket_link.hash()
     );
 
@@ -63,7 +89,8 @@ pub async fn handler(
         http::StatusCode::OK,
         Json(PublishResponse {
             bucket_id: req.bucket_id,
-            published: true,
+            published: publish,
+            published_paths: mount.published_paths().await,
             new_bucket_link: new_bucket_link.hash().to_string(),
         }),
     )
@@ -76,11 +103,38 @@ pub enum PublishError {
     Mount(#[from] MountError),
     #[error("Only the bucket owner can publish")]
     NotOwner,
+    #[error("Path must be absolute: {0}")]
+    InvalidPath(PathBuf),
 }
 
 impl IntoResponse for PublishError {
     fn into_response(self) -> Response {
         match self {
+            PublishError::InvalidPath(path) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Path must be absolute: {}", path.display()),
+            )
+                .into_response(),
+            PublishError::Mount(MountError::PathNotFound(path)) => (
+                http::StatusCode::NOT_FOUND,
+                format!("Path not found: {}", path.display()),
+            )
+                .into_response(),
+            PublishError::Mount(MountError::PathNotNode(path)) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Only directories can be published: {}", path.display()),
+            )
+                .into_response(),
+            PublishError::Mount(MountError::PublishRoot) => (
+                http::StatusCode::BAD_REQUEST,
+                "Publish the whole bucket by leaving out paths".to_string(),
+            )
+                .into_response(),
+            PublishError::Mount(MountError::OutOfScope(path)) => (
+                http::StatusCode::FORBIDDEN,
+                format!("Path is outside this key's write scope: {}", path.display()),
+            )
+                .into_response(),
             PublishError::Mount(_) => (
                 http::StatusCode::INTERNAL_SERVER_ERROR,
                 "Unexpected error".to_string(),
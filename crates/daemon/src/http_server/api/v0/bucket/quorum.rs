@@ -0,0 +1,107 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use common::mount::MembershipError;
+use common::prelude::MountError;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::proposals::{update_outcome, ProposalInfo};
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// View or set how many owners must approve changes to a bucket's shares.
+///
+/// Without a `quorum` this just returns the current one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuorumRequest {
+    pub bucket_id: Uuid,
+    #[serde(default)]
+    pub quorum: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuorumResponse {
+    pub bucket_id: Uuid,
+    pub quorum: u32,
+    /// New bucket link, if the quorum changed
+    pub new_bucket_link: Option<String>,
+    /// The change, if it needs approvals under the current quorum
+    pub proposal: Option<ProposalInfo>,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<QuorumRequest>,
+) -> Result<impl IntoResponse, QuorumError> {
+    let mount = state.peer().mount(req.bucket_id).await?;
+    let current = mount.quorum().await;
+
+    let (new_bucket_link, proposal) = match req.quorum {
+        Some(quorum) if quorum != current => {
+            tracing::info!(
+                "QUORUM API: Setting quorum for bucket {} to {}",
+                req.bucket_id,
+                quorum
+            );
+            mount.set_quorum(quorum).await?;
+            let publish = mount.is_published().await;
+            update_outcome(state.peer().save_membership(&mount, publish).await?)
+        }
+        _ => (None, None),
+    };
+
+    Ok((
+        http::StatusCode::OK,
+        Json(QuorumResponse {
+            bucket_id: req.bucket_id,
+            quorum: match (&new_bucket_link, req.quorum) {
+                (Some(_), Some(quorum)) => quorum,
+                _ => current,
+            },
+            new_bucket_link,
+            proposal,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum QuorumError {
+    #[error("Mount error: {0}")]
+    Mount(#[from] MountError),
+}
+
+impl IntoResponse for QuorumError {
+    fn into_response(self) -> Response {
+        match self {
+            QuorumError::Mount(MountError::Unauthorized) => (
+                http::StatusCode::FORBIDDEN,
+                "Unauthorized: only unscoped owners can set the quorum".to_string(),
+            )
+                .into_response(),
+            QuorumError::Mount(MountError::Membership(
+                e @ MembershipError::InvalidQuorum { .. },
+            )) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Invalid quorum: {}", e),
+            )
+                .into_response(),
+            QuorumError::Mount(_) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Unexpected error".to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for QuorumRequest {
+    type Response = QuorumResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/quorum").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use axum::extract::{Json, State};
 use axum::response::{IntoResponse, Response};
 use common::prelude::MountError;
@@ -6,6 +8,9 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use common::crypto::PublicKey;
+use common::mount::{MembershipError, PrincipalRole};
+
+use super::proposals::{update_outcome, ProposalInfo};
 
 use crate::http_server::api::client::ApiRequest;
 use crate::ServiceState;
@@ -19,6 +24,21 @@ pub enum ShareRole {
     Owner,
     /// Mirror - can sync but cannot decrypt until bucket is published
     Mirror,
+    /// Reader - gets encrypted share immediately, can decrypt but not write
+    Reader,
+    /// Depositor - can submit files to the inbox but cannot decrypt
+    Depositor,
+}
+
+impl From<ShareRole> for PrincipalRole {
+    fn from(role: ShareRole) -> Self {
+        match role {
+            ShareRole::Owner => PrincipalRole::Owner,
+            ShareRole::Mirror => PrincipalRole::Mirror,
+            ShareRole::Reader => PrincipalRole::Reader,
+            ShareRole::Depositor => PrincipalRole::Depositor,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, clap::Args)]
@@ -31,17 +51,30 @@ pub struct ShareRequest {
     #[arg(long)]
     pub peer_public_key: String,
 
-    /// Role for the peer (owner or mirror, defaults to owner)
+    /// Role for the peer (owner, mirror, reader or depositor, defaults to owner)
     #[arg(long, default_value = "owner")]
     #[serde(default)]
     pub role: ShareRole,
+
+    /// Restrict an owner to writing under these absolute paths (repeatable)
+    #[arg(long = "scope")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub write_scope: Vec<PathBuf>,
+
+    /// When access ends, in seconds since the Unix epoch
+    #[arg(long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShareResponse {
     pub bucket_id: Uuid,
     pub peer_public_key: String,
-    pub new_bucket_link: String,
+    /// New bucket link, if the change was saved
+    pub new_bucket_link: Option<String>,
+    /// The change, if it waits for other owners to approve it
+    pub proposal: Option<ProposalInfo>,
 }
 
 pub async fn handler(
@@ -61,6 +94,28 @@ pub async fn handler(
 
     tracing::info!("SHARE API: Parsed peer public key successfully");
 
+    if !req.write_scope.is_empty() && req.role != ShareRole::Owner {
+        return Err(ShareError::InvalidScope(
+            "only owners can have a write scope".to_string(),
+        ));
+    }
+    if let Some(path) = req.write_scope.iter().find(|path| !path.is_absolute()) {
+        return Err(ShareError::InvalidScope(format!(
+            "path must be absolute: {}",
+            path.display()
+        )));
+    }
+
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    if req
+        .expires_at
+        .is_some_and(|expires_at| expires_at as i64 <= now)
+    {
+        return Err(ShareError::InvalidExpiry(
+            "expiry must be in the future".to_string(),
+        ));
+    }
+
     // Load mount at current head
     let mut mount = state.peer().mount(req.bucket_id).await?;
     tracing::info!("SHARE API: Loaded mount for bucket {}", req.bucket_id);
@@ -69,9 +124,15 @@ pub async fn handler(
     match req.role {
         ShareRole::Owner => {
             mount.add_owner(peer_public_key).await?;
+            if !req.write_scope.is_empty() {
+                mount
+                    .set_write_scope(peer_public_key, req.write_scope.clone())
+                    .await?;
+            }
             tracing::info!(
-                "SHARE API: Mount.add_owner() completed for peer {}",
-                req.peer_public_key
+                "SHARE API: Mount.add_owner() completed for peer {} with scope {:?}",
+                req.peer_public_key,
+                req.write_scope
             );
         }
         ShareRole::Mirror => {
@@ -81,18 +142,40 @@ pub async fn handler(
                 req.peer_public_key
             );
         }
+        ShareRole::Reader => {
+            mount.add_reader(peer_public_key).await?;
+            tracing::info!(
+                "SHARE API: Mount.add_reader() completed for peer {}",
+                req.peer_public_key
+            );
+        }
+        ShareRole::Depositor => {
+            mount.add_depositor(peer_public_key).await;
+            tracing::info!(
+                "SHARE API: Mount.add_depositor() completed for peer {}",
+                req.peer_public_key
+            );
+        }
+    }
+
+    if req.expires_at.is_some() {
+        mount
+            .set_share_expiry(peer_public_key, req.expires_at)
+            .await?;
     }
 
-    tracing::info!("SHARE API: Calling save_mount for bucket {}", req.bucket_id);
-    // Save mount and update log
-    let new_bucket_link = state.peer().save_mount(&mount, false).await?;
+    tracing::info!("SHARE API: Saving membership for bucket {}", req.bucket_id);
+    // Save mount and update log, or propose the change if it needs approvals
+    let update = state.peer().save_membership(&mount, false).await?;
+    let (new_bucket_link, proposal) = update_outcome(update);
 
     tracing::info!(
-        "SHARE API: Bucket {} shared with peer {} as {:?}, new link: {}",
+        "SHARE API: Bucket {} shared with peer {} as {:?}, new link: {:?}, proposed: {}",
         req.bucket_id,
         req.peer_public_key,
         req.role,
-        new_bucket_link.hash()
+        new_bucket_link,
+        proposal.is_some()
     );
 
     Ok((
@@ -100,7 +183,8 @@ pub async fn handler(
         Json(ShareResponse {
             bucket_id: req.bucket_id,
             peer_public_key: req.peer_public_key,
-            new_bucket_link: new_bucket_link.hash().to_string(),
+            new_bucket_link,
+            proposal,
         }),
     )
         .into_response())
@@ -110,6 +194,10 @@ pub async fn handler(
 pub enum ShareError {
     #[error("Invalid public key: {0}")]
     InvalidPublicKey(String),
+    #[error("Invalid write scope: {0}")]
+    InvalidScope(String),
+    #[error("Invalid expiry: {0}")]
+    InvalidExpiry(String),
     #[error("Mount error: {0}")]
     Mount(#[from] MountError),
 }
@@ -122,6 +210,33 @@ impl IntoResponse for ShareError {
                 format!("Invalid public key: {}", msg),
             )
                 .into_response(),
+            ShareError::InvalidScope(msg) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Invalid write scope: {}", msg),
+            )
+                .into_response(),
+            ShareError::InvalidExpiry(msg) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Invalid expiry: {}", msg),
+            )
+                .into_response(),
+            ShareError::Mount(MountError::Unauthorized) => (
+                http::StatusCode::FORBIDDEN,
+                "Unauthorized: changing our own expiry needs a quorum of owners".to_string(),
+            )
+                .into_response(),
+            ShareError::Mount(MountError::ShareExpired) => (
+                http::StatusCode::FORBIDDEN,
+                "Our share in this bucket has expired".to_string(),
+            )
+                .into_response(),
+            ShareError::Mount(MountError::Membership(
+                e @ MembershipError::InvalidQuorum { .. },
+            )) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Invalid quorum: {}", e),
+            )
+                .into_response(),
             ShareError::Mount(_) => (
                 http::StatusCode::INTERNAL_SERVER_ERROR,
                 "Unexpected error".to_string(),
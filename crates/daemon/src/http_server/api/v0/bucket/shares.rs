@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use axum::extract::{Json, State};
 use axum::response::{IntoResponse, Response};
 use common::mount::PrincipalRole;
@@ -28,6 +30,12 @@ pub struct ShareInfo {
     pub public_key: String,
     pub role: String,
     pub is_self: bool,
+    /// Paths an owner is restricted to writing under; empty if unrestricted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub write_scope: Vec<PathBuf>,
+    /// When access ends, in seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
 pub async fn handler(
@@ -46,11 +54,15 @@ pub async fn handler(
             let role = match share.role() {
                 PrincipalRole::Owner => "Owner",
                 PrincipalRole::Mirror => "Mirror",
+                PrincipalRole::Reader => "Reader",
+                PrincipalRole::Depositor => "Depositor",
             };
             ShareInfo {
                 public_key: key_hex.clone(),
                 role: role.to_string(),
                 is_self: *key_hex == self_key,
+                write_scope: share.write_scope().to_vec(),
+                expires_at: share.expires_at(),
             }
         })
         .collect();
@@ -23,6 +23,9 @@ pub struct StatResponse {
     pub height: u64,
     pub link: Link,
     pub published: bool,
+    /// Directories published on their own, by absolute path
+    #[serde(default)]
+    pub published_paths: Vec<String>,
     pub peers: Vec<StatPeerInfo>,
 }
 
@@ -50,6 +53,8 @@ pub async fn handler(
             let role = match share.role() {
                 PrincipalRole::Owner => "Owner",
                 PrincipalRole::Mirror => "Mirror",
+                PrincipalRole::Reader => "Reader",
+                PrincipalRole::Depositor => "Depositor",
             };
             StatPeerInfo {
                 public_key: key_hex.clone(),
@@ -67,6 +72,7 @@ pub async fn handler(
             height: inner.height(),
             link: mount.link().await,
             published: manifest.is_published(),
+            published_paths: manifest.published().keys().cloned().collect(),
             peers,
         }),
     )
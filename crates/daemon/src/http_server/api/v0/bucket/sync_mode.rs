@@ -0,0 +1,142 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use common::bucket_log::{BucketLogProvider, SyncMode};
+use common::mount::Mount;
+use common::peer::sync::{DownloadPinsJob, SyncJob};
+
+use crate::database::models::BucketSyncMode;
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// View or set how much of a bucket this node replicates.
+///
+/// Without a `mode` this just returns the current one. Switching to `full`
+/// starts downloading everything pinned at the head; switching to `lazy`
+/// keeps content that is already downloaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncModeRequest {
+    pub bucket_id: Uuid,
+    #[serde(default)]
+    pub mode: Option<SyncMode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncModeResponse {
+    pub bucket_id: Uuid,
+    pub mode: SyncMode,
+    /// Whether the mode changed
+    pub changed: bool,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<SyncModeRequest>,
+) -> Result<impl IntoResponse, SyncModeError> {
+    let logs = state.peer().logs();
+    let exists = logs
+        .exists(req.bucket_id)
+        .await
+        .map_err(|e| SyncModeError::Internal(e.to_string()))?;
+    if !exists {
+        return Err(SyncModeError::BucketNotFound(req.bucket_id));
+    }
+
+    let current = logs
+        .sync_mode(req.bucket_id)
+        .await
+        .map_err(|e| SyncModeError::Internal(e.to_string()))?;
+
+    let mode = match req.mode {
+        Some(mode) if mode != current => {
+            tracing::info!(
+                "SYNC MODE API: Setting sync mode for bucket {} to {}",
+                req.bucket_id,
+                mode
+            );
+            BucketSyncMode::set(req.bucket_id, mode, state.database())
+                .await
+                .map_err(|e| SyncModeError::Internal(e.to_string()))?;
+            if mode == SyncMode::Full {
+                download_head_pins(&state, req.bucket_id).await?;
+            }
+            mode
+        }
+        _ => current,
+    };
+
+    Ok((
+        http::StatusCode::OK,
+        Json(SyncModeResponse {
+            bucket_id: req.bucket_id,
+            mode,
+            changed: mode != current,
+        }),
+    )
+        .into_response())
+}
+
+/// Queue a download of everything pinned at the bucket's head
+async fn download_head_pins(state: &ServiceState, bucket_id: Uuid) -> Result<(), SyncModeError> {
+    let peer = state.peer();
+    let (head, _) = peer
+        .logs()
+        .head(bucket_id, None)
+        .await
+        .map_err(|e| SyncModeError::Internal(e.to_string()))?;
+    let manifest = Mount::load_manifest(&head, peer.blobs())
+        .await
+        .map_err(|e| SyncModeError::Internal(e.to_string()))?;
+
+    let our_id = peer.secret().public();
+    let peer_ids = manifest
+        .get_peer_ids()
+        .into_iter()
+        .filter(|peer_id| *peer_id != our_id)
+        .collect();
+    peer.dispatch(SyncJob::DownloadPins(DownloadPinsJob {
+        bucket_id,
+        pins_link: manifest.pins().clone(),
+        peer_ids,
+    }))
+    .await
+    .map_err(|e| SyncModeError::Internal(e.to_string()))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SyncModeError {
+    #[error("Bucket not found: {0}")]
+    BucketNotFound(Uuid),
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl IntoResponse for SyncModeError {
+    fn into_response(self) -> Response {
+        match self {
+            SyncModeError::BucketNotFound(id) => (
+                http::StatusCode::NOT_FOUND,
+                format!("Bucket not found: {}", id),
+            )
+                .into_response(),
+            SyncModeError::Internal(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Internal error: {}", msg),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for SyncModeRequest {
+    type Response = SyncModeResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/sync-mode").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
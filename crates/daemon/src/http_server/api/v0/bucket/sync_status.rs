@@ -0,0 +1,125 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use common::prelude::Link;
+
+use crate::database::models::BucketSyncState;
+use crate::database::types::BucketSyncStatus;
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncStatusRequest {
+    /// Only report this bucket; all buckets that have synced otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncStatusResponse {
+    pub buckets: Vec<BucketSyncInfo>,
+}
+
+/// Sync progress of a bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BucketSyncInfo {
+    pub bucket_id: Uuid,
+    pub status: BucketSyncStatus,
+    /// The version being synced to, or the version reached once synced
+    pub target_link: Option<Link>,
+    pub target_height: Option<u64>,
+    /// Manifests downloaded by the current sync
+    pub manifests_fetched: u64,
+    /// Pinned blobs that were missing locally
+    pub blobs_total: u64,
+    /// Pinned blobs fetched so far
+    pub blobs_done: u64,
+    pub bytes_downloaded: u64,
+    /// Peer currently being downloaded from (hex key)
+    pub current_peer: Option<String>,
+    pub last_error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl BucketSyncInfo {
+    /// Whether the sync has stopped, successfully or not
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.status,
+            BucketSyncStatus::Synced | BucketSyncStatus::Failed
+        )
+    }
+}
+
+impl From<BucketSyncState> for BucketSyncInfo {
+    fn from(state: BucketSyncState) -> Self {
+        Self {
+            bucket_id: *state.bucket_id,
+            status: state.status,
+            target_link: state.target_link.map(Link::from),
+            target_height: state.target_height.map(|height| height as u64),
+            manifests_fetched: state.manifests_fetched as u64,
+            blobs_total: state.blobs_total as u64,
+            blobs_done: state.blobs_done as u64,
+            bytes_downloaded: state.bytes_downloaded as u64,
+            current_peer: state.current_peer,
+            last_error: state.last_error,
+            started_at: state.started_at,
+            updated_at: state.updated_at,
+        }
+    }
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<SyncStatusRequest>,
+) -> Result<impl IntoResponse, SyncStatusError> {
+    let states = match req.bucket_id {
+        Some(bucket_id) => BucketSyncState::get(bucket_id, state.database())
+            .await
+            .map_err(|e| SyncStatusError::Database(e.to_string()))?
+            .into_iter()
+            .collect(),
+        None => BucketSyncState::list(state.database())
+            .await
+            .map_err(|e| SyncStatusError::Database(e.to_string()))?,
+    };
+    let buckets = states.into_iter().map(BucketSyncInfo::from).collect();
+
+    Ok((http::StatusCode::OK, Json(SyncStatusResponse { buckets })).into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SyncStatusError {
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl IntoResponse for SyncStatusError {
+    fn into_response(self) -> Response {
+        match self {
+            SyncStatusError::Database(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", msg),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for SyncStatusRequest {
+    type Response = SyncStatusResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/bucket/sync-status").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
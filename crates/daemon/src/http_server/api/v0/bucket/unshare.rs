@@ -6,6 +6,9 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use common::crypto::PublicKey;
+use common::mount::MembershipError;
+
+use super::proposals::{update_outcome, ProposalInfo};
 
 use crate::http_server::api::client::ApiRequest;
 use crate::ServiceState;
@@ -25,7 +28,10 @@ pub struct UnshareRequest {
 pub struct UnshareResponse {
     pub bucket_id: Uuid,
     pub peer_public_key: String,
-    pub new_bucket_link: String,
+    /// New bucket link, if the change was saved
+    pub new_bucket_link: Option<String>,
+    /// The change, if it waits for other owners to approve it
+    pub proposal: Option<ProposalInfo>,
 }
 
 pub async fn handler(
@@ -48,14 +54,16 @@ pub async fn handler(
     // Remove the share (verifies caller is owner)
     mount.remove_share(peer_public_key).await?;
 
-    // Save mount and update log
-    let new_bucket_link = state.peer().save_mount(&mount, false).await?;
+    // Save mount and update log, or propose the change if it needs approvals
+    let update = state.peer().save_membership(&mount, false).await?;
+    let (new_bucket_link, proposal) = update_outcome(update);
 
     tracing::info!(
-        "UNSHARE API: Peer {} removed from bucket {}, new link: {}",
+        "UNSHARE API: Peer {} removed from bucket {}, new link: {:?}, proposed: {}",
         req.peer_public_key,
         req.bucket_id,
-        new_bucket_link.hash()
+        new_bucket_link,
+        proposal.is_some()
     );
 
     Ok((
@@ -63,7 +71,8 @@ pub async fn handler(
         Json(UnshareResponse {
             bucket_id: req.bucket_id,
             peer_public_key: req.peer_public_key,
-            new_bucket_link: new_bucket_link.hash().to_string(),
+            new_bucket_link,
+            proposal,
         }),
     )
         .into_response())
@@ -93,6 +102,13 @@ impl IntoResponse for UnshareError {
             UnshareError::Mount(MountError::ShareNotFound) => {
                 (http::StatusCode::NOT_FOUND, "Share not found".to_string()).into_response()
             }
+            UnshareError::Mount(MountError::Membership(
+                e @ MembershipError::InvalidQuorum { .. },
+            )) => (
+                http::StatusCode::BAD_REQUEST,
+                format!("Invalid quorum: {}", e),
+            )
+                .into_response(),
             UnshareError::Mount(_) => (
                 http::StatusCode::INTERNAL_SERVER_ERROR,
                 "Unexpected error".to_string(),
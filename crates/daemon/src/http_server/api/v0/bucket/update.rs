@@ -155,6 +155,11 @@ impl IntoResponse for UpdateError {
                 format!("Bad request: {}", msg),
             )
                 .into_response(),
+            UpdateError::Mount(MountError::OutOfScope(path)) => (
+                http::StatusCode::FORBIDDEN,
+                format!("Outside your write scope: {}", path.display()),
+            )
+                .into_response(),
             UpdateError::Mount(_) => (
                 http::StatusCode::INTERNAL_SERVER_ERROR,
                 "Unexpected error".to_string(),
@@ -1,13 +1,20 @@
 use axum::Router;
 
+pub mod access_requests;
+pub mod blobs;
 pub mod bucket;
 #[cfg(feature = "fuse")]
 pub mod mounts;
+pub mod sync;
 
 use crate::ServiceState;
 
 pub fn router(state: ServiceState) -> Router<ServiceState> {
-    let router = Router::new().nest("/bucket", bucket::router(state.clone()));
+    let router = Router::new()
+        .nest("/bucket", bucket::router(state.clone()))
+        .nest("/blobs", blobs::router(state.clone()))
+        .nest("/sync", sync::router(state.clone()))
+        .nest("/access-requests", access_requests::router(state.clone()));
 
     #[cfg(feature = "fuse")]
     let router = router.nest("/mounts", mounts::router(state.clone()));
@@ -0,0 +1,68 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::database::models::SyncJobRecord;
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// Stop a job from being retried
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelJobRequest {
+    /// ID of the job, see `/api/v0/sync/jobs`
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelJobResponse {
+    pub id: i64,
+    /// False if the job doesn't exist or is already cancelled
+    pub cancelled: bool,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<CancelJobRequest>,
+) -> Result<impl IntoResponse, CancelJobError> {
+    let cancelled = SyncJobRecord::cancel(req.id, state.database())
+        .await
+        .map_err(|e| CancelJobError::Database(e.to_string()))?;
+
+    Ok((
+        http::StatusCode::OK,
+        Json(CancelJobResponse {
+            id: req.id,
+            cancelled,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CancelJobError {
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl IntoResponse for CancelJobError {
+    fn into_response(self) -> Response {
+        match self {
+            CancelJobError::Database(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", msg),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for CancelJobRequest {
+    type Response = CancelJobResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/sync/jobs/cancel").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
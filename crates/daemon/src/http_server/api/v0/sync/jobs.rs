@@ -0,0 +1,119 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use common::prelude::Link;
+
+use crate::database::models::{SyncJobRecord, MAX_ATTEMPTS};
+use crate::database::types::{SyncJobKind, SyncJobStatus};
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobsRequest {
+    /// Only list jobs with this status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<SyncJobStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobsResponse {
+    pub jobs: Vec<SyncJobInfo>,
+}
+
+/// A durable sync job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncJobInfo {
+    pub id: i64,
+    pub kind: SyncJobKind,
+    pub bucket_id: Uuid,
+    /// The version to sync to, or the pins hash list to download
+    pub target_link: Link,
+    /// Height of the target version (bucket syncs only)
+    pub target_height: Option<u64>,
+    /// Peers to fetch from, in priority order (hex keys)
+    pub peer_ids: Vec<String>,
+    pub status: SyncJobStatus,
+    /// Failed attempts so far
+    pub attempts: u32,
+    /// Attempts before the job is marked failed
+    pub max_attempts: u32,
+    /// When a pending job runs next
+    #[serde(with = "time::serde::rfc3339")]
+    pub next_retry_at: OffsetDateTime,
+    pub last_error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<SyncJobRecord> for SyncJobInfo {
+    fn from(record: SyncJobRecord) -> Self {
+        Self {
+            id: record.id,
+            kind: record.kind,
+            bucket_id: *record.bucket_id,
+            target_link: record.target_link.into(),
+            target_height: record.target_height.map(|height| height as u64),
+            peer_ids: record
+                .peer_ids()
+                .iter()
+                .map(|peer_id| peer_id.to_hex())
+                .collect(),
+            status: record.status,
+            attempts: record.attempts as u32,
+            max_attempts: MAX_ATTEMPTS as u32,
+            next_retry_at: OffsetDateTime::from_unix_timestamp(record.next_retry_at)
+                .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+            last_error: record.last_error,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<JobsRequest>,
+) -> Result<impl IntoResponse, JobsError> {
+    let jobs = SyncJobRecord::list(req.status, state.database())
+        .await
+        .map_err(|e| JobsError::Database(e.to_string()))?
+        .into_iter()
+        .map(SyncJobInfo::from)
+        .collect();
+
+    Ok((http::StatusCode::OK, Json(JobsResponse { jobs })).into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JobsError {
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl IntoResponse for JobsError {
+    fn into_response(self) -> Response {
+        match self {
+            JobsError::Database(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", msg),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for JobsRequest {
+    type Response = JobsResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/sync/jobs").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -0,0 +1,20 @@
+use axum::routing::post;
+use axum::Router;
+
+use crate::ServiceState;
+
+pub mod cancel;
+pub mod jobs;
+pub mod retry;
+
+pub use cancel::CancelJobRequest;
+pub use jobs::JobsRequest;
+pub use retry::RetryJobRequest;
+
+pub fn router(state: ServiceState) -> Router<ServiceState> {
+    Router::new()
+        .route("/jobs", post(jobs::handler))
+        .route("/jobs/retry", post(retry::handler))
+        .route("/jobs/cancel", post(cancel::handler))
+        .with_state(state)
+}
@@ -0,0 +1,68 @@
+use axum::extract::{Json, State};
+use axum::response::{IntoResponse, Response};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::database::models::SyncJobRecord;
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// Queue a failed or cancelled job to run right away
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryJobRequest {
+    /// ID of the job, see `/api/v0/sync/jobs`
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryJobResponse {
+    pub id: i64,
+    /// False if the job doesn't exist or is running
+    pub retried: bool,
+}
+
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Json(req): Json<RetryJobRequest>,
+) -> Result<impl IntoResponse, RetryJobError> {
+    let retried = SyncJobRecord::retry(req.id, state.database())
+        .await
+        .map_err(|e| RetryJobError::Database(e.to_string()))?;
+
+    Ok((
+        http::StatusCode::OK,
+        Json(RetryJobResponse {
+            id: req.id,
+            retried,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RetryJobError {
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl IntoResponse for RetryJobError {
+    fn into_response(self) -> Response {
+        match self {
+            RetryJobError::Database(msg) => (
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", msg),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// Client implementation - builds request for this operation
+impl ApiRequest for RetryJobRequest {
+    type Response = RetryJobResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/api/v0/sync/jobs/retry").unwrap();
+        client.post(full_url).json(&self)
+    }
+}
@@ -14,7 +14,6 @@ pub struct Config {
     // log level for http tracing
     pub log_level: tracing::Level,
     // External gateway URL for generating share/download links
-    #[allow(unused)]
     pub gateway_url: Option<String>,
 }
 
@@ -0,0 +1,90 @@
+use axum::extract::{Path, Query, State};
+use axum::response::{IntoResponse, Response};
+use common::mount::{CapabilityError, FileCapability};
+use serde::Deserialize;
+
+use crate::ServiceState;
+
+/// Query parameters for capability requests.
+#[derive(Debug, Deserialize)]
+pub struct CapabilityQuery {
+    /// If true, serve with Content-Disposition: attachment
+    #[serde(default)]
+    pub download: Option<bool>,
+}
+
+/// Serve the single file a capability link grants access to.
+///
+/// Only capabilities minted by this node or an owner of the bucket are
+/// served. The contents are read from the local store, or fetched from the
+/// bucket's peers, and decrypted with the secret the capability carries.
+pub async fn handler(
+    State(state): State<ServiceState>,
+    Path(capability): Path<String>,
+    Query(query): Query<CapabilityQuery>,
+) -> Response {
+    let capability = match capability.parse::<FileCapability>() {
+        Ok(capability) => capability,
+        Err(e) => {
+            tracing::debug!("Invalid capability: {}", e);
+            return (
+                axum::http::StatusCode::BAD_REQUEST,
+                "Invalid capability link",
+            )
+                .into_response();
+        }
+    };
+
+    let blobs = match state.peer().capability_blobs(&capability).await {
+        Ok(blobs) => blobs,
+        Err(CapabilityError::Expired(_)) => {
+            return (axum::http::StatusCode::GONE, "This link has expired").into_response();
+        }
+        Err(e) => {
+            tracing::debug!("Rejected capability: {}", e);
+            return (axum::http::StatusCode::FORBIDDEN, "Invalid capability link").into_response();
+        }
+    };
+    let file_data = match blobs.get(&capability.hash).await {
+        Ok(data) => data,
+        Err(e) => {
+            tracing::error!("Failed to fetch capability blob {}: {}", capability.hash, e);
+            return super::not_found_response("File not available");
+        }
+    };
+    let file_data = match capability.decrypt(&file_data) {
+        Ok(data) => data,
+        Err(e) => {
+            tracing::error!(
+                "Failed to decrypt capability blob {}: {}",
+                capability.hash,
+                e
+            );
+            return super::error_response("Failed to read file");
+        }
+    };
+
+    let mime_type = capability
+        .mime
+        .clone()
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let disposition = if query.download.unwrap_or(false) {
+        "attachment"
+    } else {
+        "inline"
+    };
+    let filename = capability.name.replace('"', "_");
+
+    (
+        axum::http::StatusCode::OK,
+        [
+            (axum::http::header::CONTENT_TYPE, mime_type),
+            (
+                axum::http::header::CONTENT_DISPOSITION,
+                format!("{}; filename=\"{}\"", disposition, filename),
+            ),
+        ],
+        file_data,
+    )
+        .into_response()
+}
@@ -7,8 +7,11 @@ use uuid::Uuid;
 
 use common::mount::NodeLink;
 
+use crate::database::models::BucketSyncState;
+use crate::database::types::BucketSyncStatus;
 use crate::ServiceState;
 
+pub mod capability;
 pub mod directory;
 pub mod file;
 pub mod index;
@@ -87,15 +90,17 @@ pub async fn handler(
         format!("/{}", file_path)
     };
 
-    // Load mount - either from specific link or latest published version
+    // Load what's published, either at a specific link or the latest
+    // published version; an owner node's own access must not leak the rest
     let mount = if let Some(hash_str) = &query.at {
         match hash_str.parse::<common::linked_data::Hash>() {
             Ok(hash) => {
                 let link = common::linked_data::Link::new(common::linked_data::LD_RAW_CODEC, hash);
-                match common::mount::Mount::load(&link, state.peer().secret(), state.peer().blobs())
-                    .await
-                {
+                match state.peer().load_published_mount(bucket_id, &link).await {
                     Ok(mount) => mount,
+                    Err(common::mount::MountError::MirrorCannotMount) => {
+                        return not_found_response("Version is not published");
+                    }
                     Err(e) => {
                         tracing::error!("Failed to load mount from link: {}", e);
                         return error_response("Failed to load historical version");
@@ -111,21 +116,19 @@ pub async fn handler(
         use common::bucket_log::BucketLogProvider;
         match state.peer().logs().latest_published(bucket_id).await {
             Ok(Some((published_link, _height))) => {
-                match common::mount::Mount::load(
-                    &published_link,
-                    state.peer().secret(),
-                    state.peer().blobs(),
-                )
-                .await
+                match state
+                    .peer()
+                    .load_published_mount(bucket_id, &published_link)
+                    .await
                 {
                     Ok(mount) => mount,
                     Err(_) => {
-                        return syncing_response();
+                        return syncing_response(&state, bucket_id).await;
                     }
                 }
             }
             _ => {
-                return syncing_response();
+                return syncing_response(&state, bucket_id).await;
             }
         }
     };
@@ -209,11 +212,33 @@ pub(super) fn error_response(message: &str) -> Response {
         .into_response()
 }
 
-fn syncing_response() -> Response {
+/// 503 for a bucket we can't serve yet, with its sync progress if recorded
+async fn syncing_response(state: &ServiceState, bucket_id: Uuid) -> Response {
+    let progress = match BucketSyncState::get(bucket_id, state.database()).await {
+        Ok(Some(sync)) => match sync.status {
+            BucketSyncStatus::Syncing => {
+                format!(" ({} manifests fetched)", sync.manifests_fetched)
+            }
+            BucketSyncStatus::Downloading => format!(
+                " ({}/{} blobs downloaded)",
+                sync.blobs_done, sync.blobs_total
+            ),
+            BucketSyncStatus::Failed => match sync.last_error {
+                Some(error) => format!(" (last attempt failed: {})", error),
+                None => String::new(),
+            },
+            BucketSyncStatus::Synced => String::new(),
+        },
+        _ => String::new(),
+    };
+
     (
         axum::http::StatusCode::SERVICE_UNAVAILABLE,
         [(axum::http::header::RETRY_AFTER, "5")],
-        "Bucket is still syncing. Please try again in a moment.",
+        format!(
+            "Bucket is still syncing{}. Please try again in a moment.",
+            progress
+        ),
     )
         .into_response()
 }
@@ -340,3 +365,94 @@ fn markdown_to_html(markdown: &str) -> String {
         html_output
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::path::PathBuf;
+
+    use common::bucket_log::BucketLogProvider;
+    use common::mount::Mount;
+
+    use super::*;
+    use crate::{BlobStoreConfig, ServiceConfig};
+
+    async fn get(state: &ServiceState, bucket_id: Uuid, path: &str) -> axum::http::StatusCode {
+        let query = GatewayQuery {
+            at: None,
+            download: None,
+            deep: None,
+            viewer: None,
+        };
+        handler(
+            State(state.clone()),
+            Path((bucket_id, path.to_string())),
+            Query(query),
+            axum::http::HeaderMap::new(),
+        )
+        .await
+        .status()
+    }
+
+    #[tokio::test]
+    async fn test_owner_gateway_serves_only_published_subtree() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = ServiceConfig {
+            node_listen_addr: None,
+            node_secret: None,
+            gossip: false,
+            blob_store: BlobStoreConfig::Legacy,
+            jax_dir: temp_dir.path().to_path_buf(),
+            max_import_size: 1024 * 1024,
+            download_parallelism: 1,
+            api_port: 0,
+            gateway_port: 0,
+            sqlite_path: None,
+            log_level: tracing::Level::INFO,
+            log_dir: None,
+            gateway_url: None,
+        };
+        let state = ServiceState::from_config(&config).await.unwrap();
+
+        // This node owns the bucket, so it could decrypt all of it
+        let bucket_id = Uuid::new_v4();
+        let mut mount = Mount::init(
+            bucket_id,
+            "site".to_string(),
+            state.peer().secret(),
+            state.peer().blobs(),
+        )
+        .await
+        .unwrap();
+        state
+            .peer()
+            .logs()
+            .append(
+                bucket_id,
+                "site".to_string(),
+                mount.link().await,
+                vec![],
+                0,
+                false,
+            )
+            .await
+            .unwrap();
+        for path in ["/public/index.txt", "/private/keys.txt"] {
+            mount
+                .add(&PathBuf::from(path), Cursor::new(b"data".to_vec()))
+                .await
+                .unwrap();
+        }
+        mount.publish_path(&PathBuf::from("/public")).await.unwrap();
+        state.peer().save_mount(&mount, false).await.unwrap();
+
+        assert_eq!(
+            get(&state, bucket_id, "public/index.txt").await,
+            axum::http::StatusCode::OK
+        );
+        assert_eq!(
+            get(&state, bucket_id, "private/keys.txt").await,
+            axum::http::StatusCode::NOT_FOUND
+        );
+    }
+}
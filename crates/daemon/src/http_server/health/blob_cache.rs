@@ -0,0 +1,44 @@
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use http::StatusCode;
+use object_store::CacheStats;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::http_server::api::client::ApiRequest;
+use crate::ServiceState;
+
+/// Request type for the blob cache stats endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobCacheRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobCacheResponse {
+    /// Whether a disk cache is configured for the blob store
+    pub enabled: bool,
+    /// Cache statistics (absent when no cache is configured)
+    pub stats: Option<CacheStats>,
+}
+
+impl ApiRequest for BlobCacheRequest {
+    type Response = BlobCacheResponse;
+
+    fn build_request(self, base_url: &Url, client: &Client) -> RequestBuilder {
+        let full_url = base_url.join("/_status/blob-cache").unwrap();
+        client.get(full_url)
+    }
+}
+
+#[tracing::instrument(skip(state))]
+pub async fn handler(State(state): State<ServiceState>) -> Response {
+    let stats = state.peer().blobs().cache_stats();
+    (
+        StatusCode::OK,
+        Json(BlobCacheResponse {
+            enabled: stats.is_some(),
+            stats,
+        }),
+    )
+        .into_response()
+}
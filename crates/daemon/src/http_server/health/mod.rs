@@ -5,6 +5,7 @@ use http::Method;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::limit::RequestBodyLimitLayer;
 
+pub mod blob_cache;
 mod data_source;
 pub mod identity;
 pub mod liveness;
@@ -29,6 +30,7 @@ pub fn router(state: ServiceState) -> Router<ServiceState> {
         .route("/readyz", get(readiness::handler))
         .route("/version", get(version::handler))
         .route("/identity", get(identity::handler))
+        .route("/blob-cache", get(blob_cache::handler))
         .with_state(state)
         .layer(cors_layer)
         .layer(RequestBodyLimitLayer::new(HEALTHCHECK_REQUEST_SIZE_LIMIT))
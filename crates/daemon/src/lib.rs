@@ -1,5 +1,5 @@
 // Service modules (daemon functionality)
-pub(crate) mod blobs;
+pub mod blobs;
 pub mod clone_state;
 pub(crate) mod database;
 #[cfg(feature = "fuse")]
@@ -24,6 +24,12 @@ pub use state::{AppConfig, AppState, BlobStoreConfig, StateError};
 pub use database::models::FuseMount;
 pub use database::types::MountStatus;
 
+// Re-exports for sync job management
+pub use database::types::{BucketSyncStatus, SyncJobKind, SyncJobStatus};
+
+// Re-exports for access requests
+pub use database::types::AccessRequestStatus;
+
 /// Daemon-specific build info that uses the daemon's BUILD_FEATURES.
 ///
 /// This is needed because `common::version::BuildInfo::new()` reads
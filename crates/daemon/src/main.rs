@@ -2,7 +2,7 @@
 mod cli;
 
 use clap::{Parser, Subcommand};
-use cli::{args::Args, op::Op, Bucket, Daemon, Health, Init, Update, Version};
+use cli::{args::Args, op::Op, Blobs, Bucket, Daemon, Health, Init, Update, Version};
 use std::io::IsTerminal;
 
 use owo_colors::OwoColorize;
@@ -12,21 +12,25 @@ use cli::Mount;
 
 #[cfg(feature = "fuse")]
 command_enum! {
+    (Blobs, Blobs),
     (Bucket, Bucket),
     (Daemon, Daemon),
     (Health, Health),
     (Init, Init),
     (Mount, Mount),
+    (Sync, cli::ops::sync::Sync),
     (Update, Update),
     (Version, Version),
 }
 
 #[cfg(not(feature = "fuse"))]
 command_enum! {
+    (Blobs, Blobs),
     (Bucket, Bucket),
     (Daemon, Daemon),
     (Health, Health),
     (Init, Init),
+    (Sync, cli::ops::sync::Sync),
     (Update, Update),
     (Version, Version),
 }
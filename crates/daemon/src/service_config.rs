@@ -14,6 +14,8 @@ pub struct Config {
     /// on system file path to our secret,
     ///  if not set then a new secret will be generated
     pub node_secret: Option<SecretKey>,
+    /// whether to spread bucket heads over per-bucket gossip topics
+    pub gossip: bool,
 
     // blob store configuration
     /// Blob storage backend configuration
@@ -22,6 +24,8 @@ pub struct Config {
     pub jax_dir: PathBuf,
     /// Maximum blob size allowed for BAO imports (bytes)
     pub max_import_size: u64,
+    /// Number of blob batches downloaded from peers at once
+    pub download_parallelism: usize,
 
     // http server configuration - separate ports for API and gateway
     /// Port for the API HTTP server (private, mutation/RPC).
@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 #[cfg(feature = "fuse")]
 use std::sync::Arc;
 
@@ -5,6 +6,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use url::Url;
 
+use crate::blobs::migrate::LiveMigration;
 use crate::blobs::{Blobs, BlobsSetupError};
 use crate::database::{Database, DatabaseSetupError};
 #[cfg(feature = "fuse")]
@@ -20,6 +22,8 @@ use common::peer::{Peer, PeerBuilder};
 pub struct State {
     database: Database,
     peer: Peer<Database>,
+    jax_dir: PathBuf,
+    migration: LiveMigration,
     #[cfg(feature = "fuse")]
     mount_manager: Arc<RwLock<Option<MountManager>>>,
 }
@@ -59,13 +63,21 @@ impl State {
         // TODO: Make queue size configurable via config
 
         // Create sync provider with worker
-        let (sync_provider, job_receiver) = QueuedSyncProvider::new(QueuedSyncConfig::default());
+        let (sync_provider, job_receiver) =
+            QueuedSyncProvider::new(QueuedSyncConfig::default(), database.clone());
 
         let mut peer_builder = PeerBuilder::new()
             .with_sync_provider(std::sync::Arc::new(sync_provider))
             .log_provider(database.clone())
-            .blobs_store(blobs.into_inner())
-            .secret_key(node_secret.clone());
+            .invite_store(std::sync::Arc::new(database.clone()))
+            .access_request_store(std::sync::Arc::new(database.clone()))
+            .blobs_store(
+                blobs
+                    .into_inner()
+                    .with_download_parallelism(config.download_parallelism),
+            )
+            .secret_key(node_secret.clone())
+            .gossip(config.gossip);
 
         if let Some(addr) = config.node_listen_addr {
             peer_builder = peer_builder.socket_address(addr);
@@ -81,15 +93,26 @@ impl State {
         // Spawn the worker for the queued sync provider
         // The worker is managed outside the peer, like the database
         let peer_for_worker = peer.clone();
-        let job_stream = job_receiver.into_async();
+        let database_for_worker = database.clone();
         tokio::spawn(async move {
-            crate::sync_provider::run_worker(peer_for_worker, job_stream).await;
+            crate::sync_provider::run_worker(peer_for_worker, database_for_worker, job_receiver)
+                .await;
         });
 
+        // Record sync progress per bucket; subscribe now so the worker's
+        //  first events aren't missed
+        let progress = peer.subscribe_sync_progress();
+        tokio::spawn(crate::sync_provider::record_progress(
+            database.clone(),
+            progress,
+        ));
+
         // Create the initial state
         let state = Self {
             database: database.clone(),
             peer: peer.clone(),
+            jax_dir: config.jax_dir.clone(),
+            migration: LiveMigration::default(),
             #[cfg(feature = "fuse")]
             mount_manager: Arc::new(RwLock::new(None)),
         };
@@ -117,6 +140,16 @@ impl State {
         &self.database
     }
 
+    /// The jax directory holding config.toml and local blob data
+    pub fn jax_dir(&self) -> &Path {
+        &self.jax_dir
+    }
+
+    /// The blob store migration started through the API, if any
+    pub fn migration(&self) -> &LiveMigration {
+        &self.migration
+    }
+
     /// Get the mount manager (only available with fuse feature)
     #[cfg(feature = "fuse")]
     pub fn mount_manager(&self) -> &Arc<RwLock<Option<MountManager>>> {
@@ -26,6 +26,13 @@ pub struct AppConfig {
     /// Maximum blob size allowed for BAO imports (bytes). Defaults to 1GB.
     #[serde(default = "default_max_import_size")]
     pub max_import_size: u64,
+    /// Spread bucket heads over per-bucket gossip topics, in addition to
+    /// direct announcements. Useful for buckets shared with many peers.
+    #[serde(default)]
+    pub gossip: bool,
+    /// Number of blob batches downloaded from peers at once when syncing
+    #[serde(default = "default_download_parallelism")]
+    pub download_parallelism: usize,
 }
 
 fn default_api_port() -> u16 {
@@ -40,6 +47,10 @@ fn default_max_import_size() -> u64 {
     object_store::DEFAULT_MAX_IMPORT_SIZE
 }
 
+fn default_download_parallelism() -> usize {
+    common::peer::DEFAULT_DOWNLOAD_PARALLELISM
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -48,13 +59,15 @@ impl Default for AppConfig {
             peer_port: None,
             blob_store: BlobStoreConfig::default(),
             max_import_size: default_max_import_size(),
+            gossip: false,
+            download_parallelism: default_download_parallelism(),
         }
     }
 }
 
 /// Configuration for the blob storage backend.
 /// This determines where blob data is stored (legacy iroh, local filesystem, or S3).
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum BlobStoreConfig {
     /// Legacy iroh FsStore (default, for backwards compatibility)
@@ -75,9 +88,132 @@ pub enum BlobStoreConfig {
         /// S3 URL in format: s3://access_key:secret_key@endpoint/bucket
         /// Example: s3://minioadmin:minioadmin@localhost:9000/jax-blobs
         url: String,
+        /// Optional local disk cache in front of S3 (disabled when absent)
+        #[serde(default)]
+        cache: Option<BlobCacheConfig>,
+    },
+
+    /// Local filesystem hot tier with large, cold blobs offloaded to S3
+    Tiered {
+        /// Absolute path for hot (local) object storage
+        path: PathBuf,
+        /// Optional separate path for SQLite metadata DB (defaults to path/blobs.db)
+        #[serde(default)]
+        db_path: Option<PathBuf>,
+        /// Cold tier S3 URL, same format as the S3 backend
+        cold_url: String,
+        /// When blobs move to the cold tier
+        #[serde(default)]
+        policy: TierPolicyConfig,
     },
 }
 
+/// Demotion policy for the tiered blob store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TierPolicyConfig {
+    /// Blobs smaller than this stay local (bytes). Defaults to 1MB, which
+    /// keeps manifests, pins and directory nodes hot.
+    #[serde(default = "default_tier_min_size")]
+    pub min_size: u64,
+    /// Blobs stored more recently than this stay local (seconds). Defaults to 1 day.
+    #[serde(default = "default_tier_min_age_secs")]
+    pub min_age_secs: u64,
+    /// Blobs read more recently than this stay local (seconds). Defaults to 1 day.
+    #[serde(default = "default_tier_min_idle_secs")]
+    pub min_idle_secs: u64,
+    /// Blobs read more often than this stay local; the count halves on every
+    /// rebalance, so it tracks recent read frequency. Defaults to 2.
+    #[serde(default = "default_tier_max_access_count")]
+    pub max_access_count: u64,
+    /// Optional cap on local blob data (bytes); beyond it the least used
+    /// blobs are offloaded early
+    #[serde(default)]
+    pub hot_max_size: Option<u64>,
+    /// Seconds between rebalance passes. Defaults to 1 hour.
+    #[serde(default = "default_tier_interval_secs")]
+    pub interval_secs: u64,
+}
+
+fn default_tier_min_size() -> u64 {
+    1024 * 1024
+}
+
+fn default_tier_min_age_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_tier_min_idle_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_tier_max_access_count() -> u64 {
+    2
+}
+
+fn default_tier_interval_secs() -> u64 {
+    60 * 60
+}
+
+impl Default for TierPolicyConfig {
+    fn default() -> Self {
+        Self {
+            min_size: default_tier_min_size(),
+            min_age_secs: default_tier_min_age_secs(),
+            min_idle_secs: default_tier_min_idle_secs(),
+            max_access_count: default_tier_max_access_count(),
+            hot_max_size: None,
+            interval_secs: default_tier_interval_secs(),
+        }
+    }
+}
+
+impl From<&TierPolicyConfig> for object_store::TierPolicy {
+    fn from(config: &TierPolicyConfig) -> Self {
+        Self {
+            min_size: config.min_size,
+            min_age: std::time::Duration::from_secs(config.min_age_secs),
+            min_idle: std::time::Duration::from_secs(config.min_idle_secs),
+            max_access_count: config.max_access_count,
+            hot_max_size: config.hot_max_size,
+            // A zero interval would make tokio's ticker panic
+            interval: std::time::Duration::from_secs(config.interval_secs.max(1)),
+        }
+    }
+}
+
+/// Local read-through disk cache for remote blob storage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobCacheConfig {
+    /// Cache directory (defaults to <jax_dir>/blob-cache)
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// Maximum total size of cached blobs (bytes). Defaults to 10GB.
+    #[serde(default = "default_blob_cache_max_size")]
+    pub max_size: u64,
+    /// Blobs larger than this are never cached (bytes). Defaults to 256MB.
+    #[serde(default = "default_blob_cache_max_entry_size")]
+    pub max_entry_size: u64,
+}
+
+fn default_blob_cache_max_size() -> u64 {
+    10 * 1024 * 1024 * 1024
+}
+
+fn default_blob_cache_max_entry_size() -> u64 {
+    256 * 1024 * 1024
+}
+
+impl BlobCacheConfig {
+    /// Cache config with the given size limit and default location.
+    pub fn with_max_size(max_size: u64) -> Self {
+        Self {
+            path: None,
+            max_size,
+            max_entry_size: default_blob_cache_max_entry_size().min(max_size),
+        }
+    }
+}
+
 /// Parsed S3 configuration from URL
 #[derive(Debug, Clone)]
 pub struct S3Config {
@@ -251,6 +387,18 @@ impl AppState {
         })
     }
 
+    /// Persist the current configuration to config.toml.
+    ///
+    /// Writes to a temporary file and renames it into place so a crash never
+    /// leaves a half-written config behind.
+    pub fn save_config(&self) -> Result<(), StateError> {
+        let config_toml = toml::to_string_pretty(&self.config)?;
+        let tmp_path = self.config_path.with_extension("toml.tmp");
+        fs::write(&tmp_path, config_toml)?;
+        fs::rename(&tmp_path, &self.config_path)?;
+        Ok(())
+    }
+
     /// Load the secret key from the key file
     pub fn load_key(&self) -> Result<SecretKey, StateError> {
         let pem = fs::read_to_string(&self.key_path)?;
@@ -2,12 +2,31 @@
 //!
 //! This module provides the app-specific implementation of `SyncProvider` using
 //! a flume channel-based job queue with a background worker.
+//!
+//! Bucket syncs, pin downloads and deposit acceptances are durable: they are
+//! persisted in the `sync_jobs` table (see [`SyncJobRecord`]) and retried
+//! with exponential backoff until they succeed, across restarts. Other jobs (pings,
+//! announcements, scrubs) are cheap to redo and only live in memory.
+//!
+//! Progress reported by running jobs is folded into the `bucket_sync_state`
+//! table by [`record_progress`].
+
+use std::sync::Arc;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use tokio::sync::Notify;
 
+use common::peer::sync::SyncProgress;
 use common::peer::{SyncJob, SyncProvider};
 
+use crate::database::models::{BucketSyncState, SyncJobRecord};
+use crate::database::types::SyncJobStatus;
+use crate::database::Database;
+
+/// Durable jobs run per wakeup before other work gets a turn
+const DURABLE_BATCH_SIZE: usize = 16;
+
 /// Configuration for the queued sync provider
 #[derive(Debug, Clone)]
 pub struct QueuedSyncConfig {
@@ -32,14 +51,17 @@ impl Default for QueuedSyncConfig {
 #[derive(Debug, Clone)]
 pub struct QueuedSyncProvider {
     tx: flume::Sender<SyncJob>,
+    database: Database,
+    /// Wakes the worker when a durable job is queued
+    wake: Arc<Notify>,
 }
 
 impl QueuedSyncProvider {
     /// Create a new queued sync provider
     ///
     /// Returns a tuple of (provider, receiver). The receiver should be passed to
-    /// the worker task.
-    pub fn new(config: QueuedSyncConfig) -> (Self, JobReceiver) {
+    /// the worker task. Durable jobs are persisted in `database`.
+    pub fn new(config: QueuedSyncConfig, database: Database) -> (Self, JobReceiver) {
         let (tx, rx) = match config.max_queue_size {
             Some(size) => {
                 tracing::info!("Creating bounded job queue with size {}", size);
@@ -51,7 +73,15 @@ impl QueuedSyncProvider {
             }
         };
 
-        (Self { tx }, JobReceiver { rx })
+        let wake = Arc::new(Notify::new());
+        (
+            Self {
+                tx,
+                database,
+                wake: wake.clone(),
+            },
+            JobReceiver { rx, wake },
+        )
     }
 }
 
@@ -62,6 +92,19 @@ where
     L::Error: std::error::Error + Send + Sync + 'static,
 {
     async fn execute(&self, _peer: &common::peer::Peer<L>, job: SyncJob) -> Result<()> {
+        match SyncJobRecord::enqueue(&job, &self.database).await {
+            Ok(Some(id)) => {
+                tracing::debug!("Persisted sync job {}: {:?}", id, job);
+                self.wake.notify_one();
+                return Ok(());
+            }
+            Ok(None) => {}
+            Err(e) => {
+                // Still run it, just without retries
+                tracing::warn!("Failed to persist sync job, queueing in memory: {}", e);
+            }
+        }
+
         tracing::debug!("Queueing job for background execution: {:?}", job);
         self.tx.try_send(job).map_err(|e| match e {
             flume::TrySendError::Full(_) => {
@@ -81,6 +124,7 @@ where
 #[derive(Debug)]
 pub struct JobReceiver {
     rx: flume::Receiver<SyncJob>,
+    wake: Arc<Notify>,
 }
 
 impl JobReceiver {
@@ -88,30 +132,34 @@ impl JobReceiver {
     pub fn into_async(self) -> flume::r#async::RecvStream<'static, SyncJob> {
         self.rx.into_stream()
     }
+
+    /// Notified whenever a durable job is queued
+    pub fn wake(&self) -> Arc<Notify> {
+        self.wake.clone()
+    }
 }
 
 /// Run the background worker for queued sync jobs
 ///
-/// This function processes jobs from the queue and also runs periodic ping scheduling.
-/// It should be spawned in a background task.
+/// This function processes jobs from the queue, runs durable jobs as they
+/// come due, and also runs periodic ping scheduling. It should be spawned in
+/// a background task.
 ///
 /// # Example
 ///
 /// ```ignore
-/// let (sync_provider, job_receiver) = QueuedSyncProvider::new(config);
+/// let (sync_provider, job_receiver) = QueuedSyncProvider::new(config, database.clone());
 /// let peer = PeerBuilder::new()
 ///     .with_sync_provider(Arc::new(sync_provider))
 ///     .build()
 ///     .await;
 ///
 /// tokio::spawn(async move {
-///     run_worker(peer, job_receiver.into_async()).await;
+///     run_worker(peer, database, job_receiver).await;
 /// });
 /// ```
-pub async fn run_worker<L>(
-    peer: common::peer::Peer<L>,
-    mut job_stream: flume::r#async::RecvStream<'static, SyncJob>,
-) where
+pub async fn run_worker<L>(peer: common::peer::Peer<L>, database: Database, receiver: JobReceiver)
+where
     L: common::bucket_log::BucketLogProvider + Clone + Send + Sync + 'static,
     L::Error: std::error::Error + Send + Sync + 'static,
 {
@@ -120,7 +168,21 @@ pub async fn run_worker<L>(
 
     tracing::info!("Starting background job worker for peer {}", peer.id());
 
-    // Create interval timer for periodic pings (every 5 seconds)
+    let wake = receiver.wake();
+    let mut job_stream = receiver.into_async();
+
+    // Jobs that were running when the daemon stopped start over
+    match SyncJobRecord::requeue_interrupted(&database).await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!("Requeued {} interrupted sync jobs", count),
+        Err(e) => tracing::error!("Failed to requeue interrupted sync jobs: {}", e),
+    }
+
+    // Check for durable jobs whose retry time has come
+    let mut retry_interval = interval(Duration::from_secs(5));
+
+    // Create interval timer for periodic polls; subscribed peers push heads
+    //  as they happen, so this only renews subscriptions and pings the rest
     let mut ping_interval = interval(Duration::from_secs(60));
     ping_interval.tick().await; // Skip first immediate tick
 
@@ -134,7 +196,19 @@ pub async fn run_worker<L>(
                 }
             }
 
-            // Periodic ping scheduler
+            // Durable jobs that were just queued or are due for a retry
+            _ = wake.notified() => {
+                if run_due_jobs(&peer, &database).await {
+                    wake.notify_one();
+                }
+            }
+            _ = retry_interval.tick() => {
+                if run_due_jobs(&peer, &database).await {
+                    wake.notify_one();
+                }
+            }
+
+            // Periodic ping scheduler, which also expires shares
             _ = ping_interval.tick() => {
                 tracing::info!("Running periodic ping scheduler");
                 schedule_periodic_pings(&peer).await;
@@ -151,10 +225,67 @@ pub async fn run_worker<L>(
     tracing::info!("Background job worker shutting down for peer {}", peer.id());
 }
 
-/// Schedule periodic pings to all peers in all buckets
+/// Run durable jobs that are due, up to [`DURABLE_BATCH_SIZE`]
+///
+/// Returns true if the batch filled up and more jobs may be due.
+async fn run_due_jobs<L>(peer: &common::peer::Peer<L>, database: &Database) -> bool
+where
+    L: common::bucket_log::BucketLogProvider + Clone + Send + Sync + 'static,
+    L::Error: std::error::Error + Send + Sync + 'static,
+{
+    use common::peer::sync::execute_job;
+
+    for _ in 0..DURABLE_BATCH_SIZE {
+        let record = match SyncJobRecord::claim_due(database).await {
+            Ok(Some(record)) => record,
+            Ok(None) => return false,
+            Err(e) => {
+                tracing::error!("Failed to claim sync job: {}", e);
+                return false;
+            }
+        };
+
+        let result = execute_job(peer, record.to_job()).await;
+        let outcome = match result {
+            Ok(()) => SyncJobRecord::complete(record.id, database).await,
+            Err(e) => {
+                let error = e.to_string();
+                SyncJobRecord::fail(record.id, &error, database)
+                    .await
+                    .map(|status| match status {
+                        Some(SyncJobStatus::Failed) => tracing::error!(
+                            "Sync job {} failed after {} attempts: {}",
+                            record.id,
+                            record.attempts + 1,
+                            error
+                        ),
+                        Some(SyncJobStatus::Pending) => tracing::warn!(
+                            "Sync job {} failed (attempt {}), will retry: {}",
+                            record.id,
+                            record.attempts + 1,
+                            error
+                        ),
+                        _ => tracing::info!(
+                            "Sync job {} failed after being cancelled: {}",
+                            record.id,
+                            error
+                        ),
+                    })
+            }
+        };
+        if let Err(e) = outcome {
+            tracing::error!("Failed to record outcome of sync job {}: {}", record.id, e);
+        }
+    }
+    true
+}
+
+/// Schedule periodic polls of all peers in all buckets
 ///
-/// This is app-specific scheduling logic - calls peer.ping_bucket_peers()
-/// for each bucket on a timer.
+/// This is app-specific scheduling logic - calls peer.poll() for each bucket
+/// on a timer, which (re)subscribes to head announcements where due and pings
+/// peers that don't push heads to us. Buckets we own also get their expired
+/// shares removed, so access ends even if nobody edits the bucket.
 async fn schedule_periodic_pings<L>(peer: &common::peer::Peer<L>)
 where
     L: common::bucket_log::BucketLogProvider + Clone + Send + Sync + 'static,
@@ -171,10 +302,42 @@ where
 
     tracing::debug!("Scheduling periodic pings for {} buckets", bucket_ids.len());
 
-    // For each bucket, ping all peers in shares
+    // For each bucket, poll all peers in shares and drop expired shares
     for bucket_id in bucket_ids {
-        if let Err(e) = peer.ping(bucket_id).await {
-            tracing::warn!("Failed to ping peers for bucket {}: {}", bucket_id, e);
+        if let Err(e) = peer.poll(bucket_id).await {
+            tracing::warn!("Failed to poll peers for bucket {}: {}", bucket_id, e);
+        }
+        if let Err(e) = peer.remove_expired_shares(bucket_id).await {
+            tracing::warn!(
+                "Failed to remove expired shares of bucket {}: {}",
+                bucket_id,
+                e
+            );
+        }
+    }
+}
+
+/// Persist sync progress events into the per-bucket sync state table
+///
+/// Runs until the peer's progress channel closes. Falling behind the channel
+/// only drops intermediate events; the next one brings the state up to date.
+pub async fn record_progress(
+    database: Database,
+    mut progress: tokio::sync::broadcast::Receiver<SyncProgress>,
+) {
+    use tokio::sync::broadcast::error::RecvError;
+
+    loop {
+        let SyncProgress { bucket_id, event } = match progress.recv().await {
+            Ok(progress) => progress,
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::debug!("Sync progress recorder skipped {} events", skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+        if let Err(e) = BucketSyncState::record(bucket_id, &event, &database).await {
+            tracing::error!("Failed to record sync progress for {}: {}", bucket_id, e);
         }
     }
 }
@@ -66,6 +66,7 @@ pub struct HistoryEntry {
     pub published: bool,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
+    pub parents: Vec<String>,
 }
 
 /// Share info for a bucket peer
@@ -351,6 +352,8 @@ pub async fn share_bucket(
     let bucket_uuid = parse_bucket_id(&bucket_id)?;
     let share_role = match role.to_lowercase().as_str() {
         "mirror" => ShareRole::Mirror,
+        "reader" => ShareRole::Reader,
+        "depositor" => ShareRole::Depositor,
         _ => ShareRole::Owner,
     };
     client
@@ -358,6 +361,8 @@ pub async fn share_bucket(
             bucket_id: bucket_uuid,
             peer_public_key,
             role: share_role,
+            write_scope: Vec::new(),
+            expires_at: None,
         })
         .await
         .map_err(|e| e.to_string())?;
@@ -405,6 +410,7 @@ pub async fn publish_bucket(state: State<'_, AppState>, bucket_id: String) -> Re
     client
         .call(PublishRequest {
             bucket_id: bucket_uuid,
+            paths: Vec::new(),
         })
         .await
         .map_err(|e| e.to_string())?;
@@ -551,6 +557,7 @@ pub async fn get_history(
             height: e.height,
             published: e.published,
             created_at: e.created_at,
+            parents: e.parents,
         })
         .collect())
 }
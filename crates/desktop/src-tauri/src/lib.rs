@@ -233,9 +233,11 @@ async fn connect_or_spawn_daemon(app_handle: &tauri::AppHandle) -> Result<(), St
     let config = jax_daemon::ServiceConfig {
         node_listen_addr,
         node_secret: Some(secret_key),
+        gossip: jax_state.config.gossip,
         blob_store: jax_state.config.blob_store.clone(),
         jax_dir: jax_state.jax_dir.clone(),
         max_import_size: jax_state.config.max_import_size,
+        download_parallelism: jax_state.config.download_parallelism,
         api_port,
         gateway_port,
         sqlite_path: Some(jax_state.db_path),
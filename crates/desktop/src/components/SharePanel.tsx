@@ -312,7 +312,9 @@ const SharePanel: Component<SharePanelProps> = (props) => {
                 }}
               >
                 <option value="owner">Owner</option>
+                <option value="reader">Reader</option>
                 <option value="mirror">Mirror</option>
+                <option value="depositor">Depositor</option>
               </select>
             </div>
             <button
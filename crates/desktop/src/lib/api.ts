@@ -38,6 +38,8 @@ export interface HistoryEntry {
   height: number;
   published: boolean;
   created_at: string;
+  /** Parent version hashes, primary first; more than one means a merge */
+  parents: string[];
 }
 
 export interface ShareInfo {
@@ -198,6 +198,22 @@ const History: Component = () => {
                       Draft
                     </span>
                   </Show>
+                  <Show when={entry.parents.length > 1}>
+                    <span
+                      title={`Merges ${entry.parents.length} versions`}
+                      style={{
+                        'font-size': '0.6875rem',
+                        'font-weight': '500',
+                        padding: '0.125rem 0.5rem',
+                        'margin-left': '0.375rem',
+                        'border-radius': '9999px',
+                        background: 'var(--muted)',
+                        color: 'var(--muted-fg)',
+                      }}
+                    >
+                      Merge
+                    </span>
+                  </Show>
                 </div>
 
                 {/* Date */}
@@ -0,0 +1,10 @@
+-- Storage tier tracking for hot/cold tiered stores
+ALTER TABLE blobs ADD COLUMN tier TEXT NOT NULL DEFAULT 'hot';
+ALTER TABLE blobs ADD COLUMN last_accessed_at INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE blobs ADD COLUMN access_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE blobs ADD COLUMN keep_hot INTEGER NOT NULL DEFAULT 0;
+
+UPDATE blobs SET last_accessed_at = updated_at;
+
+-- Index for selecting demotion candidates
+CREATE INDEX IF NOT EXISTS idx_blobs_tier_access ON blobs(tier, last_accessed_at);
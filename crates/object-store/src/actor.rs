@@ -226,11 +226,11 @@ impl ObjectStoreActor {
                 } = cmd;
                 let store = self.store.clone();
                 self.spawn(async move {
+                    // Size comes from metadata so status checks never pull
+                    // data from (or promote out of) a cold tier
                     let status = match store.get_state(&hash).await {
-                        Ok(Some(BlobState::Complete)) => match store.get(&hash).await {
-                            Ok(Some(data)) => BlobStatus::Complete {
-                                size: data.len() as u64,
-                            },
+                        Ok(Some(BlobState::Complete)) => match store.get_size(&hash).await {
+                            Ok(Some(size)) => BlobStatus::Complete { size },
                             _ => BlobStatus::NotFound,
                         },
                         Ok(Some(BlobState::Partial)) => BlobStatus::Partial { size: None },
@@ -939,8 +939,8 @@ async fn export_path(store: BlobStore, cmd: ExportPathMsg) {
 async fn observe(store: BlobStore, hash: Hash, tx: mpsc::Sender<iroh_blobs::api::blobs::Bitfield>) {
     // Check current status including partial blobs
     let bitfield = match store.get_state(&hash).await {
-        Ok(Some(BlobState::Complete)) => match store.get(&hash).await {
-            Ok(Some(data)) => iroh_blobs::api::blobs::Bitfield::complete(data.len() as u64),
+        Ok(Some(BlobState::Complete)) => match store.get_size(&hash).await {
+            Ok(Some(size)) => iroh_blobs::api::blobs::Bitfield::complete(size),
             _ => iroh_blobs::api::blobs::Bitfield::empty(),
         },
         Ok(Some(BlobState::Partial)) => {
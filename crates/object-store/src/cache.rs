@@ -0,0 +1,416 @@
+//! Bounded LRU disk cache for blob data fetched from remote object storage.
+//!
+//! The cache sits between [`crate::storage::Storage`] and a (typically slow,
+//! remote) object store such as S3. Reads are served from local disk when
+//! possible and populated on miss; writes go through to the cache so freshly
+//! stored blobs are hot. Blob outboards are cached alongside their data.
+//! Entries are keyed by content hash, so a cached file can never be stale -
+//! only evicted.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::error::Result;
+
+/// Suffix for in-flight writes; renamed into place once complete.
+///
+/// Each write gets its own numbered temp file, so concurrent inserts of the
+/// same key never write to the same file.
+const TEMP_SUFFIX: &str = ".tmp";
+
+/// Configuration for the local disk cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskCacheConfig {
+    /// Directory holding cached blob files
+    pub path: PathBuf,
+    /// Maximum total size of cached data in bytes
+    pub max_size: u64,
+    /// Blobs larger than this (in bytes) are never cached
+    pub max_entry_size: u64,
+}
+
+/// Point-in-time statistics for a disk cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// Reads served from the cache
+    pub hits: u64,
+    /// Reads that had to go to the backing store
+    pub misses: u64,
+    /// Entries written into the cache
+    pub insertions: u64,
+    /// Entries evicted to stay within the size limit
+    pub evictions: u64,
+    /// Number of entries currently cached
+    pub entries: u64,
+    /// Total bytes currently cached
+    pub size_bytes: u64,
+    /// Configured size limit in bytes
+    pub max_size_bytes: u64,
+}
+
+/// LRU bookkeeping: key -> (size, tick) plus tick -> key ordering.
+#[derive(Debug, Default)]
+struct LruIndex {
+    entries: HashMap<String, (u64, u64)>,
+    order: BTreeMap<u64, String>,
+    tick: u64,
+    size: u64,
+}
+
+impl LruIndex {
+    fn touch(&mut self, key: &str) -> bool {
+        let Some((_, tick)) = self.entries.get_mut(key) else {
+            return false;
+        };
+        self.order.remove(tick);
+        self.tick += 1;
+        *tick = self.tick;
+        self.order.insert(self.tick, key.to_string());
+        true
+    }
+
+    fn insert(&mut self, key: &str, size: u64) {
+        self.remove(key);
+        self.tick += 1;
+        self.entries.insert(key.to_string(), (size, self.tick));
+        self.order.insert(self.tick, key.to_string());
+        self.size += size;
+    }
+
+    fn remove(&mut self, key: &str) -> bool {
+        match self.entries.remove(key) {
+            Some((size, tick)) => {
+                self.order.remove(&tick);
+                self.size -= size;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pop least-recently-used entries until `size` fits under `limit`.
+    fn evict_to(&mut self, limit: u64) -> Vec<String> {
+        let mut evicted = Vec::new();
+        while self.size > limit {
+            let Some((_, key)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some((size, _)) = self.entries.remove(&key) {
+                self.size -= size;
+            }
+            evicted.push(key);
+        }
+        evicted
+    }
+}
+
+#[derive(Debug)]
+struct DiskCacheInner {
+    config: DiskCacheConfig,
+    index: Mutex<LruIndex>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    insertions: AtomicU64,
+    evictions: AtomicU64,
+    /// Numbers the temp files of in-flight writes
+    writes: AtomicU64,
+}
+
+/// Bounded LRU cache of blob data on local disk.
+///
+/// Cheap to clone; all clones share the same index and statistics.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    inner: Arc<DiskCacheInner>,
+}
+
+impl DiskCache {
+    /// Open (or create) a cache directory, indexing any entries left from a
+    /// previous run in modification-time order.
+    pub async fn open(config: DiskCacheConfig) -> Result<Self> {
+        tokio::fs::create_dir_all(&config.path).await?;
+
+        let mut existing = Vec::new();
+        let mut dir = tokio::fs::read_dir(&config.path).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.ends_with(TEMP_SUFFIX) {
+                // Leftover from an interrupted write
+                let _ = tokio::fs::remove_file(entry.path()).await;
+                continue;
+            }
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let modified = metadata.modified().unwrap_or(std::time::UNIX_EPOCH);
+            existing.push((modified, name, metadata.len()));
+        }
+        existing.sort();
+
+        let mut index = LruIndex::default();
+        for (_, name, size) in existing {
+            index.insert(&name, size);
+        }
+        let stale = index.evict_to(config.max_size);
+        debug!(
+            path = %config.path.display(),
+            entries = index.entries.len(),
+            size = index.size,
+            "opened blob disk cache"
+        );
+
+        let cache = Self {
+            inner: Arc::new(DiskCacheInner {
+                config,
+                index: Mutex::new(index),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+                insertions: AtomicU64::new(0),
+                evictions: AtomicU64::new(0),
+                writes: AtomicU64::new(0),
+            }),
+        };
+        cache.remove_files(&stale).await;
+        Ok(cache)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.inner.config.path.join(key)
+    }
+
+    /* Getters */
+
+    /// Snapshot the current cache statistics.
+    pub fn stats(&self) -> CacheStats {
+        let index = self.inner.index.lock().expect("cache index poisoned");
+        CacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            insertions: self.inner.insertions.load(Ordering::Relaxed),
+            evictions: self.inner.evictions.load(Ordering::Relaxed),
+            entries: index.entries.len() as u64,
+            size_bytes: index.size,
+            max_size_bytes: self.inner.config.max_size,
+        }
+    }
+
+    /// Read an entry, recording a hit or miss.
+    pub async fn get(&self, key: &str) -> Result<Option<Bytes>> {
+        let present = self
+            .inner
+            .index
+            .lock()
+            .expect("cache index poisoned")
+            .touch(key);
+        if !present {
+            self.inner.misses.fetch_add(1, Ordering::Relaxed);
+            return Ok(None);
+        }
+
+        match tokio::fs::read(self.entry_path(key)).await {
+            Ok(data) => {
+                self.inner.hits.fetch_add(1, Ordering::Relaxed);
+                Ok(Some(Bytes::from(data)))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                // File vanished underneath us (evicted concurrently or removed by hand)
+                self.inner
+                    .index
+                    .lock()
+                    .expect("cache index poisoned")
+                    .remove(key);
+                self.inner.misses.fetch_add(1, Ordering::Relaxed);
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /* Setters */
+
+    /// Insert an entry, evicting least-recently-used entries as needed.
+    ///
+    /// Entries larger than `max_entry_size` (or the whole cache) are skipped.
+    pub async fn insert(&self, key: &str, data: &Bytes) -> Result<()> {
+        let size = data.len() as u64;
+        let config = &self.inner.config;
+        if size > config.max_entry_size || size > config.max_size {
+            debug!(key, size, "blob too large for disk cache, skipping");
+            return Ok(());
+        }
+
+        let path = self.entry_path(key);
+        let write = self.inner.writes.fetch_add(1, Ordering::Relaxed);
+        let temp_path = self.entry_path(&format!("{key}.{write}{TEMP_SUFFIX}"));
+        tokio::fs::write(&temp_path, data).await?;
+        tokio::fs::rename(&temp_path, &path).await?;
+
+        let evicted = {
+            let mut index = self.inner.index.lock().expect("cache index poisoned");
+            index.insert(key, size);
+            index.evict_to(config.max_size)
+        };
+        self.inner.insertions.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .evictions
+            .fetch_add(evicted.len() as u64, Ordering::Relaxed);
+        self.remove_files(&evicted).await;
+        Ok(())
+    }
+
+    /// Drop an entry from the cache, if present.
+    pub async fn remove(&self, key: &str) -> Result<()> {
+        let removed = self
+            .inner
+            .index
+            .lock()
+            .expect("cache index poisoned")
+            .remove(key);
+        if removed {
+            self.remove_files(&[key.to_string()]).await;
+        }
+        Ok(())
+    }
+
+    async fn remove_files(&self, keys: &[String]) {
+        for key in keys {
+            match tokio::fs::remove_file(self.entry_path(key)).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => warn!(key = %key, error = %e, "failed to remove cached blob"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn config(dir: &Path, max_size: u64, max_entry_size: u64) -> DiskCacheConfig {
+        DiskCacheConfig {
+            path: dir.to_path_buf(),
+            max_size,
+            max_entry_size,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hit_and_miss() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(config(temp_dir.path(), 1024, 1024))
+            .await
+            .unwrap();
+
+        assert!(cache.get("a").await.unwrap().is_none());
+        cache.insert("a", &Bytes::from("hello")).await.unwrap();
+        assert_eq!(cache.get("a").await.unwrap().unwrap(), "hello");
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.size_bytes, 5);
+    }
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(config(temp_dir.path(), 10, 10))
+            .await
+            .unwrap();
+
+        cache.insert("a", &Bytes::from("aaaa")).await.unwrap();
+        cache.insert("b", &Bytes::from("bbbb")).await.unwrap();
+        // Touch "a" so "b" becomes the eviction candidate
+        cache.get("a").await.unwrap();
+        cache.insert("c", &Bytes::from("cccc")).await.unwrap();
+
+        assert!(cache.get("a").await.unwrap().is_some());
+        assert!(cache.get("b").await.unwrap().is_none());
+        assert!(cache.get("c").await.unwrap().is_some());
+        assert!(!temp_dir.path().join("b").exists());
+
+        let stats = cache.stats();
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.size_bytes, 8);
+    }
+
+    #[tokio::test]
+    async fn test_skips_oversized_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(config(temp_dir.path(), 100, 4))
+            .await
+            .unwrap();
+
+        cache
+            .insert("big", &Bytes::from("too large"))
+            .await
+            .unwrap();
+        assert!(cache.get("big").await.unwrap().is_none());
+        assert_eq!(cache.stats().insertions, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reopen_restores_index() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        {
+            let cache = DiskCache::open(config(temp_dir.path(), 100, 100))
+                .await
+                .unwrap();
+            cache.insert("a", &Bytes::from("persisted")).await.unwrap();
+        }
+        // Leftover partial write should be cleaned up
+        std::fs::write(temp_dir.path().join("b.tmp"), b"partial").unwrap();
+
+        let cache = DiskCache::open(config(temp_dir.path(), 100, 100))
+            .await
+            .unwrap();
+        assert_eq!(cache.stats().entries, 1);
+        assert_eq!(cache.get("a").await.unwrap().unwrap(), "persisted");
+        assert!(!temp_dir.path().join("b.tmp").exists());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_inserts_of_same_key() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(config(temp_dir.path(), 1 << 20, 1 << 20))
+            .await
+            .unwrap();
+        let data = Bytes::from(vec![7u8; 64 * 1024]);
+
+        let inserts = (0..8).map(|_| {
+            let cache = cache.clone();
+            let data = data.clone();
+            tokio::spawn(async move { cache.insert("a", &data).await })
+        });
+        for insert in futures::future::join_all(inserts).await {
+            insert.unwrap().unwrap();
+        }
+
+        assert_eq!(cache.get("a").await.unwrap().unwrap(), data);
+        assert_eq!(cache.stats().entries, 1);
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_remove() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(config(temp_dir.path(), 100, 100))
+            .await
+            .unwrap();
+
+        cache.insert("a", &Bytes::from("data")).await.unwrap();
+        cache.remove("a").await.unwrap();
+        assert!(cache.get("a").await.unwrap().is_none());
+        assert_eq!(cache.stats().size_bytes, 0);
+    }
+}
@@ -8,6 +8,7 @@ use sqlx::{
 };
 
 use crate::error::Result;
+use crate::tier::Tier;
 
 /// Blob metadata stored in SQLite.
 ///
@@ -20,6 +21,8 @@ pub(crate) struct BlobMetadata {
     pub size: i64,
     pub has_outboard: bool,
     pub state: BlobState,
+    pub tier: Tier,
+    pub keep_hot: bool,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -189,7 +192,7 @@ impl Database {
     pub async fn get_blob(&self, hash: &str) -> Result<Option<BlobMetadata>> {
         let row = sqlx::query(
             r#"
-            SELECT hash, size, has_outboard, state, created_at, updated_at
+            SELECT hash, size, has_outboard, state, tier, keep_hot, created_at, updated_at
             FROM blobs
             WHERE hash = ?
             "#,
@@ -203,6 +206,8 @@ impl Database {
             size: r.get("size"),
             has_outboard: r.get::<i32, _>("has_outboard") != 0,
             state: BlobState::parse(r.get("state")),
+            tier: Tier::parse(r.get("tier")),
+            keep_hot: r.get::<i32, _>("keep_hot") != 0,
             created_at: r.get("created_at"),
             updated_at: r.get("updated_at"),
         }))
@@ -249,6 +254,144 @@ impl Database {
 
         Ok(rows.iter().map(|r| r.get("hash")).collect())
     }
+
+    /// Record a read of a blob for tiering decisions.
+    pub async fn record_access(&self, hash: &str) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query(
+            r#"
+            UPDATE blobs SET last_accessed_at = ?, access_count = access_count + 1
+            WHERE hash = ?
+            "#,
+        )
+        .bind(now)
+        .bind(hash)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Move a blob to a storage tier.
+    ///
+    /// Moving into the hot tier also counts as an access.
+    pub async fn set_tier(&self, hash: &str, tier: Tier) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query(
+            r#"
+            UPDATE blobs SET
+                tier = ?,
+                last_accessed_at = COALESCE(?, last_accessed_at),
+                updated_at = ?
+            WHERE hash = ?
+            "#,
+        )
+        .bind(tier.as_str())
+        .bind((tier == Tier::Hot).then_some(now))
+        .bind(now)
+        .bind(hash)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark whether a blob must always stay in the hot tier.
+    pub async fn set_keep_hot(&self, hash: &str, keep_hot: bool) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE blobs SET keep_hot = ? WHERE hash = ?
+            "#,
+        )
+        .bind(keep_hot)
+        .bind(hash)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Hot blobs eligible for demotion under the given thresholds,
+    /// least recently read first.
+    pub async fn demotion_candidates(
+        &self,
+        min_size: i64,
+        created_before: i64,
+        accessed_before: i64,
+        max_access_count: i64,
+    ) -> Result<Vec<(String, i64)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT hash, size FROM blobs
+            WHERE state = ? AND tier = ? AND keep_hot = 0 AND size >= ?
+                AND created_at <= ? AND last_accessed_at <= ? AND access_count <= ?
+            ORDER BY last_accessed_at ASC
+            "#,
+        )
+        .bind(BlobState::Complete.as_str())
+        .bind(Tier::Hot.as_str())
+        .bind(min_size)
+        .bind(created_before)
+        .bind(accessed_before)
+        .bind(max_access_count)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|r| (r.get("hash"), r.get("size")))
+            .collect())
+    }
+
+    /// All hot blobs that may be demoted to relieve size pressure,
+    /// least used first.
+    pub async fn pressure_candidates(&self, min_size: i64) -> Result<Vec<(String, i64)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT hash, size FROM blobs
+            WHERE state = ? AND tier = ? AND keep_hot = 0 AND size >= ?
+            ORDER BY access_count ASC, last_accessed_at ASC
+            "#,
+        )
+        .bind(BlobState::Complete.as_str())
+        .bind(Tier::Hot.as_str())
+        .bind(min_size)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|r| (r.get("hash"), r.get("size")))
+            .collect())
+    }
+
+    /// Halve all read counts so access frequency favours recent reads.
+    pub async fn decay_access_counts(&self) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE blobs SET access_count = access_count / 2 WHERE access_count > 0
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Number of complete blobs and their total size, per tier.
+    pub async fn tier_usage(&self) -> Result<Vec<(Tier, i64, i64)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT tier, COUNT(*) as count, COALESCE(SUM(size), 0) as total
+            FROM blobs WHERE state = ?
+            GROUP BY tier
+            "#,
+        )
+        .bind(BlobState::Complete.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|r| (Tier::parse(r.get("tier")), r.get("count"), r.get("total")))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -393,6 +536,45 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_tier_tracking() {
+        let db = Database::in_memory().await.unwrap();
+        db.insert_blob("small", 10, false).await.unwrap();
+        db.insert_blob("large", 4096, true).await.unwrap();
+        db.insert_blob("pinned", 4096, true).await.unwrap();
+        assert!(db.set_keep_hot("pinned", true).await.unwrap());
+
+        let now = chrono::Utc::now().timestamp();
+        let candidates = db.demotion_candidates(1024, now, now, 0).await.unwrap();
+        assert_eq!(candidates, vec![("large".to_string(), 4096)]);
+
+        // Frequently read blobs stay hot until their count decays
+        db.record_access("large").await.unwrap();
+        db.record_access("large").await.unwrap();
+        assert!(db
+            .demotion_candidates(1024, now, now, 1)
+            .await
+            .unwrap()
+            .is_empty());
+        db.decay_access_counts().await.unwrap();
+        assert_eq!(
+            db.demotion_candidates(1024, now, now, 1)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        db.set_tier("large", Tier::Cold).await.unwrap();
+        assert_eq!(
+            db.get_blob("large").await.unwrap().unwrap().tier,
+            Tier::Cold
+        );
+        let usage = db.tier_usage().await.unwrap();
+        assert!(usage.contains(&(Tier::Cold, 1, 4096)));
+        assert!(usage.contains(&(Tier::Hot, 2, 4106)));
+    }
+
     #[tokio::test]
     async fn test_get_blob_state_nonexistent() {
         let db = Database::in_memory().await.unwrap();
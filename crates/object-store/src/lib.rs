@@ -9,6 +9,10 @@
 //! - Content-addressed storage using BLAKE3 hashes (compatible with iroh-blobs)
 //! - SQLite for fast metadata queries
 //! - Multiple storage backends: S3, MinIO, local filesystem, in-memory
+//! - Optional bounded LRU disk cache in front of remote object storage
+//! - Optional hot/cold tiering: large, cold blobs are offloaded to a second
+//!   backend and promoted back on read
+//! - Integrity scrub: re-hash blobs and reconcile metadata with object storage
 //! - Recovery support: rebuild metadata from object storage
 //!
 //! # Example
@@ -32,12 +36,18 @@
 //! ```
 
 mod actor;
+mod cache;
 mod database;
 mod error;
 mod object_store;
+mod scrub;
 mod storage;
+mod tier;
 
 pub use actor::DEFAULT_MAX_IMPORT_SIZE;
+pub use cache::{CacheStats, DiskCacheConfig};
 pub use error::{BlobStoreError, Result};
 pub use object_store::ObjectStore;
+pub use scrub::{scrub_iroh_store, ScrubReport};
 pub use storage::ObjectStoreConfig;
+pub use tier::{RebalanceReport, Tier, TierPolicy, TierStats};
@@ -6,26 +6,31 @@
 
 use std::ops::Deref;
 use std::path::Path;
+use std::sync::Arc;
 
 use bytes::Bytes;
 use iroh_blobs::api::proto::Command;
 use iroh_blobs::Hash;
+use tokio::sync::MutexGuard;
 use tracing::{debug, info, warn};
 
 use crate::actor::{ObjectStoreActor, DEFAULT_MAX_IMPORT_SIZE};
+use crate::cache::{CacheStats, DiskCache, DiskCacheConfig};
 use crate::database::{BlobState, Database};
 use crate::error::Result;
+use crate::scrub::{self, ScrubReport};
 use crate::storage::{ObjectStoreConfig, Storage};
+use crate::tier::{ColdTier, RebalanceReport, Tier, TierPolicy, TierStats};
 
 /// Size threshold for generating BAO outboard data (16KB).
 /// Blobs larger than this will have outboard verification data stored separately.
-const OUTBOARD_THRESHOLD: usize = 16 * 1024;
+pub(crate) const OUTBOARD_THRESHOLD: usize = 16 * 1024;
 
 /// Block size for BAO tree operations (matches iroh-blobs IROH_BLOCK_SIZE).
-const IROH_BLOCK_SIZE: bao_tree::BlockSize = bao_tree::BlockSize::from_chunk_log(4);
+pub(crate) const IROH_BLOCK_SIZE: bao_tree::BlockSize = bao_tree::BlockSize::from_chunk_log(4);
 
 /// Type alias for the irpc client
-type ApiClient = irpc::Client<iroh_blobs::api::proto::Request>;
+pub(crate) type ApiClient = irpc::Client<iroh_blobs::api::proto::Request>;
 
 /// Internal BlobStore combining SQLite metadata with object storage.
 ///
@@ -34,6 +39,8 @@ type ApiClient = irpc::Client<iroh_blobs::api::proto::Request>;
 pub(crate) struct BlobStore {
     db: Database,
     storage: Storage,
+    /// Cold backend for tiered stores; `storage` is then the hot tier
+    cold: Option<Arc<ColdTier>>,
 }
 
 impl BlobStore {
@@ -41,14 +48,22 @@ impl BlobStore {
     pub async fn new(db_path: &Path, config: ObjectStoreConfig) -> Result<Self> {
         let db = Database::new(db_path).await?;
         let storage = Storage::new(config).await?;
-        Ok(Self { db, storage })
+        Ok(Self {
+            db,
+            storage,
+            cold: None,
+        })
     }
 
     /// Create a new BlobStore with an in-memory SQLite database.
     pub async fn in_memory(config: ObjectStoreConfig) -> Result<Self> {
         let db = Database::in_memory().await?;
         let storage = Storage::new(config).await?;
-        Ok(Self { db, storage })
+        Ok(Self {
+            db,
+            storage,
+            cold: None,
+        })
     }
 
     /// Create a new BlobStore backed by local filesystem.
@@ -68,6 +83,65 @@ impl BlobStore {
         Self::in_memory(ObjectStoreConfig::Memory).await
     }
 
+    /// Front blob data in object storage with a local disk cache.
+    pub fn with_cache(mut self, cache: DiskCache) -> Self {
+        self.storage = self.storage.with_cache(cache);
+        self
+    }
+
+    /// Offload cold blob data to a second backend according to `policy`.
+    pub fn with_cold_tier(mut self, cold: Storage, policy: TierPolicy) -> Self {
+        self.cold = Some(Arc::new(ColdTier::new(cold, policy)));
+        self
+    }
+
+    /// Statistics for the disk cache, if one is configured.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.storage.cache_stats()
+    }
+
+    /// Tiering policy, if this is a tiered store.
+    pub fn tier_policy(&self) -> Option<&TierPolicy> {
+        self.cold.as_ref().map(|cold| &cold.policy)
+    }
+
+    /// Per-tier usage, if this is a tiered store.
+    pub async fn tier_stats(&self) -> Result<Option<TierStats>> {
+        let Some(cold) = &self.cold else {
+            return Ok(None);
+        };
+        let mut stats = TierStats {
+            promotions: cold.promotions(),
+            demotions: cold.demotions(),
+            ..Default::default()
+        };
+        for (tier, count, bytes) in self.db.tier_usage().await? {
+            match tier {
+                Tier::Hot => {
+                    stats.hot_blobs = count as u64;
+                    stats.hot_bytes = bytes as u64;
+                }
+                Tier::Cold => {
+                    stats.cold_blobs = count as u64;
+                    stats.cold_bytes = bytes as u64;
+                }
+            }
+        }
+        Ok(Some(stats))
+    }
+
+    /// Verify every blob against its hash and reconcile metadata with storage.
+    pub async fn scrub(&self) -> Result<ScrubReport> {
+        let cold = self.cold.as_ref().map(|cold| &cold.storage);
+        scrub::scrub(&self.db, &self.storage, cold).await
+    }
+
+    /// Access the underlying object storage (test-only).
+    #[cfg(test)]
+    pub(crate) fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
     /// Close the database connection pool.
     #[allow(dead_code)]
     #[cfg(test)]
@@ -86,30 +160,215 @@ impl BlobStore {
 
         debug!(hash = %hash_str, size = size, "storing blob");
 
+        let _lock = self.lock(&hash_str).await;
         let has_outboard = size > OUTBOARD_THRESHOLD;
         if has_outboard {
-            let outboard =
-                bao_tree::io::outboard::PreOrderMemOutboard::create(&data, IROH_BLOCK_SIZE);
+            let outboard = compute_outboard(&data);
             self.storage
-                .put_outboard(&hash_str, Bytes::from(outboard.data))
+                .put_outboard(&hash_str, Bytes::from(outboard))
                 .await?;
         }
         self.storage.put_data(&hash_str, Bytes::from(data)).await?;
         self.db
             .insert_blob(&hash_str, size as i64, has_outboard)
             .await?;
+        self.mark_hot(&hash_str).await?;
 
         info!(hash = %hash_str, size = size, "blob stored successfully");
         Ok(hash)
     }
 
     /// Retrieve blob data by hash.
+    ///
+    /// In a tiered store this records the read and promotes cold blobs back
+    /// into the hot tier.
     pub async fn get(&self, hash: &Hash) -> Result<Option<Bytes>> {
         let hash_str = hash.to_string();
-        if !self.db.has_blob(&hash_str).await? {
+        let Some(cold) = &self.cold else {
+            if !self.db.has_blob(&hash_str).await? {
+                return Ok(None);
+            }
+            return self.storage.get_data(&hash_str).await;
+        };
+
+        let Some(metadata) = self.db.get_blob(&hash_str).await? else {
+            return Ok(None);
+        };
+        if metadata.state != BlobState::Complete {
+            return Ok(None);
+        }
+        self.db.record_access(&hash_str).await?;
+        match metadata.tier {
+            // A demotion may have moved it since the metadata was read, in
+            //  which case promoting reads it back from the cold tier
+            Tier::Hot => match self.storage.get_data(&hash_str).await? {
+                Some(data) => Ok(Some(data)),
+                None => self.promote(cold, hash, &hash_str).await,
+            },
+            Tier::Cold => self.promote(cold, hash, &hash_str).await,
+        }
+    }
+
+    /// Size of a complete blob, from metadata only (never touches storage).
+    pub async fn get_size(&self, hash: &Hash) -> Result<Option<u64>> {
+        let hash_str = hash.to_string();
+        Ok(self
+            .db
+            .get_blob(&hash_str)
+            .await?
+            .filter(|metadata| metadata.state == BlobState::Complete)
+            .map(|metadata| metadata.size as u64))
+    }
+
+    /// Pin a blob to the hot tier (or release it), promoting it if needed.
+    ///
+    /// A no-op in untiered stores, which is what every node, manifest and pin
+    /// put goes through. Returns false if the store is untiered or does not
+    /// hold the blob.
+    pub async fn set_keep_hot(&self, hash: &Hash, keep_hot: bool) -> Result<bool> {
+        let Some(cold) = &self.cold else {
+            return Ok(false);
+        };
+        let hash_str = hash.to_string();
+        if !self.db.set_keep_hot(&hash_str, keep_hot).await? {
+            return Ok(false);
+        }
+        if keep_hot {
+            if let Some(metadata) = self.db.get_blob(&hash_str).await? {
+                if metadata.tier == Tier::Cold {
+                    self.promote(cold, hash, &hash_str).await?;
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Demote blobs to the cold tier according to the tiering policy.
+    pub async fn rebalance(&self) -> Result<RebalanceReport> {
+        let mut report = RebalanceReport::default();
+        let Some(cold) = &self.cold else {
+            return Ok(report);
+        };
+        let policy = &cold.policy;
+        let now = chrono::Utc::now().timestamp();
+        let min_size = policy.min_size as i64;
+
+        let candidates = self
+            .db
+            .demotion_candidates(
+                min_size,
+                now - policy.min_age.as_secs() as i64,
+                now - policy.min_idle.as_secs() as i64,
+                policy.max_access_count as i64,
+            )
+            .await?;
+        for (hash_str, size) in candidates {
+            if self.demote(cold, &hash_str).await? {
+                report.demoted += 1;
+                report.bytes += size as u64;
+            }
+        }
+
+        // Over the hot size cap: keep demoting the least used blobs
+        if let Some(limit) = policy.hot_max_size {
+            let mut hot_bytes = self
+                .db
+                .tier_usage()
+                .await?
+                .into_iter()
+                .find(|(tier, _, _)| *tier == Tier::Hot)
+                .map_or(0, |(_, _, bytes)| bytes as u64);
+            if hot_bytes > limit {
+                for (hash_str, size) in self.db.pressure_candidates(min_size).await? {
+                    if hot_bytes <= limit {
+                        break;
+                    }
+                    if self.demote(cold, &hash_str).await? {
+                        hot_bytes = hot_bytes.saturating_sub(size as u64);
+                        report.demoted += 1;
+                        report.bytes += size as u64;
+                    }
+                }
+            }
+        }
+
+        self.db.decay_access_counts().await?;
+        cold.record_demotions(report.demoted);
+        if report.demoted > 0 {
+            info!(
+                demoted = report.demoted,
+                bytes = report.bytes,
+                "moved blobs to cold tier"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Hold off tier moves of a blob while it is written, in tiered stores.
+    async fn lock(&self, hash_str: &str) -> Option<MutexGuard<'_, ()>> {
+        match &self.cold {
+            Some(cold) => Some(cold.lock(hash_str).await),
+            None => None,
+        }
+    }
+
+    /// Mark a freshly written blob as living in the hot tier.
+    async fn mark_hot(&self, hash_str: &str) -> Result<()> {
+        if self.cold.is_some() {
+            self.db.set_tier(hash_str, Tier::Hot).await?;
+        }
+        Ok(())
+    }
+
+    /// Copy a cold blob back into the hot tier and return its data.
+    ///
+    /// The cold copy is kept so a later demotion does not need to re-upload.
+    async fn promote(&self, cold: &ColdTier, hash: &Hash, hash_str: &str) -> Result<Option<Bytes>> {
+        let _lock = cold.lock(hash_str).await;
+        // Another reader may have promoted it while we waited
+        if let Some(metadata) = self.db.get_blob(hash_str).await? {
+            if metadata.tier == Tier::Hot {
+                return self.storage.get_data(hash_str).await;
+            }
+        }
+        let Some(data) = cold.storage.get_data(hash_str).await? else {
+            warn!(hash = %hash_str, "cold blob missing from cold storage");
             return Ok(None);
+        };
+        if Hash::new(&data) != *hash {
+            warn!(hash = %hash_str, "cold blob does not match its hash, not promoting");
+            return Ok(None);
+        }
+        self.storage.put_data(hash_str, data.clone()).await?;
+        self.db.set_tier(hash_str, Tier::Hot).await?;
+        cold.record_promotion();
+        debug!(hash = %hash_str, "promoted blob to hot tier");
+        Ok(Some(data))
+    }
+
+    /// Move a hot blob's data into the cold tier.
+    ///
+    /// The blob is re-checked under its lock, since it may have been
+    /// rewritten, pinned or deleted since it was picked as a candidate.
+    async fn demote(&self, cold: &ColdTier, hash_str: &str) -> Result<bool> {
+        let _lock = cold.lock(hash_str).await;
+        match self.db.get_blob(hash_str).await? {
+            Some(metadata)
+                if metadata.state == BlobState::Complete
+                    && metadata.tier == Tier::Hot
+                    && !metadata.keep_hot => {}
+            _ => return Ok(false),
         }
-        self.storage.get_data(&hash_str).await
+        let Some(data) = self.storage.get_data_uncached(hash_str).await? else {
+            warn!(hash = %hash_str, "hot blob missing from storage, not demoting");
+            return Ok(false);
+        };
+        cold.storage.put_data(hash_str, data).await?;
+        // Switch readers over before removing the hot copy
+        self.db.set_tier(hash_str, Tier::Cold).await?;
+        self.storage.delete_data(hash_str).await?;
+        debug!(hash = %hash_str, "demoted blob to cold tier");
+        Ok(true)
     }
 
     /// Delete a blob from the store.
@@ -122,7 +381,11 @@ impl BlobStore {
         }
 
         let metadata = metadata.unwrap();
+        let _lock = self.lock(&hash_str).await;
         self.storage.delete_data(&hash_str).await?;
+        if let Some(cold) = &self.cold {
+            cold.storage.delete_data(&hash_str).await?;
+        }
         if metadata.has_outboard {
             self.storage.delete_outboard(&hash_str).await?;
         }
@@ -142,6 +405,7 @@ impl BlobStore {
 
         debug!(hash = %hash_str, size = size, "storing blob with outboard");
 
+        let _lock = self.lock(&hash_str).await;
         let has_outboard = !outboard.is_empty();
         self.storage.put_data(&hash_str, Bytes::from(data)).await?;
         if has_outboard {
@@ -152,6 +416,7 @@ impl BlobStore {
         self.db
             .insert_blob(&hash_str, size as i64, has_outboard)
             .await?;
+        self.mark_hot(&hash_str).await?;
 
         info!(hash = %hash_str, size = size, "blob with outboard stored successfully");
         Ok(hash)
@@ -192,6 +457,11 @@ impl BlobStore {
     }
 }
 
+/// Compute the BAO outboard for a blob's data.
+pub(crate) fn compute_outboard(data: &[u8]) -> Vec<u8> {
+    bao_tree::io::outboard::PreOrderMemOutboard::create(data, IROH_BLOCK_SIZE).data
+}
+
 /// ObjectStore provides an iroh-blobs compatible store backed by SQLite + object storage.
 ///
 /// This store can be used with iroh-blobs' BlobsProtocol to enable P2P sync
@@ -219,6 +489,7 @@ impl BlobStore {
 #[derive(Debug, Clone)]
 pub struct ObjectStore {
     client: ApiClient,
+    store: BlobStore,
 }
 
 impl ObjectStore {
@@ -240,6 +511,55 @@ impl ObjectStore {
         ))
     }
 
+    /// Create a new ObjectStore whose blob data is fronted by a bounded LRU
+    /// disk cache. Reads populate the cache on miss; writes go through it.
+    ///
+    /// # Arguments
+    /// * `db_path` - Path to the SQLite database file
+    /// * `config` - Object storage configuration (S3, MinIO, local, or memory)
+    /// * `cache_config` - Location and size limits for the disk cache
+    /// * `max_import_size` - Maximum blob size for BAO imports, or None for default (1GB)
+    pub async fn new_cached(
+        db_path: &Path,
+        config: ObjectStoreConfig,
+        cache_config: DiskCacheConfig,
+        max_import_size: Option<u64>,
+    ) -> Result<Self> {
+        let cache = DiskCache::open(cache_config).await?;
+        let store = BlobStore::new(db_path, config).await?.with_cache(cache);
+        Ok(Self::from_blob_store(
+            store,
+            max_import_size.unwrap_or(DEFAULT_MAX_IMPORT_SIZE),
+        ))
+    }
+
+    /// Create a new tiered ObjectStore: blob data lands in the `hot` backend
+    /// and is moved to the `cold` backend in the background according to
+    /// `policy`. Reading a cold blob promotes it back into the hot tier.
+    ///
+    /// # Arguments
+    /// * `db_path` - Path to the SQLite database file
+    /// * `hot` - Fast (usually local) object storage for recent and small blobs
+    /// * `cold` - Cheap object storage for large, rarely read blobs
+    /// * `policy` - When blobs are demoted and how often to check
+    /// * `max_import_size` - Maximum blob size for BAO imports, or None for default (1GB)
+    pub async fn new_tiered(
+        db_path: &Path,
+        hot: ObjectStoreConfig,
+        cold: ObjectStoreConfig,
+        policy: TierPolicy,
+        max_import_size: Option<u64>,
+    ) -> Result<Self> {
+        let cold = Storage::new(cold).await?;
+        let store = BlobStore::new(db_path, hot)
+            .await?
+            .with_cold_tier(cold, policy);
+        Ok(Self::from_blob_store(
+            store,
+            max_import_size.unwrap_or(DEFAULT_MAX_IMPORT_SIZE),
+        ))
+    }
+
     /// Create a new ObjectStore backed by local filesystem.
     ///
     /// # Arguments
@@ -298,10 +618,49 @@ impl ObjectStore {
     /// Create an ObjectStore from an existing BlobStore.
     fn from_blob_store(store: BlobStore, max_import_size: u64) -> Self {
         let (tx, rx) = tokio::sync::mpsc::channel::<Command>(256);
-        let actor = ObjectStoreActor::new(store, rx, max_import_size);
+        let actor = ObjectStoreActor::new(store.clone(), rx, max_import_size);
         tokio::spawn(actor.run());
+        if let Some(policy) = store.tier_policy() {
+            tokio::spawn(rebalance_loop(
+                store.clone(),
+                policy.interval,
+                tx.downgrade(),
+            ));
+        }
         let client: ApiClient = tx.into();
-        Self { client }
+        Self { client, store }
+    }
+
+    /// Statistics for the disk cache, if one is configured.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.store.cache_stats()
+    }
+
+    /// Per-tier blob counts and sizes, if this is a tiered store.
+    pub async fn tier_stats(&self) -> Result<Option<TierStats>> {
+        self.store.tier_stats().await
+    }
+
+    /// Run a tiering pass now instead of waiting for the background one.
+    pub async fn rebalance(&self) -> Result<RebalanceReport> {
+        self.store.rebalance().await
+    }
+
+    /// Keep a blob in the hot tier regardless of policy (or release it).
+    ///
+    /// Used for metadata such as manifests, pins and directory nodes.
+    /// Returns false if the store is untiered or does not hold the blob.
+    pub async fn set_keep_hot(&self, hash: &Hash, keep_hot: bool) -> Result<bool> {
+        self.store.set_keep_hot(hash, keep_hot).await
+    }
+
+    /// Run an integrity scrub over all stored blobs.
+    ///
+    /// Re-hashes data, regenerates missing or wrong outboards and reconciles
+    /// SQLite metadata with object storage. Corrupt or missing blobs are
+    /// evicted so they can be re-fetched from peers.
+    pub async fn scrub(&self) -> Result<ScrubReport> {
+        self.store.scrub().await
     }
 
     /// Convert to an iroh_blobs::api::Store.
@@ -326,6 +685,25 @@ impl ObjectStore {
     }
 }
 
+/// Periodically rebalance a tiered store until its ObjectStore is dropped.
+async fn rebalance_loop(
+    store: BlobStore,
+    interval: std::time::Duration,
+    alive: tokio::sync::mpsc::WeakSender<Command>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await; // Skip first immediate tick
+    loop {
+        ticker.tick().await;
+        if alive.upgrade().is_none() {
+            break;
+        }
+        if let Err(e) = store.rebalance().await {
+            warn!(error = %e, "blob tier rebalance failed");
+        }
+    }
+}
+
 /// Convert ObjectStore to iroh_blobs::api::Store.
 ///
 /// This allows ObjectStore to be used with BlobsProtocol for P2P sync.
@@ -794,4 +1172,202 @@ mod tests {
         let status = store.status(fake_hash).await.unwrap();
         assert!(matches!(status, BlobStatus::NotFound));
     }
+
+    fn eager_policy() -> TierPolicy {
+        TierPolicy {
+            min_size: 1024,
+            min_age: std::time::Duration::ZERO,
+            min_idle: std::time::Duration::ZERO,
+            max_access_count: 0,
+            hot_max_size: None,
+            interval: std::time::Duration::from_secs(3600),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tiered_demote_and_promote() {
+        let cold = Storage::memory();
+        let store = BlobStore::new_ephemeral()
+            .await
+            .unwrap()
+            .with_cold_tier(cold.clone(), eager_policy());
+
+        let small = store.put(b"tiny metadata".to_vec()).await.unwrap();
+        let large_data = vec![5u8; 64 * 1024];
+        let large = store.put(large_data.clone()).await.unwrap();
+        let pinned = store.put(vec![6u8; 64 * 1024]).await.unwrap();
+        assert!(store.set_keep_hot(&pinned, true).await.unwrap());
+
+        // Only the large, unpinned blob moves
+        let report = store.rebalance().await.unwrap();
+        assert_eq!(report.demoted, 1);
+        assert_eq!(report.bytes, large_data.len() as u64);
+        assert!(!store.storage().has_data(&large.to_string()).await.unwrap());
+        assert!(cold.has_data(&large.to_string()).await.unwrap());
+        assert!(store.storage().has_data(&small.to_string()).await.unwrap());
+
+        let stats = store.tier_stats().await.unwrap().unwrap();
+        assert_eq!(stats.cold_blobs, 1);
+        assert_eq!(stats.cold_bytes, large_data.len() as u64);
+        assert_eq!(stats.hot_blobs, 2);
+        assert_eq!(stats.demotions, 1);
+
+        // Size lookups never touch the cold tier
+        assert_eq!(
+            store.get_size(&large).await.unwrap(),
+            Some(large_data.len() as u64)
+        );
+        assert_eq!(store.tier_stats().await.unwrap().unwrap().promotions, 0);
+
+        // Reading promotes it back
+        assert_eq!(store.get(&large).await.unwrap().unwrap(), large_data);
+        assert!(store.storage().has_data(&large.to_string()).await.unwrap());
+        let stats = store.tier_stats().await.unwrap().unwrap();
+        assert_eq!(stats.cold_blobs, 0);
+        assert_eq!(stats.promotions, 1);
+
+        // Scrub understands both tiers
+        store.rebalance().await.unwrap();
+        let report = store.scrub().await.unwrap();
+        assert!(report.is_clean());
+        assert_eq!(report.rows_repaired, 0);
+    }
+
+    #[tokio::test]
+    async fn test_tiered_demote_rechecks_blob() {
+        let store = BlobStore::new_ephemeral()
+            .await
+            .unwrap()
+            .with_cold_tier(Storage::memory(), eager_policy());
+        let cold = store.cold.clone().unwrap();
+        let data = vec![7u8; 64 * 1024];
+        let hash = store.put(data.clone()).await.unwrap();
+        let hash_str = hash.to_string();
+
+        // Pinned after being picked as a candidate: left alone
+        assert!(store.set_keep_hot(&hash, true).await.unwrap());
+        assert!(!store.demote(&cold, &hash_str).await.unwrap());
+        assert!(store.storage().has_data(&hash_str).await.unwrap());
+
+        // Already cold: the hot copy is not touched twice
+        assert!(store.set_keep_hot(&hash, false).await.unwrap());
+        assert!(store.demote(&cold, &hash_str).await.unwrap());
+        assert!(!store.demote(&cold, &hash_str).await.unwrap());
+
+        // A demotion waits while the blob is being written or promoted
+        assert_eq!(store.get(&hash).await.unwrap().unwrap(), data);
+        let guard = cold.lock(&hash_str).await;
+        let demotion = tokio::spawn({
+            let (store, cold, hash_str) = (store.clone(), cold.clone(), hash_str.clone());
+            async move { store.demote(&cold, &hash_str).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!demotion.is_finished());
+        drop(guard);
+        assert!(demotion.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_tiered_read_during_demotion() {
+        let store = BlobStore::new_ephemeral()
+            .await
+            .unwrap()
+            .with_cold_tier(Storage::memory(), eager_policy());
+        let cold = store.cold.clone().unwrap();
+        let data = vec![3u8; 64 * 1024];
+        let hash = store.put(data.clone()).await.unwrap();
+        let hash_str = hash.to_string();
+
+        // Reads that saw the blob hot while it moved still find it
+        for _ in 0..20 {
+            let read = tokio::spawn({
+                let store = store.clone();
+                async move { store.get(&hash).await }
+            });
+            store.demote(&cold, &hash_str).await.unwrap();
+            assert_eq!(read.await.unwrap().unwrap().unwrap(), data);
+            assert_eq!(store.get(&hash).await.unwrap().unwrap(), data);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_untiered_keep_hot_is_noop() {
+        let store = BlobStore::new_ephemeral().await.unwrap();
+        let hash = store.put(b"metadata".to_vec()).await.unwrap();
+
+        assert!(!store.set_keep_hot(&hash, true).await.unwrap());
+        let metadata = store.db.get_blob(&hash.to_string()).await.unwrap().unwrap();
+        assert!(!metadata.keep_hot);
+    }
+
+    #[tokio::test]
+    async fn test_tiered_frequently_read_stays_hot() {
+        let policy = TierPolicy {
+            max_access_count: 1,
+            ..eager_policy()
+        };
+        let store = BlobStore::new_ephemeral()
+            .await
+            .unwrap()
+            .with_cold_tier(Storage::memory(), policy);
+        let hash = store.put(vec![1u8; 64 * 1024]).await.unwrap();
+        for _ in 0..4 {
+            store.get(&hash).await.unwrap();
+        }
+
+        // 4 reads -> stays hot, decays to 2 -> stays hot, decays to 1 -> demoted
+        assert_eq!(store.rebalance().await.unwrap().demoted, 0);
+        assert_eq!(store.rebalance().await.unwrap().demoted, 0);
+        assert_eq!(store.rebalance().await.unwrap().demoted, 1);
+    }
+
+    #[tokio::test]
+    async fn test_tiered_hot_size_cap() {
+        let policy = TierPolicy {
+            min_idle: std::time::Duration::from_secs(3600),
+            hot_max_size: Some(100 * 1024),
+            ..eager_policy()
+        };
+        let store = BlobStore::new_ephemeral()
+            .await
+            .unwrap()
+            .with_cold_tier(Storage::memory(), policy);
+        let read_often = store.put(vec![1u8; 64 * 1024]).await.unwrap();
+        let read_once = store.put(vec![2u8; 64 * 1024]).await.unwrap();
+        store.get(&read_often).await.unwrap();
+        store.get(&read_often).await.unwrap();
+        store.get(&read_once).await.unwrap();
+
+        // Nothing is idle, but the cap forces the least used blob out
+        let report = store.rebalance().await.unwrap();
+        assert_eq!(report.demoted, 1);
+        let stats = store.tier_stats().await.unwrap().unwrap();
+        assert_eq!(stats.hot_bytes, 64 * 1024);
+        assert!(store
+            .storage()
+            .has_data(&read_often.to_string())
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_scrub_corrects_tier() {
+        let cold = Storage::memory();
+        let store = BlobStore::new_ephemeral()
+            .await
+            .unwrap()
+            .with_cold_tier(cold.clone(), eager_policy());
+        let hash = store.put(vec![9u8; 64 * 1024]).await.unwrap();
+        let hash_str = hash.to_string();
+
+        // Data lives in cold storage, but the row still says hot
+        let data = store.storage().get_data(&hash_str).await.unwrap().unwrap();
+        cold.put_data(&hash_str, data).await.unwrap();
+        store.storage().delete_data(&hash_str).await.unwrap();
+
+        let report = store.scrub().await.unwrap();
+        assert!(report.is_clean());
+        assert_eq!(report.rows_repaired, 1);
+        assert_eq!(store.tier_stats().await.unwrap().unwrap().cold_blobs, 1);
+    }
 }
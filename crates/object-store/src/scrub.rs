@@ -0,0 +1,393 @@
+//! Integrity scrub for blob stores.
+//!
+//! For the SQLite + object storage backend this re-hashes every complete
+//! blob, verifies stored outboards and reconciles the `blobs` table against
+//! object storage. Plain iroh stores are verified through their BAO export.
+//! In tiered stores each blob is checked in the tier its row points at, and
+//! rows that point at the wrong tier are corrected. Anything that cannot be
+//! fixed locally (corrupt or missing data) is evicted so it can be re-fetched
+//! from peers.
+
+use bao_tree::io::outboard::PreOrderMemOutboard;
+use bao_tree::io::sync::encode_ranges_validated;
+use bao_tree::ChunkRanges;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use iroh_blobs::api::blobs::BlobStatus;
+use iroh_blobs::api::proto::BlobDeleteRequest;
+use iroh_blobs::Hash;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::database::Database;
+use crate::error::{BlobStoreError, Result};
+use crate::object_store::{compute_outboard, ApiClient, IROH_BLOCK_SIZE, OUTBOARD_THRESHOLD};
+use crate::storage::Storage;
+use crate::tier::Tier;
+
+/// Result of a blob store integrity scrub.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScrubReport {
+    /// Number of blobs checked
+    pub checked: u64,
+    /// Blobs whose data matched their hash
+    pub healthy: u64,
+    /// Blobs whose data no longer matches their hash (evicted)
+    pub corrupt: Vec<Hash>,
+    /// Blobs recorded as complete but with no data in storage (evicted)
+    pub missing: Vec<Hash>,
+    /// Outboards that were missing or wrong and have been regenerated
+    pub outboards_repaired: u64,
+    /// Metadata rows whose size or outboard flag was corrected
+    pub rows_repaired: u64,
+    /// Objects in storage without metadata that were re-registered
+    pub orphans_recovered: u64,
+    /// Objects in storage without metadata that failed verification (deleted)
+    pub orphans_removed: u64,
+}
+
+impl ScrubReport {
+    /// Hashes that need to be re-fetched from peers.
+    pub fn damaged(&self) -> impl Iterator<Item = &Hash> {
+        self.corrupt.iter().chain(self.missing.iter())
+    }
+
+    /// Whether the scrub found nothing that needs re-fetching.
+    pub fn is_clean(&self) -> bool {
+        self.corrupt.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Scrub all blobs tracked in `db` against `storage` (and the `cold` tier,
+/// for tiered stores).
+pub(crate) async fn scrub(
+    db: &Database,
+    storage: &Storage,
+    cold: Option<&Storage>,
+) -> Result<ScrubReport> {
+    let mut report = ScrubReport::default();
+
+    for hash_str in db.list_blobs().await? {
+        let Ok(hash) = hash_str.parse::<Hash>() else {
+            warn!(hash = %hash_str, "invalid hash in database, removing row");
+            db.delete_blob(&hash_str).await?;
+            report.rows_repaired += 1;
+            continue;
+        };
+        report.checked += 1;
+        check_blob(db, storage, cold, hash, &hash_str, &mut report).await?;
+    }
+
+    reconcile_orphans(db, storage, &mut report).await?;
+
+    info!(
+        checked = report.checked,
+        healthy = report.healthy,
+        corrupt = report.corrupt.len(),
+        missing = report.missing.len(),
+        outboards_repaired = report.outboards_repaired,
+        orphans_recovered = report.orphans_recovered,
+        orphans_removed = report.orphans_removed,
+        "object store scrub complete"
+    );
+    Ok(report)
+}
+
+async fn check_blob(
+    db: &Database,
+    storage: &Storage,
+    cold: Option<&Storage>,
+    hash: Hash,
+    hash_str: &str,
+    report: &mut ScrubReport,
+) -> Result<()> {
+    let metadata = db.get_blob(hash_str).await?;
+    let tier = metadata.as_ref().map_or(Tier::Hot, |m| m.tier);
+    let (home, other) = match (tier, cold) {
+        (Tier::Cold, Some(cold)) => ((Tier::Cold, cold), Some((Tier::Hot, storage))),
+        (_, cold) => ((Tier::Hot, storage), cold.map(|cold| (Tier::Cold, cold))),
+    };
+
+    // Bypass the disk cache so we verify what is actually in object storage
+    let mut found = None;
+    for (tier, backend) in std::iter::once(home).chain(other) {
+        if let Some(data) = backend.get_data_uncached(hash_str).await? {
+            found = Some((tier, backend, data));
+            break;
+        }
+    }
+    let Some((found_tier, backend, data)) = found else {
+        warn!(hash = %hash_str, "blob data missing from storage");
+        evict(db, storage, cold, hash_str).await?;
+        report.missing.push(hash);
+        return Ok(());
+    };
+
+    if Hash::new(&data) != hash {
+        warn!(hash = %hash_str, "blob data does not match its hash");
+        backend.delete_data(hash_str).await?;
+        evict(db, storage, cold, hash_str).await?;
+        report.corrupt.push(hash);
+        return Ok(());
+    }
+
+    if found_tier != home.0 {
+        debug!(hash = %hash_str, tier = found_tier.as_str(), "correcting blob tier");
+        db.set_tier(hash_str, found_tier).await?;
+        report.rows_repaired += 1;
+    }
+
+    let has_outboard = data.len() > OUTBOARD_THRESHOLD;
+    if has_outboard {
+        let expected = compute_outboard(&data);
+        if storage.get_outboard_uncached(hash_str).await?.as_deref() != Some(expected.as_slice()) {
+            debug!(hash = %hash_str, "regenerating outboard");
+            storage
+                .put_outboard(hash_str, Bytes::from(expected))
+                .await?;
+            report.outboards_repaired += 1;
+        }
+    }
+
+    if let Some(metadata) = metadata {
+        if metadata.size != data.len() as i64 || metadata.has_outboard != has_outboard {
+            db.insert_blob(hash_str, data.len() as i64, has_outboard)
+                .await?;
+            report.rows_repaired += 1;
+        }
+    }
+
+    report.healthy += 1;
+    Ok(())
+}
+
+/// Register or remove objects that exist in storage but not in the database.
+async fn reconcile_orphans(
+    db: &Database,
+    storage: &Storage,
+    report: &mut ScrubReport,
+) -> Result<()> {
+    let mut stream = std::pin::pin!(storage.list_data_hashes_stream());
+    while let Some(hash_str) = stream.try_next().await? {
+        // Any row (including partial imports in flight) means it is not an orphan
+        if db.get_blob_state(&hash_str).await?.is_some() {
+            continue;
+        }
+
+        let data = storage.get_data_uncached(&hash_str).await?;
+        let verified = match (&data, hash_str.parse::<Hash>()) {
+            (Some(data), Ok(hash)) => Hash::new(data) == hash,
+            _ => false,
+        };
+
+        match data {
+            Some(data) if verified => {
+                let has_outboard = data.len() > OUTBOARD_THRESHOLD;
+                if has_outboard {
+                    let outboard = compute_outboard(&data);
+                    storage
+                        .put_outboard(&hash_str, Bytes::from(outboard))
+                        .await?;
+                }
+                db.insert_blob(&hash_str, data.len() as i64, has_outboard)
+                    .await?;
+                debug!(hash = %hash_str, "recovered orphaned blob");
+                report.orphans_recovered += 1;
+            }
+            _ => {
+                warn!(hash = %hash_str, "removing unverifiable orphaned object");
+                storage.delete_data(&hash_str).await?;
+                storage.delete_outboard(&hash_str).await?;
+                report.orphans_removed += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Scrub a plain iroh-blobs store (e.g. the legacy `FsStore`).
+///
+/// Every complete blob is exported as a BAO stream and compared against an
+/// encoding recomputed from its data, which checks both the data hash and the
+/// stored outboard. Blobs that fail are force-deleted so they can be re-fetched.
+pub async fn scrub_iroh_store(store: &iroh_blobs::api::Store) -> Result<ScrubReport> {
+    let mut report = ScrubReport::default();
+    let hashes = store.blobs().list().hashes().await.map_err(other)?;
+
+    for hash in hashes {
+        let status = store.blobs().status(hash).await.map_err(other)?;
+        if !matches!(status, BlobStatus::Complete { .. }) {
+            continue;
+        }
+        report.checked += 1;
+
+        if verify_iroh_blob(store, hash).await {
+            report.healthy += 1;
+        } else {
+            warn!(hash = %hash, "blob failed verification");
+            report.corrupt.push(hash);
+        }
+    }
+
+    if !report.corrupt.is_empty() {
+        evict_blobs(store, report.corrupt.clone()).await?;
+    }
+
+    info!(
+        checked = report.checked,
+        healthy = report.healthy,
+        corrupt = report.corrupt.len(),
+        "iroh store scrub complete"
+    );
+    Ok(report)
+}
+
+/// Check that a blob's exported BAO encoding matches one computed from its data.
+async fn verify_iroh_blob(store: &iroh_blobs::api::Store, hash: Hash) -> bool {
+    let Ok(data) = store.blobs().get_bytes(hash).await else {
+        return false;
+    };
+    if Hash::new(&data) != hash {
+        return false;
+    }
+    let Ok(encoded) = store
+        .blobs()
+        .export_bao(hash, ChunkRanges::all())
+        .bao_to_vec()
+        .await
+    else {
+        return false;
+    };
+
+    let outboard = PreOrderMemOutboard::create(&data, IROH_BLOCK_SIZE);
+    let mut expected = (data.len() as u64).to_le_bytes().to_vec();
+    if encode_ranges_validated(&data[..], &outboard, &ChunkRanges::all(), &mut expected).is_err() {
+        return false;
+    }
+    encoded == expected
+}
+
+/// Forcibly delete blobs from an iroh-blobs store.
+///
+/// iroh-blobs only deletes through garbage collection, which never touches a
+/// tagged blob - even a corrupt one.
+async fn evict_blobs(store: &iroh_blobs::api::Store, hashes: Vec<Hash>) -> Result<()> {
+    // SAFETY: Store is repr(transparent) over ApiClient, see ObjectStore::as_iroh_store.
+    let client = unsafe { std::mem::transmute::<&iroh_blobs::api::Store, &ApiClient>(store) };
+    let request = BlobDeleteRequest {
+        hashes,
+        force: true,
+    };
+    client.rpc(request).await.map_err(other)?.map_err(other)?;
+    Ok(())
+}
+
+fn other(e: impl std::fmt::Display) -> BlobStoreError {
+    BlobStoreError::Io(std::io::Error::other(e.to_string()))
+}
+
+async fn evict(
+    db: &Database,
+    storage: &Storage,
+    cold: Option<&Storage>,
+    hash_str: &str,
+) -> Result<()> {
+    if let Some(cold) = cold {
+        cold.delete_data(hash_str).await?;
+    }
+    storage.delete_outboard(hash_str).await?;
+    db.delete_blob(hash_str).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_store::BlobStore;
+
+    #[tokio::test]
+    async fn test_scrub_iroh_store() {
+        let store = iroh_blobs::store::mem::MemStore::new();
+        store.add_bytes(b"small blob".to_vec()).await.unwrap();
+        store.add_bytes(vec![3u8; 64 * 1024]).await.unwrap();
+
+        let report = scrub_iroh_store(&store).await.unwrap();
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.healthy, 2);
+        assert!(report.is_clean());
+    }
+
+    #[tokio::test]
+    async fn test_clean_store() {
+        let store = BlobStore::new_ephemeral().await.unwrap();
+        store.put(b"small blob".to_vec()).await.unwrap();
+        store.put(vec![7u8; 64 * 1024]).await.unwrap();
+
+        let report = store.scrub().await.unwrap();
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.healthy, 2);
+        assert!(report.is_clean());
+        assert_eq!(report.outboards_repaired, 0);
+    }
+
+    #[tokio::test]
+    async fn test_detects_corrupt_and_missing() {
+        let store = BlobStore::new_ephemeral().await.unwrap();
+        let corrupt = store.put(b"will be corrupted".to_vec()).await.unwrap();
+        let missing = store.put(b"will go missing".to_vec()).await.unwrap();
+
+        store
+            .storage()
+            .put_data(&corrupt.to_string(), Bytes::from("bit rot"))
+            .await
+            .unwrap();
+        store
+            .storage()
+            .delete_data(&missing.to_string())
+            .await
+            .unwrap();
+
+        let report = store.scrub().await.unwrap();
+        assert_eq!(report.corrupt, vec![corrupt]);
+        assert_eq!(report.missing, vec![missing]);
+        assert_eq!(report.damaged().count(), 2);
+
+        // Both are evicted so they can be re-fetched
+        assert!(store.get(&corrupt).await.unwrap().is_none());
+        assert!(store.get(&missing).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_repairs_outboard_and_orphans() {
+        let store = BlobStore::new_ephemeral().await.unwrap();
+        let large = store.put(vec![1u8; 64 * 1024]).await.unwrap();
+        store
+            .storage()
+            .delete_outboard(&large.to_string())
+            .await
+            .unwrap();
+
+        // Valid orphan (data without a row) and an unverifiable one
+        let orphan_data = Bytes::from("orphaned data");
+        let orphan = Hash::new(&orphan_data);
+        store
+            .storage()
+            .put_data(&orphan.to_string(), orphan_data)
+            .await
+            .unwrap();
+        let bogus = Hash::new(b"something else").to_string();
+        store
+            .storage()
+            .put_data(&bogus, Bytes::from("not matching"))
+            .await
+            .unwrap();
+
+        let report = store.scrub().await.unwrap();
+        assert!(report.is_clean());
+        assert_eq!(report.outboards_repaired, 1);
+        assert_eq!(report.orphans_recovered, 1);
+        assert_eq!(report.orphans_removed, 1);
+        assert!(store.get(&orphan).await.unwrap().is_some());
+        assert!(store.storage().get_data(&bogus).await.unwrap().is_none());
+    }
+}
@@ -10,7 +10,9 @@ use object_store::memory::InMemory;
 use object_store::path::Path as ObjectPath;
 use object_store::ObjectStore;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
+use crate::cache::DiskCache;
 use crate::error::{BlobStoreError, Result};
 
 /// Configuration for the object storage backend.
@@ -43,9 +45,16 @@ pub enum ObjectStoreConfig {
 }
 
 /// Wrapper around different object storage backends.
+///
+/// Blob data and outboards may optionally be fronted by a local
+/// [`DiskCache`]: reads are served from disk when cached and populated on
+/// miss, writes go through. Outboards are cached under their own keys (see
+/// [`Storage::outboard_key`]) so they never collide with blob data, and
+/// count towards the same size limit and statistics.
 #[derive(Debug, Clone)]
 pub(crate) struct Storage {
     inner: Arc<dyn ObjectStore>,
+    cache: Option<DiskCache>,
 }
 
 impl Storage {
@@ -112,7 +121,13 @@ impl Storage {
             }
         };
 
-        Ok(Self { inner })
+        Ok(Self { inner, cache: None })
+    }
+
+    /// Front blob data and outboard reads and writes with a local disk cache.
+    pub fn with_cache(mut self, cache: DiskCache) -> Self {
+        self.cache = Some(cache);
+        self
     }
 
     /// Build the object path for blob data.
@@ -125,28 +140,45 @@ impl Storage {
         ObjectPath::from(format!("outboard/{}", hash))
     }
 
+    /// Build the cache key for blob outboard data.
+    ///
+    /// Blob data is cached under its bare hash, so the prefix keeps the two
+    /// apart.
+    fn outboard_key(hash: &str) -> String {
+        format!("outboard-{}", hash)
+    }
+
     /// Put blob data into storage.
     pub async fn put_data(&self, hash: &str, data: Bytes) -> Result<()> {
         let path = Self::data_path(hash);
-        self.inner.put(&path, data.into()).await?;
+        self.inner.put(&path, data.clone().into()).await?;
+        self.cache_insert(hash, &data).await;
         Ok(())
     }
 
     /// Get blob data from storage.
     pub async fn get_data(&self, hash: &str) -> Result<Option<Bytes>> {
-        let path = Self::data_path(hash);
-        match self.inner.get(&path).await {
-            Ok(result) => {
-                let bytes = result.bytes().await?;
-                Ok(Some(bytes))
-            }
-            Err(object_store::Error::NotFound { .. }) => Ok(None),
-            Err(e) => Err(e.into()),
+        if let Some(bytes) = self.cache_get(hash).await {
+            return Ok(Some(bytes));
         }
+
+        let bytes = self.get_data_uncached(hash).await?;
+        if let Some(bytes) = &bytes {
+            self.cache_insert(hash, bytes).await;
+        }
+        Ok(bytes)
+    }
+
+    /// Get blob data straight from the backend, bypassing the cache.
+    pub async fn get_data_uncached(&self, hash: &str) -> Result<Option<Bytes>> {
+        self.get_object(&Self::data_path(hash)).await
     }
 
     /// Delete blob data from storage.
     pub async fn delete_data(&self, hash: &str) -> Result<()> {
+        if let Some(cache) = &self.cache {
+            cache.remove(hash).await?;
+        }
         let path = Self::data_path(hash);
         // Ignore NotFound errors - the blob may already be deleted
         match self.inner.delete(&path).await {
@@ -158,6 +190,9 @@ impl Storage {
 
     /// Delete blob outboard data from storage.
     pub async fn delete_outboard(&self, hash: &str) -> Result<()> {
+        if let Some(cache) = &self.cache {
+            cache.remove(&Self::outboard_key(hash)).await?;
+        }
         let path = Self::outboard_path(hash);
         // Ignore NotFound errors
         match self.inner.delete(&path).await {
@@ -170,24 +205,34 @@ impl Storage {
     /// Put blob outboard data into storage.
     pub async fn put_outboard(&self, hash: &str, data: Bytes) -> Result<()> {
         let path = Self::outboard_path(hash);
-        self.inner.put(&path, data.into()).await?;
+        self.inner.put(&path, data.clone().into()).await?;
+        self.cache_insert(&Self::outboard_key(hash), &data).await;
         Ok(())
     }
-}
 
-#[cfg(test)]
-impl Storage {
-    /// Create an in-memory storage backend (test-only).
-    pub fn memory() -> Self {
-        Self {
-            inner: Arc::new(InMemory::new()),
+    /// Get blob outboard data from storage.
+    #[allow(dead_code)]
+    pub async fn get_outboard(&self, hash: &str) -> Result<Option<Bytes>> {
+        let key = Self::outboard_key(hash);
+        if let Some(bytes) = self.cache_get(&key).await {
+            return Ok(Some(bytes));
         }
+
+        let bytes = self.get_outboard_uncached(hash).await?;
+        if let Some(bytes) = &bytes {
+            self.cache_insert(&key, bytes).await;
+        }
+        Ok(bytes)
     }
 
-    /// Get blob outboard data from storage.
-    pub async fn get_outboard(&self, hash: &str) -> Result<Option<Bytes>> {
-        let path = Self::outboard_path(hash);
-        match self.inner.get(&path).await {
+    /// Get blob outboard data straight from the backend, bypassing the cache.
+    pub async fn get_outboard_uncached(&self, hash: &str) -> Result<Option<Bytes>> {
+        self.get_object(&Self::outboard_path(hash)).await
+    }
+
+    /// Read an object from the backend, or `None` if it doesn't exist.
+    async fn get_object(&self, path: &ObjectPath) -> Result<Option<Bytes>> {
+        match self.inner.get(path).await {
             Ok(result) => {
                 let bytes = result.bytes().await?;
                 Ok(Some(bytes))
@@ -197,16 +242,6 @@ impl Storage {
         }
     }
 
-    /// Check if blob data exists in storage.
-    pub async fn has_data(&self, hash: &str) -> Result<bool> {
-        let path = Self::data_path(hash);
-        match self.inner.head(&path).await {
-            Ok(_) => Ok(true),
-            Err(object_store::Error::NotFound { .. }) => Ok(false),
-            Err(e) => Err(e.into()),
-        }
-    }
-
     /// Stream all blob hashes in the data directory without collecting into memory.
     pub fn list_data_hashes_stream(&self) -> impl futures::Stream<Item = Result<String>> + '_ {
         use futures::StreamExt;
@@ -222,6 +257,53 @@ impl Storage {
             }
         })
     }
+
+    /// Statistics for the disk cache, if one is configured.
+    pub fn cache_stats(&self) -> Option<crate::cache::CacheStats> {
+        self.cache.as_ref().map(DiskCache::stats)
+    }
+
+    /// Read from the cache; failures are treated as a miss.
+    async fn cache_get(&self, key: &str) -> Option<Bytes> {
+        let cache = self.cache.as_ref()?;
+        match cache.get(key).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!(key, error = %e, "blob cache read failed");
+                None
+            }
+        }
+    }
+
+    /// Populate the cache; failures only cost a future cache miss.
+    async fn cache_insert(&self, key: &str, data: &Bytes) {
+        if let Some(cache) = &self.cache {
+            if let Err(e) = cache.insert(key, data).await {
+                warn!(key, error = %e, "blob cache write failed");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+impl Storage {
+    /// Create an in-memory storage backend (test-only).
+    pub fn memory() -> Self {
+        Self {
+            inner: Arc::new(InMemory::new()),
+            cache: None,
+        }
+    }
+
+    /// Check if blob data exists in storage.
+    pub async fn has_data(&self, hash: &str) -> Result<bool> {
+        let path = Self::data_path(hash);
+        match self.inner.head(&path).await {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -292,4 +374,104 @@ mod tests {
         storage.delete_outboard(hash).await.unwrap();
         assert!(storage.get_outboard(hash).await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn test_cached_storage() {
+        use crate::cache::DiskCacheConfig;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(DiskCacheConfig {
+            path: temp_dir.path().to_path_buf(),
+            max_size: 1024,
+            max_entry_size: 1024,
+        })
+        .await
+        .unwrap();
+        let storage = Storage::memory().with_cache(cache.clone());
+
+        let hash = "cached1";
+        let data = Bytes::from("cached data");
+
+        // Write-through populates the cache
+        storage.put_data(hash, data.clone()).await.unwrap();
+        assert!(temp_dir.path().join(hash).exists());
+        assert_eq!(storage.get_data(hash).await.unwrap().unwrap(), data);
+        assert_eq!(cache.stats().hits, 1);
+
+        // Delete evicts from the cache as well as the backend
+        storage.delete_data(hash).await.unwrap();
+        assert!(storage.get_data(hash).await.unwrap().is_none());
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cached_outboard() {
+        use crate::cache::DiskCacheConfig;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(DiskCacheConfig {
+            path: temp_dir.path().to_path_buf(),
+            max_size: 1024,
+            max_entry_size: 1024,
+        })
+        .await
+        .unwrap();
+        let storage = Storage::memory().with_cache(cache.clone());
+
+        let hash = "cached2";
+        let data = Bytes::from("blob data");
+        let outboard = Bytes::from("outboard data");
+        storage.put_data(hash, data.clone()).await.unwrap();
+        storage
+            .inner
+            .put(&Storage::outboard_path(hash), outboard.clone().into())
+            .await
+            .unwrap();
+
+        // The first read misses and populates the cache; the second is a hit
+        assert_eq!(storage.get_outboard(hash).await.unwrap().unwrap(), outboard);
+        assert_eq!(cache.stats().misses, 1);
+        assert_eq!(storage.get_outboard(hash).await.unwrap().unwrap(), outboard);
+        assert_eq!(cache.stats().hits, 1);
+
+        // Outboards share the size limit and stats with data, under their own key
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.size_bytes, (data.len() + outboard.len()) as u64);
+        assert_eq!(storage.get_data(hash).await.unwrap().unwrap(), data);
+
+        // Delete evicts the outboard but leaves the data cached
+        storage.delete_outboard(hash).await.unwrap();
+        assert!(storage.get_outboard(hash).await.unwrap().is_none());
+        assert_eq!(cache.stats().entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_outboard_write_through() {
+        use crate::cache::DiskCacheConfig;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(DiskCacheConfig {
+            path: temp_dir.path().to_path_buf(),
+            max_size: 1024,
+            max_entry_size: 1024,
+        })
+        .await
+        .unwrap();
+        let storage = Storage::memory().with_cache(cache.clone());
+
+        let hash = "cached3";
+        let outboard = Bytes::from("outboard data");
+        storage.put_outboard(hash, outboard.clone()).await.unwrap();
+
+        // Served from the cache even once the backend lost it
+        storage
+            .inner
+            .delete(&Storage::outboard_path(hash))
+            .await
+            .unwrap();
+        assert_eq!(storage.get_outboard(hash).await.unwrap().unwrap(), outboard);
+        assert_eq!(cache.stats().hits, 1);
+        assert!(storage.get_outboard_uncached(hash).await.unwrap().is_none());
+    }
 }
@@ -0,0 +1,157 @@
+//! Hot/cold storage tiering.
+//!
+//! A tiered store keeps blob data in a fast local "hot" backend and offloads
+//! large, rarely read blobs to a cheaper "cold" backend (typically S3/MinIO).
+//! Metadata stays in SQLite either way, and outboards always stay hot so
+//! verified streaming only needs the data itself. Reading a cold blob
+//! transparently promotes it back into the hot tier.
+//!
+//! Demotion is driven by a [`TierPolicy`]: a blob is demoted once it is at
+//! least `min_size` bytes, was stored more than `min_age` ago, has not been
+//! read for `min_idle`, and its read count - halved on every rebalance pass, so
+//! it tracks recent access frequency - is at most `max_access_count`. Blobs
+//! marked keep-hot (manifests, pins, directory nodes) are never demoted.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::storage::Storage;
+
+/// Storage tier a blob's data currently lives in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tier {
+    /// Fast local storage
+    #[default]
+    Hot,
+    /// Cheap remote storage
+    Cold,
+}
+
+impl Tier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Tier::Hot => "hot",
+            Tier::Cold => "cold",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "cold" => Tier::Cold,
+            _ => Tier::Hot,
+        }
+    }
+}
+
+/// Policy deciding which blobs move to the cold tier.
+#[derive(Debug, Clone)]
+pub struct TierPolicy {
+    /// Blobs smaller than this (in bytes) always stay hot
+    pub min_size: u64,
+    /// Blobs stored more recently than this stay hot
+    pub min_age: Duration,
+    /// Blobs read more recently than this stay hot
+    pub min_idle: Duration,
+    /// Blobs with a higher read count stay hot; counts halve on every pass
+    pub max_access_count: u64,
+    /// Optional cap on hot tier data (bytes); when exceeded, the least used
+    /// eligible blobs are demoted regardless of idle time
+    pub hot_max_size: Option<u64>,
+    /// How often the background rebalance runs
+    pub interval: Duration,
+}
+
+impl Default for TierPolicy {
+    fn default() -> Self {
+        Self {
+            min_size: 1024 * 1024,
+            min_age: Duration::from_secs(24 * 60 * 60),
+            min_idle: Duration::from_secs(24 * 60 * 60),
+            max_access_count: 2,
+            hot_max_size: None,
+            interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Per-tier usage and movement counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TierStats {
+    /// Complete blobs held in the hot tier
+    pub hot_blobs: u64,
+    /// Bytes held in the hot tier
+    pub hot_bytes: u64,
+    /// Complete blobs held in the cold tier
+    pub cold_blobs: u64,
+    /// Bytes held in the cold tier
+    pub cold_bytes: u64,
+    /// Blobs promoted back to the hot tier on read since startup
+    pub promotions: u64,
+    /// Blobs demoted to the cold tier since startup
+    pub demotions: u64,
+}
+
+/// Result of a single rebalance pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RebalanceReport {
+    /// Blobs moved to the cold tier
+    pub demoted: u64,
+    /// Bytes moved to the cold tier
+    pub bytes: u64,
+}
+
+/// Number of locks blob hashes are spread over when moving between tiers.
+const TIER_LOCKS: usize = 256;
+
+/// Cold backend plus policy and counters for a tiered store.
+#[derive(Debug)]
+pub(crate) struct ColdTier {
+    pub storage: Storage,
+    pub policy: TierPolicy,
+    promotions: AtomicU64,
+    demotions: AtomicU64,
+    /// Serializes writes, promotions and demotions of the same blob, so a
+    /// demotion can never delete a hot copy that was just rewritten
+    locks: Vec<Mutex<()>>,
+}
+
+impl ColdTier {
+    pub fn new(storage: Storage, policy: TierPolicy) -> Self {
+        Self {
+            storage,
+            policy,
+            promotions: AtomicU64::new(0),
+            demotions: AtomicU64::new(0),
+            locks: (0..TIER_LOCKS).map(|_| Mutex::new(())).collect(),
+        }
+    }
+
+    /// Lock a blob against concurrent tier moves, keyed by its hex hash.
+    pub async fn lock(&self, hash_str: &str) -> MutexGuard<'_, ()> {
+        let slot = hash_str
+            .get(..2)
+            .and_then(|prefix| usize::from_str_radix(prefix, 16).ok())
+            .unwrap_or(0);
+        self.locks[slot % TIER_LOCKS].lock().await
+    }
+
+    pub fn record_promotion(&self) {
+        self.promotions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_demotions(&self, count: u64) {
+        self.demotions.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn promotions(&self) -> u64 {
+        self.promotions.load(Ordering::Relaxed)
+    }
+
+    pub fn demotions(&self) -> u64 {
+        self.demotions.load(Ordering::Relaxed)
+    }
+}